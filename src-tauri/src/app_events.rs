@@ -0,0 +1,47 @@
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+pub const UPLOAD_PROGRESS_EVENT: &str = "submission_upload_progress";
+pub const WORKFLOW_STATUS_EVENT: &str = "workflow_status";
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// 上传进度推送，节流沿用 UploadProgressLimiter 的落库频率。
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadProgressEvent {
+  pub target_type: &'static str,
+  pub segment_id: String,
+  pub upload_status: Option<String>,
+  pub upload_progress: Option<f64>,
+  pub upload_uploaded_bytes: Option<i64>,
+  pub upload_total_bytes: Option<i64>,
+  pub upload_speed_bps: Option<i64>,
+  pub upload_eta_seconds: Option<i64>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowStatusEvent {
+  pub task_id: String,
+  pub status: String,
+  pub current_step: Option<String>,
+  pub progress: f64,
+}
+
+pub fn init(app_handle: AppHandle) {
+  let _ = APP_HANDLE.set(app_handle);
+}
+
+pub fn app_handle() -> Option<&'static AppHandle> {
+  APP_HANDLE.get()
+}
+
+pub fn emit<S: Serialize + Clone>(event: &str, payload: S) -> Result<(), String> {
+  match APP_HANDLE.get() {
+    Some(app_handle) => app_handle.emit(event, payload).map_err(|err| err.to_string()),
+    None => Ok(()),
+  }
+}
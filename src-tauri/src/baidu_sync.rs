@@ -1,12 +1,15 @@
+use std::collections::HashSet;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 use tokio::time::{sleep, Duration};
 
+use crate::commands::settings::upsert_setting;
+use crate::commands::system::probe_binary_version;
 use crate::config::resolve_baidu_pcs_path;
 use crate::db::Db;
 use crate::utils::{append_log, now_rfc3339, sanitize_filename};
@@ -20,6 +23,21 @@ pub struct BaiduSyncSettings {
   pub policy: String,
   pub retry: i64,
   pub concurrency: i64,
+  pub path_template: String,
+  pub filename_template: String,
+}
+
+const BAIDU_TEMPLATE_KEYS: [&str; 4] = ["anchor", "date", "title", "room_id"];
+// 百度网盘分片上传的文件 md5 由分片列表计算，仅单分片文件的 md5 等于整文件 md5
+const BAIDU_WHOLE_FILE_MD5_MAX_SIZE: u64 = 4 * 1024 * 1024;
+
+static UNKNOWN_TEMPLATE_KEYS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+struct BaiduTemplateValues {
+  anchor: String,
+  date: String,
+  title: String,
+  room_id: String,
 }
 
 #[derive(Clone, Serialize)]
@@ -88,6 +106,44 @@ impl BaiduSyncRuntime {
   }
 }
 
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaiduSyncVerifyReport {
+  pub task_id: i64,
+  pub local_path: String,
+  pub remote_path: String,
+  pub local_size: u64,
+  pub remote_size: Option<u64>,
+  pub local_md5: String,
+  pub remote_md5: Option<String>,
+  pub size_match: bool,
+  pub md5_match: Option<bool>,
+  pub matched: bool,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaiduPcsCandidateResult {
+  pub path: String,
+  pub error: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaiduPcsCheckReport {
+  pub resolved_path: Option<String>,
+  pub version: Option<String>,
+  pub candidates: Vec<BaiduPcsCandidateResult>,
+  pub logged_in: bool,
+  pub uid: Option<String>,
+  pub username: Option<String>,
+  pub quota_total_bytes: Option<u64>,
+  pub quota_used_bytes: Option<u64>,
+  pub quota_remaining_bytes: Option<u64>,
+  pub quota_raw: Option<String>,
+  pub error: Option<String>,
+}
+
 #[derive(Clone)]
 struct BaiduSyncTask {
   id: i64,
@@ -96,6 +152,8 @@ struct BaiduSyncTask {
   remote_name: String,
   retry_count: i64,
   policy: Option<String>,
+  uploaded_bytes: i64,
+  total_bytes: i64,
 }
 
 pub fn load_baidu_sync_settings(db: &Db) -> Result<BaiduSyncSettings, String> {
@@ -117,6 +175,8 @@ pub fn load_baidu_sync_settings(db: &Db) -> Result<BaiduSyncSettings, String> {
       let now = now_rfc3339();
       let _ = upsert_setting(conn, "baidu_sync_concurrency", "3", &now);
     }
+    let path_template = read_setting(conn, "baidu_sync_path_template").unwrap_or_default();
+    let filename_template = read_setting(conn, "baidu_sync_filename_template").unwrap_or_default();
     Ok(BaiduSyncSettings {
       enabled,
       exec_path,
@@ -124,6 +184,8 @@ pub fn load_baidu_sync_settings(db: &Db) -> Result<BaiduSyncSettings, String> {
       policy,
       retry,
       concurrency,
+      path_template,
+      filename_template,
     })
   })
   .map_err(|err| err.to_string())
@@ -189,6 +251,8 @@ pub fn update_baidu_sync_settings(db: &Db, settings: &BaiduSyncSettings) -> Resu
     upsert_setting(conn, "baidu_sync_policy", &settings.policy, &now)?;
     upsert_setting(conn, "baidu_sync_retry", &settings.retry.to_string(), &now)?;
     upsert_setting(conn, "baidu_sync_concurrency", &settings.concurrency.to_string(), &now)?;
+    upsert_setting(conn, "baidu_sync_path_template", settings.path_template.trim(), &now)?;
+    upsert_setting(conn, "baidu_sync_filename_template", settings.filename_template.trim(), &now)?;
     Ok(())
   })
   .map_err(|err| err.to_string())
@@ -333,18 +397,19 @@ pub fn enqueue_submission_sync(
   );
   let task = db.with_conn(|conn| {
     conn.query_row(
-      "SELECT title, baidu_sync_enabled, baidu_sync_path, baidu_sync_filename FROM submission_task WHERE task_id = ?1",
+      "SELECT title, baidu_sync_enabled, baidu_sync_path, baidu_sync_filename, created_at FROM submission_task WHERE task_id = ?1",
       [task_id],
       |row| {
         let title: String = row.get(0)?;
         let enabled: i64 = row.get(1)?;
         let path: Option<String> = row.get(2)?;
         let filename: Option<String> = row.get(3)?;
-        Ok((title, enabled != 0, path, filename))
+        let created_at: String = row.get(4)?;
+        Ok((title, enabled != 0, path, filename, created_at))
       },
     )
   });
-  let (title, task_enabled, task_path, task_filename) = match task {
+  let (title, task_enabled, task_path, task_filename, created_at) = match task {
     Ok(value) => value,
     Err(err) => return Err(err.to_string()),
   };
@@ -382,14 +447,24 @@ pub fn enqueue_submission_sync(
     .1
     .or_else(|| Path::new(&local_path).file_name().and_then(|v| v.to_str()).map(|v| v.to_string()))
     .unwrap_or_else(|| "merged.mp4".to_string());
-  let base_path = normalize_baidu_path(task_path.as_deref().unwrap_or(&settings.target_path));
-  let remote_dir = base_path;
-  let remote_name = task_filename
+  let values = BaiduTemplateValues {
+    anchor: String::new(),
+    date: parse_date(&created_at).unwrap_or_else(|| Utc::now().format("%Y%m%d").to_string()),
+    title: title.clone(),
+    room_id: String::new(),
+  };
+  let remote_dir = match task_path.as_deref().filter(|value| !value.trim().is_empty()) {
+    Some(value) => expand_baidu_path_template(value, &values, app_log_path),
+    None => join_baidu_path(
+      &normalize_baidu_path(&settings.target_path),
+      &expand_baidu_path_template(&settings.path_template, &values, app_log_path),
+    ),
+  };
+  let filename_template = task_filename
     .as_deref()
-    .map(|name| name.trim())
-    .filter(|name| !name.is_empty())
-    .map(sanitize_filename)
-    .unwrap_or_else(|| sanitize_filename(&local_name));
+    .filter(|name| !name.trim().is_empty())
+    .unwrap_or(&settings.filename_template);
+  let remote_name = render_filename(filename_template, &values, &local_name, app_log_path);
   append_log(
     app_log_path,
     &format!(
@@ -459,8 +534,16 @@ pub fn enqueue_live_sync(
     );
     return Ok(());
   }
+  let values = BaiduTemplateValues {
+    anchor: load_anchor_nickname(db, &room_id).unwrap_or_default(),
+    date: live_date.clone(),
+    title: title.clone().unwrap_or_default(),
+    room_id: room_id.clone(),
+  };
   let base_path = match room_path {
-    Some(value) if !value.trim().is_empty() => normalize_baidu_path(&value),
+    Some(value) if !value.trim().is_empty() => {
+      expand_baidu_path_template(&value, &values, app_log_path)
+    }
     _ => {
       append_log(
         app_log_path,
@@ -469,8 +552,14 @@ pub fn enqueue_live_sync(
       return Ok(());
     }
   };
-  let remote_dir = join_baidu_path(&base_path, &live_date);
-  let remote_name = render_filename(None, &local_name, &live_date, None, &local_name);
+  let sub_path = if settings.path_template.trim().is_empty() {
+    live_date.clone()
+  } else {
+    expand_baidu_path_template(&settings.path_template, &values, app_log_path)
+  };
+  let remote_dir = join_baidu_path(&base_path, &sub_path);
+  let remote_name =
+    render_filename(&settings.filename_template, &values, &local_name, app_log_path);
   append_log(
     app_log_path,
     &format!(
@@ -545,22 +634,154 @@ pub fn start_baidu_sync_loop(context: BaiduSyncContext) {
 pub fn recover_baidu_sync_tasks(db: &Db, app_log_path: &Path) {
   let now = now_rfc3339();
   let result = db.with_conn(|conn| {
-    conn.execute(
-      "UPDATE baidu_sync_task SET status = 'PENDING', progress = 0.0, updated_at = ?1 WHERE status = 'UPLOADING'",
-      [&now],
+    let mut stmt = conn.prepare(
+      "SELECT id, local_path, progress, COALESCE(uploaded_bytes, 0), COALESCE(total_bytes, 0) \
+       FROM baidu_sync_task WHERE status = 'UPLOADING'",
     )?;
-    Ok(())
+    let rows = stmt
+      .query_map([], |row| {
+        Ok((
+          row.get::<_, i64>(0)?,
+          row.get::<_, String>(1)?,
+          row.get::<_, f64>(2)?,
+          row.get::<_, i64>(3)?,
+          row.get::<_, i64>(4)?,
+        ))
+      })?
+      .collect::<Result<Vec<_>, _>>()?;
+    let mut resumed = Vec::with_capacity(rows.len());
+    for (task_id, local_path, progress, uploaded_bytes, total_bytes) in rows {
+      // BaiduPCS-Go 会记录未完成的分片上传，同一文件再次上传时自动续传；
+      // 本地文件大小变化说明文件已被替换，此时从头开始。
+      let local_size = std::fs::metadata(&local_path)
+        .map(|meta| meta.len() as i64)
+        .unwrap_or(-1);
+      let keep_offset = uploaded_bytes > 0 && total_bytes > 0 && local_size == total_bytes;
+      let (progress, uploaded_bytes, total_bytes) = if keep_offset {
+        (progress, uploaded_bytes, total_bytes)
+      } else {
+        (0.0, 0, 0)
+      };
+      conn.execute(
+        "UPDATE baidu_sync_task SET status = 'PENDING', progress = ?1, uploaded_bytes = ?2, total_bytes = ?3, updated_at = ?4 WHERE id = ?5",
+        (progress, uploaded_bytes, total_bytes, &now, task_id),
+      )?;
+      resumed.push((task_id, local_path, uploaded_bytes, total_bytes));
+    }
+    Ok(resumed)
   });
-  if result.is_ok() {
-    append_log(app_log_path, "baidu_sync_recover_ok");
+  match result {
+    Ok(resumed) => {
+      for (task_id, local_path, uploaded_bytes, total_bytes) in resumed {
+        append_log(
+          app_log_path,
+          &format!(
+            "baidu_sync_recover_task id={} local={} offset={} total={}",
+            task_id, local_path, uploaded_bytes, total_bytes
+          ),
+        );
+      }
+      append_log(app_log_path, "baidu_sync_recover_ok");
+    }
+    Err(err) => {
+      append_log(app_log_path, &format!("baidu_sync_recover_error err={}", err));
+    }
   }
 }
 
+pub fn verify_baidu_sync_task(
+  db: &Db,
+  app_log_path: &Path,
+  task_id: i64,
+) -> Result<BaiduSyncVerifyReport, String> {
+  let (local_path, remote_dir, remote_name, status) = db
+    .with_conn(|conn| {
+      conn.query_row(
+        "SELECT local_path, remote_dir, remote_name, status FROM baidu_sync_task WHERE id = ?1",
+        [task_id],
+        |row| {
+          Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+          ))
+        },
+      )
+    })
+    .map_err(|_| "同步任务不存在".to_string())?;
+  if status != "SUCCESS" {
+    return Err("仅可校验已完成的同步任务".to_string());
+  }
+  let local_size = std::fs::metadata(&local_path)
+    .map(|meta| meta.len())
+    .map_err(|_| "本地文件不存在".to_string())?;
+  let whole_file_md5 = local_size <= BAIDU_WHOLE_FILE_MD5_MAX_SIZE;
+  let local_md5 = if whole_file_md5 {
+    compute_file_md5(Path::new(&local_path))?
+  } else {
+    String::new()
+  };
+  let settings = load_baidu_sync_settings(db)?;
+  let exec_path = resolve_baidu_exec_path(&settings.exec_path);
+  let remote_path = join_baidu_path(&remote_dir, &remote_name);
+  let meta = run_baidu_pcs_command(&exec_path, &["meta".to_string(), remote_path.clone()])?;
+  let remote_size = parse_meta_size(&meta.stdout);
+  let remote_md5 = parse_meta_md5(&meta.stdout);
+  let size_match = remote_size == Some(local_size);
+  let md5_match = if whole_file_md5 {
+    remote_md5
+      .as_deref()
+      .map(|value| value.eq_ignore_ascii_case(&local_md5))
+  } else {
+    None
+  };
+  let matched = size_match && md5_match.unwrap_or(true);
+  append_log(
+    app_log_path,
+    &format!(
+      "baidu_sync_verify id={} remote={} local_size={} remote_size={} local_md5={} remote_md5={} md5_checked={} matched={}",
+      task_id,
+      remote_path,
+      local_size,
+      remote_size.map(|value| value.to_string()).unwrap_or_default(),
+      local_md5,
+      remote_md5.as_deref().unwrap_or(""),
+      md5_match.is_some(),
+      matched
+    ),
+  );
+  if !matched {
+    let err = if size_match {
+      "远端文件MD5不一致".to_string()
+    } else {
+      format!(
+        "远端文件大小不一致 本地={} 远端={}",
+        local_size,
+        remote_size.map(|value| value.to_string()).unwrap_or_else(|| "未知".to_string())
+      )
+    };
+    update_baidu_sync_status(db, task_id, "FAILED", 0.0, Some(err))?;
+  }
+  Ok(BaiduSyncVerifyReport {
+    task_id,
+    local_path,
+    remote_path,
+    local_size,
+    remote_size,
+    local_md5,
+    remote_md5,
+    size_match,
+    md5_match,
+    matched,
+  })
+}
+
 pub fn retry_baidu_sync_task(db: &Db, task_id: i64) -> Result<(), String> {
   let now = now_rfc3339();
   db.with_conn(|conn| {
     conn.execute(
-      "UPDATE baidu_sync_task SET status = 'PENDING', progress = 0.0, error = NULL, updated_at = ?1 WHERE id = ?2",
+      "UPDATE baidu_sync_task SET status = 'PENDING', progress = 0.0, uploaded_bytes = 0, total_bytes = 0, error = NULL, updated_at = ?1 WHERE id = ?2",
       (&now, task_id),
     )?;
     Ok(())
@@ -658,6 +879,122 @@ fn check_baidu_login_internal(db: &Db, allow_auto_relogin: bool) -> Result<Baidu
   Ok(info)
 }
 
+/// 依次尝试候选路径，找到可执行的 BaiduPCS-Go 后检查登录状态与网盘容量。
+pub fn check_baidu_pcs(db: &Db, app_log_path: &Path) -> Result<BaiduPcsCheckReport, String> {
+  let settings = load_baidu_sync_settings(db)?;
+  let mut paths = Vec::new();
+  if !settings.exec_path.trim().is_empty() {
+    paths.push(settings.exec_path.trim().to_string());
+  }
+  for candidate in crate::config::resolve_baidu_pcs_candidates() {
+    if !paths.contains(&candidate) {
+      paths.push(candidate);
+    }
+  }
+  let mut report = BaiduPcsCheckReport {
+    resolved_path: None,
+    version: None,
+    candidates: Vec::new(),
+    logged_in: false,
+    uid: None,
+    username: None,
+    quota_total_bytes: None,
+    quota_used_bytes: None,
+    quota_remaining_bytes: None,
+    quota_raw: None,
+    error: None,
+  };
+  for path in paths {
+    let probe = probe_binary_version("baidu_pcs", std::slice::from_ref(&path), "--version");
+    report.candidates.push(BaiduPcsCandidateResult {
+      path,
+      error: probe.error,
+    });
+    if probe.resolved_path.is_some() {
+      report.resolved_path = probe.resolved_path;
+      report.version = probe.version;
+      break;
+    }
+  }
+  let Some(resolved_path) = report.resolved_path.clone() else {
+    report.error = Some("未找到可执行的 BaiduPCS-Go".to_string());
+    append_log(app_log_path, "baidu_sync_check_fail reason=binary_missing");
+    return Ok(report);
+  };
+  let exec_path = PathBuf::from(&resolved_path);
+  match run_baidu_pcs_command(&exec_path, &["who".to_string()]) {
+    Ok(output) => {
+      let (logged_in, uid, username) = parse_who_output(&output.stdout);
+      report.logged_in = logged_in;
+      report.uid = uid;
+      report.username = username;
+    }
+    Err(err) => report.error = Some(err),
+  }
+  if report.logged_in {
+    match run_baidu_pcs_command(&exec_path, &["quota".to_string()]) {
+      Ok(output) => {
+        let raw = output
+          .stdout
+          .lines()
+          .map(|line| line.trim())
+          .find(|line| line.contains("总空间"))
+          .map(|line| line.to_string());
+        if let Some(line) = raw.as_deref() {
+          report.quota_total_bytes = extract_quota_field(line, "总空间:");
+          report.quota_used_bytes = extract_quota_field(line, "已使用:");
+          if let (Some(total), Some(used)) = (report.quota_total_bytes, report.quota_used_bytes) {
+            report.quota_remaining_bytes = Some(total.saturating_sub(used));
+          }
+        }
+        report.quota_raw = raw;
+      }
+      Err(err) => report.error = Some(err),
+    }
+  } else if report.error.is_none() {
+    report.error = Some("百度网盘未登录或登录已失效".to_string());
+  }
+  append_log(
+    app_log_path,
+    &format!(
+      "baidu_sync_check path={} logged_in={} remaining_bytes={} err={}",
+      resolved_path,
+      report.logged_in,
+      report
+        .quota_remaining_bytes
+        .map(|value| value.to_string())
+        .unwrap_or_default(),
+      report.error.as_deref().unwrap_or("")
+    ),
+  );
+  Ok(report)
+}
+
+// "账号: xxx, uid: 123, 总空间: 2.01TB, 已使用: 1.23TB, 比率: 61.19%"
+fn extract_quota_field(line: &str, key: &str) -> Option<u64> {
+  let start = line.find(key)? + key.len();
+  let value = line[start..].split(',').next()?.trim();
+  parse_size_text(value)
+}
+
+fn parse_size_text(value: &str) -> Option<u64> {
+  let value = value.trim().to_ascii_uppercase();
+  let split_at = value
+    .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+    .unwrap_or(value.len());
+  let number = value[..split_at].parse::<f64>().ok()?;
+  let multiplier = match value[split_at..].trim() {
+    "" | "B" => 1.0,
+    "KB" | "K" => 1024.0,
+    "MB" | "M" => 1024.0 * 1024.0,
+    "GB" | "G" => 1024.0 * 1024.0 * 1024.0,
+    "TB" | "T" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+    "PB" | "P" => 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0,
+    _ => return None,
+  };
+  Some((number * multiplier) as u64)
+}
+
 fn is_baidu_busy_error(err: &str) -> bool {
   err.contains("50052") || err.contains("系统繁忙")
 }
@@ -974,7 +1311,19 @@ async fn run_baidu_sync_task(
   settings: BaiduSyncSettings,
   task: BaiduSyncTask,
 ) -> Result<(), String> {
-  update_baidu_sync_status(context.db.as_ref(), task.id, "UPLOADING", 0.0, None)?;
+  if task.uploaded_bytes > 0 && task.total_bytes > 0 {
+    let progress = (task.uploaded_bytes as f64 / task.total_bytes as f64 * 100.0).min(99.0);
+    update_baidu_sync_status(context.db.as_ref(), task.id, "UPLOADING", progress, None)?;
+    append_log(
+      context.app_log_path.as_ref(),
+      &format!(
+        "baidu_sync_task_resume id={} local={} offset={} total={}",
+        task.id, task.local_path, task.uploaded_bytes, task.total_bytes
+      ),
+    );
+  } else {
+    update_baidu_sync_status(context.db.as_ref(), task.id, "UPLOADING", 0.0, None)?;
+  }
   let exec_path = resolve_baidu_exec_path(&settings.exec_path);
   let policy = normalize_baidu_upload_policy(task.policy.as_deref().or(Some(&settings.policy)))
     .unwrap_or_else(|| "overwrite".to_string());
@@ -993,8 +1342,14 @@ async fn run_baidu_sync_task(
       task.local_path.clone(),
       task.remote_dir.clone(),
     ],
-    |progress| {
-      let _ = update_baidu_sync_progress(context.db.as_ref(), task.id, progress);
+    |progress, uploaded_bytes, total_bytes| {
+      let _ = update_baidu_sync_progress(
+        context.db.as_ref(),
+        task.id,
+        progress,
+        uploaded_bytes,
+        total_bytes,
+      );
     },
   );
   match upload_result {
@@ -1066,7 +1421,7 @@ fn handle_baidu_sync_failure(
     let now = now_rfc3339();
     db.with_conn(|conn| {
       conn.execute(
-        "UPDATE baidu_sync_task SET status = 'PENDING', progress = 0.0, retry_count = ?1, error = ?2, updated_at = ?3 WHERE id = ?4",
+        "UPDATE baidu_sync_task SET status = 'PENDING', progress = 0.0, uploaded_bytes = 0, total_bytes = 0, retry_count = ?1, error = ?2, updated_at = ?3 WHERE id = ?4",
         (next_retry, err, &now, task.id),
       )?;
       Ok(())
@@ -1096,12 +1451,18 @@ fn update_baidu_sync_status(
   .map_err(|err| err.to_string())
 }
 
-fn update_baidu_sync_progress(db: &Db, task_id: i64, progress: f64) -> Result<(), String> {
+fn update_baidu_sync_progress(
+  db: &Db,
+  task_id: i64,
+  progress: f64,
+  uploaded_bytes: u64,
+  total_bytes: u64,
+) -> Result<(), String> {
   let now = now_rfc3339();
   db.with_conn(|conn| {
     conn.execute(
-      "UPDATE baidu_sync_task SET progress = ?1, updated_at = ?2 WHERE id = ?3",
-      (progress, &now, task_id),
+      "UPDATE baidu_sync_task SET progress = ?1, uploaded_bytes = ?2, total_bytes = ?3, updated_at = ?4 WHERE id = ?5",
+      (progress, uploaded_bytes as i64, total_bytes as i64, &now, task_id),
     )?;
     Ok(())
   })
@@ -1144,7 +1505,8 @@ fn load_next_pending_task(db: &Db) -> Result<Option<BaiduSyncTask>, String> {
   let now = now_rfc3339();
   db.with_conn(|conn| {
     let mut stmt = conn.prepare(
-      "SELECT id, local_path, remote_dir, remote_name, retry_count, policy FROM baidu_sync_task WHERE status = 'PENDING' ORDER BY created_at ASC LIMIT 1",
+      "SELECT id, local_path, remote_dir, remote_name, retry_count, policy, COALESCE(uploaded_bytes, 0), COALESCE(total_bytes, 0) \
+       FROM baidu_sync_task WHERE status = 'PENDING' ORDER BY created_at ASC LIMIT 1",
     )?;
     let mut rows = stmt.query([])?;
     if let Some(row) = rows.next()? {
@@ -1160,6 +1522,8 @@ fn load_next_pending_task(db: &Db) -> Result<Option<BaiduSyncTask>, String> {
         remote_name: row.get(3)?,
         retry_count: row.get(4)?,
         policy: row.get(5)?,
+        uploaded_bytes: row.get(6)?,
+        total_bytes: row.get(7)?,
       };
       Ok(Some(task))
     } else {
@@ -1194,20 +1558,6 @@ fn read_setting(conn: &rusqlite::Connection, key: &str) -> Option<String> {
     .ok()
 }
 
-fn upsert_setting(
-  conn: &rusqlite::Connection,
-  key: &str,
-  value: &str,
-  now: &str,
-) -> rusqlite::Result<()> {
-  conn.execute(
-    "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
-     ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-    (key, value, now),
-  )?;
-  Ok(())
-}
-
 fn normalize_baidu_path(path: &str) -> String {
   let trimmed = path.trim();
   if trimmed.is_empty() {
@@ -1235,23 +1585,16 @@ fn join_baidu_path(base: &str, segment: &str) -> String {
 }
 
 fn render_filename(
-  template: Option<&str>,
-  title: &str,
-  date: &str,
-  index: Option<i64>,
+  template: &str,
+  values: &BaiduTemplateValues,
   fallback: &str,
+  app_log_path: &Path,
 ) -> String {
-  let raw = template.unwrap_or("").trim();
+  let raw = template.trim();
   if raw.is_empty() {
     return sanitize_filename(fallback);
   }
-  let mut output = raw.to_string();
-  output = output.replace("{{ title }}", title);
-  output = output.replace("{{ date }}", date);
-  if let Some(index) = index {
-    output = output.replace("{{ index }}", &index.to_string());
-    output = output.replace("{{ part }}", &index.to_string());
-  }
+  let output = expand_baidu_template(raw, values, false, app_log_path);
   let trimmed = output.trim();
   if trimmed.is_empty() {
     sanitize_filename(fallback)
@@ -1260,6 +1603,84 @@ fn render_filename(
   }
 }
 
+/// 替换 `{{ key }}` 占位符，未知占位符原样保留并记录一次；`sanitize_values` 为 true 时
+/// 替换值中的路径分隔符等非法字符，避免标题把路径拆成多级目录。
+fn expand_baidu_template(
+  template: &str,
+  values: &BaiduTemplateValues,
+  sanitize_values: bool,
+  app_log_path: &Path,
+) -> String {
+  let mut output = String::with_capacity(template.len());
+  let mut rest = template;
+  while let Some(start) = rest.find("{{") {
+    output.push_str(&rest[..start]);
+    let after = &rest[start + 2..];
+    let Some(end) = after.find("}}") else {
+      output.push_str(&rest[start..]);
+      rest = "";
+      break;
+    };
+    let key = after[..end].trim();
+    let value = match key {
+      "anchor" => Some(&values.anchor),
+      "date" => Some(&values.date),
+      "title" => Some(&values.title),
+      "room_id" => Some(&values.room_id),
+      _ => None,
+    };
+    match value {
+      Some(value) if sanitize_values => output.push_str(&sanitize_filename(value)),
+      Some(value) => output.push_str(value),
+      None => {
+        output.push_str(&rest[start..start + end + 4]);
+        log_unknown_template_key(key, app_log_path);
+      }
+    }
+    rest = &after[end + 2..];
+  }
+  output.push_str(rest);
+  output
+}
+
+fn log_unknown_template_key(key: &str, app_log_path: &Path) {
+  let known = UNKNOWN_TEMPLATE_KEYS.get_or_init(|| Mutex::new(HashSet::new()));
+  let first_seen = known
+    .lock()
+    .map(|mut guard| guard.insert(key.to_string()))
+    .unwrap_or(false);
+  if first_seen {
+    append_log(
+      app_log_path,
+      &format!(
+        "baidu_sync_template_unknown key={} supported={}",
+        key,
+        BAIDU_TEMPLATE_KEYS.join(",")
+      ),
+    );
+  }
+}
+
+fn expand_baidu_path_template(
+  template: &str,
+  values: &BaiduTemplateValues,
+  app_log_path: &Path,
+) -> String {
+  normalize_baidu_path(&expand_baidu_template(template, values, true, app_log_path))
+}
+
+fn load_anchor_nickname(db: &Db, room_id: &str) -> Option<String> {
+  db.with_conn(|conn| {
+    conn.query_row(
+      "SELECT nickname FROM anchor WHERE uid = ?1",
+      [room_id],
+      |row| row.get::<_, Option<String>>(0),
+    )
+  })
+  .ok()
+  .flatten()
+}
+
 fn load_room_baidu_sync_config(
   db: &Db,
   room_id: &str,
@@ -1397,7 +1818,7 @@ fn run_baidu_pcs_upload<F>(
   mut on_progress: F,
 ) -> Result<CommandOutput, String>
 where
-  F: FnMut(f64),
+  F: FnMut(f64, u64, u64),
 {
   let mut child = Command::new(exec_path)
     .args(args)
@@ -1449,15 +1870,15 @@ where
         continue;
       }
       let line = String::from_utf8_lossy(&line_bytes);
-      if let Some(progress) = parse_progress_line(&line) {
-        on_progress(progress);
+      if let Some((progress, uploaded, total)) = parse_progress_line(&line) {
+        on_progress(progress, uploaded, total);
       }
     }
   }
   if !pending.is_empty() {
     let line = String::from_utf8_lossy(&pending);
-    if let Some(progress) = parse_progress_line(&line) {
-      on_progress(progress);
+    if let Some((progress, uploaded, total)) = parse_progress_line(&line) {
+      on_progress(progress, uploaded, total);
     }
   }
 
@@ -1478,7 +1899,7 @@ where
   Err(format!("BaiduPCS-Go 执行失败: {}", stderr_output.trim()))
 }
 
-fn parse_progress_line(line: &str) -> Option<f64> {
+fn parse_progress_line(line: &str) -> Option<(f64, u64, u64)> {
   let cleaned = strip_ansi(line).replace('\r', " ").replace('\n', " ");
   let arrow_pos = cleaned.find('↑')?;
   let after = cleaned[arrow_pos + '↑'.len_utf8()..].trim_start();
@@ -1496,7 +1917,7 @@ fn parse_progress_line(line: &str) -> Option<f64> {
   if percent > 99.0 {
     percent = 99.0;
   }
-  Some(percent)
+  Some((percent, uploaded, total))
 }
 
 fn strip_ansi(input: &str) -> String {
@@ -1569,6 +1990,37 @@ fn parse_meta_size(output: &str) -> Option<u64> {
   None
 }
 
+fn parse_meta_md5(output: &str) -> Option<String> {
+  for line in output.lines() {
+    if !line.to_ascii_lowercase().contains("md5") {
+      continue;
+    }
+    let value = line
+      .split_whitespace()
+      .find(|value| value.len() == 32 && value.chars().all(|ch| ch.is_ascii_hexdigit()));
+    if let Some(value) = value {
+      return Some(value.to_ascii_lowercase());
+    }
+  }
+  None
+}
+
+fn compute_file_md5(path: &Path) -> Result<String, String> {
+  let mut file = std::fs::File::open(path).map_err(|err| format!("读取本地文件失败: {}", err))?;
+  let mut context = md5::Context::new();
+  let mut buffer = vec![0u8; 1024 * 1024];
+  loop {
+    let read_size = file
+      .read(&mut buffer)
+      .map_err(|err| format!("读取本地文件失败: {}", err))?;
+    if read_size == 0 {
+      break;
+    }
+    context.consume(&buffer[..read_size]);
+  }
+  Ok(format!("{:x}", context.compute()))
+}
+
 fn parse_who_output(output: &str) -> (bool, Option<String>, Option<String>) {
   if output.contains("请先登录") || output.contains("uid: 0") {
     return (false, None, None);
@@ -1,8 +1,11 @@
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, ACCEPT_LANGUAGE, REFERER, USER_AGENT};
+use reqwest::header::{
+  HeaderMap, HeaderValue, ACCEPT, ACCEPT_LANGUAGE, CONTENT_TYPE, REFERER, USER_AGENT,
+};
 use reqwest::Client;
 use serde_json::Value;
 use std::sync::Mutex;
 
+use crate::config::{live_referer_or, user_agent_or, DEFAULT_USER_AGENT};
 use crate::login_store::AuthInfo;
 use crate::bilibili::signer::WbiSigner;
 
@@ -17,7 +20,7 @@ pub struct BilibiliClient {
 impl BilibiliClient {
   pub fn new() -> Self {
     Self {
-      client: Client::new(),
+      client: crate::config::build_http_client(),
       base_url: "https://api.bilibili.com".to_string(),
       passport_base_url: "https://passport.bilibili.com".to_string(),
       signer: WbiSigner::new(),
@@ -61,7 +64,9 @@ impl BilibiliClient {
       );
     }
     if url.contains("live.bilibili.com") {
-      headers.insert(REFERER, HeaderValue::from_static("https://live.bilibili.com/"));
+      if let Ok(value) = HeaderValue::from_str(&live_referer_or("https://live.bilibili.com/")) {
+        headers.insert(REFERER, value);
+      }
       headers.insert("Origin", HeaderValue::from_static("https://live.bilibili.com"));
     }
 
@@ -101,7 +106,9 @@ impl BilibiliClient {
       );
     }
     if url.contains("live.bilibili.com") {
-      headers.insert(REFERER, HeaderValue::from_static("https://live.bilibili.com/"));
+      if let Ok(value) = HeaderValue::from_str(&live_referer_or("https://live.bilibili.com/")) {
+        headers.insert(REFERER, value);
+      }
       headers.insert("Origin", HeaderValue::from_static("https://live.bilibili.com"));
     }
 
@@ -120,6 +127,46 @@ impl BilibiliClient {
     parse_response(&response)
   }
 
+  pub async fn post_form(
+    &self,
+    url: &str,
+    params: &[(String, String)],
+    form: &[(String, String)],
+    auth: Option<&AuthInfo>,
+  ) -> Result<Value, String> {
+    let full_url = if params.is_empty() {
+      url.to_string()
+    } else {
+      format!("{}?{}", url, build_query(params))
+    };
+
+    let mut headers = default_headers();
+    if let Some(auth) = auth {
+      headers.insert(
+        "Cookie",
+        HeaderValue::from_str(&auth.cookie).map_err(|_| "Invalid cookie header".to_string())?,
+      );
+    }
+    headers.insert(
+      CONTENT_TYPE,
+      HeaderValue::from_static("application/x-www-form-urlencoded"),
+    );
+
+    let response = self
+      .client
+      .post(full_url)
+      .headers(headers)
+      .body(build_query(form))
+      .send()
+      .await
+      .map_err(|err| format!("Request failed: {}", err))?
+      .text()
+      .await
+      .map_err(|err| format!("Failed to read response: {}", err))?;
+
+    parse_response(&response)
+  }
+
   pub fn cached_buvid3(&self) -> Option<String> {
     self
       .buvid3
@@ -195,12 +242,9 @@ fn parse_response(response: &str) -> Result<Value, String> {
 
 fn default_headers() -> HeaderMap {
   let mut headers = HeaderMap::new();
-  headers.insert(
-    USER_AGENT,
-    HeaderValue::from_static(
-      "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/132.0.0.0 Safari/537.36 Edg/132.0.0.0",
-    ),
-  );
+  if let Ok(value) = HeaderValue::from_str(&user_agent_or(DEFAULT_USER_AGENT)) {
+    headers.insert(USER_AGENT, value);
+  }
   headers.insert(ACCEPT, HeaderValue::from_static("application/json, text/javascript, */*; q=0.01"));
   headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("zh-CN"));
   headers
@@ -1,10 +1,16 @@
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tauri::State;
 
 use crate::api::ApiResponse;
-use crate::commands::settings::{default_live_settings, load_live_settings_from_db};
-use crate::live_recorder::{fetch_room_info, start_recording, stop_recording, LiveContext};
+use crate::commands::settings::{
+  default_live_settings, load_live_settings_from_db, LiveSettings, LiveSettingsOverride,
+};
+use crate::live_recorder::{
+  auto_record_quota_exceeded, fetch_room_info, start_recording, stop_recording, LiveContext,
+};
 use crate::utils::{append_log, now_rfc3339};
 use crate::AppState;
 
@@ -33,11 +39,48 @@ pub struct Anchor {
   pub auto_record: bool,
   pub baidu_sync_enabled: bool,
   pub baidu_sync_path: Option<String>,
+  pub max_daily_recording_hours: f64,
+  pub settings_override: Option<LiveSettingsOverride>,
   pub recording_status: Option<String>,
   pub recording_file: Option<String>,
   pub recording_start_time: Option<String>,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnchorExportItem {
+  #[serde(alias = "uid")]
+  pub room_id: String,
+  #[serde(default)]
+  pub nickname: Option<String>,
+  #[serde(default = "default_import_auto_record")]
+  pub auto_record: bool,
+  #[serde(default)]
+  pub baidu_sync_enabled: bool,
+  #[serde(default)]
+  pub baidu_sync_path: Option<String>,
+  #[serde(default)]
+  pub max_daily_recording_hours: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnchorImportResult {
+  pub imported: usize,
+  pub skipped: usize,
+  pub invalid: Vec<String>,
+}
+
+fn default_import_auto_record() -> bool {
+  true
+}
+
+fn parse_settings_override(raw: Option<String>) -> Option<LiveSettingsOverride> {
+  raw
+    .and_then(|value| serde_json::from_str::<LiveSettingsOverride>(&value).ok())
+    .filter(|value| !value.is_empty())
+}
+
 struct AnchorLiveInfo {
   nickname: Option<String>,
   live_status: i64,
@@ -70,79 +113,151 @@ pub async fn anchor_subscribe(
     if uid.is_empty() {
       continue;
     }
-
-    let info = match fetch_live_info(&state, &uid).await {
-      Ok(value) => value,
-      Err(_) => AnchorLiveInfo {
-        nickname: None,
-        live_status: 0,
-        avatar_url: None,
-        live_title: None,
-        category: None,
-      },
-    };
-
-    let result = state.db.with_conn(|conn| {
-      conn.execute(
-        "INSERT INTO anchor (uid, nickname, live_status, last_check_time, create_time, update_time) \
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
-         ON CONFLICT(uid) DO UPDATE SET \
-         nickname = excluded.nickname, \
-         live_status = excluded.live_status, \
-         last_check_time = excluded.last_check_time, \
-         update_time = excluded.update_time",
-        (
-          &uid,
-          info.nickname.as_deref(),
-          info.live_status,
-          &now,
-          &now,
-          &now,
-        ),
-      )?;
-      conn.execute(
-        "INSERT INTO live_room_settings (room_id, auto_record, update_time) VALUES (?1, 1, ?2) \
-         ON CONFLICT(room_id) DO UPDATE SET update_time = excluded.update_time",
-        (&uid, &now),
-      )?;
-      Ok(())
-    });
-
-    if let Err(err) = result {
+    if let Err(err) = subscribe_anchor(&state, &context, &settings, &uid, &now, None).await {
       append_log(
         &state.app_log_path,
         &format!("anchor_subscribe_error uid={} err={}", uid, err),
       );
       return Ok(ApiResponse::error("Failed to subscribe anchor"));
     }
+  }
 
-    if info.live_status == 1 {
-      if let Ok(room_info) = fetch_room_info(&state.bilibili, &uid).await {
-        if !state.live_runtime.is_recording(&uid) {
-          if let Err(err) = start_recording(context.clone(), &uid, room_info, settings.clone()) {
-            append_log(
-              &state.app_log_path,
-              &format!("auto_record_subscribe_failed room={} err={}", uid, err),
-            );
-          } else {
-            append_log(
-              &state.app_log_path,
-              &format!("auto_record_subscribe_start room={}", uid),
-            );
-          }
-        }
+  Ok(anchor_list(state))
+}
+
+#[tauri::command]
+pub fn anchor_export(state: State<'_, AppState>) -> ApiResponse<Vec<AnchorExportItem>> {
+  match state.db.with_conn(|conn| {
+    let mut stmt = conn.prepare(
+      "SELECT a.uid, a.nickname, IFNULL(l.auto_record, 1), IFNULL(l.baidu_sync_enabled, 0), l.baidu_sync_path, IFNULL(l.max_daily_recording_hours, 0) \
+       FROM anchor a LEFT JOIN live_room_settings l ON a.uid = l.room_id ORDER BY a.id ASC",
+    )?;
+    let items = stmt
+      .query_map([], |row| {
+        Ok(AnchorExportItem {
+          room_id: row.get(0)?,
+          nickname: row.get(1)?,
+          auto_record: row.get::<_, i64>(2)? != 0,
+          baidu_sync_enabled: row.get::<_, i64>(3)? != 0,
+          baidu_sync_path: row.get(4)?,
+          max_daily_recording_hours: row.get(5)?,
+        })
+      })?
+      .collect::<Result<Vec<_>, _>>()?;
+    Ok(items)
+  }) {
+    Ok(items) => ApiResponse::success(items),
+    Err(err) => ApiResponse::error(format!("Failed to export anchors: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub async fn anchor_import(
+  state: State<'_, AppState>,
+  json: String,
+  mode: Option<String>,
+) -> Result<ApiResponse<AnchorImportResult>, String> {
+  let items: Vec<AnchorExportItem> = match serde_json::from_str(&json) {
+    Ok(items) => items,
+    Err(err) => return Ok(ApiResponse::error(format!("导入数据格式错误: {}", err))),
+  };
+  let replace = match mode.as_deref().map(|value| value.trim()).unwrap_or("merge") {
+    "" | "merge" => false,
+    "replace" => true,
+    other => return Ok(ApiResponse::error(format!("不支持的导入模式: {}", other))),
+  };
+  let settings = load_live_settings_from_db(&state.db).unwrap_or_else(|_| default_live_settings());
+  let context = LiveContext {
+    db: state.db.clone(),
+    bilibili: state.bilibili.clone(),
+    login_store: state.login_store.clone(),
+    app_log_path: state.app_log_path.clone(),
+    live_runtime: state.live_runtime.clone(),
+  };
+  let existing: HashSet<String> = match state.db.with_conn(|conn| {
+    let mut stmt = conn.prepare("SELECT uid FROM anchor")?;
+    let list = stmt
+      .query_map([], |row| row.get::<_, String>(0))?
+      .collect::<Result<HashSet<_>, _>>()?;
+    Ok(list)
+  }) {
+    Ok(list) => list,
+    Err(err) => return Ok(ApiResponse::error(format!("Failed to read anchors: {}", err))),
+  };
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "anchor_import_start count={} mode={} existing={}",
+      items.len(),
+      if replace { "replace" } else { "merge" },
+      existing.len()
+    ),
+  );
+
+  let mut result = AnchorImportResult {
+    imported: 0,
+    skipped: 0,
+    invalid: Vec::new(),
+  };
+  let mut valid_items = Vec::new();
+  let mut seen = HashSet::new();
+  for mut item in items {
+    item.room_id = item.room_id.trim().to_string();
+    if !is_valid_room_id(&item.room_id) {
+      result.invalid.push(item.room_id);
+      continue;
+    }
+    if !seen.insert(item.room_id.clone()) || (!replace && existing.contains(&item.room_id)) {
+      result.skipped += 1;
+      continue;
+    }
+    valid_items.push(item);
+  }
+
+  if replace {
+    for uid in existing.iter().filter(|uid| !seen.contains(*uid)) {
+      stop_recording(context.clone(), uid, "导入替换订阅");
+      let removed = state.db.with_conn(|conn| {
+        conn.execute("DELETE FROM anchor WHERE uid = ?1", [uid.as_str()])?;
+        conn.execute("DELETE FROM live_room_settings WHERE room_id = ?1", [uid.as_str()])?;
+        Ok(())
+      });
+      if let Err(err) = removed {
+        return Ok(ApiResponse::error(format!("Failed to replace anchors: {}", err)));
       }
     }
   }
 
-  Ok(anchor_list(state))
+  let now = now_rfc3339();
+  for item in valid_items {
+    match subscribe_anchor(&state, &context, &settings, &item.room_id, &now, Some(&item)).await {
+      Ok(()) => result.imported += 1,
+      Err(err) => {
+        append_log(
+          &state.app_log_path,
+          &format!("anchor_import_error uid={} err={}", item.room_id, err),
+        );
+        result.skipped += 1;
+      }
+    }
+  }
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "anchor_import_done imported={} skipped={} invalid={}",
+      result.imported,
+      result.skipped,
+      result.invalid.len()
+    ),
+  );
+  Ok(ApiResponse::success(result))
 }
 
 #[tauri::command]
 pub fn anchor_list(state: State<'_, AppState>) -> ApiResponse<Vec<Anchor>> {
   match state.db.with_conn(|conn| {
     let mut stmt = conn.prepare(
-      "SELECT a.id, a.uid, a.nickname, a.live_status, a.last_check_time, a.create_time, a.update_time, IFNULL(l.auto_record, 1), IFNULL(l.baidu_sync_enabled, 0), l.baidu_sync_path \
+      "SELECT a.id, a.uid, a.nickname, a.live_status, a.last_check_time, a.create_time, a.update_time, IFNULL(l.auto_record, 1), IFNULL(l.baidu_sync_enabled, 0), l.baidu_sync_path, IFNULL(l.max_daily_recording_hours, 0), l.settings_override \
        FROM anchor a LEFT JOIN live_room_settings l ON a.uid = l.room_id ORDER BY a.id DESC",
     )?;
     let anchors = stmt
@@ -165,6 +280,8 @@ pub fn anchor_list(state: State<'_, AppState>) -> ApiResponse<Vec<Anchor>> {
           auto_record: auto_record != 0,
           baidu_sync_enabled: sync_enabled != 0,
           baidu_sync_path: row.get(9)?,
+          max_daily_recording_hours: row.get(10)?,
+          settings_override: parse_settings_override(row.get(11)?),
           recording_status: record_info.as_ref().map(|_| "RECORDING".to_string()),
           recording_file: record_info.as_ref().map(|info| info.file_path.clone()),
           recording_start_time: record_info.map(|info| info.start_time),
@@ -211,7 +328,7 @@ pub async fn anchor_check(state: State<'_, AppState>) -> Result<ApiResponse<Vec<
   };
   let anchors = match state.db.with_conn(|conn| {
     let mut stmt = conn.prepare(
-      "SELECT a.id, a.uid, a.nickname, a.live_status, a.last_check_time, a.create_time, a.update_time, IFNULL(l.auto_record, 1), IFNULL(l.baidu_sync_enabled, 0), l.baidu_sync_path \
+      "SELECT a.id, a.uid, a.nickname, a.live_status, a.last_check_time, a.create_time, a.update_time, IFNULL(l.auto_record, 1), IFNULL(l.baidu_sync_enabled, 0), l.baidu_sync_path, IFNULL(l.max_daily_recording_hours, 0), l.settings_override \
        FROM anchor a LEFT JOIN live_room_settings l ON a.uid = l.room_id ORDER BY a.id DESC",
     )?;
     let list = stmt
@@ -231,6 +348,8 @@ pub async fn anchor_check(state: State<'_, AppState>) -> Result<ApiResponse<Vec<
           auto_record: row.get::<_, i64>(7)? != 0,
           baidu_sync_enabled: sync_enabled != 0,
           baidu_sync_path: row.get(9)?,
+          max_daily_recording_hours: row.get(10)?,
+          settings_override: parse_settings_override(row.get(11)?),
           recording_status: None,
           recording_file: None,
           recording_start_time: None,
@@ -287,12 +406,18 @@ pub async fn anchor_check(state: State<'_, AppState>) -> Result<ApiResponse<Vec<
       auto_record: anchor.auto_record,
       baidu_sync_enabled: anchor.baidu_sync_enabled,
       baidu_sync_path: anchor.baidu_sync_path,
+      max_daily_recording_hours: anchor.max_daily_recording_hours,
+      settings_override: anchor.settings_override,
       recording_status: record_info.as_ref().map(|_| "RECORDING".to_string()),
       recording_file: record_info.as_ref().map(|info| info.file_path.clone()),
       recording_start_time: record_info.map(|info| info.start_time),
     });
 
-    if anchor.auto_record && info.live_status == 1 && !state.live_runtime.is_recording(&room_id) {
+    if anchor.auto_record
+      && info.live_status == 1
+      && !state.live_runtime.is_recording(&room_id)
+      && !auto_record_quota_exceeded(&state.db, &state.app_log_path, &room_id)
+    {
       if let Ok(room_info) = fetch_room_info(&state.bilibili, &room_id).await {
         if let Err(err) = start_recording(context.clone(), &room_id, room_info, settings.clone()) {
           append_log(
@@ -312,6 +437,99 @@ pub async fn anchor_check(state: State<'_, AppState>) -> Result<ApiResponse<Vec<
   Ok(ApiResponse::success(updated))
 }
 
+/// 订阅单个直播间；`preset` 来自导入数据时同时写入录制与同步设置，并按其自动录制开关决定是否立即开录。
+async fn subscribe_anchor(
+  state: &State<'_, AppState>,
+  context: &LiveContext,
+  settings: &LiveSettings,
+  uid: &str,
+  now: &str,
+  preset: Option<&AnchorExportItem>,
+) -> Result<(), String> {
+  let info = match fetch_live_info(state, uid).await {
+    Ok(value) => value,
+    Err(_) => AnchorLiveInfo {
+      nickname: preset.and_then(|item| item.nickname.clone()),
+      live_status: 0,
+      avatar_url: None,
+      live_title: None,
+      category: None,
+    },
+  };
+
+  state
+    .db
+    .with_conn(|conn| {
+      conn.execute(
+        "INSERT INTO anchor (uid, nickname, live_status, last_check_time, create_time, update_time) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+         ON CONFLICT(uid) DO UPDATE SET \
+         nickname = excluded.nickname, \
+         live_status = excluded.live_status, \
+         last_check_time = excluded.last_check_time, \
+         update_time = excluded.update_time",
+        (uid, info.nickname.as_deref(), info.live_status, now, now, now),
+      )?;
+      match preset {
+        Some(item) => {
+          conn.execute(
+            "INSERT INTO live_room_settings (room_id, auto_record, baidu_sync_enabled, baidu_sync_path, max_daily_recording_hours, update_time) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+             ON CONFLICT(room_id) DO UPDATE SET \
+             auto_record = excluded.auto_record, \
+             baidu_sync_enabled = excluded.baidu_sync_enabled, \
+             baidu_sync_path = excluded.baidu_sync_path, \
+             max_daily_recording_hours = excluded.max_daily_recording_hours, \
+             update_time = excluded.update_time",
+            (
+              uid,
+              item.auto_record as i64,
+              item.baidu_sync_enabled as i64,
+              item.baidu_sync_path.as_deref(),
+              item.max_daily_recording_hours.clamp(0.0, 24.0),
+              now,
+            ),
+          )?;
+        }
+        None => {
+          conn.execute(
+            "INSERT INTO live_room_settings (room_id, auto_record, update_time) VALUES (?1, 1, ?2) \
+             ON CONFLICT(room_id) DO UPDATE SET update_time = excluded.update_time",
+            (uid, now),
+          )?;
+        }
+      }
+      Ok(())
+    })
+    .map_err(|err| err.to_string())?;
+
+  let auto_record = preset.map(|item| item.auto_record).unwrap_or(true);
+  if auto_record && info.live_status == 1 {
+    if let Ok(room_info) = fetch_room_info(&state.bilibili, uid).await {
+      if !state.live_runtime.is_recording(uid) {
+        if let Err(err) = start_recording(context.clone(), uid, room_info, settings.clone()) {
+          append_log(
+            &state.app_log_path,
+            &format!("auto_record_subscribe_failed room={} err={}", uid, err),
+          );
+        } else {
+          append_log(
+            &state.app_log_path,
+            &format!("auto_record_subscribe_start room={}", uid),
+          );
+        }
+      }
+    }
+  }
+  Ok(())
+}
+
+fn is_valid_room_id(value: &str) -> bool {
+  !value.is_empty()
+    && value.len() <= 20
+    && value.chars().all(|ch| ch.is_ascii_digit())
+    && !value.trim_start_matches('0').is_empty()
+}
 
 async fn fetch_live_info(
   state: &State<'_, AppState>,
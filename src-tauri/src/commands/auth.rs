@@ -1,6 +1,4 @@
 use std::collections::HashMap;
-use std::fs::OpenOptions;
-use std::io::Write;
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::time::Duration;
@@ -66,12 +64,13 @@ pub async fn auth_sms_login(
   code: String,
   captcha_key: String,
 ) -> Result<ApiResponse<i32>, String> {
-  let client = reqwest::Client::new();
+  let client = crate::config::build_http_client();
   let mut headers = HeaderMap::new();
-  headers.insert(
-    USER_AGENT,
-    HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36"),
-  );
+  if let Ok(value) = HeaderValue::from_str(&crate::config::user_agent_or(
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
+  )) {
+    headers.insert(USER_AGENT, value);
+  }
 
   let response = match client
     .post("https://passport.bilibili.com/x/passport-login/web/login/sms")
@@ -141,12 +140,13 @@ pub async fn auth_pwd_login(
   validate: String,
   seccode: String,
 ) -> Result<ApiResponse<i32>, String> {
-  let client = reqwest::Client::new();
+  let client = crate::config::build_http_client();
   let mut headers = HeaderMap::new();
-  headers.insert(
-    USER_AGENT,
-    HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36"),
-  );
+  if let Ok(value) = HeaderValue::from_str(&crate::config::user_agent_or(
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
+  )) {
+    headers.insert(USER_AGENT, value);
+  }
 
   let response = match client
     .post("https://passport.bilibili.com/x/passport-login/web/login")
@@ -353,7 +353,7 @@ async fn poll_qrcode_once_inner(
   qrcode_key: &str,
   log_path: Option<&Path>,
 ) -> Result<PollResult, String> {
-  let client = reqwest::Client::new();
+  let client = crate::config::build_http_client();
   let url = format!(
     "{}{}?qrcode_key={}&source=main-fe-header",
     bilibili.passport_base_url(),
@@ -364,7 +364,7 @@ async fn poll_qrcode_once_inner(
     .get(&url)
     .header(
       USER_AGENT,
-      "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
+      crate::config::user_agent_or("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36"),
     )
     .send()
     .await
@@ -696,13 +696,14 @@ fn build_cookie_from_headers(headers: &HeaderMap) -> Option<String> {
 async fn exchange_cookie_from_url(url: &str, log_path: Option<&Path>) -> Result<Option<String>, String> {
   let client = reqwest::Client::builder()
     .redirect(reqwest::redirect::Policy::none())
+    .proxy(crate::config::http_proxy())
     .build()
     .map_err(|err| format!("Cookie exchange client failed: {}", err))?;
   let response = client
     .get(url)
     .header(
       USER_AGENT,
-      "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
+      crate::config::user_agent_or("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36"),
     )
     .send()
     .await
@@ -794,9 +795,7 @@ fn now_millis() -> u128 {
 
 fn append_auth_log(path: Option<&Path>, line: &str) {
   let Some(path) = path else { return; };
-  if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
-    let _ = writeln!(file, "{}", line);
-  }
+  crate::utils::write_log_line(path, line);
 }
 
 fn summarize_cookie_keys(cookie: &str) -> String {
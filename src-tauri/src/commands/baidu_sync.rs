@@ -69,6 +69,8 @@ pub struct BaiduRemoteListRequest {
 #[serde(rename_all = "camelCase")]
 pub struct BaiduSyncUpdateRequest {
   pub concurrency: Option<i64>,
+  pub path_template: Option<String>,
+  pub filename_template: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -285,6 +287,41 @@ pub fn baidu_sync_retry(state: State<'_, AppState>, task_id: i64) -> ApiResponse
   }
 }
 
+#[tauri::command]
+pub async fn baidu_sync_verify(
+  state: State<'_, AppState>,
+  task_id: i64,
+) -> Result<ApiResponse<baidu_sync::BaiduSyncVerifyReport>, String> {
+  let db = state.db.clone();
+  let app_log_path = state.app_log_path.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    baidu_sync::verify_baidu_sync_task(db.as_ref(), app_log_path.as_ref(), task_id)
+  })
+  .await
+  .map_err(|_| "校验任务执行失败".to_string())?;
+  match result {
+    Ok(report) => Ok(ApiResponse::success(report)),
+    Err(err) => Ok(ApiResponse::error(err)),
+  }
+}
+
+#[tauri::command]
+pub async fn baidu_sync_check(
+  state: State<'_, AppState>,
+) -> Result<ApiResponse<baidu_sync::BaiduPcsCheckReport>, String> {
+  let db = state.db.clone();
+  let app_log_path = state.app_log_path.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    baidu_sync::check_baidu_pcs(db.as_ref(), app_log_path.as_ref())
+  })
+  .await
+  .map_err(|_| "检测任务执行失败".to_string())?;
+  match result {
+    Ok(report) => Ok(ApiResponse::success(report)),
+    Err(err) => Ok(ApiResponse::error(err)),
+  }
+}
+
 #[tauri::command]
 pub fn baidu_sync_cancel(state: State<'_, AppState>, task_id: i64) -> ApiResponse<String> {
   match baidu_sync::cancel_baidu_sync_task(&state.db, task_id) {
@@ -314,7 +351,11 @@ pub fn baidu_sync_update_settings(
   state: State<'_, AppState>,
   request: Option<BaiduSyncUpdateRequest>,
 ) -> ApiResponse<String> {
-  let request = request.unwrap_or(BaiduSyncUpdateRequest { concurrency: None });
+  let request = request.unwrap_or(BaiduSyncUpdateRequest {
+    concurrency: None,
+    path_template: None,
+    filename_template: None,
+  });
   let mut settings = match baidu_sync::load_baidu_sync_settings(&state.db) {
     Ok(value) => value,
     Err(err) => return ApiResponse::error(err),
@@ -322,6 +363,12 @@ pub fn baidu_sync_update_settings(
   if let Some(concurrency) = request.concurrency {
     settings.concurrency = concurrency.max(1);
   }
+  if let Some(path_template) = request.path_template {
+    settings.path_template = path_template.trim().to_string();
+  }
+  if let Some(filename_template) = request.filename_template {
+    settings.filename_template = filename_template.trim().to_string();
+  }
   match baidu_sync::update_baidu_sync_settings(&state.db, &settings) {
     Ok(()) => ApiResponse::success("ok".to_string()),
     Err(err) => ApiResponse::error(err),
@@ -3,31 +3,46 @@ use std::io::{BufReader, Read};
 use std::net::TcpListener;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use reqwest::blocking::Client;
 use serde::de::DeserializeOwned;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 use tokio::time::sleep;
 use url::Url;
 
 use crate::api::ApiResponse;
 use crate::config::{default_download_dir, resolve_aria2c_candidates};
 use crate::commands::settings::load_download_settings_from_db;
-use crate::ffmpeg::{run_ffmpeg, run_ffmpeg_with_progress, run_ffprobe_json};
+use crate::ffmpeg::{run_ffmpeg_until, run_ffmpeg_with_progress, run_ffprobe_json};
 use crate::login_store::AuthInfo;
 use crate::utils::{append_log, build_output_path, now_rfc3339, sanitize_filename};
 use crate::bilibili::client::BilibiliClient;
 use crate::db::Db;
+use crate::desktop_notify::{self, NotifyCategory};
 use crate::login_store::LoginStore;
+use crate::processing::{clip_source_to_file, decide_clip_copy, parse_clip_range, ClipSource};
 use crate::AppState;
 
+const DOWNLOAD_PROGRESS_EVENT: &str = "download_progress";
+const DOWNLOAD_CLIP_RESULT_EVENT: &str = "download_clip_result";
+const DOWNLOAD_PAUSED_ERROR: &str = "下载已暂停，可重试续传";
+const DOWNLOAD_CANCELLED_MESSAGE: &str = "下载已取消";
+const ARIA2C_PAUSE_EXIT_WAIT_SECS: u64 = 5;
+const DOWNLOAD_PROGRESS_EMIT_INTERVAL_MILLIS: u64 = 1000;
+const DOWNLOAD_CLIP_POLL_SECS: u64 = 2;
+
+static DOWNLOAD_PAUSE_REQUESTS: OnceLock<Mutex<HashSet<i64>>> = OnceLock::new();
+static DOWNLOAD_CANCEL_REQUESTS: OnceLock<Mutex<HashSet<i64>>> = OnceLock::new();
+static DOWNLOAD_FFMPEG_RUNNING: OnceLock<Mutex<HashSet<i64>>> = OnceLock::new();
+
 #[derive(Clone)]
 struct DownloadContext {
+  app_handle: AppHandle,
   db: Arc<Db>,
   bilibili: Arc<BilibiliClient>,
   login_store: Arc<LoginStore>,
@@ -39,6 +54,7 @@ struct DownloadContext {
 impl DownloadContext {
   fn new(state: &State<'_, AppState>) -> Self {
     Self {
+      app_handle: state.app_handle.clone(),
       db: state.db.clone(),
       bilibili: state.bilibili.clone(),
       login_store: state.login_store.clone(),
@@ -50,6 +66,7 @@ impl DownloadContext {
 
   fn from_state(state: &AppState) -> Self {
     Self {
+      app_handle: state.app_handle.clone(),
       db: state.db.clone(),
       bilibili: state.bilibili.clone(),
       login_store: state.login_store.clone(),
@@ -60,6 +77,24 @@ impl DownloadContext {
   }
 }
 
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DownloadProgressEvent {
+  id: i64,
+  downloaded_bytes: u64,
+  total_bytes: u64,
+  speed: u64,
+  progress: i64,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DownloadClipResultEvent {
+  record_id: i64,
+  output_path: Option<String>,
+  error: Option<String>,
+}
+
 #[derive(Clone)]
 struct StreamCandidate {
   id: Option<i64>,
@@ -77,6 +112,9 @@ pub struct DownloadConfig {
   pub codec: Option<String>,
   pub format: Option<String>,
   pub content: Option<String>,
+  /// 清晰度上限（qn），优先清晰度不可用时也不会超过该值。
+  #[serde(default)]
+  pub max_resolution: Option<i64>,
 }
 
 #[derive(Deserialize, Clone)]
@@ -91,10 +129,28 @@ pub struct DownloadPart {
 #[serde(rename_all = "camelCase")]
 pub struct DownloadRequest {
   pub video_url: String,
+  #[serde(default)]
   pub parts: Vec<DownloadPart>,
+  /// 按分P序号（从 1 开始）选择，优先于 parts。
+  #[serde(default)]
+  pub part_indices: Option<Vec<i64>>,
   pub config: DownloadConfig,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadClipRequest {
+  pub video_url: String,
+  pub cid: Option<i64>,
+  pub page: Option<i64>,
+  pub start_time: Option<String>,
+  pub end_time: Option<String>,
+  pub output_path: String,
+  pub resolution: Option<String>,
+  pub codec: Option<String>,
+  pub max_resolution: Option<i64>,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmissionVideoPart {
@@ -154,6 +210,9 @@ pub struct VideoDownloadRecord {
   pub progress: i64,
   pub progress_total: i64,
   pub progress_done: i64,
+  pub priority: i64,
+  /// 失败或取消原因，目前仅在全部取消时写入。
+  pub status_message: Option<String>,
   pub create_time: String,
   pub update_time: String,
 }
@@ -170,6 +229,7 @@ struct PendingDownloadRecord {
   cid: Option<i64>,
   content: Option<String>,
   progress: i64,
+  max_resolution: Option<i64>,
 }
 
 #[derive(Clone)]
@@ -217,11 +277,239 @@ pub async fn download_video(
   }
 }
 
+/// 只下载并裁剪单个分P，不创建投稿任务；创建下载任务后立即返回记录 ID，
+/// 剪辑结果通过 `download_clip_result` 事件通知。
+#[tauri::command]
+pub async fn download_clip(
+  state: State<'_, AppState>,
+  request: DownloadClipRequest,
+) -> Result<ApiResponse<i64>, String> {
+  let context = DownloadContext::new(&state);
+  match start_download_clip(context, request).await {
+    Ok(record_id) => Ok(ApiResponse::success(record_id)),
+    Err(err) => Ok(ApiResponse::error(err)),
+  }
+}
+
+async fn start_download_clip(
+  context: DownloadContext,
+  request: DownloadClipRequest,
+) -> Result<i64, String> {
+  let output_path = request.output_path.trim();
+  if output_path.is_empty() {
+    return Err("请选择输出路径".to_string());
+  }
+  let output_path = PathBuf::from(output_path);
+  let (start_time, end_time) =
+    parse_clip_range(request.start_time.as_deref(), request.end_time.as_deref())?;
+  let (bvid, aid) = parse_video_id(request.video_url.trim());
+  if bvid.is_none() && aid.is_none() {
+    return Err("无法识别视频 BV/AV 号".to_string());
+  }
+  let part = resolve_clip_part(&context, bvid.as_deref(), aid.as_deref(), &request).await?;
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "download_clip_start video={} cid={} start={} end={} output={}",
+      request.video_url.trim(),
+      part.cid,
+      request.start_time.as_deref().unwrap_or(""),
+      request.end_time.as_deref().unwrap_or(""),
+      output_path.to_string_lossy()
+    ),
+  );
+  let download_request = DownloadRequest {
+    video_url: request.video_url.trim().to_string(),
+    parts: vec![part],
+    part_indices: None,
+    config: DownloadConfig {
+      download_name: None,
+      download_path: None,
+      resolution: request.resolution.clone(),
+      codec: request.codec.clone(),
+      format: None,
+      content: None,
+      max_resolution: request.max_resolution,
+    },
+  };
+  let record = create_download_tasks(context.clone(), download_request)
+    .await?
+    .into_iter()
+    .next()
+    .ok_or_else(|| "No download task created".to_string())?;
+  let record_id = record.id;
+  let start_time = start_time.map(|(value, _)| value);
+  let end_time = end_time.map(|(value, _)| value);
+  tauri::async_runtime::spawn(async move {
+    let result =
+      finish_download_clip(&context, record_id, start_time, end_time, &output_path).await;
+    let event = match result {
+      Ok(output) => DownloadClipResultEvent {
+        record_id,
+        output_path: Some(output),
+        error: None,
+      },
+      Err(err) => DownloadClipResultEvent {
+        record_id,
+        output_path: None,
+        error: Some(err),
+      },
+    };
+    if let Err(err) = context.app_handle.emit(DOWNLOAD_CLIP_RESULT_EVENT, event) {
+      append_log(
+        &context.app_log_path,
+        &format!("download_clip_emit_fail record_id={} err={}", record_id, err),
+      );
+    }
+  });
+  Ok(record_id)
+}
+
+async fn finish_download_clip(
+  context: &DownloadContext,
+  record_id: i64,
+  start_time: Option<String>,
+  end_time: Option<String>,
+  output_path: &Path,
+) -> Result<String, String> {
+  let source_path = match wait_for_download_record(context, record_id).await {
+    Ok(path) => path,
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("download_clip_fail record_id={} err={}", record_id, err),
+      );
+      return Err(err);
+    }
+  };
+
+  let clip_dir = output_path
+    .parent()
+    .map(|parent| parent.to_path_buf())
+    .unwrap_or_else(|| PathBuf::from("."))
+    .join(format!(".clip_{}", record_id));
+  let source = ClipSource {
+    input_path: source_path,
+    start_time,
+    end_time,
+    order: 1,
+  };
+  let clip_log_path = context.app_log_path.clone();
+  let output_clone = output_path.to_path_buf();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let use_copy = decide_clip_copy(std::slice::from_ref(&source))
+      .map(|decision| decision.use_copy)
+      .unwrap_or(false);
+    clip_source_to_file(&source, &clip_dir, &output_clone, use_copy, |command| {
+      append_log(
+        &clip_log_path,
+        &format!("download_clip_command record_id={} args={}", record_id, command),
+      );
+    })
+  })
+  .await
+  .map_err(|_| "剪辑任务执行失败".to_string())?;
+  if let Err(err) = result {
+    append_log(
+      &context.app_log_path,
+      &format!("download_clip_fail record_id={} err={}", record_id, err),
+    );
+    return Err(err);
+  }
+  let output = output_path.to_string_lossy().to_string();
+  append_log(
+    &context.app_log_path,
+    &format!("download_clip_done record_id={} output={}", record_id, output),
+  );
+  Ok(output)
+}
+
+async fn resolve_clip_part(
+  context: &DownloadContext,
+  bvid: Option<&str>,
+  aid: Option<&str>,
+  request: &DownloadClipRequest,
+) -> Result<DownloadPart, String> {
+  let data = fetch_video_view(context, bvid, aid)
+    .await
+    .map_err(|err| format!("获取视频信息失败: {}", err))?;
+  let pages = data
+    .get("pages")
+    .and_then(|value| value.as_array())
+    .cloned()
+    .unwrap_or_default();
+  let page = match request.cid {
+    Some(cid) => pages
+      .iter()
+      .find(|page| page.get("cid").and_then(|value| value.as_i64()) == Some(cid)),
+    None => {
+      let index = request.page.unwrap_or(1).max(1) as usize - 1;
+      pages.get(index)
+    }
+  }
+  .ok_or_else(|| "未找到对应分P".to_string())?;
+  let cid = page
+    .get("cid")
+    .and_then(|value| value.as_i64())
+    .ok_or_else(|| "分P缺少 cid".to_string())?;
+  let title = page
+    .get("part")
+    .and_then(|value| value.as_str())
+    .filter(|value| !value.trim().is_empty())
+    .map(|value| value.to_string())
+    .or_else(|| data.get("title").and_then(|value| value.as_str()).map(|value| value.to_string()))
+    .unwrap_or_else(|| cid.to_string());
+  Ok(DownloadPart {
+    cid,
+    title,
+    duration: page.get("duration").and_then(|value| value.as_i64()),
+  })
+}
+
+async fn wait_for_download_record(context: &DownloadContext, record_id: i64) -> Result<String, String> {
+  // 复用的历史记录可能处于失败状态，首次遇到时重新入队一次；暂停由用户发起，等待其继续下载
+  let mut requeued = false;
+  loop {
+    let (status, local_path, status_message) = context
+      .db
+      .with_conn(|conn| {
+        conn.query_row(
+          "SELECT status, local_path, status_message FROM video_download WHERE id = ?1",
+          [record_id],
+          |row| {
+            Ok((
+              row.get::<_, i64>(0)?,
+              row.get::<_, Option<String>>(1)?,
+              row.get::<_, Option<String>>(2)?,
+            ))
+          },
+        )
+      })
+      .map_err(|err| format!("读取下载任务失败: {}", err))?;
+    match status {
+      2 => {
+        return local_path
+          .filter(|path| Path::new(path).is_file())
+          .ok_or_else(|| "下载文件不存在".to_string());
+      }
+      3 if status_message.as_deref() == Some(DOWNLOAD_CANCELLED_MESSAGE) => {
+        return Err(DOWNLOAD_CANCELLED_MESSAGE.to_string());
+      }
+      3 if !requeued => {
+        requeued = true;
+        requeue_download_record(context, record_id).await?;
+      }
+      3 => return Err("下载失败".to_string()),
+      _ => sleep(Duration::from_secs(DOWNLOAD_CLIP_POLL_SECS)).await,
+    }
+  }
+}
+
 #[tauri::command]
 pub fn download_get(state: State<'_, AppState>, task_id: i64) -> ApiResponse<VideoDownloadRecord> {
   match state.db.with_conn(|conn| {
     conn.query_row(
-      "SELECT id, bvid, aid, title, part_title, part_count, current_part, download_url, local_path, resolution, codec, format, status, progress, progress_total, progress_done, create_time, update_time \
+      "SELECT id, bvid, aid, title, part_title, part_count, current_part, download_url, local_path, resolution, codec, format, status, progress, progress_total, progress_done, create_time, update_time, COALESCE(priority, 0), status_message \
        FROM video_download WHERE id = ?1",
       [task_id],
       |row| {
@@ -242,6 +530,8 @@ pub fn download_get(state: State<'_, AppState>, task_id: i64) -> ApiResponse<Vid
           progress: row.get(13)?,
           progress_total: row.get(14)?,
           progress_done: row.get(15)?,
+          priority: row.get(18)?,
+          status_message: row.get(19)?,
           create_time: row.get(16)?,
           update_time: row.get(17)?,
         })
@@ -260,7 +550,7 @@ pub fn download_list_by_status(
 ) -> ApiResponse<Vec<VideoDownloadRecord>> {
   match state.db.with_conn(|conn| {
     let mut stmt = conn.prepare(
-      "SELECT id, bvid, aid, title, part_title, part_count, current_part, download_url, local_path, resolution, codec, format, status, progress, progress_total, progress_done, create_time, update_time \
+      "SELECT id, bvid, aid, title, part_title, part_count, current_part, download_url, local_path, resolution, codec, format, status, progress, progress_total, progress_done, create_time, update_time, COALESCE(priority, 0), status_message \
        FROM video_download WHERE status = ?1 ORDER BY id DESC",
     )?;
     let list = stmt
@@ -282,6 +572,8 @@ pub fn download_list_by_status(
           progress: row.get(13)?,
           progress_total: row.get(14)?,
           progress_done: row.get(15)?,
+          priority: row.get(18)?,
+          status_message: row.get(19)?,
           create_time: row.get(16)?,
           update_time: row.get(17)?,
         })
@@ -338,6 +630,167 @@ pub fn download_delete(
   }
 }
 
+#[tauri::command]
+pub fn download_set_priority(
+  state: State<'_, AppState>,
+  task_id: i64,
+  priority: i64,
+) -> ApiResponse<String> {
+  let now = now_rfc3339();
+  match state.db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE video_download SET priority = ?1, update_time = ?2 WHERE id = ?3",
+      (priority, &now, task_id),
+    )
+  }) {
+    Ok(0) => ApiResponse::error("下载任务不存在"),
+    Ok(_) => {
+      append_log(
+        &state.app_log_path,
+        &format!("download_set_priority record_id={} priority={}", task_id, priority),
+      );
+      ApiResponse::success("Priority updated".to_string())
+    }
+    Err(err) => ApiResponse::error(format!("更新下载优先级失败: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn download_pause(state: State<'_, AppState>, task_id: i64) -> ApiResponse<String> {
+  let context = DownloadContext::new(&state);
+  let status = match context.db.with_conn(|conn| {
+    conn.query_row(
+      "SELECT status FROM video_download WHERE id = ?1",
+      [task_id],
+      |row| row.get::<_, i64>(0),
+    )
+  }) {
+    Ok(value) => value,
+    Err(err) => return ApiResponse::error(format!("读取下载任务失败: {}", err)),
+  };
+  match status {
+    0 => {
+      // 尚未启动的任务直接置为暂停，队列只调度 status = 0 的记录
+      if let Err(err) = update_download_status_only(&context, task_id, 4) {
+        return ApiResponse::error(format!("暂停下载失败: {}", err));
+      }
+      append_log(
+        &context.app_log_path,
+        &format!("download_pause record_id={} from=queued", task_id),
+      );
+      ApiResponse::success("Paused".to_string())
+    }
+    // FFmpeg 下载无法断点续传，停止后只能从头开始，因此不提供暂停
+    1 if is_download_ffmpeg_running(task_id) => {
+      append_log(
+        &context.app_log_path,
+        &format!("download_pause_reject record_id={} reason=ffmpeg", task_id),
+      );
+      ApiResponse::error("该任务正在通过 FFmpeg 下载，不支持暂停，可取消后重新下载")
+    }
+    1 => {
+      set_download_pause_requested(task_id, true);
+      append_log(
+        &context.app_log_path,
+        &format!("download_pause record_id={} from=running", task_id),
+      );
+      ApiResponse::success("Pausing".to_string())
+    }
+    4 => ApiResponse::error("任务已暂停"),
+    _ => ApiResponse::error("任务未在下载中，无法暂停"),
+  }
+}
+
+/// 取消所有排队中与下载中的任务（status 0/1 -> 3，并记录取消原因），已完成的记录不受影响。
+/// 下载中的任务复用暂停通道让 aria2c/FFmpeg 退出，任务结束时保持取消状态而不是转为暂停。
+#[tauri::command]
+pub fn download_cancel_all(state: State<'_, AppState>) -> ApiResponse<i64> {
+  let context = DownloadContext::new(&state);
+  let now = now_rfc3339();
+  let result = context.db.with_conn(|conn| {
+    let mut stmt = conn.prepare("SELECT id FROM video_download WHERE status = 1")?;
+    let running = stmt
+      .query_map([], |row| row.get::<_, i64>(0))?
+      .collect::<Result<Vec<_>, _>>()?;
+    let affected = conn.execute(
+      "UPDATE video_download SET status = 3, status_message = ?1, update_time = ?2 WHERE status IN (0, 1)",
+      (DOWNLOAD_CANCELLED_MESSAGE, &now),
+    )?;
+    Ok((running, affected as i64))
+  });
+  let (running, affected) = match result {
+    Ok(value) => value,
+    Err(err) => return ApiResponse::error(format!("取消下载失败: {}", err)),
+  };
+  for record_id in &running {
+    set_download_cancel_requested(*record_id, true);
+    set_download_pause_requested(*record_id, true);
+  }
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "download_cancel_all affected={} running={}",
+      affected,
+      running.len()
+    ),
+  );
+  ApiResponse::success(affected)
+}
+
+fn set_download_cancel_requested(record_id: i64, requested: bool) {
+  let requests = DOWNLOAD_CANCEL_REQUESTS.get_or_init(|| Mutex::new(HashSet::new()));
+  if let Ok(mut guard) = requests.lock() {
+    if requested {
+      guard.insert(record_id);
+    } else {
+      guard.remove(&record_id);
+    }
+  }
+}
+
+fn take_download_cancel_requested(record_id: i64) -> bool {
+  DOWNLOAD_CANCEL_REQUESTS
+    .get()
+    .and_then(|requests| requests.lock().ok().map(|mut guard| guard.remove(&record_id)))
+    .unwrap_or(false)
+}
+
+fn set_download_pause_requested(record_id: i64, requested: bool) {
+  let requests = DOWNLOAD_PAUSE_REQUESTS.get_or_init(|| Mutex::new(HashSet::new()));
+  if let Ok(mut guard) = requests.lock() {
+    if requested {
+      guard.insert(record_id);
+    } else {
+      guard.remove(&record_id);
+    }
+  }
+}
+
+fn is_download_pause_requested(record_id: i64) -> bool {
+  DOWNLOAD_PAUSE_REQUESTS
+    .get()
+    .and_then(|requests| requests.lock().ok().map(|guard| guard.contains(&record_id)))
+    .unwrap_or(false)
+}
+
+fn set_download_ffmpeg_running(record_id: i64, running: bool) {
+  let records = DOWNLOAD_FFMPEG_RUNNING.get_or_init(|| Mutex::new(HashSet::new()));
+  if let Ok(mut guard) = records.lock() {
+    if running {
+      guard.insert(record_id);
+    } else {
+      guard.remove(&record_id);
+    }
+  }
+}
+
+fn is_download_ffmpeg_running(record_id: i64) -> bool {
+  DOWNLOAD_FFMPEG_RUNNING
+    .get()
+    .and_then(|records| records.lock().ok().map(|guard| guard.contains(&record_id)))
+    .unwrap_or(false)
+}
+
 #[tauri::command]
 pub async fn download_retry(
   state: State<'_, AppState>,
@@ -348,7 +801,7 @@ pub async fn download_retry(
     .db
     .with_conn(|conn| {
       conn.query_row(
-        "SELECT bvid, aid, part_title, local_path, resolution, codec, format, cid, content, status \
+        "SELECT bvid, aid, part_title, local_path, resolution, codec, format, cid, content, status, max_resolution \
          FROM video_download WHERE id = ?1",
         [task_id],
         |row| {
@@ -363,13 +816,14 @@ pub async fn download_retry(
             row.get::<_, Option<i64>>(7)?,
             row.get::<_, Option<String>>(8)?,
             row.get::<_, i64>(9)?,
+            row.get::<_, Option<i64>>(10)?,
           ))
         },
       )
     })
     .map_err(|err| format!("读取下载任务失败: {}", err))?;
 
-  let (bvid, aid, part_title, local_path, resolution, codec, format, cid, content, status) =
+  let (bvid, aid, part_title, local_path, resolution, codec, format, cid, content, status, max_resolution) =
     record;
 
   if status == 1 {
@@ -403,6 +857,7 @@ pub async fn download_retry(
     codec,
     format,
     content,
+    max_resolution,
   };
 
   let duration = if bvid.is_some() || aid.is_some() {
@@ -470,7 +925,7 @@ pub async fn download_resume(
     .db
     .with_conn(|conn| {
       conn.query_row(
-        "SELECT bvid, aid, part_title, local_path, resolution, codec, format, cid, content, status, progress \
+        "SELECT bvid, aid, part_title, local_path, resolution, codec, format, cid, content, status, progress, max_resolution \
          FROM video_download WHERE id = ?1",
         [task_id],
         |row| {
@@ -486,14 +941,27 @@ pub async fn download_resume(
             row.get::<_, Option<String>>(8)?,
             row.get::<_, i64>(9)?,
             row.get::<_, i64>(10)?,
+            row.get::<_, Option<i64>>(11)?,
           ))
         },
       )
     })
     .map_err(|err| format!("读取下载任务失败: {}", err))?;
 
-  let (bvid, aid, part_title, local_path, resolution, codec, format, cid, content, status, progress) =
-    record;
+  let (
+    bvid,
+    aid,
+    part_title,
+    local_path,
+    resolution,
+    codec,
+    format,
+    cid,
+    content,
+    status,
+    progress,
+    max_resolution,
+  ) = record;
 
   if status == 1 {
     return Ok(ApiResponse::error("任务正在下载"));
@@ -525,6 +993,7 @@ pub async fn download_resume(
     codec,
     format,
     content,
+    max_resolution,
   };
 
   let duration = if bvid.is_some() || aid.is_some() {
@@ -587,7 +1056,7 @@ async fn requeue_download_record(
     .db
     .with_conn(|conn| {
       conn.query_row(
-        "SELECT bvid, aid, part_title, local_path, resolution, codec, format, cid, content, status \
+        "SELECT bvid, aid, part_title, local_path, resolution, codec, format, cid, content, status, max_resolution \
          FROM video_download WHERE id = ?1",
         [record_id],
         |row| {
@@ -602,13 +1071,14 @@ async fn requeue_download_record(
             row.get::<_, Option<i64>>(7)?,
             row.get::<_, Option<String>>(8)?,
             row.get::<_, i64>(9)?,
+            row.get::<_, Option<i64>>(10)?,
           ))
         },
       )
     })
     .map_err(|err| format!("读取下载任务失败: {}", err))?;
 
-  let (bvid, aid, part_title, local_path, resolution, codec, format, cid, content, status) =
+  let (bvid, aid, part_title, local_path, resolution, codec, format, cid, content, status, max_resolution) =
     record;
 
   if status == 1 || status == 0 {
@@ -637,6 +1107,7 @@ async fn requeue_download_record(
     codec,
     format,
     content,
+    max_resolution,
   };
 
   let duration = if bvid.is_some() || aid.is_some() {
@@ -689,6 +1160,26 @@ pub fn recover_stale_downloads(state: &AppState) {
   }
 
   let now = now_rfc3339();
+  // 多P下载中仍有其它分P排队时，中断的分P重新入队（保留进度续传），避免整组卡在暂停
+  let requeued = context
+    .db
+    .with_conn(|conn| {
+      conn.execute(
+        "UPDATE video_download SET status = 0, update_time = ?1 \
+         WHERE status = 1 AND COALESCE(part_count, 1) > 1 AND EXISTS ( \
+           SELECT 1 FROM video_download sibling \
+           WHERE sibling.download_url = video_download.download_url \
+             AND sibling.id != video_download.id AND sibling.status = 0)",
+        [&now],
+      )
+    })
+    .unwrap_or(0);
+  if requeued > 0 {
+    append_log(
+      &context.app_log_path,
+      &format!("download_recover_stale status=requeued_group count={}", requeued),
+    );
+  }
   if context
     .db
     .with_conn(|conn| {
@@ -702,7 +1193,10 @@ pub fn recover_stale_downloads(state: &AppState) {
   {
     append_log(
       &context.app_log_path,
-      &format!("download_recover_stale status=paused count={}", stale_ids.len()),
+      &format!(
+        "download_recover_stale status=paused count={}",
+        stale_ids.len().saturating_sub(requeued)
+      ),
     );
   }
 
@@ -887,7 +1381,18 @@ async fn create_download_tasks(
   request: DownloadRequest,
 ) -> Result<Vec<DownloadTaskCreateResult>, String> {
   let (bvid, aid) = parse_video_id(&request.video_url);
-  let video_title = fetch_video_title(&context, bvid.as_deref(), aid.as_deref()).await;
+  let video_view = fetch_video_view(&context, bvid.as_deref(), aid.as_deref()).await.ok();
+  let video_title = video_view
+    .as_ref()
+    .and_then(|view| view.get("title"))
+    .and_then(|value| value.as_str())
+    .map(|value| value.to_string());
+  let pages = video_view
+    .as_ref()
+    .and_then(|view| view.get("pages"))
+    .and_then(|value| value.as_array())
+    .cloned()
+    .unwrap_or_default();
 
   let folder_name = request
     .config
@@ -899,9 +1404,13 @@ async fn create_download_tasks(
 
   let now = now_rfc3339();
 
-  let parts = request.parts.clone();
+  let parts = match request.part_indices.as_ref().filter(|indices| !indices.is_empty()) {
+    Some(indices) => parts_from_page_indices(&pages, indices)?,
+    None => request.parts.clone(),
+  };
   let mut record_ids = Vec::with_capacity(parts.len());
-  let part_count = parts.len() as i64;
+  // 记录在整个稿件中的分P位置，便于展示 "P3/P12"
+  let part_count = if pages.is_empty() { parts.len() } else { pages.len() } as i64;
   let settings = load_download_settings_from_db(&context.db)
     .map_err(|err| format!("Failed to load download settings: {}", err))?;
   let base_dir = request
@@ -947,15 +1456,15 @@ async fn create_download_tasks(
       .db
       .with_conn(|conn| {
         conn.execute(
-          "INSERT INTO video_download (bvid, aid, title, part_title, part_count, current_part, download_url, local_path, status, progress, progress_total, progress_done, create_time, update_time, resolution, codec, format, cid, content) \
-           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0, 0, 0, 0, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+          "INSERT INTO video_download (bvid, aid, title, part_title, part_count, current_part, download_url, local_path, status, progress, progress_total, progress_done, create_time, update_time, resolution, codec, format, cid, content, max_resolution) \
+           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0, 0, 0, 0, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
           (
             bvid.as_deref(),
             aid.as_deref(),
             video_title.as_deref(),
             part.title.as_str(),
             part_count,
-            (index + 1) as i64,
+            page_position(&pages, part.cid).unwrap_or(index + 1) as i64,
             request.video_url.as_str(),
             actual_path.as_str(),
             &now,
@@ -965,6 +1474,7 @@ async fn create_download_tasks(
             request.config.format.as_deref(),
             part.cid,
             request.config.content.as_deref(),
+            request.config.max_resolution.filter(|value| *value > 0),
           ),
         )?;
         Ok(conn.last_insert_rowid())
@@ -1144,8 +1654,9 @@ fn load_pending_downloads(
     .db
     .with_conn(|conn| {
       let mut stmt = conn.prepare(
-        "SELECT id, bvid, aid, part_title, local_path, resolution, codec, format, cid, content, progress \
-         FROM video_download WHERE status = 0 ORDER BY id ASC LIMIT ?1",
+        "SELECT id, bvid, aid, part_title, local_path, resolution, codec, format, cid, content, progress, max_resolution \
+         FROM video_download WHERE status = 0 \
+         ORDER BY COALESCE(priority, 0) DESC, create_time ASC, id ASC LIMIT ?1",
       )?;
       let rows = stmt.query_map([limit], |row| {
         Ok(PendingDownloadRecord {
@@ -1160,6 +1671,7 @@ fn load_pending_downloads(
           cid: row.get(8)?,
           content: row.get(9)?,
           progress: row.get(10)?,
+          max_resolution: row.get(11)?,
         })
       })?;
       Ok(rows.collect::<Result<Vec<_>, _>>()?)
@@ -1217,6 +1729,7 @@ fn start_pending_download(
     codec: record.codec,
     format: record.format,
     content: record.content,
+    max_resolution: record.max_resolution,
   };
 
   try_start_download_job(
@@ -1331,19 +1844,33 @@ async fn run_download_job(
   resume_progress: Option<i64>,
 ) {
   clear_download_progress(&context, record_id);
+  set_download_pause_requested(record_id, false);
   append_log(
     &context.app_log_path,
     &format!("download_job_start record_id={} cid={}", record_id, part.cid),
   );
 
+  let part_title = part.title.clone();
   let result =
     download_part(&context, record_id, bvid, aid, part, config, output_path, resume_progress)
       .await;
+  set_download_pause_requested(record_id, false);
+  let cancelled = take_download_cancel_requested(record_id);
   release_download_slot(&context);
   let context_clone = context.clone();
   tauri::async_runtime::spawn(async move {
     schedule_pending_downloads(context_clone).await;
   });
+  if cancelled && result.is_err() {
+    let _ = mark_download_cancelled(&context, record_id);
+    clear_download_progress(&context, record_id);
+    append_log(
+      &context.app_log_path,
+      &format!("download_job_complete record_id={} status=cancelled", record_id),
+    );
+    let _ = refresh_integration_status(&context, record_id).await;
+    return;
+  }
   match result {
     Ok(()) => {
       let _ = update_download_status(&context, record_id, 2, 100);
@@ -1352,6 +1879,13 @@ async fn run_download_job(
         &context.app_log_path,
         &format!("download_job_complete record_id={} status=completed", record_id),
       );
+      desktop_notify::notify(
+        &context.db,
+        &context.app_log_path,
+        NotifyCategory::Download,
+        "下载完成",
+        &part_title,
+      );
       let _ = refresh_integration_status(&context, record_id).await;
     }
     Err(err) => {
@@ -1399,7 +1933,16 @@ async fn download_part(
   let settings = load_download_settings_from_db(&context.db)
     .map_err(|err| format!("Failed to load download settings: {}", err))?;
   let block_pcdn = settings.block_pcdn;
-  let enable_aria2c = settings.enable_aria2c;
+  // aria2c 与 FFmpeg 都只支持 HTTP 代理，SOCKS 代理下改用内置下载器，保证媒体流量走代理
+  let socks_proxy =
+    crate::config::current_proxy_url().is_some() && crate::config::current_http_proxy_url().is_none();
+  if socks_proxy {
+    append_log(
+      &context.app_log_path,
+      &format!("download_proxy_socks_fallback record_id={} downloader=http", record_id),
+    );
+  }
+  let enable_aria2c = settings.enable_aria2c && !socks_proxy;
   let aria2c_connections = settings.aria2c_connections.max(1).min(32);
   let aria2c_split = settings.aria2c_split.max(1).min(32);
   let min_progress = resume_progress.filter(|value| *value > 0).map(|value| value.min(99));
@@ -1438,6 +1981,18 @@ async fn download_part(
 
   if format == "mp4" || format == "flv" {
     let urls = collect_durl_urls(&play_info, block_pcdn)?;
+    if socks_proxy {
+      return download_with_http_client(
+        context,
+        record_id,
+        track_progress,
+        &output_path,
+        &urls,
+        &header,
+        "main",
+      )
+      .await;
+    }
       if enable_aria2c {
       if let Err(err) = download_with_aria2c(
         context,
@@ -1452,7 +2007,7 @@ async fn download_part(
       )
       .await
       {
-        if has_partial_file(&output_path) {
+        if is_download_pause_requested(record_id) || has_partial_file(&output_path) {
           append_log(
             &context.app_log_path,
             &format!(
@@ -1483,12 +2038,7 @@ async fn download_part(
       &urls,
       |url| {
         let mut args = Vec::new();
-        if !header.is_empty() {
-          args.push("-headers".to_string());
-          args.push(header.clone());
-        }
-        args.push("-i".to_string());
-        args.push(url.to_string());
+        push_network_input(&mut args, &header, url);
         args.extend(["-c".to_string(), "copy".to_string()]);
         if track_progress {
           args.push("-progress".to_string());
@@ -1511,11 +2061,30 @@ async fn download_part(
   match content.as_str() {
     "video_only" => {
       let video_candidates =
-        select_video_candidates(dash, config.resolution.as_deref(), config.codec.as_deref(), block_pcdn)?;
-      let video_urls = video_candidates
+        select_video_candidates(
+          dash,
+          config.resolution.as_deref(),
+          config.codec.as_deref(),
+          config.max_resolution,
+          block_pcdn,
+        )?;
+      let video_candidate = video_candidates
         .first()
-        .map(|candidate| candidate.urls.clone())
         .ok_or_else(|| "Missing video URL".to_string())?;
+      record_selected_stream(context, record_id, video_candidate);
+      let video_urls = video_candidate.urls.clone();
+      if socks_proxy {
+        return download_with_http_client(
+          context,
+          record_id,
+          track_progress,
+          &output_path,
+          &video_urls,
+          &header,
+          "main",
+        )
+        .await;
+      }
       if enable_aria2c {
       if let Err(err) = download_with_aria2c(
         context,
@@ -1530,7 +2099,7 @@ async fn download_part(
       )
       .await
       {
-          if has_partial_file(&output_path) {
+          if is_download_pause_requested(record_id) || has_partial_file(&output_path) {
             append_log(
               &context.app_log_path,
               &format!(
@@ -1561,12 +2130,7 @@ async fn download_part(
         &video_urls,
         |url| {
           let mut args = Vec::new();
-          if !header.is_empty() {
-            args.push("-headers".to_string());
-            args.push(header.clone());
-          }
-          args.push("-i".to_string());
-          args.push(url.to_string());
+          push_network_input(&mut args, &header, url);
           args.extend(["-c".to_string(), "copy".to_string()]);
           if track_progress {
             args.push("-progress".to_string());
@@ -1586,6 +2150,18 @@ async fn download_part(
         .first()
         .map(|candidate| candidate.urls.clone())
         .ok_or_else(|| "Missing audio URL".to_string())?;
+      if socks_proxy {
+        return download_with_http_client(
+          context,
+          record_id,
+          track_progress,
+          &output_path,
+          &audio_urls,
+          &header,
+          "main",
+        )
+        .await;
+      }
       if enable_aria2c {
       if let Err(err) = download_with_aria2c(
         context,
@@ -1600,7 +2176,7 @@ async fn download_part(
       )
       .await
       {
-          if has_partial_file(&output_path) {
+          if is_download_pause_requested(record_id) || has_partial_file(&output_path) {
             append_log(
               &context.app_log_path,
               &format!(
@@ -1631,12 +2207,7 @@ async fn download_part(
         &audio_urls,
         |url| {
           let mut args = Vec::new();
-          if !header.is_empty() {
-            args.push("-headers".to_string());
-            args.push(header.clone());
-          }
-          args.push("-i".to_string());
-          args.push(url.to_string());
+          push_network_input(&mut args, &header, url);
           args.extend(["-c".to_string(), "copy".to_string()]);
           if track_progress {
             args.push("-progress".to_string());
@@ -1652,40 +2223,70 @@ async fn download_part(
     }
     _ => {
       let video_candidates =
-        select_video_candidates(dash, config.resolution.as_deref(), config.codec.as_deref(), block_pcdn)?;
+        select_video_candidates(
+          dash,
+          config.resolution.as_deref(),
+          config.codec.as_deref(),
+          config.max_resolution,
+          block_pcdn,
+        )?;
       let audio_candidates = select_audio_candidates(dash, block_pcdn)?;
       let mut last_error: Option<String> = None;
-      let mut aria2c_enabled = enable_aria2c;
+      let mut aria2c_enabled = enable_aria2c || socks_proxy;
       for (video_index, video_candidate) in video_candidates.iter().enumerate() {
+        record_selected_stream(context, record_id, video_candidate);
         for (audio_index, audio_candidate) in audio_candidates.iter().enumerate() {
           let mut aria2c_failed = !aria2c_enabled;
           let temp_video_path = output_path.with_extension("video");
           let temp_audio_path = output_path.with_extension("audio");
           if aria2c_enabled {
-            let (video_result, audio_result) = tokio::join!(
-              download_with_aria2c(
-                context,
-                record_id,
-                track_progress,
-                &temp_video_path,
-                &video_candidate.urls,
-                &header,
-                aria2c_connections,
-                aria2c_split,
-                "video",
-              ),
-              download_with_aria2c(
-                context,
-                record_id,
-                track_progress,
-                &temp_audio_path,
-                &audio_candidate.urls,
-                &header,
-                aria2c_connections,
-                aria2c_split,
-                "audio",
-              ),
-            );
+            let (video_result, audio_result) = if socks_proxy {
+              tokio::join!(
+                download_with_http_client(
+                  context,
+                  record_id,
+                  track_progress,
+                  &temp_video_path,
+                  &video_candidate.urls,
+                  &header,
+                  "video",
+                ),
+                download_with_http_client(
+                  context,
+                  record_id,
+                  track_progress,
+                  &temp_audio_path,
+                  &audio_candidate.urls,
+                  &header,
+                  "audio",
+                ),
+              )
+            } else {
+              tokio::join!(
+                download_with_aria2c(
+                  context,
+                  record_id,
+                  track_progress,
+                  &temp_video_path,
+                  &video_candidate.urls,
+                  &header,
+                  aria2c_connections,
+                  aria2c_split,
+                  "video",
+                ),
+                download_with_aria2c(
+                  context,
+                  record_id,
+                  track_progress,
+                  &temp_audio_path,
+                  &audio_candidate.urls,
+                  &header,
+                  aria2c_connections,
+                  aria2c_split,
+                  "audio",
+                ),
+              )
+            };
             if let Err(err) = &video_result {
               append_log(
                 &context.app_log_path,
@@ -1705,7 +2306,10 @@ async fn download_part(
               }
             }
             if video_result.is_err() || audio_result.is_err() {
-              if has_partial_file(&temp_video_path) || has_partial_file(&temp_audio_path) {
+              if is_download_pause_requested(record_id)
+                || has_partial_file(&temp_video_path)
+                || has_partial_file(&temp_audio_path)
+              {
                 let resume_path = if has_partial_file(&temp_audio_path) {
                   &temp_audio_path
                 } else {
@@ -1723,6 +2327,10 @@ async fn download_part(
               }
               cleanup_aria2c_files(&temp_video_path);
               cleanup_aria2c_files(&temp_audio_path);
+              if socks_proxy {
+                last_error = video_result.err().or(audio_result.err());
+                continue;
+              }
               aria2c_failed = true;
             } else {
               let _ = update_download_progress(context, record_id, 95);
@@ -1880,7 +2488,7 @@ async fn download_part(
             }
           }
 
-          if !aria2c_failed {
+          if !aria2c_failed || socks_proxy {
             continue;
           }
 
@@ -1902,18 +2510,8 @@ async fn download_part(
                 );
               }
               let mut args = Vec::new();
-              if !header.is_empty() {
-                args.push("-headers".to_string());
-                args.push(header.clone());
-              }
-              args.push("-i".to_string());
-              args.push(video_url.clone());
-              if !header.is_empty() {
-                args.push("-headers".to_string());
-                args.push(header.clone());
-              }
-              args.push("-i".to_string());
-              args.push(audio_url.clone());
+              push_network_input(&mut args, &header, video_url);
+              push_network_input(&mut args, &header, audio_url);
               args.extend([
                 "-map".to_string(),
                 "0:v:0".to_string(),
@@ -2039,30 +2637,41 @@ async fn run_ffmpeg_job(
     ),
   );
 
+  set_download_ffmpeg_running(record_id, true);
   let exec_result = if track_progress {
     let min_progress = min_progress.unwrap_or(0).clamp(0, 99);
     let mut last_progress = min_progress;
     let context_clone = context.clone();
     let record_id_clone = record_id;
     tauri::async_runtime::spawn_blocking(move || {
-      run_ffmpeg_with_progress(&args, duration_ms, |progress| {
-        if progress <= last_progress {
-          return;
-        }
-        let progress = progress.max(min_progress);
-        if progress > last_progress {
-          last_progress = progress;
-          let _ = update_download_progress(&context_clone, record_id_clone, progress);
-        }
-      })
+      run_ffmpeg_with_progress(
+        &args,
+        duration_ms,
+        |progress| {
+          if progress <= last_progress {
+            return;
+          }
+          let progress = progress.max(min_progress);
+          if progress > last_progress {
+            last_progress = progress;
+            let _ = update_download_progress(&context_clone, record_id_clone, progress);
+          }
+        },
+        || is_download_pause_requested(record_id_clone),
+      )
     })
     .await
-    .map_err(|_| "Failed to execute download task".to_string())?
+    .map_err(|_| "Failed to execute download task".to_string())
+    .and_then(|result| result)
   } else {
-    tauri::async_runtime::spawn_blocking(move || run_ffmpeg(&args))
-      .await
-      .map_err(|_| "Failed to execute download task".to_string())?
+    tauri::async_runtime::spawn_blocking(move || {
+      run_ffmpeg_until(&args, || is_download_pause_requested(record_id))
+    })
+    .await
+    .map_err(|_| "Failed to execute download task".to_string())
+    .and_then(|result| result)
   };
+  set_download_ffmpeg_running(record_id, false);
 
   match &exec_result {
     Ok(_) => {
@@ -2123,6 +2732,10 @@ where
       Ok(_) => return Ok(()),
       Err(err) => {
         let _ = std::fs::remove_file(output_path);
+        // 暂停或取消时已终止 FFmpeg，不再尝试其他地址；FFmpeg 下载不支持断点，继续时重新下载
+        if is_download_pause_requested(record_id) {
+          return Err("FFmpeg下载已中止，可重试续传".to_string());
+        }
         last_error = Some(err);
         continue;
       }
@@ -2155,10 +2768,13 @@ fn build_aria2c_args(
     format!("--max-connection-per-server={}", connections),
     format!("--split={}", split),
     "--min-split-size=1M".to_string(),
-    "--referer=https://www.bilibili.com/".to_string(),
+    format!("--referer={}", crate::config::web_referer_or("https://www.bilibili.com/")),
     format!("--dir={}", parent.to_string_lossy()),
     format!("--out={}", file_name.to_string_lossy()),
   ];
+  if let Some(proxy) = crate::config::current_http_proxy_url() {
+    args.push(format!("--all-proxy={}", proxy));
+  }
   for line in header.split("\r\n").map(|value| value.trim()) {
     if !line.is_empty() {
       args.push(format!("--header={}", line));
@@ -2194,6 +2810,8 @@ struct Aria2cTaskStatus {
   total_length: String,
   #[serde(rename = "completedLength")]
   completed_length: String,
+  #[serde(rename = "downloadSpeed", default)]
+  download_speed: Option<String>,
   #[serde(rename = "errorCode")]
   error_code: Option<String>,
   #[serde(rename = "errorMessage")]
@@ -2283,6 +2901,14 @@ fn aria2c_status_bytes(status: &Aria2cTaskStatus) -> Option<(u64, u64)> {
   Some((total, completed))
 }
 
+fn aria2c_status_speed(status: &Aria2cTaskStatus) -> u64 {
+  status
+    .download_speed
+    .as_deref()
+    .and_then(|value| value.parse().ok())
+    .unwrap_or(0)
+}
+
 fn aria2c_status_error(status: &Aria2cTaskStatus) -> Option<String> {
   if status.status == "error" {
     return Some(
@@ -2335,7 +2961,7 @@ fn run_aria2c_with_path<F>(
   rpc: &Aria2cRpcConfig,
 ) -> Result<(), String>
 where
-  F: FnMut(u64, u64),
+  F: FnMut(u64, u64, u64),
 {
   let mut child = Command::new(path)
     .args(args)
@@ -2397,6 +3023,30 @@ where
   let mut logged_zero_total = false;
   let mut logged_parse_error = false;
   loop {
+    if is_download_pause_requested(record_id) {
+      // 正常关闭会保留 .aria2 控制文件，继续下载时从断点续传
+      if rpc_ready {
+        let _ = aria2c_rpc_request::<Value>(&client, rpc, "shutdown", vec![]);
+      }
+      for _ in 0..ARIA2C_PAUSE_EXIT_WAIT_SECS * 10 {
+        if let Ok(Some(status)) = child.try_wait() {
+          exit_status = Some(status);
+          break;
+        }
+        thread::sleep(Duration::from_millis(100));
+      }
+      append_log(
+        app_log_path,
+        &format!(
+          "aria2c_paused record_id={} key={} exited={}",
+          record_id,
+          progress_key,
+          exit_status.is_some()
+        ),
+      );
+      last_error = Some(DOWNLOAD_PAUSED_ERROR.to_string());
+      break;
+    }
     if rpc_ready {
       match aria2c_rpc_fetch_status(&client, rpc) {
         Ok(Some(status)) => {
@@ -2420,7 +3070,7 @@ where
                   ),
                 );
               }
-              on_progress(content, chunk);
+              on_progress(content, chunk, aria2c_status_speed(&status));
             } else if !logged_parse_error {
               logged_parse_error = true;
               append_log(
@@ -2438,7 +3088,7 @@ where
           }
           if status.status == "complete" {
             if let Some((content, _chunk)) = aria2c_status_bytes(&status) {
-              on_progress(content, content);
+              on_progress(content, content, 0);
             }
             completed = true;
             break;
@@ -2521,7 +3171,7 @@ fn run_aria2c_command<F>(
   rpc: &Aria2cRpcConfig,
 ) -> Result<(), String>
 where
-  F: FnMut(u64, u64),
+  F: FnMut(u64, u64, u64),
 {
   let mut last_error = None;
   for path in resolve_aria2c_candidates() {
@@ -2536,6 +3186,7 @@ where
       rpc,
     ) {
       Ok(_) => return Ok(()),
+      Err(err) if is_download_pause_requested(record_id) => return Err(err),
       Err(err) => {
         last_error = Some(err);
       }
@@ -2587,8 +3238,15 @@ async fn download_with_aria2c(
   let progress_key = progress_key.to_string();
   let rpc_config_clone = rpc_config.clone();
   let exec_result = tauri::async_runtime::spawn_blocking(move || {
-    let mut update = |content: u64, chunk: u64| {
-      let _ = update_download_bytes(&context_clone, record_id, &progress_key, content, chunk);
+    let mut update = |content: u64, chunk: u64, speed: u64| {
+      let _ = update_download_bytes(
+        &context_clone,
+        record_id,
+        &progress_key,
+        content,
+        chunk,
+        speed,
+      );
     };
     run_aria2c_command(
       context_clone.app_log_path.as_ref(),
@@ -2621,6 +3279,177 @@ async fn download_with_aria2c(
   exec_result
 }
 
+/// 内置下载器，请求走 `config::http_proxy()`，用于 aria2c 与 FFmpeg 无法使用的 SOCKS 代理。
+/// 已有部分文件时按 Range 续传，按顺序尝试备用地址。
+async fn download_with_http_client(
+  context: &DownloadContext,
+  record_id: i64,
+  track_progress: bool,
+  output_path: &Path,
+  urls: &[String],
+  header: &str,
+  progress_key: &str,
+) -> Result<(), String> {
+  if urls.is_empty() {
+    return Err("Missing stream url".to_string());
+  }
+  if let Some(parent) = output_path.parent() {
+    std::fs::create_dir_all(parent).map_err(|err| format!("Failed to create directory: {}", err))?;
+  }
+  let headers = build_http_download_headers(header);
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "http_download_start record_id={} output={}",
+      record_id,
+      output_path.to_string_lossy()
+    ),
+  );
+  let mut last_error: Option<String> = None;
+  for (index, url) in urls.iter().enumerate() {
+    match download_url_with_http_client(
+      context,
+      record_id,
+      track_progress,
+      output_path,
+      url,
+      &headers,
+      progress_key,
+    )
+    .await
+    {
+      Ok(()) => {
+        append_log(
+          &context.app_log_path,
+          &format!("http_download_done record_id={} status=ok", record_id),
+        );
+        return Ok(());
+      }
+      Err(err) => {
+        append_log(
+          &context.app_log_path,
+          &format!(
+            "http_download_done record_id={} status=err url_index={} msg={}",
+            record_id,
+            index + 1,
+            err
+          ),
+        );
+        if err == DOWNLOAD_PAUSED_ERROR {
+          return Err(err);
+        }
+        last_error = Some(err);
+      }
+    }
+  }
+  Err(last_error.unwrap_or_else(|| "Missing stream url".to_string()))
+}
+
+async fn download_url_with_http_client(
+  context: &DownloadContext,
+  record_id: i64,
+  track_progress: bool,
+  output_path: &Path,
+  url: &str,
+  headers: &reqwest::header::HeaderMap,
+  progress_key: &str,
+) -> Result<(), String> {
+  use std::io::Write;
+
+  let existing = std::fs::metadata(output_path).map(|meta| meta.len()).unwrap_or(0);
+  let mut request = crate::config::build_http_client()
+    .get(url)
+    .headers(headers.clone());
+  if existing > 0 {
+    request = request.header(reqwest::header::RANGE, format!("bytes={}-", existing));
+  }
+  let mut response = request
+    .send()
+    .await
+    .map_err(|err| format!("Download request failed: {}", err))?;
+  let status = response.status();
+  if !status.is_success() {
+    return Err(format!("Download request failed: HTTP {}", status.as_u16()));
+  }
+  // 服务端不支持 Range 时返回完整内容，需要从头写入
+  let resumed = existing > 0 && status == reqwest::StatusCode::PARTIAL_CONTENT;
+  let mut downloaded = if resumed { existing } else { 0 };
+  let total = response
+    .content_length()
+    .map(|length| length + downloaded)
+    .unwrap_or(0);
+  let mut file = std::fs::OpenOptions::new()
+    .create(true)
+    .write(true)
+    .append(resumed)
+    .truncate(!resumed)
+    .open(output_path)
+    .map_err(|err| format!("Failed to open output file: {}", err))?;
+  let started = std::time::Instant::now();
+  let mut last_emit = started;
+  loop {
+    if is_download_pause_requested(record_id) {
+      return Err(DOWNLOAD_PAUSED_ERROR.to_string());
+    }
+    let chunk = tokio::time::timeout(Duration::from_secs(60), response.chunk())
+      .await
+      .map_err(|_| "Download stalled".to_string())?
+      .map_err(|err| format!("Download read failed: {}", err))?;
+    let Some(chunk) = chunk else {
+      break;
+    };
+    file
+      .write_all(&chunk)
+      .map_err(|err| format!("Failed to write output file: {}", err))?;
+    downloaded += chunk.len() as u64;
+    if track_progress
+      && total > 0
+      && last_emit.elapsed() >= Duration::from_millis(DOWNLOAD_PROGRESS_EMIT_INTERVAL_MILLIS)
+    {
+      let elapsed = started.elapsed().as_secs().max(1);
+      let speed = downloaded.saturating_sub(if resumed { existing } else { 0 }) / elapsed;
+      let _ = update_download_bytes(context, record_id, progress_key, total, downloaded, speed);
+      last_emit = std::time::Instant::now();
+    }
+  }
+  file
+    .flush()
+    .map_err(|err| format!("Failed to write output file: {}", err))?;
+  if total > 0 && downloaded < total {
+    return Err(format!("Download incomplete: {}/{}", downloaded, total));
+  }
+  if track_progress && total > 0 {
+    let _ = update_download_bytes(context, record_id, progress_key, total, downloaded, 0);
+  }
+  Ok(())
+}
+
+fn build_http_download_headers(header: &str) -> reqwest::header::HeaderMap {
+  use reqwest::header::{HeaderMap, HeaderName, HeaderValue, REFERER, USER_AGENT};
+
+  let mut headers = HeaderMap::new();
+  if let Ok(value) = HeaderValue::from_str(&crate::config::web_referer_or("https://www.bilibili.com")) {
+    headers.insert(REFERER, value);
+  }
+  if let Ok(value) = HeaderValue::from_str(&crate::config::user_agent_or(
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
+  )) {
+    headers.insert(USER_AGENT, value);
+  }
+  for line in header.split("\r\n") {
+    let Some((name, value)) = line.split_once(':') else {
+      continue;
+    };
+    if let (Ok(name), Ok(value)) = (
+      HeaderName::from_bytes(name.trim().as_bytes()),
+      HeaderValue::from_str(value.trim()),
+    ) {
+      headers.insert(name, value);
+    }
+  }
+  headers
+}
+
 async fn fetch_play_info(
   context: &DownloadContext,
   bvid: Option<String>,
@@ -2635,6 +3464,7 @@ async fn fetch_play_info(
     .resolution
     .clone()
     .unwrap_or_else(|| if is_logged_in { "127".to_string() } else { "64".to_string() });
+  let qn = cap_quality(&qn, config.max_resolution);
   let fnval = match format {
     "flv" => "0",
     "mp4" => "1",
@@ -2732,11 +3562,21 @@ fn candidate_codec_matches(candidate: &StreamCandidate, codec: &str) -> bool {
 fn choose_target_resolution(
   candidates: &[StreamCandidate],
   resolution: Option<&str>,
+  max_resolution: Option<i64>,
 ) -> Option<i64> {
   let mut ids: Vec<i64> = candidates.iter().filter_map(|candidate| candidate.id).collect();
   if ids.is_empty() {
     return None;
   }
+  ids.sort_unstable();
+  if let Some(max_resolution) = max_resolution.filter(|value| *value > 0) {
+    let lowest = ids[0];
+    ids.retain(|id| *id <= max_resolution);
+    // 上限低于所有可用清晰度时退回最低一档
+    if ids.is_empty() {
+      ids.push(lowest);
+    }
+  }
   if let Some(resolution) = resolution {
     if let Ok(resolution) = resolution.parse::<i64>() {
       if ids.iter().any(|id| *id == resolution) {
@@ -2744,10 +3584,70 @@ fn choose_target_resolution(
       }
     }
   }
-  ids.sort_unstable();
   ids.pop()
 }
 
+pub(crate) fn cap_quality(qn: &str, max_resolution: Option<i64>) -> String {
+  match (qn.parse::<i64>(), max_resolution.filter(|value| *value > 0)) {
+    (Ok(value), Some(max_resolution)) if value > max_resolution => max_resolution.to_string(),
+    _ => qn.to_string(),
+  }
+}
+
+/// 编码偏好支持完整 codecs 串或 avc/hevc/av1/vp9 等简写。
+fn codec_family_prefixes(codec: &str) -> Vec<&'static str> {
+  let normalized = codec.trim().to_ascii_lowercase().replace(['.', '-', ' '], "");
+  if normalized.starts_with("avc") || normalized == "h264" {
+    vec!["avc1"]
+  } else if normalized.starts_with("hev")
+    || normalized.starts_with("hvc")
+    || normalized == "hevc"
+    || normalized == "h265"
+  {
+    vec!["hev1", "hvc1"]
+  } else if normalized.starts_with("av01") || normalized == "av1" {
+    vec!["av01"]
+  } else if normalized.starts_with("vp09") || normalized == "vp9" {
+    vec!["vp09"]
+  } else {
+    Vec::new()
+  }
+}
+
+/// 按下载逻辑挑选首选视频流，返回 (清晰度 qn, codecs)。
+pub(crate) fn pick_video_stream(
+  dash: &Value,
+  resolution: Option<&str>,
+  codec: Option<&str>,
+  max_resolution: Option<i64>,
+) -> Option<(Option<i64>, Option<String>)> {
+  let candidates = select_video_candidates(dash, resolution, codec, max_resolution, false).ok()?;
+  candidates
+    .first()
+    .map(|candidate| (candidate.id, candidate.codec.clone()))
+}
+
+fn record_selected_stream(context: &DownloadContext, record_id: i64, candidate: &StreamCandidate) {
+  let resolution = candidate.id.map(|value| value.to_string());
+  let result = context.db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE video_download SET resolution = COALESCE(?1, resolution), codec = COALESCE(?2, codec) WHERE id = ?3",
+      (resolution.as_deref(), candidate.codec.as_deref(), record_id),
+    )?;
+    Ok(())
+  });
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "download_stream_selected record_id={} resolution={} codec={} saved={}",
+      record_id,
+      resolution.as_deref().unwrap_or(""),
+      candidate.codec.as_deref().unwrap_or(""),
+      result.is_ok()
+    ),
+  );
+}
+
 fn choose_target_codec(
   candidates: &[StreamCandidate],
   target_resolution: Option<i64>,
@@ -2768,6 +3668,11 @@ fn choose_target_codec(
     if filtered.iter().any(|candidate| candidate_codec_matches(candidate, codec)) {
       return Some(codec.to_string());
     }
+    for prefix in codec_family_prefixes(codec) {
+      if filtered.iter().any(|candidate| candidate_codec_matches(candidate, prefix)) {
+        return Some(prefix.to_string());
+      }
+    }
   }
   for codec in ["avc1", "hev1", "hvc1", "vp09", "av01"] {
     if filtered.iter().any(|candidate| candidate_codec_matches(candidate, codec)) {
@@ -2812,6 +3717,7 @@ fn select_video_candidates(
   dash: &Value,
   resolution: Option<&str>,
   codec: Option<&str>,
+  max_resolution: Option<i64>,
   block_pcdn: bool,
 ) -> Result<Vec<StreamCandidate>, String> {
   let videos = dash
@@ -2841,7 +3747,7 @@ fn select_video_candidates(
   if candidates.is_empty() {
     return Err("Missing video URL".to_string());
   }
-  let target_resolution = choose_target_resolution(&candidates, resolution);
+  let target_resolution = choose_target_resolution(&candidates, resolution, max_resolution);
   let target_codec = choose_target_codec(&candidates, target_resolution, codec);
   candidates.sort_by(|a, b| {
     let a_res = target_resolution.map(|resolution| a.id == Some(resolution)).unwrap_or(false);
@@ -3257,11 +4163,52 @@ fn stream_urls_from_item(item: &Value, block_pcdn: bool) -> Vec<String> {
   normalize_stream_urls(urls, block_pcdn)
 }
 
-async fn fetch_video_title(
+fn page_position(pages: &[Value], cid: i64) -> Option<usize> {
+  pages
+    .iter()
+    .position(|page| page.get("cid").and_then(|value| value.as_i64()) == Some(cid))
+    .map(|index| index + 1)
+}
+
+fn parts_from_page_indices(pages: &[Value], indices: &[i64]) -> Result<Vec<DownloadPart>, String> {
+  if pages.is_empty() {
+    return Err("获取分P列表失败".to_string());
+  }
+  let mut seen = HashSet::new();
+  let mut parts = Vec::with_capacity(indices.len());
+  for index in indices {
+    if !seen.insert(*index) {
+      continue;
+    }
+    let page = usize::try_from(*index)
+      .ok()
+      .filter(|value| *value >= 1)
+      .and_then(|value| pages.get(value - 1))
+      .ok_or_else(|| format!("分P P{} 不存在，共 {} P", index, pages.len()))?;
+    let cid = page
+      .get("cid")
+      .and_then(|value| value.as_i64())
+      .ok_or_else(|| format!("分P P{} 缺少 cid", index))?;
+    let title = page
+      .get("part")
+      .and_then(|value| value.as_str())
+      .filter(|value| !value.trim().is_empty())
+      .map(|value| value.to_string())
+      .unwrap_or_else(|| format!("P{}", index));
+    parts.push(DownloadPart {
+      cid,
+      title,
+      duration: page.get("duration").and_then(|value| value.as_i64()),
+    });
+  }
+  Ok(parts)
+}
+
+async fn fetch_video_view(
   context: &DownloadContext,
   bvid: Option<&str>,
   aid: Option<&str>,
-) -> Option<String> {
+) -> Result<Value, String> {
   let mut params = Vec::new();
   if let Some(bvid) = bvid {
     params.push(("bvid".to_string(), bvid.to_string()));
@@ -3272,11 +4219,7 @@ async fn fetch_video_title(
 
   let auth = load_auth(context);
   let url = format!("{}/x/web-interface/view", context.bilibili.base_url());
-  let data = context.bilibili.get_json(&url, &params, auth.as_ref(), false).await.ok()?;
-  data
-    .get("title")
-    .and_then(|value| value.as_str())
-    .map(|value| value.to_string())
+  context.bilibili.get_json(&url, &params, auth.as_ref(), false).await
 }
 
 fn parse_video_id(url: &str) -> (Option<String>, Option<String>) {
@@ -3321,12 +4264,32 @@ fn extract_aid(input: &str) -> Option<String> {
   None
 }
 
+/// 追加一个网络输入：请求头、HTTP 代理（与 aria2c 使用同一代理）以及输入地址。
+fn push_network_input(args: &mut Vec<String>, header: &str, url: &str) {
+  if !header.is_empty() {
+    args.push("-headers".to_string());
+    args.push(header.to_string());
+  }
+  if let Some(proxy) = crate::config::current_http_proxy_url() {
+    args.push("-http_proxy".to_string());
+    args.push(proxy);
+  }
+  args.push("-i".to_string());
+  args.push(url.to_string());
+}
+
 fn build_ffmpeg_headers(context: &DownloadContext) -> Option<String> {
   let auth = load_auth(context)?;
   let mut headers = String::new();
-  headers.push_str("Referer: https://www.bilibili.com\r\n");
+  headers.push_str(&format!(
+    "Referer: {}\r\n",
+    crate::config::web_referer_or("https://www.bilibili.com")
+  ));
   headers.push_str("Origin: https://www.bilibili.com\r\n");
-  headers.push_str("User-Agent: Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36\r\n");
+  headers.push_str(&format!(
+    "User-Agent: {}\r\n",
+    crate::config::user_agent_or("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
+  ));
   headers.push_str(&format!("Cookie: {}\r\n", auth.cookie));
   Some(headers)
 }
@@ -3346,7 +4309,7 @@ fn update_download_status(
     .db
     .with_conn(|conn| {
       conn.execute(
-        "UPDATE video_download SET status = ?1, progress = ?2, update_time = ?3 WHERE id = ?4",
+        "UPDATE video_download SET status = ?1, progress = ?2, status_message = NULL, update_time = ?3 WHERE id = ?4",
         (status, progress, &now, record_id),
       )?;
       Ok(())
@@ -3408,6 +4371,20 @@ fn reset_download_progress_bytes(
     .map_err(|err| format!("Failed to reset download bytes: {}", err))
 }
 
+fn mark_download_cancelled(context: &DownloadContext, record_id: i64) -> Result<(), String> {
+  let now = now_rfc3339();
+  context
+    .db
+    .with_conn(|conn| {
+      conn.execute(
+        "UPDATE video_download SET status = 3, status_message = ?1, update_time = ?2 WHERE id = ?3",
+        (DOWNLOAD_CANCELLED_MESSAGE, &now, record_id),
+      )?;
+      Ok(())
+    })
+    .map_err(|err| format!("Failed to update download status: {}", err))
+}
+
 fn update_download_status_only(
   context: &DownloadContext,
   record_id: i64,
@@ -3418,7 +4395,7 @@ fn update_download_status_only(
     .db
     .with_conn(|conn| {
       conn.execute(
-        "UPDATE video_download SET status = ?1, update_time = ?2 WHERE id = ?3",
+        "UPDATE video_download SET status = ?1, status_message = NULL, update_time = ?2 WHERE id = ?3",
         (status, &now, record_id),
       )?;
       Ok(())
@@ -3430,6 +4407,9 @@ fn clear_download_progress(context: &DownloadContext, record_id: i64) {
   if let Ok(mut state) = context.download_runtime.progress_state.lock() {
     state.remove(&record_id);
   }
+  if let Ok(mut state) = context.download_runtime.telemetry_state.lock() {
+    state.remove(&record_id);
+  }
 }
 
 fn update_download_bytes(
@@ -3438,6 +4418,7 @@ fn update_download_bytes(
   key: &str,
   content: u64,
   chunk: u64,
+  speed: u64,
 ) -> Result<(), String> {
   let mut state = context
     .download_runtime
@@ -3451,8 +4432,10 @@ fn update_download_bytes(
     return Ok(());
   }
   let total_chunk: u64 = entry.values().map(|(_, value)| *value).sum();
+  drop(state);
   let progress = ((total_chunk.saturating_mul(100)) / total_content) as i64;
   let progress = progress.min(99);
+  emit_download_progress(context, record_id, key, total_content, total_chunk, speed, progress);
   let total_content = i64::try_from(total_content).unwrap_or(i64::MAX);
   let total_chunk = i64::try_from(total_chunk).unwrap_or(i64::MAX);
   let now = now_rfc3339();
@@ -3471,6 +4454,48 @@ fn update_download_bytes(
     .map_err(|err| format!("Failed to update download progress: {}", err))
 }
 
+fn emit_download_progress(
+  context: &DownloadContext,
+  record_id: i64,
+  key: &str,
+  total_bytes: u64,
+  downloaded_bytes: u64,
+  speed: u64,
+  progress: i64,
+) {
+  let speed = {
+    let mut state = match context.download_runtime.telemetry_state.lock() {
+      Ok(state) => state,
+      Err(_) => return,
+    };
+    let telemetry = state.entry(record_id).or_default();
+    telemetry.speeds.insert(key.to_string(), speed);
+    let finished = downloaded_bytes >= total_bytes;
+    let due = telemetry
+      .last_emit
+      .map(|last| last.elapsed() >= Duration::from_millis(DOWNLOAD_PROGRESS_EMIT_INTERVAL_MILLIS))
+      .unwrap_or(true);
+    if !due && !finished {
+      return;
+    }
+    telemetry.last_emit = Some(std::time::Instant::now());
+    telemetry.speeds.values().sum::<u64>()
+  };
+  let payload = DownloadProgressEvent {
+    id: record_id,
+    downloaded_bytes,
+    total_bytes,
+    speed,
+    progress,
+  };
+  if let Err(err) = context.app_handle.emit(DOWNLOAD_PROGRESS_EVENT, payload) {
+    append_log(
+      &context.app_log_path,
+      &format!("download_progress_emit_fail record_id={} err={}", record_id, err),
+    );
+  }
+}
+
 fn is_dir_empty(dir: &Path) -> bool {
   let entries = match std::fs::read_dir(dir) {
     Ok(value) => value,
@@ -1,11 +1,12 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use serde::Serialize;
 
 use crate::api::ApiResponse;
 use crate::config::{default_download_dir, resolve_ffmpeg_path};
+use crate::processing::{probe_media_info, MediaProbeInfo};
 
 #[derive(Serialize)]
 pub struct FileEntry {
@@ -114,6 +115,18 @@ pub fn video_duration(path: String) -> ApiResponse<i64> {
   }
 }
 
+#[tauri::command]
+pub fn media_probe(path: String) -> ApiResponse<MediaProbeInfo> {
+  let trimmed = path.trim();
+  if trimmed.is_empty() {
+    return ApiResponse::error("Path is empty");
+  }
+  match probe_media_info(Path::new(trimmed)) {
+    Ok(info) => ApiResponse::success(info),
+    Err(err) => ApiResponse::error(format!("Failed to probe media: {}", err)),
+  }
+}
+
 fn parse_ffmpeg_duration(text: &str) -> Option<i64> {
   let marker = "Duration:";
   let start = text.find(marker)?;
@@ -1,8 +1,15 @@
 use tauri::State;
 
 use crate::api::ApiResponse;
-use crate::commands::settings::{default_live_settings, load_live_settings_from_db};
-use crate::live_recorder::{fetch_room_info, start_recording, stop_recording, LiveContext};
+use crate::commands::settings::{
+  default_live_settings, format_settings_errors, load_live_settings_from_db,
+  validate_live_settings, LiveSettingsOverride,
+};
+use crate::live_recorder::{
+  auto_record_quota_exceeded, fetch_room_info, merge_record_session, start_recording,
+  stop_recording, test_stream_urls, LiveContext, LiveRecordDebugSnapshot, LiveRecordMergeResult,
+  LiveRecordStatus, LiveStreamTestResult, RECORDING_LIMIT_ERROR,
+};
 use crate::utils::{append_log, now_rfc3339};
 use crate::AppState;
 
@@ -10,8 +17,20 @@ use crate::AppState;
 pub async fn live_record_start(
   state: State<'_, AppState>,
   room_id: String,
+  force: Option<bool>,
 ) -> Result<ApiResponse<String>, String> {
   let settings = load_live_settings_from_db(&state.db).unwrap_or_else(|_| default_live_settings());
+  if !force.unwrap_or(false)
+    && !state.live_runtime.is_recording(&room_id)
+    && state
+      .live_runtime
+      .recording_limit_reached(settings.max_concurrent_recordings)
+  {
+    return Ok(ApiResponse::error(format!(
+      "{}（{}）",
+      RECORDING_LIMIT_ERROR, settings.max_concurrent_recordings
+    )));
+  }
   let room_info = fetch_room_info(&state.bilibili, &room_id).await?;
   let context = LiveContext {
     db: state.db.clone(),
@@ -42,6 +61,93 @@ pub fn live_record_stop(
   ApiResponse::success("录制已停止".to_string())
 }
 
+#[tauri::command]
+pub fn live_record_debug(
+  state: State<'_, AppState>,
+  room_id: String,
+) -> ApiResponse<LiveRecordDebugSnapshot> {
+  match state.live_runtime.get_record_debug(room_id.trim()) {
+    Some(snapshot) => ApiResponse::success(snapshot),
+    None => ApiResponse::error("当前房间未在录制"),
+  }
+}
+
+#[tauri::command]
+pub fn live_record_status(
+  state: State<'_, AppState>,
+  room_id: String,
+) -> ApiResponse<Option<LiveRecordStatus>> {
+  ApiResponse::success(state.live_runtime.get_record_status(room_id.trim()))
+}
+
+#[tauri::command]
+pub async fn live_test_stream(
+  state: State<'_, AppState>,
+  room_id: String,
+) -> Result<ApiResponse<LiveStreamTestResult>, String> {
+  let room_id = room_id.trim().to_string();
+  if room_id.is_empty() {
+    return Ok(ApiResponse::error("房间号不能为空"));
+  }
+  let room_info = match fetch_room_info(&state.bilibili, &room_id).await {
+    Ok(info) => info,
+    Err(err) => return Ok(ApiResponse::error(format!("获取房间信息失败: {}", err))),
+  };
+  let settings = load_live_settings_from_db(&state.db).unwrap_or_else(|_| default_live_settings());
+  let context = LiveContext {
+    db: state.db.clone(),
+    bilibili: state.bilibili.clone(),
+    login_store: state.login_store.clone(),
+    app_log_path: state.app_log_path.clone(),
+    live_runtime: state.live_runtime.clone(),
+  };
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let result = test_stream_urls(&context, &room_info, settings);
+    match &result {
+      Ok(test) => append_log(
+        &context.app_log_path,
+        &format!(
+          "live_test_stream room={} live_status={} qn={} fallback={} urls={}",
+          test.room_id,
+          test.live_status,
+          test.requested_qn,
+          test.quality_fallback,
+          test.urls.len()
+        ),
+      ),
+      Err(err) => append_log(
+        &context.app_log_path,
+        &format!("live_test_stream_fail room={} err={}", room_info.room_id, err),
+      ),
+    }
+    result
+  })
+  .await
+  .map_err(|_| "测试直播流失败".to_string())?;
+  match result {
+    Ok(test) => Ok(ApiResponse::success(test)),
+    Err(err) => Ok(ApiResponse::error(err)),
+  }
+}
+
+#[tauri::command]
+pub async fn live_record_merge(
+  state: State<'_, AppState>,
+  room_id_or_task_id: String,
+) -> Result<ApiResponse<LiveRecordMergeResult>, String> {
+  let db = state.db.clone();
+  let app_log_path = state.app_log_path.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    merge_record_session(&db, app_log_path.as_ref(), &room_id_or_task_id)
+  })
+  .await
+  .map_err(|_| "合并录制分段失败".to_string())?;
+  match result {
+    Ok(merged) => Ok(ApiResponse::success(merged)),
+    Err(err) => Ok(ApiResponse::error(err)),
+  }
+}
+
 #[tauri::command]
 pub async fn live_room_auto_record_update(
   state: State<'_, AppState>,
@@ -64,7 +170,9 @@ pub async fn live_room_auto_record_update(
   if auto_record {
     let settings = load_live_settings_from_db(&state.db).unwrap_or_else(|_| default_live_settings());
     let room_info = fetch_room_info(&state.bilibili, &room_id).await?;
-    if room_info.live_status == 1 {
+    if room_info.live_status == 1
+      && !auto_record_quota_exceeded(&state.db, &state.app_log_path, &room_id)
+    {
       let context = LiveContext {
         db: state.db.clone(),
         bilibili: state.bilibili.clone(),
@@ -117,6 +225,99 @@ pub fn live_room_baidu_sync_update(
   }
 }
 
+#[tauri::command]
+pub fn live_room_daily_limit_update(
+  state: State<'_, AppState>,
+  room_id: String,
+  max_daily_recording_hours: f64,
+) -> ApiResponse<String> {
+  if !max_daily_recording_hours.is_finite()
+    || !(0.0..=24.0).contains(&max_daily_recording_hours)
+  {
+    return ApiResponse::error("每日录制时长上限需在 0-24 小时之间".to_string());
+  }
+  let now = now_rfc3339();
+  let result = state.db.with_conn(|conn| {
+    conn.execute(
+      "INSERT INTO live_room_settings (room_id, auto_record, max_daily_recording_hours, update_time) \
+       VALUES (?1, 1, ?2, ?3) \
+       ON CONFLICT(room_id) DO UPDATE SET \
+         max_daily_recording_hours = excluded.max_daily_recording_hours, \
+         update_time = excluded.update_time",
+      (room_id.as_str(), max_daily_recording_hours, &now),
+    )?;
+    Ok(())
+  });
+  match result {
+    Ok(()) => {
+      append_log(
+        &state.app_log_path,
+        &format!(
+          "live_room_daily_limit_update room={} hours={}",
+          room_id, max_daily_recording_hours
+        ),
+      );
+      ApiResponse::success("已更新".to_string())
+    }
+    Err(err) => ApiResponse::error(format!("Failed to update daily limit: {}", err)),
+  }
+}
+
+/// 设置房间的录制设置覆盖，`overrides` 为空或所有字段未设置时清除覆盖。
+/// 覆盖后的完整设置按全局设置的规则校验，下次开始录制时生效。
+#[tauri::command]
+pub fn live_room_settings_override_update(
+  state: State<'_, AppState>,
+  room_id: String,
+  overrides: Option<LiveSettingsOverride>,
+) -> ApiResponse<Option<LiveSettingsOverride>> {
+  let room_id = room_id.trim().to_string();
+  if room_id.is_empty() {
+    return ApiResponse::error("房间号不能为空".to_string());
+  }
+  let overrides = overrides
+    .map(LiveSettingsOverride::normalized)
+    .filter(|value| !value.is_empty());
+  if let Some(overrides) = overrides.as_ref() {
+    let mut merged = load_live_settings_from_db(&state.db).unwrap_or_else(|_| default_live_settings());
+    overrides.apply(&mut merged);
+    let errors = validate_live_settings(&merged);
+    if !errors.is_empty() {
+      return ApiResponse::error(format_settings_errors(&errors));
+    }
+  }
+  let serialized = match overrides.as_ref().map(serde_json::to_string).transpose() {
+    Ok(value) => value,
+    Err(err) => return ApiResponse::error(format!("序列化覆盖设置失败: {}", err)),
+  };
+  let now = now_rfc3339();
+  let result = state.db.with_conn(|conn| {
+    conn.execute(
+      "INSERT INTO live_room_settings (room_id, auto_record, settings_override, update_time) \
+       VALUES (?1, 1, ?2, ?3) \
+       ON CONFLICT(room_id) DO UPDATE SET \
+         settings_override = excluded.settings_override, \
+         update_time = excluded.update_time",
+      (room_id.as_str(), serialized.as_deref(), &now),
+    )?;
+    Ok(())
+  });
+  match result {
+    Ok(()) => {
+      append_log(
+        &state.app_log_path,
+        &format!(
+          "live_room_settings_override_update room={} override={}",
+          room_id,
+          serialized.as_deref().unwrap_or("-")
+        ),
+      );
+      ApiResponse::success(overrides)
+    }
+    Err(err) => ApiResponse::error(format!("Failed to update settings override: {}", err)),
+  }
+}
+
 #[tauri::command]
 pub fn live_room_baidu_sync_toggle(
   state: State<'_, AppState>,
@@ -9,3 +9,5 @@ pub mod submission;
 pub mod live;
 pub mod toolbox;
 pub mod baidu_sync;
+pub mod system;
+pub mod storage;
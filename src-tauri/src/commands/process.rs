@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
@@ -5,9 +6,10 @@ use serde_json::json;
 use tauri::State;
 
 use crate::api::ApiResponse;
+use crate::commands::settings::{load_download_settings_from_db, DEFAULT_CLIP_CONCURRENCY};
 use crate::config::{default_download_dir, default_temp_dir};
-use crate::processing::{clip_sources, decide_clip_copy, merge_files, ClipSource};
-use crate::utils::{now_rfc3339, sanitize_filename};
+use crate::processing::{clip_sources, decide_clip_copy, extract_frame, merge_files, ClipSource};
+use crate::utils::{append_log, now_rfc3339, sanitize_filename};
 use crate::db::Db;
 use crate::AppState;
 
@@ -124,6 +126,59 @@ pub fn process_status(state: State<'_, AppState>, task_id: i64) -> ApiResponse<V
   }
 }
 
+#[tauri::command]
+pub async fn process_extract_frame(
+  state: State<'_, AppState>,
+  video_path: String,
+  timestamp_seconds: f64,
+) -> Result<ApiResponse<String>, String> {
+  let video_path = video_path.trim().to_string();
+  if video_path.is_empty() {
+    return Ok(ApiResponse::error("Video path is required"));
+  }
+  let source = Path::new(&video_path);
+  if !source.is_file() {
+    return Ok(ApiResponse::error("Video file not found"));
+  }
+  let stem = source
+    .file_stem()
+    .map(|value| value.to_string_lossy().to_string())
+    .unwrap_or_else(|| "frame".to_string());
+  let millis = (timestamp_seconds.max(0.0) * 1000.0).round() as i64;
+  let output_path = source.with_file_name(format!("{}_frame_{}.jpg", stem, millis));
+
+  let log_path = state.app_log_path.clone();
+  let frame_source = source.to_path_buf();
+  let frame_output = output_path.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    extract_frame(&frame_source, timestamp_seconds, &frame_output)
+  })
+  .await
+  .map_err(|_| "Failed to extract frame".to_string())?;
+  match result {
+    Ok(actual) => {
+      append_log(
+        log_path.as_ref(),
+        &format!(
+          "process_extract_frame_ok input={} requested={} actual={} output={}",
+          video_path,
+          timestamp_seconds,
+          actual,
+          output_path.to_string_lossy()
+        ),
+      );
+      Ok(ApiResponse::success(output_path.to_string_lossy().to_string()))
+    }
+    Err(err) => {
+      append_log(
+        log_path.as_ref(),
+        &format!("process_extract_frame_fail input={} err={}", video_path, err),
+      );
+      Ok(ApiResponse::error(err))
+    }
+  }
+}
+
 async fn run_process_task(
   context: ProcessContext,
   task_id: i64,
@@ -149,8 +204,12 @@ async fn run_process_task(
     reason: None,
   });
   let use_copy = copy_decision.use_copy;
+  let clip_concurrency = load_download_settings_from_db(context.db.as_ref())
+    .map(|settings| settings.clip_concurrency)
+    .unwrap_or(DEFAULT_CLIP_CONCURRENCY)
+    .max(1) as usize;
   let clip_outputs = tauri::async_runtime::spawn_blocking(move || {
-    clip_sources(&sources, &temp_dir, use_copy)
+    clip_sources(&sources, &temp_dir, use_copy, None, None, clip_concurrency, |_| {})
   })
   .await
   .map_err(|_| "Failed to clip videos".to_string())??;
@@ -158,7 +217,7 @@ async fn run_process_task(
   let output_name = format!("{}_merged.mp4", sanitize_filename(&request.task_name));
   let output_path = default_download_dir().join(output_name);
   let output_path_clone = output_path.clone();
-  tauri::async_runtime::spawn_blocking(move || merge_files(&clip_outputs, &output_path_clone))
+  tauri::async_runtime::spawn_blocking(move || merge_files(&clip_outputs, &output_path_clone, None, None, |_| {}))
     .await
     .map_err(|_| "Failed to merge videos".to_string())??;
 
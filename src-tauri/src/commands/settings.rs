@@ -12,12 +12,34 @@ pub const DEFAULT_THREADS: i64 = 3;
 pub const DEFAULT_QUEUE_SIZE: i64 = 10;
 pub const DEFAULT_UPLOAD_CONCURRENCY: i64 = 3;
 pub const MAX_UPLOAD_CONCURRENCY: i64 = 5;
+pub const DEFAULT_CHUNK_CONCURRENCY: i64 = 1;
+pub const MAX_CHUNK_CONCURRENCY: i64 = 8;
+pub const DEFAULT_SUBMISSION_AUTO_RETRY: bool = false;
+pub const DEFAULT_SUBMISSION_AUTO_RETRY_MAX_ATTEMPTS: i64 = 3;
+pub const MAX_SUBMISSION_AUTO_RETRY_MAX_ATTEMPTS: i64 = 10;
+pub const DEFAULT_MAX_CONCURRENT_SUBMISSION_TASKS: i64 = 1;
+pub const MAX_CONCURRENT_SUBMISSION_TASKS: i64 = 5;
+pub const DEFAULT_CLIP_CONCURRENCY: i64 = 2;
+pub const MAX_CLIP_CONCURRENCY: i64 = 8;
+pub const DEFAULT_MIN_LAST_SEGMENT_SECONDS: i64 = 10;
+pub const MAX_MIN_LAST_SEGMENT_SECONDS: i64 = 600;
+pub const DEFAULT_SEGMENT_INDEX_PADDING: i64 = 0;
+pub const MAX_SEGMENT_INDEX_PADDING: i64 = 4;
+pub const MAX_LOG_SIZE_MB: i64 = 1024;
 pub const DEFAULT_SUBMISSION_REMOTE_REFRESH_MINUTES: i64 = 10;
+pub const DEFAULT_CREATE_OPTIONS_TTL_SECS: i64 = 300;
+pub const MAX_CREATE_OPTIONS_TTL_SECS: i64 = 86_400;
 pub const DEFAULT_BLOCK_PCDN: bool = true;
 pub const DEFAULT_ENABLE_ARIA2C: bool = true;
 pub const DEFAULT_ARIA2C_CONNECTIONS: i64 = 4;
 pub const DEFAULT_ARIA2C_SPLIT: i64 = 4;
 pub const LOG_DIR_SETTING_KEY: &str = "log_dir";
+pub const DEFAULT_DANMAKU_FORMAT: &str = "json";
+pub const DEFAULT_MIN_FREE_SPACE_MB: i64 = 1024;
+pub const MIN_CHECK_INTERVAL_SEC: i64 = 10;
+pub const MIN_STREAM_RETRY_MS: i64 = 1000;
+pub const MIN_STREAM_CONNECT_TIMEOUT_MS: i64 = 1000;
+pub const MAX_ARIA2C_CONNECTIONS: i64 = 32;
 pub const LEGACY_LIVE_FILE_TEMPLATE: &str =
   "live/{{ roomId }}/录制-{{ roomId }}-{{ now }}-{{ title }}.flv";
 pub const LEGACY_LIVE_FILE_TEMPLATE_DATE: &str =
@@ -33,11 +55,51 @@ pub struct DownloadSettings {
   pub download_path: String,
   pub log_dir: String,
   pub upload_concurrency: i64,
+  pub chunk_concurrency: i64,
+  pub auto_retry_submission: bool,
+  pub auto_retry_submission_max_attempts: i64,
+  pub max_concurrent_submission_tasks: i64,
+  #[serde(default)]
+  pub clip_concurrency: i64,
+  #[serde(default)]
+  pub min_last_segment_seconds: i64,
+  /// 分P序号补零到的位数（2 表示 P01），0 表示不补零。
+  #[serde(default)]
+  pub segment_index_padding: i64,
   pub submission_remote_refresh_minutes: i64,
+  /// 投稿页合集与分区列表的缓存秒数，0 表示不缓存。
+  #[serde(default)]
+  pub submission_create_options_ttl_secs: i64,
   pub block_pcdn: bool,
   pub enable_aria2c: bool,
   pub aria2c_connections: i64,
   pub aria2c_split: i64,
+  #[serde(default)]
+  pub structured_logs: bool,
+  #[serde(default)]
+  pub max_log_size_mb: i64,
+  #[serde(default)]
+  pub proxy_url: String,
+  #[serde(default)]
+  pub custom_user_agent: String,
+  #[serde(default)]
+  pub custom_live_referer: String,
+  #[serde(default)]
+  pub custom_web_referer: String,
+  #[serde(default)]
+  pub keep_intermediates: bool,
+  #[serde(default)]
+  pub auto_cleanup_after_complete: bool,
+  #[serde(default)]
+  pub hw_decode: bool,
+  #[serde(default)]
+  pub notify_webhook_url: String,
+  #[serde(default = "default_desktop_notify")]
+  pub notify_recording: bool,
+  #[serde(default = "default_desktop_notify")]
+  pub notify_download: bool,
+  #[serde(default = "default_desktop_notify")]
+  pub notify_submission: bool,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -53,7 +115,23 @@ pub struct LiveSettings {
   pub cutting_number: i64,
   pub cutting_by_title: bool,
   pub title_split_min_seconds: i64,
+  #[serde(default)]
+  pub split_by_size_mb: i64,
+  #[serde(default)]
+  pub record_backup_quality: i64,
+  #[serde(default)]
+  pub min_free_space_mb: i64,
+  #[serde(default)]
+  pub max_concurrent_recordings: i64,
+  #[serde(default)]
+  pub remux_fragmented_mp4: bool,
   pub danmaku_transport: i64,
+  #[serde(default)]
+  pub danmaku_format: String,
+  #[serde(default)]
+  pub danmaku_filter_keywords: Vec<String>,
+  #[serde(default)]
+  pub danmaku_max_per_second: i64,
   pub record_danmaku: bool,
   pub record_danmaku_raw: bool,
   pub record_danmaku_superchat: bool,
@@ -69,6 +147,123 @@ pub struct LiveSettings {
   pub baidu_sync_path: String,
 }
 
+/// 单个房间的录制设置覆盖，未设置的字段沿用全局 `LiveSettings`。
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LiveSettingsOverride {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub recording_quality: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub record_backup_quality: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub record_path: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub file_name_template: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub cutting_mode: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub cutting_number: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub danmaku_format: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub record_danmaku: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub record_danmaku_raw: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub record_danmaku_superchat: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub record_danmaku_gift: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub record_danmaku_guard: Option<bool>,
+}
+
+impl LiveSettingsOverride {
+  /// 去掉空字符串，便于判断覆盖是否为空。
+  pub fn normalized(mut self) -> Self {
+    for value in [
+      &mut self.recording_quality,
+      &mut self.record_path,
+      &mut self.file_name_template,
+      &mut self.danmaku_format,
+    ] {
+      *value = value
+        .take()
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty());
+    }
+    self
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.recording_quality.is_none()
+      && self.record_backup_quality.is_none()
+      && self.record_path.is_none()
+      && self.file_name_template.is_none()
+      && self.cutting_mode.is_none()
+      && self.cutting_number.is_none()
+      && self.danmaku_format.is_none()
+      && self.record_danmaku.is_none()
+      && self.record_danmaku_raw.is_none()
+      && self.record_danmaku_superchat.is_none()
+      && self.record_danmaku_gift.is_none()
+      && self.record_danmaku_guard.is_none()
+  }
+
+  pub fn apply(&self, settings: &mut LiveSettings) {
+    if let Some(value) = self.recording_quality.as_ref() {
+      settings.recording_quality = value.clone();
+    }
+    if let Some(value) = self.record_backup_quality {
+      settings.record_backup_quality = value;
+    }
+    if let Some(value) = self.record_path.as_ref() {
+      settings.record_path = value.clone();
+    }
+    if let Some(value) = self.file_name_template.as_ref() {
+      settings.file_name_template = value.clone();
+    }
+    if let Some(value) = self.cutting_mode {
+      settings.cutting_mode = value;
+    }
+    if let Some(value) = self.cutting_number {
+      settings.cutting_number = value;
+    }
+    if let Some(value) = self.danmaku_format.as_ref() {
+      settings.danmaku_format = normalize_danmaku_format(value).to_string();
+    }
+    if let Some(value) = self.record_danmaku {
+      settings.record_danmaku = value;
+    }
+    if let Some(value) = self.record_danmaku_raw {
+      settings.record_danmaku_raw = value;
+    }
+    if let Some(value) = self.record_danmaku_superchat {
+      settings.record_danmaku_superchat = value;
+    }
+    if let Some(value) = self.record_danmaku_gift {
+      settings.record_danmaku_gift = value;
+    }
+    if let Some(value) = self.record_danmaku_guard {
+      settings.record_danmaku_guard = value;
+    }
+  }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsFieldError {
+  pub field: String,
+  pub code: String,
+  pub message: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsValidationResult {
+  pub valid: bool,
+  pub errors: Vec<SettingsFieldError>,
+}
+
 #[tauri::command]
 pub fn get_download_settings(state: State<'_, AppState>) -> ApiResponse<DownloadSettings> {
   match load_download_settings_from_db(&state.db) {
@@ -85,131 +280,356 @@ pub fn get_live_settings(state: State<'_, AppState>) -> ApiResponse<LiveSettings
   }
 }
 
+/// 下载与投稿处理设置；网络、通知与日志设置各自通过独立命令保存。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadSettingsPayload {
+  pub threads: i64,
+  pub queue_size: i64,
+  pub download_path: String,
+  pub upload_concurrency: i64,
+  pub chunk_concurrency: i64,
+  pub auto_retry_submission: bool,
+  pub auto_retry_submission_max_attempts: i64,
+  pub max_concurrent_submission_tasks: i64,
+  pub clip_concurrency: i64,
+  pub min_last_segment_seconds: i64,
+  pub segment_index_padding: i64,
+  pub submission_remote_refresh_minutes: i64,
+  pub submission_create_options_ttl_secs: i64,
+  pub block_pcdn: bool,
+  pub aria2c_connections: i64,
+  pub aria2c_split: i64,
+  pub keep_intermediates: bool,
+  pub auto_cleanup_after_complete: bool,
+  pub hw_decode: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkSettingsPayload {
+  pub proxy_url: String,
+  pub custom_user_agent: String,
+  pub custom_live_referer: String,
+  pub custom_web_referer: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationSettingsPayload {
+  pub notify_webhook_url: String,
+  pub notify_recording: bool,
+  pub notify_download: bool,
+  pub notify_submission: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggingSettingsPayload {
+  pub log_dir: String,
+  pub structured_logs: bool,
+  pub max_log_size_mb: i64,
+}
+
 #[tauri::command]
 pub fn update_download_settings(
   state: State<'_, AppState>,
-  threads: i64,
-  queue_size: i64,
-  download_path: String,
-  log_dir: String,
-  upload_concurrency: i64,
-  submission_remote_refresh_minutes: i64,
-  block_pcdn: bool,
-  aria2c_connections: i64,
-  aria2c_split: i64,
-  _enable_aria2c: bool,
+  payload: DownloadSettingsPayload,
 ) -> ApiResponse<DownloadSettings> {
-  if threads <= 0
-    || queue_size <= 0
-    || submission_remote_refresh_minutes <= 0
-    || aria2c_connections <= 0
-    || aria2c_split <= 0
+  if payload.threads <= 0
+    || payload.queue_size <= 0
+    || payload.submission_remote_refresh_minutes <= 0
+    || payload.aria2c_connections <= 0
+    || payload.aria2c_split <= 0
   {
     return ApiResponse::error("Values must be greater than 0");
   }
-  if upload_concurrency <= 0 || upload_concurrency > MAX_UPLOAD_CONCURRENCY {
+  if !(1..=MAX_UPLOAD_CONCURRENCY).contains(&payload.upload_concurrency) {
     return ApiResponse::error("投稿并发上传数需在 1-5 之间");
   }
+  if !(1..=MAX_CHUNK_CONCURRENCY).contains(&payload.chunk_concurrency) {
+    return ApiResponse::error("分片并发上传数需在 1-8 之间");
+  }
+  if !(1..=MAX_SUBMISSION_AUTO_RETRY_MAX_ATTEMPTS).contains(&payload.auto_retry_submission_max_attempts) {
+    return ApiResponse::error("投稿自动重试次数需在 1-10 之间");
+  }
+  if !(1..=MAX_CONCURRENT_SUBMISSION_TASKS).contains(&payload.max_concurrent_submission_tasks) {
+    return ApiResponse::error("同时投稿任务数需在 1-5 之间");
+  }
+  if !(1..=MAX_CLIP_CONCURRENCY).contains(&payload.clip_concurrency) {
+    return ApiResponse::error("并行剪辑数需在 1-8 之间");
+  }
+  if !(0..=MAX_MIN_LAST_SEGMENT_SECONDS).contains(&payload.min_last_segment_seconds) {
+    return ApiResponse::error("末段最短时长需在 0-600 秒之间");
+  }
+  if !(0..=MAX_SEGMENT_INDEX_PADDING).contains(&payload.segment_index_padding) {
+    return ApiResponse::error("分P序号补零位数需在 0-4 之间");
+  }
+  if !(0..=MAX_CREATE_OPTIONS_TTL_SECS).contains(&payload.submission_create_options_ttl_secs) {
+    return ApiResponse::error("投稿选项缓存时间需在 0-86400 秒之间");
+  }
 
-  let normalized_path = if download_path.trim().is_empty() {
+  let normalized_path = if payload.download_path.trim().is_empty() {
     default_download_dir().to_string_lossy().to_string()
   } else {
-    download_path.trim().to_string()
-  };
-  let normalized_log_dir = if log_dir.trim().is_empty() {
-    std::path::PathBuf::from(&normalized_path)
-      .join("log")
-      .to_string_lossy()
-      .to_string()
-  } else {
-    log_dir.trim().to_string()
+    payload.download_path.trim().to_string()
   };
-  let normalized_aria2c_connections = aria2c_connections.clamp(1, 32);
-  let normalized_aria2c_split = aria2c_split.clamp(1, 32);
+  let mut path_errors = Vec::new();
+  check_writable_dir(&mut path_errors, "downloadPath", "下载目录", &normalized_path);
+  if !path_errors.is_empty() {
+    return ApiResponse::error(format_settings_errors(&path_errors));
+  }
+  if let Err(err) = prepare_writable_dir("下载目录", &normalized_path) {
+    return ApiResponse::error(err);
+  }
+  let aria2c_connections = payload.aria2c_connections.clamp(1, MAX_ARIA2C_CONNECTIONS);
+  let aria2c_split = payload.aria2c_split.clamp(1, MAX_ARIA2C_CONNECTIONS);
 
   let now = Utc::now().to_rfc3339();
-  let enable_aria2c = true;
   let result = state.db.with_conn(|conn| {
-    conn.execute(
-      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
-       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-      ("download_threads", threads.to_string(), &now),
+    upsert_setting(conn, "download_threads", &payload.threads.to_string(), &now)?;
+    upsert_setting(conn, "download_queue_size", &payload.queue_size.to_string(), &now)?;
+    upsert_setting(conn, "download_path", &normalized_path, &now)?;
+    upsert_setting(
+      conn,
+      "submission_upload_concurrency",
+      &payload.upload_concurrency.to_string(),
+      &now,
     )?;
-    conn.execute(
-      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
-       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-      ("download_queue_size", queue_size.to_string(), &now),
+    upsert_setting(
+      conn,
+      "submission_chunk_concurrency",
+      &payload.chunk_concurrency.to_string(),
+      &now,
     )?;
-    conn.execute(
-      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
-       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-      ("download_path", &normalized_path, &now),
+    upsert_setting(
+      conn,
+      "submission_auto_retry_enabled",
+      bool_setting(payload.auto_retry_submission),
+      &now,
     )?;
-    conn.execute(
-      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
-       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-      (LOG_DIR_SETTING_KEY, &normalized_log_dir, &now),
+    upsert_setting(
+      conn,
+      "submission_auto_retry_max_attempts",
+      &payload.auto_retry_submission_max_attempts.to_string(),
+      &now,
     )?;
-    conn.execute(
-      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
-       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-      (
-        "submission_upload_concurrency",
-        upload_concurrency.to_string(),
-        &now,
-      ),
+    upsert_setting(
+      conn,
+      "submission_max_concurrent_tasks",
+      &payload.max_concurrent_submission_tasks.to_string(),
+      &now,
     )?;
-    conn.execute(
-      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
-       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-      (
-        "submission_remote_refresh_minutes",
-        submission_remote_refresh_minutes.to_string(),
-        &now,
-      ),
+    upsert_setting(
+      conn,
+      "submission_clip_concurrency",
+      &payload.clip_concurrency.to_string(),
+      &now,
     )?;
-    conn.execute(
-      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
-       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-      ("download_block_pcdn", if block_pcdn { "1" } else { "0" }, &now),
+    upsert_setting(
+      conn,
+      "submission_min_last_segment_seconds",
+      &payload.min_last_segment_seconds.to_string(),
+      &now,
     )?;
-    conn.execute(
-      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
-       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-      ("download_enable_aria2c", "1", &now),
+    upsert_setting(
+      conn,
+      "submission_segment_index_padding",
+      &payload.segment_index_padding.to_string(),
+      &now,
     )?;
-    conn.execute(
-      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
-       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-      (
-        "download_aria2c_connections",
-        normalized_aria2c_connections.to_string(),
-        &now,
-      ),
+    upsert_setting(
+      conn,
+      "submission_remote_refresh_minutes",
+      &payload.submission_remote_refresh_minutes.to_string(),
+      &now,
     )?;
-    conn.execute(
-      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
-       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-      ("download_aria2c_split", normalized_aria2c_split.to_string(), &now),
+    upsert_setting(
+      conn,
+      "submission_create_options_ttl_secs",
+      &payload.submission_create_options_ttl_secs.to_string(),
+      &now,
     )?;
+    upsert_setting(conn, "download_block_pcdn", bool_setting(payload.block_pcdn), &now)?;
+    upsert_setting(conn, "download_enable_aria2c", "1", &now)?;
+    upsert_setting(
+      conn,
+      "download_aria2c_connections",
+      &aria2c_connections.to_string(),
+      &now,
+    )?;
+    upsert_setting(conn, "download_aria2c_split", &aria2c_split.to_string(), &now)?;
+    upsert_setting(
+      conn,
+      "submission_keep_intermediates",
+      bool_setting(payload.keep_intermediates),
+      &now,
+    )?;
+    upsert_setting(
+      conn,
+      "submission_auto_cleanup_after_complete",
+      bool_setting(payload.auto_cleanup_after_complete),
+      &now,
+    )?;
+    upsert_setting(conn, "ffmpeg_hw_decode", bool_setting(payload.hw_decode), &now)?;
     Ok(())
   });
 
   if let Err(err) = result {
     return ApiResponse::error(format!("Failed to update download settings: {}", err));
   }
+  crate::ffmpeg::set_hwaccel_enabled(payload.hw_decode);
+  get_download_settings(state)
+}
+
+#[tauri::command]
+pub fn update_network_settings(
+  state: State<'_, AppState>,
+  payload: NetworkSettingsPayload,
+) -> ApiResponse<DownloadSettings> {
+  let proxy_url = payload.proxy_url.trim().to_string();
+  if !proxy_url.is_empty() {
+    if let Err(err) = crate::config::validate_proxy_url(&proxy_url) {
+      return ApiResponse::error(err);
+    }
+  }
+  let custom_user_agent = payload.custom_user_agent.trim().to_string();
+  if let Err(err) = crate::config::validate_header_override("User-Agent", &custom_user_agent) {
+    return ApiResponse::error(err);
+  }
+  let custom_live_referer = payload.custom_live_referer.trim().to_string();
+  if let Err(err) = crate::config::validate_header_override("直播 Referer", &custom_live_referer) {
+    return ApiResponse::error(err);
+  }
+  let custom_web_referer = payload.custom_web_referer.trim().to_string();
+  if let Err(err) = crate::config::validate_header_override("主站 Referer", &custom_web_referer) {
+    return ApiResponse::error(err);
+  }
+  let now = Utc::now().to_rfc3339();
+  let result = state.db.with_conn(|conn| {
+    upsert_setting(conn, "proxy_url", &proxy_url, &now)?;
+    upsert_setting(conn, "custom_user_agent", &custom_user_agent, &now)?;
+    upsert_setting(conn, "custom_live_referer", &custom_live_referer, &now)?;
+    upsert_setting(conn, "custom_web_referer", &custom_web_referer, &now)?;
+    Ok(())
+  });
+  if let Err(err) = result {
+    return ApiResponse::error(format!("Failed to update network settings: {}", err));
+  }
+  crate::config::set_proxy_url(Some(&proxy_url));
+  crate::config::set_request_identity(
+    Some(&custom_user_agent),
+    Some(&custom_live_referer),
+    Some(&custom_web_referer),
+  );
+  get_download_settings(state)
+}
+
+#[tauri::command]
+pub fn update_notification_settings(
+  state: State<'_, AppState>,
+  payload: NotificationSettingsPayload,
+) -> ApiResponse<DownloadSettings> {
+  let notify_webhook_url = payload.notify_webhook_url.trim().to_string();
+  if !notify_webhook_url.is_empty() {
+    if let Err(err) = validate_webhook_url(&notify_webhook_url) {
+      return ApiResponse::error(err);
+    }
+  }
+  let now = Utc::now().to_rfc3339();
+  let result = state.db.with_conn(|conn| {
+    upsert_setting(conn, "notify_webhook_url", &notify_webhook_url, &now)?;
+    upsert_setting(conn, "notify_recording", bool_setting(payload.notify_recording), &now)?;
+    upsert_setting(conn, "notify_download", bool_setting(payload.notify_download), &now)?;
+    upsert_setting(conn, "notify_submission", bool_setting(payload.notify_submission), &now)?;
+    Ok(())
+  });
+  if let Err(err) = result {
+    return ApiResponse::error(format!("Failed to update notification settings: {}", err));
+  }
+  get_download_settings(state)
+}
+
+/// 日志目录留空时使用下载目录下的 log 子目录，修改后需重启生效。
+#[tauri::command]
+pub fn update_logging_settings(
+  state: State<'_, AppState>,
+  payload: LoggingSettingsPayload,
+) -> ApiResponse<DownloadSettings> {
+  if !(0..=MAX_LOG_SIZE_MB).contains(&payload.max_log_size_mb) {
+    return ApiResponse::error("日志文件大小上限需在 0-1024 MB 之间");
+  }
+  let log_dir = if payload.log_dir.trim().is_empty() {
+    let download_path = load_download_settings_from_db(&state.db)
+      .map(|settings| settings.download_path)
+      .unwrap_or_else(|_| default_download_dir().to_string_lossy().to_string());
+    std::path::PathBuf::from(download_path)
+      .join("log")
+      .to_string_lossy()
+      .to_string()
+  } else {
+    payload.log_dir.trim().to_string()
+  };
+  let mut path_errors = Vec::new();
+  check_writable_dir(&mut path_errors, "logDir", "日志目录", &log_dir);
+  if !path_errors.is_empty() {
+    return ApiResponse::error(format_settings_errors(&path_errors));
+  }
+  if let Err(err) = prepare_writable_dir("日志目录", &log_dir) {
+    return ApiResponse::error(err);
+  }
+  let now = Utc::now().to_rfc3339();
+  let result = state.db.with_conn(|conn| {
+    upsert_setting(conn, LOG_DIR_SETTING_KEY, &log_dir, &now)?;
+    upsert_setting(conn, "structured_logs", bool_setting(payload.structured_logs), &now)?;
+    upsert_setting(conn, "max_log_size_mb", &payload.max_log_size_mb.to_string(), &now)?;
+    Ok(())
+  });
+  if let Err(err) = result {
+    return ApiResponse::error(format!("Failed to update logging settings: {}", err));
+  }
+  crate::utils::set_structured_logs(payload.structured_logs);
+  crate::utils::set_max_log_size_mb(payload.max_log_size_mb);
+  get_download_settings(state)
+}
+
+pub(crate) fn upsert_setting(
+  conn: &rusqlite::Connection,
+  key: &str,
+  value: &str,
+  now: &str,
+) -> rusqlite::Result<()> {
+  conn.execute(
+    "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
+     ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+    (key, value, now),
+  )?;
+  Ok(())
+}
+
+fn bool_setting(value: bool) -> &'static str {
+  if value {
+    "1"
+  } else {
+    "0"
+  }
+}
 
-  ApiResponse::success(DownloadSettings {
-    threads,
-    queue_size,
-    download_path: normalized_path,
-    log_dir: normalized_log_dir,
-    upload_concurrency,
-    submission_remote_refresh_minutes,
-    block_pcdn,
-    enable_aria2c,
-    aria2c_connections: normalized_aria2c_connections,
-    aria2c_split: normalized_aria2c_split,
+#[tauri::command]
+pub fn validate_settings(
+  download: Option<DownloadSettings>,
+  live: Option<LiveSettings>,
+) -> ApiResponse<SettingsValidationResult> {
+  let mut errors = Vec::new();
+  if let Some(settings) = download.as_ref() {
+    errors.extend(validate_download_settings(settings));
+  }
+  if let Some(settings) = live.as_ref() {
+    errors.extend(validate_live_settings(settings));
+  }
+  ApiResponse::success(SettingsValidationResult {
+    valid: errors.is_empty(),
+    errors,
   })
 }
 
@@ -218,11 +638,18 @@ pub fn update_live_settings(
   state: State<'_, AppState>,
   payload: LiveSettings,
 ) -> ApiResponse<LiveSettings> {
+  let errors = validate_live_settings(&payload);
+  if !errors.is_empty() {
+    return ApiResponse::error(format_settings_errors(&errors));
+  }
+  if let Err(err) = prepare_writable_dir("录制目录", &payload.record_path) {
+    return ApiResponse::error(err);
+  }
   let now = Utc::now().to_rfc3339();
   let result = state.db.with_conn(|conn| {
     conn.execute(
-      "INSERT INTO live_settings (id, file_name_template, record_path, write_metadata, save_cover, recording_quality, record_mode, cutting_mode, cutting_number, cutting_by_title, title_split_min_seconds, danmaku_transport, record_danmaku, record_danmaku_raw, record_danmaku_superchat, record_danmaku_gift, record_danmaku_guard, stream_retry_ms, stream_retry_no_qn_sec, stream_connect_timeout_ms, check_interval_sec, flv_fix_split_on_missing, flv_fix_disable_on_annexb, baidu_sync_enabled, baidu_sync_path, create_time, update_time) \
-       VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26) \
+      "INSERT INTO live_settings (id, file_name_template, record_path, write_metadata, save_cover, recording_quality, record_mode, cutting_mode, cutting_number, cutting_by_title, title_split_min_seconds, danmaku_transport, record_danmaku, record_danmaku_raw, record_danmaku_superchat, record_danmaku_gift, record_danmaku_guard, stream_retry_ms, stream_retry_no_qn_sec, stream_connect_timeout_ms, check_interval_sec, flv_fix_split_on_missing, flv_fix_disable_on_annexb, baidu_sync_enabled, baidu_sync_path, create_time, update_time, split_by_size_mb, record_backup_quality, danmaku_format, min_free_space_mb, max_concurrent_recordings, remux_fragmented_mp4, danmaku_filter_keywords, danmaku_max_per_second) \
+       VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34) \
        ON CONFLICT(id) DO UPDATE SET \
        file_name_template = excluded.file_name_template, \
        record_path = excluded.record_path, \
@@ -248,6 +675,14 @@ pub fn update_live_settings(
        flv_fix_disable_on_annexb = excluded.flv_fix_disable_on_annexb, \
        baidu_sync_enabled = excluded.baidu_sync_enabled, \
        baidu_sync_path = excluded.baidu_sync_path, \
+       split_by_size_mb = excluded.split_by_size_mb, \
+       record_backup_quality = excluded.record_backup_quality, \
+       danmaku_format = excluded.danmaku_format, \
+       min_free_space_mb = excluded.min_free_space_mb, \
+       max_concurrent_recordings = excluded.max_concurrent_recordings, \
+       remux_fragmented_mp4 = excluded.remux_fragmented_mp4, \
+       danmaku_filter_keywords = excluded.danmaku_filter_keywords, \
+       danmaku_max_per_second = excluded.danmaku_max_per_second, \
        update_time = excluded.update_time",
       params![
         payload.file_name_template.as_str(),
@@ -276,6 +711,14 @@ pub fn update_live_settings(
         payload.baidu_sync_path.as_str(),
         &now,
         &now,
+        payload.split_by_size_mb.max(0),
+        payload.record_backup_quality.max(0),
+        normalize_danmaku_format(&payload.danmaku_format),
+        payload.min_free_space_mb.max(0),
+        payload.max_concurrent_recordings.max(0),
+        payload.remux_fragmented_mp4 as i64,
+        normalize_danmaku_filter_keywords(&payload.danmaku_filter_keywords).join("\n"),
+        payload.danmaku_max_per_second.max(0),
       ],
     )?;
     Ok(())
@@ -285,9 +728,317 @@ pub fn update_live_settings(
     return ApiResponse::error(format!("Failed to update live settings: {}", err));
   }
 
+  let mut payload = payload;
+  payload.danmaku_filter_keywords = normalize_danmaku_filter_keywords(&payload.danmaku_filter_keywords);
   ApiResponse::success(payload)
 }
 
+pub fn validate_download_settings(settings: &DownloadSettings) -> Vec<SettingsFieldError> {
+  let mut errors = Vec::new();
+  check_range(&mut errors, "threads", "下载线程数", settings.threads, 1, None);
+  check_range(&mut errors, "queueSize", "下载队列长度", settings.queue_size, 1, None);
+  check_range(
+    &mut errors,
+    "uploadConcurrency",
+    "投稿并发上传数",
+    settings.upload_concurrency,
+    1,
+    Some(MAX_UPLOAD_CONCURRENCY),
+  );
+  check_range(
+    &mut errors,
+    "chunkConcurrency",
+    "分片并发上传数",
+    settings.chunk_concurrency,
+    1,
+    Some(MAX_CHUNK_CONCURRENCY),
+  );
+  check_range(
+    &mut errors,
+    "autoRetrySubmissionMaxAttempts",
+    "投稿自动重试次数",
+    settings.auto_retry_submission_max_attempts,
+    1,
+    Some(MAX_SUBMISSION_AUTO_RETRY_MAX_ATTEMPTS),
+  );
+  check_range(
+    &mut errors,
+    "maxConcurrentSubmissionTasks",
+    "同时投稿任务数",
+    settings.max_concurrent_submission_tasks,
+    1,
+    Some(MAX_CONCURRENT_SUBMISSION_TASKS),
+  );
+  check_range(
+    &mut errors,
+    "clipConcurrency",
+    "并行剪辑数",
+    settings.clip_concurrency,
+    1,
+    Some(MAX_CLIP_CONCURRENCY),
+  );
+  check_range(
+    &mut errors,
+    "minLastSegmentSeconds",
+    "末段最短时长",
+    settings.min_last_segment_seconds,
+    0,
+    Some(MAX_MIN_LAST_SEGMENT_SECONDS),
+  );
+  check_range(
+    &mut errors,
+    "segmentIndexPadding",
+    "分P序号补零位数",
+    settings.segment_index_padding,
+    0,
+    Some(MAX_SEGMENT_INDEX_PADDING),
+  );
+  check_range(
+    &mut errors,
+    "maxLogSizeMb",
+    "日志文件大小上限",
+    settings.max_log_size_mb,
+    0,
+    Some(MAX_LOG_SIZE_MB),
+  );
+  if !settings.proxy_url.is_empty() {
+    if let Err(err) = crate::config::validate_proxy_url(&settings.proxy_url) {
+      errors.push(SettingsFieldError {
+        field: "proxyUrl".to_string(),
+        code: "INVALID_PROXY".to_string(),
+        message: err,
+      });
+    }
+  }
+  if !settings.notify_webhook_url.is_empty() {
+    if let Err(err) = validate_webhook_url(&settings.notify_webhook_url) {
+      errors.push(SettingsFieldError {
+        field: "notifyWebhookUrl".to_string(),
+        code: "INVALID_WEBHOOK".to_string(),
+        message: err,
+      });
+    }
+  }
+  for (field, label, value) in [
+    ("customUserAgent", "User-Agent", &settings.custom_user_agent),
+    ("customLiveReferer", "直播 Referer", &settings.custom_live_referer),
+    ("customWebReferer", "主站 Referer", &settings.custom_web_referer),
+  ] {
+    if let Err(err) = crate::config::validate_header_override(label, value) {
+      errors.push(SettingsFieldError {
+        field: field.to_string(),
+        code: "INVALID_HEADER".to_string(),
+        message: err,
+      });
+    }
+  }
+  check_range(
+    &mut errors,
+    "submissionRemoteRefreshMinutes",
+    "投稿状态刷新间隔",
+    settings.submission_remote_refresh_minutes,
+    1,
+    None,
+  );
+  check_range(
+    &mut errors,
+    "submissionCreateOptionsTtlSecs",
+    "投稿选项缓存时间",
+    settings.submission_create_options_ttl_secs,
+    0,
+    Some(MAX_CREATE_OPTIONS_TTL_SECS),
+  );
+  check_range(
+    &mut errors,
+    "aria2cConnections",
+    "aria2c 连接数",
+    settings.aria2c_connections,
+    1,
+    Some(MAX_ARIA2C_CONNECTIONS),
+  );
+  check_range(
+    &mut errors,
+    "aria2cSplit",
+    "aria2c 分片数",
+    settings.aria2c_split,
+    1,
+    Some(MAX_ARIA2C_CONNECTIONS),
+  );
+  check_writable_dir(&mut errors, "downloadPath", "下载目录", &settings.download_path);
+  check_writable_dir(&mut errors, "logDir", "日志目录", &settings.log_dir);
+  errors
+}
+
+pub fn validate_live_settings(settings: &LiveSettings) -> Vec<SettingsFieldError> {
+  let mut errors = Vec::new();
+  check_range(&mut errors, "recordMode", "录制模式", settings.record_mode, 0, Some(1));
+  check_range(&mut errors, "cuttingMode", "分段模式", settings.cutting_mode, 0, Some(2));
+  if settings.cutting_mode != 0 {
+    check_range(&mut errors, "cuttingNumber", "分段数值", settings.cutting_number, 1, None);
+  }
+  check_range(
+    &mut errors,
+    "titleSplitMinSeconds",
+    "标题分段最小间隔",
+    settings.title_split_min_seconds,
+    0,
+    None,
+  );
+  check_range(&mut errors, "splitBySizeMb", "按大小分段", settings.split_by_size_mb, 0, None);
+  check_range(
+    &mut errors,
+    "recordBackupQuality",
+    "备用画质",
+    settings.record_backup_quality,
+    0,
+    None,
+  );
+  check_range(
+    &mut errors,
+    "minFreeSpaceMb",
+    "最小剩余空间",
+    settings.min_free_space_mb,
+    0,
+    None,
+  );
+  check_range(
+    &mut errors,
+    "maxConcurrentRecordings",
+    "最大同时录制数",
+    settings.max_concurrent_recordings,
+    0,
+    None,
+  );
+  check_range(
+    &mut errors,
+    "danmakuMaxPerSecond",
+    "每秒弹幕上限",
+    settings.danmaku_max_per_second,
+    0,
+    None,
+  );
+  check_range(
+    &mut errors,
+    "danmakuTransport",
+    "弹幕传输方式",
+    settings.danmaku_transport,
+    0,
+    None,
+  );
+  check_range(
+    &mut errors,
+    "streamRetryMs",
+    "录制重试间隔",
+    settings.stream_retry_ms,
+    MIN_STREAM_RETRY_MS,
+    None,
+  );
+  check_range(
+    &mut errors,
+    "streamRetryNoQnSec",
+    "无目标画质重试时间",
+    settings.stream_retry_no_qn_sec,
+    0,
+    None,
+  );
+  check_range(
+    &mut errors,
+    "streamConnectTimeoutMs",
+    "连接超时",
+    settings.stream_connect_timeout_ms,
+    MIN_STREAM_CONNECT_TIMEOUT_MS,
+    None,
+  );
+  check_range(
+    &mut errors,
+    "checkIntervalSec",
+    "开播检测间隔",
+    settings.check_interval_sec,
+    MIN_CHECK_INTERVAL_SEC,
+    None,
+  );
+  check_writable_dir(&mut errors, "recordPath", "录制目录", &settings.record_path);
+  errors
+}
+
+pub fn format_settings_errors(errors: &[SettingsFieldError]) -> String {
+  errors
+    .iter()
+    .map(|error| error.message.as_str())
+    .collect::<Vec<_>>()
+    .join("；")
+}
+
+fn check_range(
+  errors: &mut Vec<SettingsFieldError>,
+  field: &str,
+  label: &str,
+  value: i64,
+  min: i64,
+  max: Option<i64>,
+) {
+  let in_range = match max {
+    Some(max) => (min..=max).contains(&value),
+    None => value >= min,
+  };
+  if in_range {
+    return;
+  }
+  let message = match max {
+    Some(max) => format!("{}需在 {}-{} 之间，当前为 {}", label, min, max, value),
+    None => format!("{}不能小于 {}，当前为 {}", label, min, value),
+  };
+  errors.push(SettingsFieldError {
+    field: field.to_string(),
+    code: "OUT_OF_RANGE".to_string(),
+    message,
+  });
+}
+
+// 空路径表示使用默认目录，不做检查。
+fn check_writable_dir(errors: &mut Vec<SettingsFieldError>, field: &str, label: &str, path: &str) {
+  let path = path.trim();
+  if path.is_empty() {
+    return;
+  }
+  // 只读取元数据，不创建目录也不写探测文件；目录不存在时检查最近的已存在上级目录
+  let Some(existing) = std::path::Path::new(path).ancestors().find(|dir| dir.exists()) else {
+    return;
+  };
+  if !existing.is_dir() {
+    errors.push(SettingsFieldError {
+      field: field.to_string(),
+      code: "NOT_DIRECTORY".to_string(),
+      message: format!("{}不是目录: {}", label, path),
+    });
+    return;
+  }
+  let readonly = std::fs::metadata(existing)
+    .map(|metadata| metadata.permissions().readonly())
+    .unwrap_or(false);
+  if readonly {
+    errors.push(SettingsFieldError {
+      field: field.to_string(),
+      code: "NOT_WRITABLE".to_string(),
+      message: format!("{}不可写: {}", label, path),
+    });
+  }
+}
+
+/// 保存设置时创建目录并写入探测文件，确认目录确实可写。
+fn prepare_writable_dir(label: &str, path: &str) -> Result<(), String> {
+  let path = path.trim();
+  if path.is_empty() {
+    return Ok(());
+  }
+  let dir = std::path::Path::new(path);
+  std::fs::create_dir_all(dir).map_err(|err| format!("创建{}失败: {}", label, err))?;
+  let probe = dir.join(".reaction_cut_write_test");
+  std::fs::write(&probe, b"")
+    .and_then(|_| std::fs::remove_file(&probe))
+    .map_err(|_| format!("{}不可写: {}", label, path))
+}
+
 pub fn load_download_settings_from_db(db: &Db) -> Result<DownloadSettings, crate::db::DbError> {
   db.with_conn(|conn| {
     let threads: Option<String> = conn
@@ -318,6 +1069,55 @@ pub fn load_download_settings_from_db(db: &Db) -> Result<DownloadSettings, crate
         |row| row.get(0),
       )
       .ok();
+    let chunk_concurrency: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'submission_chunk_concurrency'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let auto_retry_submission: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'submission_auto_retry_enabled'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let auto_retry_submission_max_attempts: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'submission_auto_retry_max_attempts'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let max_concurrent_submission_tasks: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'submission_max_concurrent_tasks'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let clip_concurrency: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'submission_clip_concurrency'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let min_last_segment_seconds: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'submission_min_last_segment_seconds'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let segment_index_padding: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'submission_segment_index_padding'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
     let submission_remote_refresh_minutes: Option<String> = conn
       .query_row(
         "SELECT value FROM app_settings WHERE key = 'submission_remote_refresh_minutes'",
@@ -325,6 +1125,114 @@ pub fn load_download_settings_from_db(db: &Db) -> Result<DownloadSettings, crate
         |row| row.get(0),
       )
       .ok();
+    let submission_create_options_ttl_secs: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'submission_create_options_ttl_secs'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let max_log_size_mb: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'max_log_size_mb'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let structured_logs: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'structured_logs'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let proxy_url: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'proxy_url'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let custom_user_agent: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'custom_user_agent'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    // 旧版只有一个 custom_referer，未单独设置时直播与主站都沿用它
+    let legacy_referer: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'custom_referer'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let custom_live_referer: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'custom_live_referer'",
+        [],
+        |row| row.get(0),
+      )
+      .ok()
+      .or_else(|| legacy_referer.clone());
+    let custom_web_referer: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'custom_web_referer'",
+        [],
+        |row| row.get(0),
+      )
+      .ok()
+      .or(legacy_referer);
+    let keep_intermediates: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'submission_keep_intermediates'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let auto_cleanup_after_complete: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'submission_auto_cleanup_after_complete'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let hw_decode: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'ffmpeg_hw_decode'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let notify_webhook_url: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'notify_webhook_url'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let notify_recording: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'notify_recording'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let notify_download: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'notify_download'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
+    let notify_submission: Option<String> = conn
+      .query_row(
+        "SELECT value FROM app_settings WHERE key = 'notify_submission'",
+        [],
+        |row| row.get(0),
+      )
+      .ok();
     let block_pcdn: Option<String> = conn
       .query_row(
         "SELECT value FROM app_settings WHERE key = 'download_block_pcdn'",
@@ -384,10 +1292,41 @@ pub fn load_download_settings_from_db(db: &Db) -> Result<DownloadSettings, crate
         .and_then(|value| value.parse::<i64>().ok())
         .unwrap_or(DEFAULT_UPLOAD_CONCURRENCY)
         .clamp(1, MAX_UPLOAD_CONCURRENCY),
+      chunk_concurrency: chunk_concurrency
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_CHUNK_CONCURRENCY)
+        .clamp(1, MAX_CHUNK_CONCURRENCY),
+      auto_retry_submission: auto_retry_submission
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(DEFAULT_SUBMISSION_AUTO_RETRY),
+      auto_retry_submission_max_attempts: auto_retry_submission_max_attempts
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_SUBMISSION_AUTO_RETRY_MAX_ATTEMPTS)
+        .clamp(1, MAX_SUBMISSION_AUTO_RETRY_MAX_ATTEMPTS),
+      max_concurrent_submission_tasks: max_concurrent_submission_tasks
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_SUBMISSION_TASKS)
+        .clamp(1, MAX_CONCURRENT_SUBMISSION_TASKS),
+      clip_concurrency: clip_concurrency
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_CLIP_CONCURRENCY)
+        .clamp(1, MAX_CLIP_CONCURRENCY),
+      min_last_segment_seconds: min_last_segment_seconds
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_MIN_LAST_SEGMENT_SECONDS)
+        .clamp(0, MAX_MIN_LAST_SEGMENT_SECONDS),
+      segment_index_padding: segment_index_padding
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_SEGMENT_INDEX_PADDING)
+        .clamp(0, MAX_SEGMENT_INDEX_PADDING),
       submission_remote_refresh_minutes: submission_remote_refresh_minutes
         .and_then(|value| value.parse::<i64>().ok())
         .unwrap_or(DEFAULT_SUBMISSION_REMOTE_REFRESH_MINUTES)
         .max(1),
+      submission_create_options_ttl_secs: submission_create_options_ttl_secs
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_CREATE_OPTIONS_TTL_SECS)
+        .clamp(0, MAX_CREATE_OPTIONS_TTL_SECS),
       block_pcdn: block_pcdn
         .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
         .unwrap_or(DEFAULT_BLOCK_PCDN),
@@ -400,10 +1339,55 @@ pub fn load_download_settings_from_db(db: &Db) -> Result<DownloadSettings, crate
         .and_then(|value| value.parse::<i64>().ok())
         .unwrap_or(DEFAULT_ARIA2C_SPLIT)
         .clamp(1, 32),
+      structured_logs: structured_logs
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false),
+      max_log_size_mb: max_log_size_mb
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(crate::utils::DEFAULT_MAX_LOG_SIZE_MB)
+        .clamp(0, MAX_LOG_SIZE_MB),
+      proxy_url: proxy_url.unwrap_or_default().trim().to_string(),
+      custom_user_agent: custom_user_agent.unwrap_or_default().trim().to_string(),
+      custom_live_referer: custom_live_referer.unwrap_or_default().trim().to_string(),
+      custom_web_referer: custom_web_referer.unwrap_or_default().trim().to_string(),
+      keep_intermediates: keep_intermediates
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false),
+      auto_cleanup_after_complete: auto_cleanup_after_complete
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false),
+      hw_decode: hw_decode
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false),
+      notify_webhook_url: notify_webhook_url.unwrap_or_default().trim().to_string(),
+      notify_recording: notify_recording
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(true),
+      notify_download: notify_download
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(true),
+      notify_submission: notify_submission
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(true),
     })
   })
 }
 
+fn default_desktop_notify() -> bool {
+  true
+}
+
+fn validate_webhook_url(value: &str) -> Result<(), String> {
+  let parsed = url::Url::parse(value.trim()).map_err(|_| "通知地址格式不正确".to_string())?;
+  if parsed.scheme() != "http" && parsed.scheme() != "https" {
+    return Err("通知地址仅支持 http、https".to_string());
+  }
+  if parsed.host_str().unwrap_or("").is_empty() {
+    return Err("通知地址缺少主机名".to_string());
+  }
+  Ok(())
+}
+
 pub fn ensure_log_dir(db: &Db, download_dir: &std::path::Path) -> String {
   let fallback_value = download_dir.join("log").to_string_lossy().to_string();
   let (current, needs_update) = db
@@ -439,7 +1423,7 @@ pub fn ensure_log_dir(db: &Db, download_dir: &std::path::Path) -> String {
 pub fn load_live_settings_from_db(db: &Db) -> Result<LiveSettings, crate::db::DbError> {
   db.with_conn(|conn| {
     let mut stmt = conn.prepare(
-      "SELECT file_name_template, record_path, write_metadata, save_cover, recording_quality, record_mode, cutting_mode, cutting_number, cutting_by_title, title_split_min_seconds, danmaku_transport, record_danmaku, record_danmaku_raw, record_danmaku_superchat, record_danmaku_gift, record_danmaku_guard, stream_retry_ms, stream_retry_no_qn_sec, stream_connect_timeout_ms, check_interval_sec, flv_fix_split_on_missing, flv_fix_disable_on_annexb, baidu_sync_enabled, baidu_sync_path \
+      "SELECT file_name_template, record_path, write_metadata, save_cover, recording_quality, record_mode, cutting_mode, cutting_number, cutting_by_title, title_split_min_seconds, danmaku_transport, record_danmaku, record_danmaku_raw, record_danmaku_superchat, record_danmaku_gift, record_danmaku_guard, stream_retry_ms, stream_retry_no_qn_sec, stream_connect_timeout_ms, check_interval_sec, flv_fix_split_on_missing, flv_fix_disable_on_annexb, baidu_sync_enabled, baidu_sync_path, split_by_size_mb, record_backup_quality, danmaku_format, min_free_space_mb, max_concurrent_recordings, remux_fragmented_mp4, danmaku_filter_keywords, danmaku_max_per_second \
        FROM live_settings WHERE id = 1",
     )?;
 
@@ -469,6 +1453,27 @@ pub fn load_live_settings_from_db(db: &Db) -> Result<LiveSettings, crate::db::Db
         flv_fix_disable_on_annexb: row.get::<_, i64>(21)? != 0,
         baidu_sync_enabled: row.get::<_, i64>(22)? != 0,
         baidu_sync_path: row.get::<_, Option<String>>(23)?.unwrap_or_default(),
+        split_by_size_mb: row.get::<_, Option<i64>>(24)?.unwrap_or(0).max(0),
+        record_backup_quality: row.get::<_, Option<i64>>(25)?.unwrap_or(0).max(0),
+        danmaku_format: normalize_danmaku_format(
+          &row.get::<_, Option<String>>(26)?.unwrap_or_default(),
+        )
+        .to_string(),
+        min_free_space_mb: row
+          .get::<_, Option<i64>>(27)?
+          .unwrap_or(DEFAULT_MIN_FREE_SPACE_MB)
+          .max(0),
+        max_concurrent_recordings: row.get::<_, Option<i64>>(28)?.unwrap_or(0).max(0),
+        remux_fragmented_mp4: row.get::<_, Option<i64>>(29)?.unwrap_or(0) != 0,
+        danmaku_filter_keywords: normalize_danmaku_filter_keywords(
+          &row
+            .get::<_, Option<String>>(30)?
+            .unwrap_or_default()
+            .lines()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>(),
+        ),
+        danmaku_max_per_second: row.get::<_, Option<i64>>(31)?.unwrap_or(0).max(0),
       })
     });
 
@@ -490,6 +1495,25 @@ pub fn load_live_settings_from_db(db: &Db) -> Result<LiveSettings, crate::db::Db
   })
 }
 
+/// 弹幕导出格式：json 为原始 jsonl 记录，xml/ass 会在其基础上额外输出弹幕轨。
+pub fn normalize_danmaku_format(value: &str) -> &'static str {
+  match value.trim().to_ascii_lowercase().as_str() {
+    "xml" => "xml",
+    "ass" => "ass",
+    _ => DEFAULT_DANMAKU_FORMAT,
+  }
+}
+
+/// 弹幕屏蔽词：去除首尾空白、空项和重复项（不区分大小写）。
+pub fn normalize_danmaku_filter_keywords(values: &[String]) -> Vec<String> {
+  let mut seen = std::collections::HashSet::new();
+  values
+    .iter()
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty() && seen.insert(value.to_lowercase()))
+    .collect()
+}
+
 pub fn default_live_settings() -> LiveSettings {
   LiveSettings {
     file_name_template: DEFAULT_LIVE_FILE_TEMPLATE.to_string(),
@@ -502,7 +1526,15 @@ pub fn default_live_settings() -> LiveSettings {
     cutting_number: 100,
     cutting_by_title: false,
     title_split_min_seconds: 1800,
+    split_by_size_mb: 0,
+    record_backup_quality: 0,
+    min_free_space_mb: DEFAULT_MIN_FREE_SPACE_MB,
+    max_concurrent_recordings: 0,
+    remux_fragmented_mp4: false,
     danmaku_transport: 0,
+    danmaku_format: DEFAULT_DANMAKU_FORMAT.to_string(),
+    danmaku_filter_keywords: Vec::new(),
+    danmaku_max_per_second: 0,
     record_danmaku: false,
     record_danmaku_raw: false,
     record_danmaku_superchat: true,
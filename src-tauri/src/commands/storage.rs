@@ -0,0 +1,322 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::api::ApiResponse;
+use crate::commands::settings::load_download_settings_from_db;
+use crate::config::default_download_dir;
+use crate::db::Db;
+use crate::utils::append_log;
+use crate::AppState;
+
+/// 只把录制/下载/投稿流程会产生的文件列为孤立候选，避免误报下载目录里的其他文件。
+const ORPHAN_FILE_EXTENSIONS: [&str; 14] = [
+  "mp4", "flv", "mkv", "ts", "m4s", "m4a", "aac", "mp3", "json", "jsonl", "xml", "ass", "jpg",
+  "aria2",
+];
+/// 最近仍在写入的文件（下载或录制中）不算孤立。
+const ORPHAN_MIN_AGE_SECS: u64 = 30 * 60;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanFile {
+  pub path: String,
+  pub size: u64,
+  pub modified_at: Option<i64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanScanReport {
+  pub root: String,
+  pub files: Vec<OrphanFile>,
+  pub total_size: u64,
+  pub scanned_files: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanDeleteFailure {
+  pub path: String,
+  pub error: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanDeleteResult {
+  pub deleted: usize,
+  pub freed_bytes: u64,
+  pub failed: Vec<OrphanDeleteFailure>,
+}
+
+/// 数据库中仍被引用的文件，以及需要整体跳过的目录。
+struct StorageReferences {
+  files: HashSet<PathBuf>,
+  stems: HashSet<(PathBuf, String)>,
+  protected_dirs: Vec<PathBuf>,
+}
+
+impl StorageReferences {
+  fn contains(&self, path: &Path) -> bool {
+    if self.files.contains(path) {
+      return true;
+    }
+    if self.protected_dirs.iter().any(|dir| path.starts_with(dir)) {
+      return true;
+    }
+    // 同目录下与被引用文件同名的附属文件（弹幕、元数据、转封装前后的文件）
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name().and_then(|value| value.to_str()))
+    else {
+      return false;
+    };
+    self
+      .stems
+      .iter()
+      .any(|(dir, stem)| dir == parent && name.starts_with(stem.as_str()))
+  }
+}
+
+#[tauri::command]
+pub fn storage_scan_orphans(state: State<'_, AppState>) -> ApiResponse<OrphanScanReport> {
+  let root = match configured_download_root(&state.db) {
+    Ok(root) => root,
+    Err(err) => return ApiResponse::error(err),
+  };
+  let references = match load_storage_references(&state.db, &root) {
+    Ok(references) => references,
+    Err(err) => return ApiResponse::error(format!("读取文件记录失败: {}", err)),
+  };
+  let mut scanned_files = 0_usize;
+  let mut files = Vec::new();
+  for path in collect_candidate_files(&root) {
+    scanned_files += 1;
+    if references.contains(&path) {
+      continue;
+    }
+    let Ok(metadata) = fs::metadata(&path) else {
+      continue;
+    };
+    if is_recently_modified(&metadata) {
+      continue;
+    }
+    let modified_at = metadata
+      .modified()
+      .ok()
+      .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+      .map(|duration| duration.as_secs() as i64);
+    files.push(OrphanFile {
+      path: path.to_string_lossy().to_string(),
+      size: metadata.len(),
+      modified_at,
+    });
+  }
+  files.sort_by_key(|file| std::cmp::Reverse(file.size));
+  let total_size = files.iter().map(|file| file.size).sum();
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "storage_scan_orphans root={} scanned={} orphans={} bytes={}",
+      root.to_string_lossy(),
+      scanned_files,
+      files.len(),
+      total_size
+    ),
+  );
+  ApiResponse::success(OrphanScanReport {
+    root: root.to_string_lossy().to_string(),
+    files,
+    total_size,
+    scanned_files,
+  })
+}
+
+/// 删除前重新校验：路径必须位于下载目录内，且仍然没有任何记录引用。
+#[tauri::command]
+pub fn storage_delete_orphans(
+  state: State<'_, AppState>,
+  paths: Vec<String>,
+) -> ApiResponse<OrphanDeleteResult> {
+  if paths.is_empty() {
+    return ApiResponse::error("请至少选择一个文件");
+  }
+  let root = match configured_download_root(&state.db) {
+    Ok(root) => root,
+    Err(err) => return ApiResponse::error(err),
+  };
+  let references = match load_storage_references(&state.db, &root) {
+    Ok(references) => references,
+    Err(err) => return ApiResponse::error(format!("读取文件记录失败: {}", err)),
+  };
+  let canonical_root = root.canonicalize().unwrap_or_else(|_| root.clone());
+  let mut result = OrphanDeleteResult {
+    deleted: 0,
+    freed_bytes: 0,
+    failed: Vec::new(),
+  };
+  for raw_path in paths {
+    let path = PathBuf::from(raw_path.trim());
+    // 规范化后再比较，避免 `..` 或符号链接指向下载目录之外
+    let inside_root = path
+      .canonicalize()
+      .map(|canonical| canonical.starts_with(&canonical_root))
+      .unwrap_or(false);
+    let check = if !inside_root {
+      Err("文件不在下载目录内".to_string())
+    } else if !is_candidate_file(&path) {
+      Err("不支持删除该类型的文件".to_string())
+    } else if references.contains(&path) {
+      Err("文件仍被任务引用".to_string())
+    } else {
+      fs::metadata(&path).map_err(|err| format!("读取文件失败: {}", err))
+    };
+    let outcome = check.and_then(|metadata| {
+      fs::remove_file(&path)
+        .map(|_| metadata.len())
+        .map_err(|err| format!("删除失败: {}", err))
+    });
+    match outcome {
+      Ok(size) => {
+        result.deleted += 1;
+        result.freed_bytes += size;
+      }
+      Err(error) => result.failed.push(OrphanDeleteFailure {
+        path: path.to_string_lossy().to_string(),
+        error,
+      }),
+    }
+  }
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "storage_delete_orphans deleted={} freed_bytes={} failed={}",
+      result.deleted,
+      result.freed_bytes,
+      result.failed.len()
+    ),
+  );
+  ApiResponse::success(result)
+}
+
+/// 孤立文件扫描只在用户明确配置的下载目录内进行；未配置时默认目录是系统下载文件夹，
+/// 其中大量文件与本应用无关，直接拒绝扫描。
+fn configured_download_root(db: &Db) -> Result<PathBuf, String> {
+  let configured = load_download_settings_from_db(db)
+    .map(|settings| settings.download_path)
+    .unwrap_or_default();
+  let configured = configured.trim();
+  if configured.is_empty() || Path::new(configured) == default_download_dir() {
+    return Err("未设置专用下载目录，请先在设置中指定下载目录后再扫描".to_string());
+  }
+  Ok(PathBuf::from(configured))
+}
+
+pub(crate) fn resolve_download_root(db: &Db) -> PathBuf {
+  let configured = load_download_settings_from_db(db)
+    .map(|settings| settings.download_path)
+    .unwrap_or_default();
+  if configured.trim().is_empty() {
+    default_download_dir()
+  } else {
+    PathBuf::from(configured.trim())
+  }
+}
+
+fn load_storage_references(db: &Db, root: &Path) -> Result<StorageReferences, String> {
+  let (paths, task_ids) = db
+    .with_conn(|conn| {
+      let mut paths = Vec::new();
+      for sql in [
+        "SELECT local_path FROM video_download WHERE local_path IS NOT NULL",
+        "SELECT source_file_path FROM task_source_video WHERE source_file_path IS NOT NULL",
+        "SELECT video_path FROM merged_video WHERE video_path IS NOT NULL",
+        "SELECT segment_file_path FROM task_output_segment WHERE segment_file_path IS NOT NULL",
+        "SELECT file_path FROM live_record_task WHERE file_path IS NOT NULL",
+      ] {
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        for row in rows {
+          paths.push(row?);
+        }
+      }
+      let mut stmt = conn.prepare("SELECT task_id FROM submission_task")?;
+      let task_ids = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok((paths, task_ids))
+    })
+    .map_err(|err| err.to_string())?;
+
+  let mut references = StorageReferences {
+    files: HashSet::new(),
+    stems: HashSet::new(),
+    protected_dirs: vec![root.join("temp")],
+  };
+  for value in paths {
+    let value = value.trim();
+    if value.is_empty() {
+      continue;
+    }
+    let path = PathBuf::from(value);
+    if let (Some(parent), Some(stem)) = (
+      path.parent(),
+      path.file_stem().and_then(|value| value.to_str()),
+    ) {
+      references
+        .stems
+        .insert((parent.to_path_buf(), stem.to_string()));
+    }
+    references.files.insert(path);
+  }
+  // 投稿任务目录内的剪辑/合并中间文件由任务自己清理
+  references
+    .protected_dirs
+    .extend(task_ids.into_iter().map(|task_id| root.join(task_id)));
+  Ok(references)
+}
+
+fn collect_candidate_files(root: &Path) -> Vec<PathBuf> {
+  let mut files = Vec::new();
+  let mut stack = vec![root.to_path_buf()];
+  while let Some(dir) = stack.pop() {
+    let Ok(entries) = fs::read_dir(&dir) else {
+      continue;
+    };
+    for entry in entries.flatten() {
+      let Ok(file_type) = entry.file_type() else {
+        continue;
+      };
+      let path = entry.path();
+      if file_type.is_dir() {
+        stack.push(path);
+      } else if file_type.is_file() && is_candidate_file(&path) {
+        files.push(path);
+      }
+    }
+  }
+  files
+}
+
+fn is_candidate_file(path: &Path) -> bool {
+  path
+    .extension()
+    .and_then(|value| value.to_str())
+    .map(|ext| {
+      ORPHAN_FILE_EXTENSIONS
+        .iter()
+        .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+    })
+    .unwrap_or(false)
+}
+
+fn is_recently_modified(metadata: &fs::Metadata) -> bool {
+  metadata
+    .modified()
+    .ok()
+    .and_then(|time| SystemTime::now().duration_since(time).ok())
+    .map(|age| age < Duration::from_secs(ORPHAN_MIN_AGE_SECS))
+    .unwrap_or(true)
+}
@@ -1,10 +1,13 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
-use std::io::{ErrorKind, SeekFrom};
+use std::io::{ErrorKind, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use chrono::Utc;
 use futures_util::stream::{FuturesUnordered, StreamExt};
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, ACCEPT_LANGUAGE, USER_AGENT};
@@ -18,21 +21,28 @@ use tokio::time::sleep;
 use url::form_urlencoded;
 
 use crate::api::ApiResponse;
+use crate::app_events::{
+  self, UploadProgressEvent, WorkflowStatusEvent, UPLOAD_PROGRESS_EVENT, WORKFLOW_STATUS_EVENT,
+};
 use crate::baidu_sync;
 use crate::bilibili::client::BilibiliClient;
 use crate::commands::settings::{
-  load_download_settings_from_db, DEFAULT_SUBMISSION_REMOTE_REFRESH_MINUTES,
-  DEFAULT_UPLOAD_CONCURRENCY,
+  load_download_settings_from_db, DEFAULT_CHUNK_CONCURRENCY, DEFAULT_CLIP_CONCURRENCY,
+  DEFAULT_MAX_CONCURRENT_SUBMISSION_TASKS, DEFAULT_MIN_LAST_SEGMENT_SECONDS, DEFAULT_SUBMISSION_AUTO_RETRY_MAX_ATTEMPTS,
+  DEFAULT_SUBMISSION_REMOTE_REFRESH_MINUTES, DEFAULT_UPLOAD_CONCURRENCY, MAX_CONCURRENT_SUBMISSION_TASKS,
+  upsert_setting,
 };
-use crate::config::default_download_dir;
+use crate::commands::storage::resolve_download_root;
 use crate::db::Db;
+use crate::desktop_notify::{self, NotifyCategory};
 use crate::login_refresh;
 use crate::login_store::{AuthInfo, LoginStore};
 use crate::processing::{
-  clip_sources, decide_clip_copy, merge_files, parse_time_to_seconds, probe_duration_seconds,
-  segment_file, ClipSource,
+  clip_sources, container_stream_warning, decide_clip_copy, merge_files, parse_time_to_seconds,
+  probe_duration_seconds, extract_frame, resolve_normalize_target, segment_file, sha256_file,
+  validate_encoder_args, ClipNormalizeConfig, ClipSource, OutputContainer,
 };
-use crate::utils::{append_log, now_rfc3339, sanitize_filename};
+use crate::utils::{append_log, jittered_duration, now_rfc3339, sanitize_filename};
 use crate::AppState;
 
 #[derive(Clone)]
@@ -40,6 +50,7 @@ struct SubmissionContext {
   db: Arc<Db>,
   app_log_path: Arc<PathBuf>,
   edit_upload_state: Arc<Mutex<EditUploadState>>,
+  rate_limit_gate: Arc<UploadRateLimitGate>,
 }
 
 impl SubmissionContext {
@@ -48,6 +59,7 @@ impl SubmissionContext {
       db: state.db.clone(),
       app_log_path: state.app_log_path.clone(),
       edit_upload_state: state.edit_upload_state.clone(),
+      rate_limit_gate: upload_rate_limit_gate(),
     }
   }
 }
@@ -59,6 +71,7 @@ struct UploadContext {
   login_store: Arc<LoginStore>,
   app_log_path: Arc<PathBuf>,
   edit_upload_state: Arc<Mutex<EditUploadState>>,
+  rate_limit_gate: Arc<UploadRateLimitGate>,
 }
 
 impl UploadContext {
@@ -69,6 +82,7 @@ impl UploadContext {
       login_store: state.login_store.clone(),
       app_log_path: state.app_log_path.clone(),
       edit_upload_state: state.edit_upload_state.clone(),
+      rate_limit_gate: upload_rate_limit_gate(),
     }
   }
 }
@@ -133,6 +147,7 @@ pub struct SubmissionTaskInput {
   pub baidu_sync_enabled: Option<bool>,
   pub baidu_sync_path: Option<String>,
   pub baidu_sync_filename: Option<String>,
+  pub scheduled_publish_at: Option<i64>,
 }
 
 #[derive(Deserialize)]
@@ -147,6 +162,7 @@ pub struct SourceVideoInput {
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmissionCreateRequest {
+  pub client_request_id: Option<String>,
   pub task: SubmissionTaskInput,
   pub source_videos: Vec<SourceVideoInput>,
   pub workflow_config: Option<Value>,
@@ -240,6 +256,63 @@ pub struct SubmissionEditUploadClearRequest {
   pub task_id: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionQuota {
+  pub logged_in: bool,
+  pub level: Option<i64>,
+  pub banned: bool,
+  pub remaining_daily: Option<i64>,
+  pub cooldown_message: Option<String>,
+}
+
+impl SubmissionQuota {
+  fn logged_out() -> Self {
+    Self {
+      logged_in: false,
+      level: None,
+      banned: false,
+      remaining_daily: None,
+      cooldown_message: None,
+    }
+  }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceTimeAdjustment {
+  pub source_id: String,
+  pub source_file_path: String,
+  pub sort_order: i64,
+  pub duration_seconds: Option<f64>,
+  pub old_start_time: Option<String>,
+  pub old_end_time: Option<String>,
+  pub new_start_time: Option<String>,
+  pub new_end_time: Option<String>,
+  pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightSourceEstimate {
+  pub source_file_path: String,
+  pub sort_order: i64,
+  pub duration_seconds: Option<f64>,
+  pub start_seconds: f64,
+  pub end_seconds: Option<f64>,
+  pub estimated_seconds: Option<f64>,
+  pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionPreflightResult {
+  pub use_copy: bool,
+  pub reason: Option<String>,
+  pub sources: Vec<PreflightSourceEstimate>,
+  pub total_estimated_seconds: f64,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskCreationResult {
@@ -257,6 +330,107 @@ pub struct WorkflowStatusRecord {
   pub progress: f64,
 }
 
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowLogEntry {
+  pub log_id: i64,
+  pub instance_id: String,
+  pub log_level: String,
+  pub message: String,
+  pub status: Option<String>,
+  pub current_step: Option<String>,
+  pub previous_status: Option<String>,
+  pub previous_step: Option<String>,
+  pub progress: Option<f64>,
+  pub created_at: String,
+}
+
+/// B 站稿件 `state` 字段的取值，未列出的状态码归为 Unknown。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemoteAuditState {
+  Open,
+  Pending,
+  Rejected,
+  PoliceLocked,
+  Locked,
+  RepairPending,
+  Deferred,
+  SourcePending,
+  Distributing,
+  TranscodeFailed,
+  Draft,
+  Reviewing,
+  Scheduled,
+  Deleted,
+  Unknown(i64),
+}
+
+impl RemoteAuditState {
+  pub fn from_code(code: i64) -> Self {
+    match code {
+      0 => Self::Open,
+      -1 => Self::Pending,
+      -2 => Self::Rejected,
+      -3 => Self::PoliceLocked,
+      -4 => Self::Locked,
+      -6 => Self::RepairPending,
+      -7 => Self::Deferred,
+      -11 => Self::SourcePending,
+      -15 => Self::Distributing,
+      -16 => Self::TranscodeFailed,
+      -20 => Self::Draft,
+      -30 => Self::Reviewing,
+      -40 => Self::Scheduled,
+      -100 => Self::Deleted,
+      other => Self::Unknown(other),
+    }
+  }
+
+  pub fn to_code(self) -> i64 {
+    match self {
+      Self::Open => 0,
+      Self::Pending => -1,
+      Self::Rejected => -2,
+      Self::PoliceLocked => -3,
+      Self::Locked => -4,
+      Self::RepairPending => -6,
+      Self::Deferred => -7,
+      Self::SourcePending => -11,
+      Self::Distributing => -15,
+      Self::TranscodeFailed => -16,
+      Self::Draft => -20,
+      Self::Reviewing => -30,
+      Self::Scheduled => -40,
+      Self::Deleted => -100,
+      Self::Unknown(code) => code,
+    }
+  }
+
+  pub fn label(self) -> &'static str {
+    match self {
+      Self::Open => "已通过",
+      Self::Pending => "待审核",
+      Self::Rejected => "未通过",
+      Self::PoliceLocked => "网警锁定",
+      Self::Locked => "已锁定",
+      Self::RepairPending => "修复待审",
+      Self::Deferred => "暂缓审核",
+      Self::SourcePending => "视频源待修",
+      Self::Distributing => "分发中",
+      Self::TranscodeFailed => "转码失败",
+      Self::Draft => "未提交",
+      Self::Reviewing => "审核中",
+      Self::Scheduled => "定时发布",
+      Self::Deleted => "已删除",
+      Self::Unknown(_) => "未知状态",
+    }
+  }
+
+  pub fn is_rejected(self) -> bool {
+    matches!(self, Self::Rejected | Self::Locked)
+  }
+}
+
 #[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmissionTaskRecord {
@@ -272,6 +446,7 @@ pub struct SubmissionTaskRecord {
   pub bvid: Option<String>,
   pub aid: Option<i64>,
   pub remote_state: Option<i64>,
+  pub remote_state_label: Option<String>,
   pub reject_reason: Option<String>,
   pub created_at: String,
   pub updated_at: String,
@@ -281,6 +456,71 @@ pub struct SubmissionTaskRecord {
   pub baidu_sync_filename: Option<String>,
   pub has_integrated_downloads: bool,
   pub workflow_status: Option<WorkflowStatusRecord>,
+  pub scheduled_publish_at: Option<i64>,
+  pub upload_failure_count: i64,
+  pub last_upload_error: Option<String>,
+  /// 用户取消上传后任务留在 WAITING_UPLOAD，但队列不再自动选取，重新投稿时清除。
+  pub upload_paused: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionCleanTempResult {
+  pub freed_bytes: u64,
+  pub removed_files: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionDiskUsageEntry {
+  pub category: String,
+  pub path: String,
+  pub bytes: u64,
+  pub file_count: usize,
+  pub missing: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionDiskUsage {
+  pub task_id: String,
+  pub base_dir: String,
+  pub total_bytes: u64,
+  pub entries: Vec<SubmissionDiskUsageEntry>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentFileIssue {
+  pub segment_id: String,
+  pub part_name: String,
+  pub part_order: i64,
+  pub file_path: String,
+  pub reason: String,
+  pub expected_size: Option<u64>,
+  pub actual_size: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionUploadPrecheck {
+  pub ready: bool,
+  pub segment_count: usize,
+  pub issues: Vec<SegmentFileIssue>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStatsRecord {
+  pub task_id: String,
+  pub clip_duration_ms: i64,
+  pub merge_duration_ms: i64,
+  pub segment_duration_ms: i64,
+  pub segment_count: i64,
+  pub upload_bytes: i64,
+  pub upload_duration_ms: i64,
+  pub upload_speed_bps: i64,
+  pub updated_at: String,
 }
 
 #[derive(Serialize)]
@@ -292,6 +532,34 @@ pub struct PaginatedSubmissionTasks {
   pub page_size: i64,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionBulkItemResult {
+  pub task_id: String,
+  pub success: bool,
+  pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionBulkResult {
+  pub items: Vec<SubmissionBulkItemResult>,
+  pub succeeded: usize,
+  pub failed: usize,
+}
+
+impl SubmissionBulkResult {
+  fn from_items(items: Vec<SubmissionBulkItemResult>) -> Self {
+    let succeeded = items.iter().filter(|item| item.success).count();
+    let failed = items.len() - succeeded;
+    Self {
+      items,
+      succeeded,
+      failed,
+    }
+  }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskSourceVideoRecord {
@@ -324,6 +592,8 @@ pub struct TaskOutputSegmentRecord {
   pub upload_uri: Option<String>,
   pub upload_chunk_size: i64,
   pub upload_last_part_index: i64,
+  pub upload_speed_bps: i64,
+  pub upload_eta_seconds: Option<i64>,
 }
 
 #[derive(Default)]
@@ -352,6 +622,7 @@ pub struct MergedVideoRecord {
   pub upload_uri: Option<String>,
   pub upload_chunk_size: i64,
   pub upload_last_part_index: i64,
+  pub content_hash: Option<String>,
   pub create_time: String,
   pub update_time: String,
 }
@@ -366,6 +637,28 @@ pub struct SubmissionTaskDetail {
   pub workflow_config: Option<Value>,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionPartitionRule {
+  #[serde(default)]
+  pub partition_ids: Vec<i64>,
+  #[serde(default)]
+  pub video_types: Vec<String>,
+  #[serde(default)]
+  pub min_tags: usize,
+  #[serde(default)]
+  pub require_description: bool,
+  pub message: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionVideoType {
+  pub value: String,
+  pub name: String,
+  pub copyright: i64,
+}
+
 fn with_edit_upload_state<T>(
   context: &SubmissionContext,
   action: impl FnOnce(&mut EditUploadState) -> T,
@@ -382,18 +675,30 @@ fn upsert_edit_upload_segment(
   segment: TaskOutputSegmentRecord,
 ) -> Result<TaskOutputSegmentRecord, String> {
   with_edit_upload_state(context, |state| {
+    save_edit_upload_segment_row(context, &segment)?;
     state
       .segments
       .insert(segment.segment_id.clone(), segment.clone());
-    segment
-  })
+    Ok(segment)
+  })?
 }
 
 fn load_edit_upload_segment(
   context: &SubmissionContext,
   segment_id: &str,
 ) -> Result<Option<TaskOutputSegmentRecord>, String> {
-  with_edit_upload_state(context, |state| state.segments.get(segment_id).cloned())
+  with_edit_upload_state(context, |state| {
+    if let Some(segment) = state.segments.get(segment_id) {
+      return Ok(Some(segment.clone()));
+    }
+    let segment = load_edit_upload_segment_row(context, segment_id)?;
+    if let Some(segment) = &segment {
+      state
+        .segments
+        .insert(segment.segment_id.clone(), segment.clone());
+    }
+    Ok(segment)
+  })?
 }
 
 fn update_edit_upload_segment(
@@ -402,14 +707,20 @@ fn update_edit_upload_segment(
   updater: impl FnOnce(&mut TaskOutputSegmentRecord),
 ) -> Result<(), String> {
   with_edit_upload_state(context, |state| {
-    if let Some(segment) = state.segments.get_mut(segment_id) {
-      updater(segment);
-      return true;
+    let mut segment = match state.segments.get(segment_id) {
+      Some(segment) => segment.clone(),
+      None => load_edit_upload_segment_row(context, segment_id)?
+        .ok_or_else(|| "未找到编辑分P".to_string())?,
+    };
+    updater(&mut segment);
+    if segment.upload_status != "UPLOADING" {
+      segment.upload_speed_bps = 0;
+      segment.upload_eta_seconds = None;
     }
-    false
+    save_edit_upload_segment_row(context, &segment)?;
+    state.segments.insert(segment.segment_id.clone(), segment);
+    Ok(())
   })?
-  .then_some(())
-  .ok_or_else(|| "未找到编辑分P".to_string())
 }
 
 fn list_edit_upload_segments_by_task(
@@ -419,17 +730,20 @@ fn list_edit_upload_segments_by_task(
 ) -> Result<Vec<TaskOutputSegmentRecord>, String> {
   with_edit_upload_state(context, |state| {
     let filter_ids = segment_ids.map(|ids| ids.iter().cloned().collect::<HashSet<_>>());
-    state
-      .segments
-      .values()
-      .filter(|segment| segment.task_id == task_id)
+    let segments = list_edit_upload_segment_rows(context, task_id)?
+      .into_iter()
       .filter(|segment| match &filter_ids {
         Some(ids) => ids.contains(&segment.segment_id),
         None => true,
       })
-      .cloned()
-      .collect::<Vec<_>>()
-  })
+      .collect::<Vec<_>>();
+    for segment in &segments {
+      state
+        .segments
+        .insert(segment.segment_id.clone(), segment.clone());
+    }
+    Ok(segments)
+  })?
 }
 
 fn clear_edit_upload_segments_by_task(
@@ -438,22 +752,212 @@ fn clear_edit_upload_segments_by_task(
 ) -> Result<(), String> {
   with_edit_upload_state(context, |state| {
     state.segments.retain(|_, segment| segment.task_id != task_id);
+    context
+      .db
+      .with_conn(|conn| {
+        conn.execute("DELETE FROM edit_upload_segment WHERE task_id = ?1", [task_id])?;
+        Ok(())
+      })
+      .map_err(|err| err.to_string())
+  })?
+}
+
+fn map_edit_upload_segment_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<TaskOutputSegmentRecord> {
+  Ok(TaskOutputSegmentRecord {
+    segment_id: row.get(0)?,
+    task_id: row.get(1)?,
+    part_name: row.get(2)?,
+    segment_file_path: row.get(3)?,
+    part_order: row.get(4)?,
+    upload_status: row.get(5)?,
+    cid: row.get(6)?,
+    file_name: row.get(7)?,
+    upload_progress: row.get(8)?,
+    upload_uploaded_bytes: row.get(9)?,
+    upload_total_bytes: row.get(10)?,
+    upload_session_id: row.get(11)?,
+    upload_biz_id: row.get(12)?,
+    upload_endpoint: row.get(13)?,
+    upload_auth: row.get(14)?,
+    upload_uri: row.get(15)?,
+    upload_chunk_size: row.get(16)?,
+    upload_last_part_index: row.get(17)?,
+    upload_speed_bps: row.get(18)?,
+    upload_eta_seconds: row.get(19)?,
   })
 }
 
+fn save_edit_upload_segment_row(
+  context: &SubmissionContext,
+  segment: &TaskOutputSegmentRecord,
+) -> Result<(), String> {
+  let now = now_rfc3339();
+  context
+    .db
+    .with_conn(|conn| {
+      conn.execute(
+        "INSERT INTO edit_upload_segment (segment_id, task_id, part_name, segment_file_path, part_order, upload_status, \
+         cid, file_name, upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, \
+         upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index, upload_speed_bps, \
+         upload_eta_seconds, update_time) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21) \
+         ON CONFLICT(segment_id) DO UPDATE SET task_id = excluded.task_id, part_name = excluded.part_name, \
+         segment_file_path = excluded.segment_file_path, part_order = excluded.part_order, \
+         upload_status = excluded.upload_status, cid = excluded.cid, file_name = excluded.file_name, \
+         upload_progress = excluded.upload_progress, upload_uploaded_bytes = excluded.upload_uploaded_bytes, \
+         upload_total_bytes = excluded.upload_total_bytes, upload_session_id = excluded.upload_session_id, \
+         upload_biz_id = excluded.upload_biz_id, upload_endpoint = excluded.upload_endpoint, \
+         upload_auth = excluded.upload_auth, upload_uri = excluded.upload_uri, \
+         upload_chunk_size = excluded.upload_chunk_size, upload_last_part_index = excluded.upload_last_part_index, \
+         upload_speed_bps = excluded.upload_speed_bps, upload_eta_seconds = excluded.upload_eta_seconds, \
+         update_time = excluded.update_time",
+        rusqlite::params![
+          segment.segment_id,
+          segment.task_id,
+          segment.part_name,
+          segment.segment_file_path,
+          segment.part_order,
+          segment.upload_status,
+          segment.cid,
+          segment.file_name,
+          segment.upload_progress,
+          segment.upload_uploaded_bytes,
+          segment.upload_total_bytes,
+          segment.upload_session_id,
+          segment.upload_biz_id,
+          segment.upload_endpoint,
+          segment.upload_auth,
+          segment.upload_uri,
+          segment.upload_chunk_size,
+          segment.upload_last_part_index,
+          segment.upload_speed_bps,
+          segment.upload_eta_seconds,
+          now,
+        ],
+      )?;
+      Ok(())
+    })
+    .map_err(|err| format!("保存编辑分P失败: {}", err))
+}
+
+fn load_edit_upload_segment_row(
+  context: &SubmissionContext,
+  segment_id: &str,
+) -> Result<Option<TaskOutputSegmentRecord>, String> {
+  context
+    .db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT segment_id, task_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, \
+                  upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, \
+                  upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index, \
+                upload_speed_bps, upload_eta_seconds \
+           FROM edit_upload_segment WHERE segment_id = ?1",
+          [segment_id],
+          map_edit_upload_segment_row,
+        )
+        .optional()
+    })
+    .map_err(|err| err.to_string())
+}
+
+fn list_edit_upload_segment_rows(
+  context: &SubmissionContext,
+  task_id: &str,
+) -> Result<Vec<TaskOutputSegmentRecord>, String> {
+  context
+    .db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT segment_id, task_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, \
+                upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, \
+                upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index, \
+                upload_speed_bps, upload_eta_seconds \
+         FROM edit_upload_segment WHERE task_id = ?1 ORDER BY rowid ASC",
+      )?;
+      let rows = stmt
+        .query_map([task_id], map_edit_upload_segment_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok(rows)
+    })
+    .map_err(|err| err.to_string())
+}
+
+fn load_uploading_edit_upload_segment_ids(context: &SubmissionContext) -> Result<Vec<String>, String> {
+  context
+    .db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT segment_id FROM edit_upload_segment WHERE upload_status = 'UPLOADING' ORDER BY rowid ASC",
+      )?;
+      let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok(rows)
+    })
+    .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 pub async fn submission_create(
   state: State<'_, AppState>,
   request: SubmissionCreateRequest,
 ) -> Result<ApiResponse<TaskCreationResult>, String> {
   let context = SubmissionContext::new(&state);
+  if let Err(err) =
+    crate::commands::video::validate_partition_id(&state, request.task.partition_id).await
+  {
+    return Ok(ApiResponse::error(err));
+  }
+  if let Err(err) = validate_partition_rules(
+    &context.db,
+    request.task.partition_id,
+    &request.task.video_type,
+    request.task.tags.as_deref(),
+    request.task.description.as_deref(),
+  ) {
+    append_log(
+      &state.app_log_path,
+      &format!(
+        "submission_create_rule_reject partition_id={} video_type={} err={}",
+        request.task.partition_id, request.task.video_type, err
+      ),
+    );
+    return Ok(ApiResponse::error(err));
+  }
+  if let Some(scheduled_publish_at) = request.task.scheduled_publish_at {
+    if let Err(err) = validate_scheduled_publish_at(scheduled_publish_at) {
+      return Ok(ApiResponse::error(err));
+    }
+  }
+  let client_request_id = normalize_optional_text(request.client_request_id.clone());
+  if let Some(client_request_id) = client_request_id.as_deref() {
+    match load_task_id_by_client_request_id(&context, client_request_id) {
+      Ok(Some(existing_task_id)) => {
+        append_log(
+          &state.app_log_path,
+          &format!(
+            "submission_create_duplicate client_request_id={} task_id={}",
+            client_request_id, existing_task_id
+          ),
+        );
+        return Ok(ApiResponse::success(load_existing_creation_result(
+          &context,
+          existing_task_id,
+        )));
+      }
+      Ok(None) => {}
+      Err(err) => return Ok(ApiResponse::error(format!("Failed to create task: {}", err))),
+    }
+  }
   let task_id = uuid::Uuid::new_v4().to_string();
   let now = now_rfc3339();
 
   let result = context.db.with_conn(|conn| {
     conn.execute(
-      "INSERT INTO submission_task (task_id, status, title, description, cover_url, partition_id, tags, video_type, collection_id, bvid, aid, created_at, updated_at, segment_prefix, baidu_sync_enabled, baidu_sync_path, baidu_sync_filename) \
-       VALUES (?1, 'PENDING', ?2, ?3, ?4, ?5, ?6, ?7, ?8, NULL, NULL, ?9, ?10, ?11, ?12, ?13, ?14)",
+      "INSERT INTO submission_task (task_id, status, title, description, cover_url, partition_id, tags, video_type, collection_id, bvid, aid, created_at, updated_at, segment_prefix, baidu_sync_enabled, baidu_sync_path, baidu_sync_filename, client_request_id, scheduled_publish_at) \
+       VALUES (?1, 'PENDING', ?2, ?3, ?4, ?5, ?6, ?7, ?8, NULL, NULL, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
       (
         &task_id,
         &request.task.title,
@@ -473,6 +977,8 @@ pub async fn submission_create(
         },
         request.task.baidu_sync_path.as_deref(),
         request.task.baidu_sync_filename.as_deref(),
+        client_request_id.as_deref(),
+        request.task.scheduled_publish_at,
       ),
     )?;
 
@@ -496,6 +1002,15 @@ pub async fn submission_create(
   });
 
   if let Err(err) = result {
+    // 并发的重复请求可能在查询之后抢先插入，唯一索引冲突时返回已有任务。
+    if let Some(client_request_id) = client_request_id.as_deref() {
+      if let Ok(Some(existing_task_id)) = load_task_id_by_client_request_id(&context, client_request_id) {
+        return Ok(ApiResponse::success(load_existing_creation_result(
+          &context,
+          existing_task_id,
+        )));
+      }
+    }
     return Ok(ApiResponse::error(format!("Failed to create task: {}", err)));
   }
 
@@ -529,6 +1044,69 @@ pub async fn submission_create(
   Ok(ApiResponse::success(result))
 }
 
+#[tauri::command]
+pub async fn submission_duplicate(
+  state: State<'_, AppState>,
+  task_id: String,
+  new_title: String,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(&state);
+  let task_id = task_id.trim().to_string();
+  if task_id.is_empty() {
+    return Ok(ApiResponse::error("任务ID不能为空"));
+  }
+  let new_title = new_title.trim().to_string();
+  if new_title.is_empty() {
+    return Ok(ApiResponse::error("标题不能为空"));
+  }
+  let workflow_config = match load_latest_workflow_config(&context, &task_id) {
+    Ok(config) => config.map(strip_duplicate_workflow_sources),
+    Err(err) => return Ok(ApiResponse::error(format!("读取工作流配置失败: {}", err))),
+  };
+  let new_task_id = uuid::Uuid::new_v4().to_string();
+  let now = now_rfc3339();
+  let result = context.db.with_conn(|conn| {
+    conn.execute(
+      "INSERT INTO submission_task (task_id, status, title, description, cover_url, partition_id, tags, video_type, collection_id, bvid, aid, created_at, updated_at, segment_prefix, baidu_sync_enabled, baidu_sync_path, baidu_sync_filename) \
+       SELECT ?1, 'PENDING', ?2, description, cover_url, partition_id, tags, video_type, collection_id, NULL, NULL, ?3, ?4, segment_prefix, baidu_sync_enabled, baidu_sync_path, baidu_sync_filename \
+       FROM submission_task WHERE task_id = ?5",
+      (&new_task_id, &new_title, &now, &now, &task_id),
+    )
+  });
+  match result {
+    Ok(0) => return Ok(ApiResponse::error("任务不存在")),
+    Ok(_) => {}
+    Err(err) => return Ok(ApiResponse::error(format!("复制任务失败: {}", err))),
+  }
+  if let Some(config) = workflow_config {
+    if let Err(err) = create_workflow_instance(&context, &new_task_id, &config) {
+      let _ = context.db.with_conn(|conn| {
+        conn.execute("DELETE FROM submission_task WHERE task_id = ?1", [&new_task_id])
+      });
+      return Ok(ApiResponse::error(format!("复制工作流配置失败: {}", err)));
+    }
+  }
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "submission_duplicate source_task_id={} task_id={}",
+      task_id, new_task_id
+    ),
+  );
+  Ok(ApiResponse::success(new_task_id))
+}
+
+/// 复制任务只沿用配置，不沿用源视频，去掉更新流程附带的源列表。
+fn strip_duplicate_workflow_sources(config: Value) -> Value {
+  match config {
+    Value::Object(mut map) => {
+      map.remove("updateSources");
+      Value::Object(map)
+    }
+    other => other,
+  }
+}
+
 #[tauri::command]
 pub async fn submission_update(
   state: State<'_, AppState>,
@@ -864,7 +1442,10 @@ fn reset_submission_for_repost(
     return Err(format!("清理任务数据失败: {}", err));
   }
   let base_dir = resolve_submission_base_dir(context, task_id);
-  if let Err(err) = cleanup_submission_derived_files(app_log_path, &base_dir) {
+  let keep_intermediates = load_download_settings_from_db(context.db.as_ref())
+    .map(|settings| settings.keep_intermediates)
+    .unwrap_or(false);
+  if let Err(err) = cleanup_submission_derived_files(app_log_path, &base_dir, keep_intermediates) {
     append_log(
       app_log_path,
       &format!(
@@ -979,7 +1560,20 @@ pub async fn submission_resegment(
   }
   let base_dir = resolve_submission_base_dir(&context, &task_id);
   let output_dir = base_dir.join("output");
-  if let Err(err) = remove_path_if_exists(state.app_log_path.as_ref(), "output", &output_dir) {
+  let keep_intermediates = load_download_settings_from_db(context.db.as_ref())
+    .map(|settings| settings.keep_intermediates)
+    .unwrap_or(false);
+  let cleanup = if keep_intermediates {
+    retain_intermediate_path(
+      state.app_log_path.as_ref(),
+      "output",
+      &output_dir,
+      &intermediate_debug_dir(&base_dir),
+    )
+  } else {
+    remove_path_if_exists(state.app_log_path.as_ref(), "output", &output_dir)
+  };
+  if let Err(err) = cleanup {
     append_log(
       &state.app_log_path,
       &format!(
@@ -990,10 +1584,14 @@ pub async fn submission_resegment(
   }
   let context_clone = context.clone();
   let task_id_clone = task_id.clone();
+  let segment_prefix = detail.task.segment_prefix.clone();
   let merged_path_clone = merged_path_buf.clone();
   let output_dir_clone = output_dir.clone();
   let app_log_path = state.app_log_path.clone();
   let segment_seconds = request.segment_duration_seconds;
+  let min_last_segment_seconds = load_download_settings_from_db(context.db.as_ref())
+    .map(|settings| settings.min_last_segment_seconds)
+    .unwrap_or(DEFAULT_MIN_LAST_SEGMENT_SECONDS);
   tauri::async_runtime::spawn(async move {
     let _ = update_workflow_status(
       &context_clone,
@@ -1003,7 +1601,13 @@ pub async fn submission_resegment(
       70.0,
     );
     let segment_outputs = match tauri::async_runtime::spawn_blocking(move || {
-      segment_file(&merged_path_clone, &output_dir_clone, segment_seconds)
+      segment_file(
+        &merged_path_clone,
+        &output_dir_clone,
+        segment_seconds,
+        min_last_segment_seconds,
+        OutputContainer::from_path(&merged_path_clone).unwrap_or_default(),
+      )
     })
     .await
     {
@@ -1011,7 +1615,17 @@ pub async fn submission_resegment(
       Err(_) => Err("Failed to segment video".to_string()),
     };
     match segment_outputs {
-      Ok(outputs) => {
+      Ok(result) => {
+        if let Some(absorbed) = result.absorbed_tail_seconds {
+          append_log(
+            app_log_path.as_ref(),
+            &format!(
+              "submission_segment_tail_absorbed task_id={} tail_seconds={:.2} min_seconds={}",
+              task_id_clone, absorbed, min_last_segment_seconds
+            ),
+          );
+        }
+        let outputs = result.paths();
         if outputs.is_empty() {
           let _ = update_submission_status(&context_clone, &task_id_clone, "FAILED");
           let _ = update_workflow_status(
@@ -1030,7 +1644,12 @@ pub async fn submission_resegment(
           );
           return;
         }
-        if let Err(err) = save_output_segments(&context_clone, &task_id_clone, &outputs) {
+        if let Err(err) = save_output_segments(
+          &context_clone,
+          &task_id_clone,
+          &outputs,
+          segment_prefix.as_deref(),
+        ) {
           let _ = update_submission_status(&context_clone, &task_id_clone, "FAILED");
           let _ = update_workflow_status(
             &context_clone,
@@ -1083,6 +1702,8 @@ pub async fn submission_list(
   state: State<'_, AppState>,
   page: Option<i64>,
   page_size: Option<i64>,
+  query: Option<String>,
+  sort: Option<String>,
   refresh_remote: Option<bool>,
 ) -> Result<ApiResponse<PaginatedSubmissionTasks>, String> {
   let context = SubmissionContext::new(&state);
@@ -1097,7 +1718,7 @@ pub async fn submission_list(
   }
   let page = page.unwrap_or(1).max(1);
   let page_size = page_size.unwrap_or(20).max(1);
-  let response = match load_tasks(&context, None, page, page_size) {
+  let response = match load_tasks(&context, None, query, sort, page, page_size) {
     Ok(result) => ApiResponse::success(result),
     Err(err) => ApiResponse::error(format!("Failed to load tasks: {}", err)),
   };
@@ -1110,6 +1731,8 @@ pub async fn submission_list_by_status(
   status: String,
   page: Option<i64>,
   page_size: Option<i64>,
+  query: Option<String>,
+  sort: Option<String>,
   refresh_remote: Option<bool>,
 ) -> Result<ApiResponse<PaginatedSubmissionTasks>, String> {
   let context = SubmissionContext::new(&state);
@@ -1127,23 +1750,178 @@ pub async fn submission_list_by_status(
   }
   let page = page.unwrap_or(1).max(1);
   let page_size = page_size.unwrap_or(20).max(1);
-  let response = match load_tasks(&context, Some(status), page, page_size) {
+  let response = match load_tasks(&context, Some(status), query, sort, page, page_size) {
     Ok(result) => ApiResponse::success(result),
     Err(err) => ApiResponse::error(format!("Failed to load tasks: {}", err)),
   };
   Ok(response)
 }
 
+/// 导出投稿记录为 CSV（UTF-8 BOM，便于 Excel 直接打开），未指定路径时写入下载目录的 exports。
 #[tauri::command]
-pub fn submission_task_dir(state: State<'_, AppState>, task_id: String) -> ApiResponse<String> {
-  let trimmed = task_id.trim();
-  if trimmed.is_empty() {
-    return ApiResponse::error("任务ID不能为空");
-  }
+pub fn submission_export_csv(
+  state: State<'_, AppState>,
+  status_filter: Option<String>,
+  output_path: Option<String>,
+) -> ApiResponse<String> {
   let context = SubmissionContext::new(&state);
-  let dir = resolve_submission_base_dir(&context, trimmed);
-  match fs::metadata(&dir) {
-    Ok(meta) => {
+  let status_filter = status_filter
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty() && !value.eq_ignore_ascii_case("ALL"));
+  let output_path = match output_path
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty())
+  {
+    Some(path) => PathBuf::from(path),
+    None => resolve_download_root(&context.db)
+      .join("exports")
+      .join(format!("submissions-{}.csv", Utc::now().format("%Y%m%d-%H%M%S"))),
+  };
+  if let Some(parent) = output_path.parent() {
+    if let Err(err) = fs::create_dir_all(parent) {
+      return ApiResponse::error(format!("创建导出目录失败: {}", err));
+    }
+  }
+  let file = match fs::File::create(&output_path) {
+    Ok(file) => file,
+    Err(err) => return ApiResponse::error(format!("创建导出文件失败: {}", err)),
+  };
+  let mut writer = std::io::BufWriter::new(file);
+  let result = write_submission_csv(&context, &mut writer, status_filter.as_deref());
+  drop(writer);
+  let count = match result {
+    Ok(count) => count,
+    Err(err) => {
+      // 写入失败时删除不完整的导出文件
+      let _ = fs::remove_file(&output_path);
+      append_log(
+        &state.app_log_path,
+        &format!(
+          "submission_export_csv_fail path={} err={}",
+          output_path.to_string_lossy(),
+          err
+        ),
+      );
+      return ApiResponse::error(err);
+    }
+  };
+  let path = output_path.to_string_lossy().to_string();
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "submission_export_csv status={} rows={} path={}",
+      status_filter.as_deref().unwrap_or("ALL"),
+      count,
+      path
+    ),
+  );
+  ApiResponse::success(path)
+}
+
+fn write_submission_csv<W: Write>(
+  context: &SubmissionContext,
+  writer: &mut W,
+  status_filter: Option<&str>,
+) -> Result<usize, String> {
+  let header = [
+    "task_id",
+    "title",
+    "bvid",
+    "aid",
+    "status",
+    "remote_state",
+    "remote_state_label",
+    "reject_reason",
+    "tags",
+    "description",
+    "created_at",
+    "updated_at",
+  ];
+  writer
+    .write_all("\u{feff}".as_bytes())
+    .and_then(|_| write_csv_record(writer, &header))
+    .map_err(|err| format!("写入导出文件失败: {}", err))?;
+  let result = context.db.with_conn(|conn| {
+    let mut stmt = conn.prepare(
+      "SELECT task_id, title, bvid, aid, status, remote_state, reject_reason, tags, description, created_at, updated_at \
+       FROM submission_task WHERE (?1 IS NULL OR status = ?1) ORDER BY created_at DESC",
+    )?;
+    let mut rows = stmt.query([status_filter])?;
+    let mut count = 0_usize;
+    while let Some(row) = rows.next()? {
+      let remote_state: Option<i64> = row.get(5)?;
+      let record = [
+        row.get::<_, String>(0)?,
+        row.get::<_, String>(1)?,
+        row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+        row
+          .get::<_, Option<i64>>(3)?
+          .map(|aid| aid.to_string())
+          .unwrap_or_default(),
+        row.get::<_, String>(4)?,
+        remote_state.map(|code| code.to_string()).unwrap_or_default(),
+        remote_state
+          .map(|code| RemoteAuditState::from_code(code).label().to_string())
+          .unwrap_or_default(),
+        row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+        row.get::<_, Option<String>>(7)?.unwrap_or_default(),
+        row.get::<_, Option<String>>(8)?.unwrap_or_default(),
+        row.get::<_, String>(9)?,
+        row.get::<_, String>(10)?,
+      ];
+      if let Err(err) = write_csv_record(writer, &record) {
+        return Ok(Err(err));
+      }
+      count += 1;
+    }
+    Ok(Ok(count))
+  });
+  let count = match result {
+    Ok(Ok(count)) => count,
+    Ok(Err(err)) => return Err(format!("写入导出文件失败: {}", err)),
+    Err(err) => return Err(format!("读取投稿记录失败: {}", err)),
+  };
+  writer
+    .flush()
+    .map_err(|err| format!("写入导出文件失败: {}", err))?;
+  Ok(count)
+}
+
+fn write_csv_record<W: Write, S: AsRef<str>>(writer: &mut W, fields: &[S]) -> std::io::Result<()> {
+  let line = fields
+    .iter()
+    .map(|field| escape_csv_field(field.as_ref()))
+    .collect::<Vec<_>>()
+    .join(",");
+  writer.write_all(line.as_bytes())?;
+  writer.write_all(b"\r\n")
+}
+
+/// 以 `=`、`+`、`-`、`@` 开头的字段前加 `'`，避免 Excel 当作公式执行；
+/// 含逗号、引号或换行的字段整体加引号，内部引号按 RFC 4180 双写。
+fn escape_csv_field(value: &str) -> String {
+  let value = if value.starts_with(['=', '+', '-', '@']) {
+    format!("'{}", value)
+  } else {
+    value.to_string()
+  };
+  if value.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", value.replace('"', "\"\""))
+  } else {
+    value
+  }
+}
+
+#[tauri::command]
+pub fn submission_task_dir(state: State<'_, AppState>, task_id: String) -> ApiResponse<String> {
+  let trimmed = task_id.trim();
+  if trimmed.is_empty() {
+    return ApiResponse::error("任务ID不能为空");
+  }
+  let context = SubmissionContext::new(&state);
+  let dir = resolve_submission_base_dir(&context, trimmed);
+  match fs::metadata(&dir) {
+    Ok(meta) => {
       if meta.is_dir() {
         ApiResponse::success(dir.to_string_lossy().to_string())
       } else {
@@ -1218,7 +1996,11 @@ pub fn submission_edit_prepare(
   if merged_path.trim().is_empty() {
     return ApiResponse::error("合并视频路径为空");
   }
-  let part_name = build_part_title(detail.task.segment_prefix.as_deref(), 1);
+  let part_name = build_part_title(
+    detail.task.segment_prefix.as_deref(),
+    1,
+    load_segment_index_padding(context.db.as_ref()),
+  );
   let has_upload = merged.upload_cid.unwrap_or(0) > 0
     && merged
       .upload_file_name
@@ -1258,6 +2040,8 @@ pub fn submission_edit_prepare(
     upload_uri: None,
     upload_chunk_size: 0,
     upload_last_part_index: 0,
+    upload_speed_bps: 0,
+    upload_eta_seconds: None,
   });
   ApiResponse::success(detail)
 }
@@ -1332,6 +2116,8 @@ pub async fn submission_edit_add_segment(
     upload_uri: None,
     upload_chunk_size: 0,
     upload_last_part_index: 0,
+    upload_speed_bps: 0,
+    upload_eta_seconds: None,
   };
   let segment = match upsert_edit_upload_segment(&context, segment) {
     Ok(segment) => segment,
@@ -1376,7 +2162,7 @@ pub async fn submission_edit_add_segment(
         segment_id_clone
       ),
     );
-    let client = Client::new();
+    let client = crate::config::build_http_client();
     let result = upload_edit_segment_with_retry(
       &context_clone,
       &upload_context_clone,
@@ -1480,6 +2266,8 @@ pub async fn submission_edit_reupload_segment(
     upload_uri: None,
     upload_chunk_size: 0,
     upload_last_part_index: 0,
+    upload_speed_bps: 0,
+    upload_eta_seconds: None,
   });
   segment.part_name = part_name;
   segment.segment_file_path = file_path;
@@ -1496,6 +2284,8 @@ pub async fn submission_edit_reupload_segment(
   segment.upload_uri = None;
   segment.upload_chunk_size = 0;
   segment.upload_last_part_index = 0;
+  segment.upload_speed_bps = 0;
+  segment.upload_eta_seconds = None;
   let segment = match upsert_edit_upload_segment(&context, segment) {
     Ok(segment) => segment,
     Err(err) => return Ok(ApiResponse::error(err)),
@@ -1509,7 +2299,7 @@ pub async fn submission_edit_reupload_segment(
   let upload_context_clone = upload_context.clone();
   let segment_id_clone = segment.segment_id.clone();
   tauri::async_runtime::spawn(async move {
-    let client = Client::new();
+    let client = crate::config::build_http_client();
     let result = upload_edit_segment_with_retry(
       &context_clone,
       &upload_context_clone,
@@ -1609,6 +2399,11 @@ pub async fn submission_edit_submit(
   if request.task.partition_id <= 0 {
     return Ok(ApiResponse::error("请选择B站分区"));
   }
+  if let Err(err) =
+    crate::commands::video::validate_partition_id(&state, request.task.partition_id).await
+  {
+    return Ok(ApiResponse::error(err));
+  }
   if request.task.video_type.trim().is_empty() {
     return Ok(ApiResponse::error("请选择视频类型"));
   }
@@ -1617,10 +2412,23 @@ pub async fn submission_edit_submit(
       return Ok(ApiResponse::error("视频描述不能超过 2000 个字符"));
     }
   }
-  let tags = request.task.tags.clone().unwrap_or_default();
-  if tags.trim().is_empty() {
+  let normalized_tags = normalize_tags(request.task.tags.as_deref().unwrap_or(""));
+  if normalized_tags.tags.is_empty() {
     return Ok(ApiResponse::error("请填写至少一个投稿标签"));
   }
+  if let Some(violation) = normalized_tags.violation() {
+    return Ok(ApiResponse::error(violation));
+  }
+  let tags = normalized_tags.joined();
+  if let Err(err) = validate_partition_rules(
+    &context.db,
+    request.task.partition_id,
+    request.task.video_type.trim(),
+    Some(&tags),
+    request.task.description.as_deref(),
+  ) {
+    return Ok(ApiResponse::error(err));
+  }
   if request.segments.is_empty() {
     return Ok(ApiResponse::error("至少需要保留一个分P"));
   }
@@ -1772,6 +2580,194 @@ pub async fn submission_edit_submit(
   Ok(ApiResponse::success("编辑投稿成功".to_string()))
 }
 
+/// 被打回的稿件改标题/标签后重新提交：有 BV 号时走编辑接口复用已上传的分P，否则重新排队投稿。
+#[tauri::command]
+pub async fn submission_resubmit_rejected(
+  state: State<'_, AppState>,
+  task_id: String,
+  new_title: Option<String>,
+  new_tags: Option<String>,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(&state);
+  let task_id = task_id.trim().to_string();
+  if task_id.is_empty() {
+    return Ok(ApiResponse::error("任务ID不能为空"));
+  }
+  let detail = match load_task_detail(&context, &task_id) {
+    Ok(detail) => detail,
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+  if detail.task.status == "UPLOADING" {
+    return Ok(ApiResponse::error("任务正在投稿中，请稍后再试"));
+  }
+  let rejected = detail
+    .task
+    .remote_state
+    .map(|code| RemoteAuditState::from_code(code).is_rejected())
+    .unwrap_or(false);
+  if !rejected {
+    return Ok(ApiResponse::error("稿件未被打回，无需重新提交"));
+  }
+
+  let mut task = detail.task.clone();
+  if let Some(title) = new_title
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty())
+  {
+    if title.chars().count() > 80 {
+      return Ok(ApiResponse::error("投稿标题不能超过 80 个字符"));
+    }
+    task.title = title;
+  }
+  if let Some(raw_tags) = new_tags {
+    let normalized_tags = normalize_tags(&raw_tags);
+    if normalized_tags.tags.is_empty() {
+      return Ok(ApiResponse::error("请填写至少一个投稿标签"));
+    }
+    if let Some(violation) = normalized_tags.violation() {
+      return Ok(ApiResponse::error(violation));
+    }
+    task.tags = Some(normalized_tags.joined());
+  }
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "submission_resubmit_rejected_start task_id={} remote_state={} title_changed={} tags_changed={}",
+      task_id,
+      detail.task.remote_state.unwrap_or(0),
+      task.title != detail.task.title,
+      task.tags != detail.task.tags
+    ),
+  );
+
+  let bvid = task.bvid.clone().unwrap_or_default();
+  if bvid.trim().is_empty() {
+    if let Err(err) = update_submission_task_for_edit(&context, &task_id, &task) {
+      return Ok(ApiResponse::error(err));
+    }
+    return match queue_submission_upload(&context, &task_id) {
+      Ok(()) => Ok(ApiResponse::success("已重新加入投稿队列".to_string())),
+      Err(err) => Ok(ApiResponse::error(err)),
+    };
+  }
+
+  let parts = match build_resubmit_parts(&context, &detail) {
+    Ok(parts) => parts,
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+  let upload_context = UploadContext::new(&state);
+  let mut auth = match load_auth_or_refresh(&upload_context, "submission_resubmit_rejected").await {
+    Ok(auth) => auth,
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+  let csrf = match auth.csrf.clone() {
+    Some(value) => value,
+    None => {
+      auth = match refresh_auth(&upload_context, "submission_resubmit_rejected_csrf").await {
+        Ok(auth) => auth,
+        Err(err) => return Ok(ApiResponse::error(err)),
+      };
+      match auth.csrf.clone() {
+        Some(value) => value,
+        None => return Ok(ApiResponse::error("登录信息缺少CSRF")),
+      }
+    }
+  };
+  let mut aid = task.aid.unwrap_or(0);
+  if aid <= 0 {
+    aid = fetch_aid_with_refresh(&upload_context, &auth, &bvid)
+      .await
+      .unwrap_or(0);
+    if aid <= 0 {
+      return Ok(ApiResponse::error("无法获取AID，无法重新提交"));
+    }
+    let _ = update_submission_aid(&context, &task_id, aid);
+    task.aid = Some(aid);
+  }
+  if let Err(err) =
+    submit_video_edit_with_refresh(&upload_context, &auth, &task, &parts, aid, &csrf).await
+  {
+    append_log(
+      &state.app_log_path,
+      &format!("submission_resubmit_rejected_fail task_id={} err={}", task_id, err),
+    );
+    return Ok(ApiResponse::error(err));
+  }
+  if let Err(err) = update_submission_task_for_edit(&context, &task_id, &task) {
+    return Ok(ApiResponse::error(err));
+  }
+  // 编辑提交后稿件重新进入审核，先按审核中展示，等待下次远端刷新
+  if let Err(err) = context.db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE submission_task SET remote_state = ?1, reject_reason = NULL WHERE task_id = ?2",
+      (RemoteAuditState::Reviewing.to_code(), &task_id),
+    )
+  }) {
+    append_log(
+      &state.app_log_path,
+      &format!(
+        "submission_resubmit_rejected_state_fail task_id={} err={}",
+        task_id, err
+      ),
+    );
+  }
+  append_log(
+    &state.app_log_path,
+    &format!("submission_resubmit_rejected_ok task_id={} aid={}", task_id, aid),
+  );
+  Ok(ApiResponse::success("已重新提交审核".to_string()))
+}
+
+/// 重新提交沿用已上传的分P；未分段的任务使用合并视频的上传结果。
+fn build_resubmit_parts(
+  context: &SubmissionContext,
+  detail: &SubmissionTaskDetail,
+) -> Result<Vec<UploadedVideoPart>, String> {
+  if !detail.output_segments.is_empty() {
+    let mut segments = detail.output_segments.clone();
+    segments.sort_by_key(|segment| segment.part_order);
+    return segments
+      .iter()
+      .map(|segment| {
+        let cid = segment.cid.filter(|cid| *cid > 0);
+        let filename = segment
+          .file_name
+          .as_deref()
+          .map(|value| value.trim())
+          .filter(|value| !value.is_empty());
+        match (cid, filename) {
+          (Some(cid), Some(filename)) => Ok(UploadedVideoPart {
+            filename: filename.to_string(),
+            cid,
+            title: segment.part_name.clone(),
+          }),
+          _ => Err("分P上传信息缺失，请重新上传".to_string()),
+        }
+      })
+      .collect();
+  }
+  let merged = load_latest_merged_video(context, &detail.task.task_id)?
+    .ok_or_else(|| "未找到合并视频".to_string())?;
+  let cid = merged
+    .upload_cid
+    .filter(|cid| *cid > 0)
+    .ok_or_else(|| "合并视频上传信息缺失，请重新上传".to_string())?;
+  let filename = merged
+    .upload_file_name
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty())
+    .ok_or_else(|| "合并视频上传信息缺失，请重新上传".to_string())?;
+  Ok(vec![UploadedVideoPart {
+    filename,
+    cid,
+    title: build_part_title(
+      detail.task.segment_prefix.as_deref(),
+      1,
+      load_segment_index_padding(context.db.as_ref()),
+    ),
+  }])
+}
+
 #[tauri::command]
 pub fn submission_delete(
   state: State<'_, AppState>,
@@ -1780,31 +2776,7 @@ pub fn submission_delete(
   let context = SubmissionContext::new(&state);
   let base_dir = resolve_submission_base_dir(&context, &task_id);
   append_log(&state.app_log_path, &format!("submission_delete_start task_id={}", task_id));
-  let result = context.db.with_conn(|conn| {
-    conn.execute(
-      "DELETE FROM workflow_execution_logs WHERE instance_id IN (SELECT instance_id FROM workflow_instances WHERE task_id = ?1)",
-      [&task_id],
-    )?;
-    conn.execute(
-      "DELETE FROM workflow_performance_metrics WHERE instance_id IN (SELECT instance_id FROM workflow_instances WHERE task_id = ?1)",
-      [&task_id],
-    )?;
-    conn.execute(
-      "DELETE FROM workflow_steps WHERE instance_id IN (SELECT instance_id FROM workflow_instances WHERE task_id = ?1)",
-      [&task_id],
-    )?;
-    conn.execute("DELETE FROM workflow_instances WHERE task_id = ?1", [&task_id])?;
-    conn.execute("DELETE FROM task_relations WHERE submission_task_id = ?1", [&task_id])?;
-    conn.execute("DELETE FROM task_output_segment WHERE task_id = ?1", [&task_id])?;
-    conn.execute("DELETE FROM merged_video WHERE task_id = ?1", [&task_id])?;
-    conn.execute("DELETE FROM task_source_video WHERE task_id = ?1", [&task_id])?;
-    conn.execute("DELETE FROM video_clip WHERE task_id = ?1", [&task_id])?;
-    let deleted = conn.execute("DELETE FROM submission_task WHERE task_id = ?1", [&task_id])?;
-    if deleted == 0 {
-      return Err(rusqlite::Error::QueryReturnedNoRows);
-    }
-    Ok(())
-  });
+  let result = context.db.with_conn(|conn| delete_submission_rows(conn, &task_id));
   match result {
     Ok(()) => {
       if let Err(err) = cleanup_submission_files(&state.app_log_path, &base_dir) {
@@ -1824,11 +2796,128 @@ pub fn submission_delete(
   }
 }
 
+/// 删除任务及其工作流、分段、素材等关联记录；任务不存在时返回 QueryReturnedNoRows。
+fn delete_submission_rows(conn: &rusqlite::Connection, task_id: &str) -> rusqlite::Result<()> {
+  conn.execute(
+    "DELETE FROM workflow_execution_logs WHERE instance_id IN (SELECT instance_id FROM workflow_instances WHERE task_id = ?1)",
+    [task_id],
+  )?;
+  conn.execute(
+    "DELETE FROM workflow_performance_metrics WHERE instance_id IN (SELECT instance_id FROM workflow_instances WHERE task_id = ?1)",
+    [task_id],
+  )?;
+  conn.execute(
+    "DELETE FROM workflow_steps WHERE instance_id IN (SELECT instance_id FROM workflow_instances WHERE task_id = ?1)",
+    [task_id],
+  )?;
+  conn.execute("DELETE FROM workflow_instances WHERE task_id = ?1", [task_id])?;
+  conn.execute("DELETE FROM task_relations WHERE submission_task_id = ?1", [task_id])?;
+  conn.execute("DELETE FROM task_output_segment WHERE task_id = ?1", [task_id])?;
+  conn.execute("DELETE FROM edit_upload_segment WHERE task_id = ?1", [task_id])?;
+  conn.execute("DELETE FROM merged_video WHERE task_id = ?1", [task_id])?;
+  conn.execute("DELETE FROM task_source_video WHERE task_id = ?1", [task_id])?;
+  conn.execute("DELETE FROM video_clip WHERE task_id = ?1", [task_id])?;
+  let deleted = conn.execute("DELETE FROM submission_task WHERE task_id = ?1", [task_id])?;
+  if deleted == 0 {
+    return Err(rusqlite::Error::QueryReturnedNoRows);
+  }
+  Ok(())
+}
+
+/// 批量删除：所有记录删除在同一事务内完成，单个任务失败只回滚自身的保存点。
+#[tauri::command]
+pub fn submission_bulk_delete(
+  state: State<'_, AppState>,
+  task_ids: Vec<String>,
+) -> ApiResponse<SubmissionBulkResult> {
+  let context = SubmissionContext::new(&state);
+  let task_ids = normalize_bulk_task_ids(task_ids);
+  if task_ids.is_empty() {
+    return ApiResponse::error("请至少选择一个任务");
+  }
+  append_log(
+    &state.app_log_path,
+    &format!("submission_bulk_delete_start count={}", task_ids.len()),
+  );
+  let base_dirs = task_ids
+    .iter()
+    .map(|task_id| resolve_submission_base_dir(&context, task_id))
+    .collect::<Vec<_>>();
+  let outcomes = context.db.with_conn_mut(|conn| {
+    let mut tx = conn.transaction()?;
+    let mut outcomes = Vec::with_capacity(task_ids.len());
+    for task_id in &task_ids {
+      let savepoint = tx.savepoint()?;
+      match delete_submission_rows(&savepoint, task_id) {
+        Ok(()) => {
+          savepoint.commit()?;
+          outcomes.push(Ok(()));
+        }
+        Err(rusqlite::Error::QueryReturnedNoRows) => outcomes.push(Err("任务不存在".to_string())),
+        Err(err) => outcomes.push(Err(err.to_string())),
+      }
+    }
+    tx.commit()?;
+    Ok(outcomes)
+  });
+  let outcomes = match outcomes {
+    Ok(outcomes) => outcomes,
+    Err(err) => {
+      append_log(
+        &state.app_log_path,
+        &format!("submission_bulk_delete_fail err={}", err),
+      );
+      return ApiResponse::error(format!("Failed to delete: {}", err));
+    }
+  };
+
+  let mut items = Vec::with_capacity(task_ids.len());
+  for ((task_id, base_dir), outcome) in task_ids.into_iter().zip(base_dirs).zip(outcomes) {
+    let error = match outcome {
+      Ok(()) => cleanup_submission_files(&state.app_log_path, &base_dir)
+        .err()
+        .map(|err| format!("任务已删除，但清理文件失败: {}", err)),
+      Err(err) => Some(err),
+    };
+    if let Some(err) = error.as_ref() {
+      append_log(
+        &state.app_log_path,
+        &format!("submission_bulk_delete_item_fail task_id={} err={}", task_id, err),
+      );
+    }
+    items.push(SubmissionBulkItemResult {
+      task_id,
+      success: error.is_none(),
+      error,
+    });
+  }
+  let result = SubmissionBulkResult::from_items(items);
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "submission_bulk_delete_done succeeded={} failed={}",
+      result.succeeded, result.failed
+    ),
+  );
+  ApiResponse::success(result)
+}
+
+fn normalize_bulk_task_ids(task_ids: Vec<String>) -> Vec<String> {
+  let mut seen = HashSet::new();
+  task_ids
+    .into_iter()
+    .map(|task_id| task_id.trim().to_string())
+    .filter(|task_id| !task_id.is_empty() && seen.insert(task_id.clone()))
+    .collect()
+}
+
 fn cleanup_submission_files(log_path: &PathBuf, base_dir: &Path) -> Result<(), String> {
   let targets = [
     ("cut", base_dir.join("cut")),
     ("merge", base_dir.join("merge")),
     ("output", base_dir.join("output")),
+    // 开启保留中间文件时移入的调试目录
+    ("debug", base_dir.join("debug")),
   ];
   for (label, path) in targets {
     remove_path_if_exists(log_path, label, &path)?;
@@ -1837,39 +2926,149 @@ fn cleanup_submission_files(log_path: &PathBuf, base_dir: &Path) -> Result<(), S
   Ok(())
 }
 
-fn cleanup_submission_derived_files(log_path: &PathBuf, base_dir: &Path) -> Result<(), String> {
-  let targets = [
-    ("cut", base_dir.join("cut")),
-    ("merge", base_dir.join("merge")),
-    ("output", base_dir.join("output")),
-  ];
-  for (label, path) in targets {
-    remove_path_if_exists(log_path, label, &path)?;
+fn cleanup_submission_derived_files(
+  log_path: &PathBuf,
+  base_dir: &Path,
+  keep_intermediates: bool,
+) -> Result<(), String> {
+  cleanup_submission_folders(log_path, base_dir, &["cut", "merge", "output"], keep_intermediates)
+}
+
+fn cleanup_submission_folders(
+  log_path: &PathBuf,
+  base_dir: &Path,
+  folders: &[&str],
+  keep_intermediates: bool,
+) -> Result<(), String> {
+  let debug_dir = intermediate_debug_dir(base_dir);
+  for label in folders {
+    let path = base_dir.join(label);
+    if keep_intermediates {
+      retain_intermediate_path(log_path, label, &path, &debug_dir)?;
+    } else {
+      remove_path_if_exists(log_path, label, &path)?;
+    }
   }
   Ok(())
 }
 
-fn remove_path_if_exists(log_path: &PathBuf, label: &str, path: &Path) -> Result<(), String> {
-  match fs::metadata(path) {
-    Ok(metadata) => {
-      append_log(
-        log_path,
-        &format!(
-          "submission_cleanup_start label={} path={}",
-          label,
-          path.to_string_lossy()
-        ),
-      );
-      let result = if metadata.is_dir() {
-        fs::remove_dir_all(path)
-      } else {
-        fs::remove_file(path)
-      };
-      match result {
-        Ok(()) => {
-          append_log(
-            log_path,
-            &format!(
+/// 投稿完成后按设置自动清理剪辑与分段产物；合并视频保留给重新分段，
+/// 已上传分段通过 cid 复用，编辑稿件不依赖本地文件。
+fn auto_cleanup_completed_task(context: &SubmissionContext, task_id: &str) {
+  let Ok(settings) = load_download_settings_from_db(context.db.as_ref()) else {
+    return;
+  };
+  if !settings.auto_cleanup_after_complete {
+    return;
+  }
+  let log_path = context.app_log_path.as_ref();
+  if settings.keep_intermediates {
+    append_log(
+      log_path,
+      &format!(
+        "submission_auto_cleanup_skip task_id={} reason=keep_intermediates",
+        task_id
+      ),
+    );
+    return;
+  }
+  let base_dir = resolve_submission_base_dir(context, task_id);
+  let folders = ["cut", "output"];
+  for folder in folders {
+    let path = base_dir.join(folder);
+    if !path.exists() {
+      continue;
+    }
+    let (bytes, file_count) = directory_size(&path);
+    append_log(
+      log_path,
+      &format!(
+        "submission_auto_cleanup task_id={} label={} path={} files={} bytes={}",
+        task_id,
+        folder,
+        path.to_string_lossy(),
+        file_count,
+        bytes
+      ),
+    );
+  }
+  if let Err(err) = cleanup_submission_folders(log_path, &base_dir, &folders, false) {
+    append_log(
+      log_path,
+      &format!("submission_auto_cleanup_fail task_id={} err={}", task_id, err),
+    );
+    return;
+  }
+  // 分段文件已删除，标记分段记录，避免之后的上传预检把它们当作缺失文件
+  let cleaned = context.db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE task_output_segment SET file_cleaned = 1 WHERE task_id = ?1",
+      [task_id],
+    )
+  });
+  match cleaned {
+    Ok(rows) => append_log(
+      log_path,
+      &format!("submission_auto_cleanup_db task_id={} segments={}", task_id, rows),
+    ),
+    Err(err) => append_log(
+      log_path,
+      &format!("submission_auto_cleanup_db_fail task_id={} err={}", task_id, err),
+    ),
+  }
+}
+
+fn intermediate_debug_dir(base_dir: &Path) -> PathBuf {
+  base_dir
+    .join("debug")
+    .join(Utc::now().format("%Y%m%d-%H%M%S").to_string())
+}
+
+/// 开启保留中间文件时，用移动代替删除，便于排查剪辑/合并异常。
+fn retain_intermediate_path(
+  log_path: &Path,
+  label: &str,
+  path: &Path,
+  debug_dir: &Path,
+) -> Result<(), String> {
+  if !path.exists() {
+    return Ok(());
+  }
+  fs::create_dir_all(debug_dir).map_err(|err| format!("创建调试目录失败: {}", err))?;
+  let target = debug_dir.join(label);
+  fs::rename(path, &target).map_err(|err| format!("保留{}失败: {}", label, err))?;
+  append_log(
+    log_path,
+    &format!(
+      "submission_intermediate_retained label={} path={}",
+      label,
+      target.to_string_lossy()
+    ),
+  );
+  Ok(())
+}
+
+fn remove_path_if_exists(log_path: &PathBuf, label: &str, path: &Path) -> Result<(), String> {
+  match fs::metadata(path) {
+    Ok(metadata) => {
+      append_log(
+        log_path,
+        &format!(
+          "submission_cleanup_start label={} path={}",
+          label,
+          path.to_string_lossy()
+        ),
+      );
+      let result = if metadata.is_dir() {
+        fs::remove_dir_all(path)
+      } else {
+        fs::remove_file(path)
+      };
+      match result {
+        Ok(()) => {
+          append_log(
+            log_path,
+            &format!(
               "submission_cleanup_ok label={} path={}",
               label,
               path.to_string_lossy()
@@ -1966,102 +3165,770 @@ pub async fn submission_integrated_execute(
 }
 
 #[tauri::command]
-pub async fn submission_upload_execute(
+pub async fn submission_retry_integrated_download(
   state: State<'_, AppState>,
   task_id: String,
+  download_id: i64,
 ) -> Result<ApiResponse<String>, String> {
   let context = SubmissionContext::new(&state);
   let task_id = task_id.trim().to_string();
   if task_id.is_empty() {
     return Ok(ApiResponse::error("任务ID不能为空"));
   }
+
   let status = match load_task_status(&context, &task_id) {
     Ok(status) => status,
     Err(err) => return Ok(ApiResponse::error(format!("读取任务状态失败: {}", err))),
   };
+  if status == "UPLOADING" {
+    return Ok(ApiResponse::error("任务正在投稿中，请稍后再试"));
+  }
+  if let Ok(Some(workflow_status)) = load_workflow_status(&context, &task_id) {
+    if workflow_status.status == "RUNNING" {
+      return Ok(ApiResponse::error("工作流执行中"));
+    }
+  }
+
+  let integrated_records = match load_integrated_download_records(&context, &task_id) {
+    Ok(records) => records,
+    Err(err) => return Ok(ApiResponse::error(format!("读取下载记录失败: {}", err))),
+  };
+  let record = match integrated_records
+    .into_iter()
+    .find(|record| record.id == download_id)
+  {
+    Some(record) => record,
+    None => return Ok(ApiResponse::error("该下载记录不属于当前任务")),
+  };
+  let (download_status, workflow_instance_id) = match context.db.with_conn(|conn| {
+    conn.query_row(
+      "SELECT vd.status, tr.workflow_instance_id FROM task_relations tr \
+       JOIN video_download vd ON tr.download_task_id = vd.id \
+       WHERE tr.submission_task_id = ?1 AND tr.download_task_id = ?2 AND tr.relation_type = 'INTEGRATED'",
+      (&task_id, download_id),
+      |row| Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?)),
+    )
+  }) {
+    Ok(value) => value,
+    Err(err) => return Ok(ApiResponse::error(format!("读取下载记录失败: {}", err))),
+  };
+  if download_status != 3 {
+    return Ok(ApiResponse::error("仅支持重试下载失败的分P"));
+  }
+  let workflow_instance_id = match workflow_instance_id {
+    Some(value) => value,
+    None => match load_workflow_instance_id(&context, &task_id) {
+      Ok(Some(value)) => value,
+      Ok(None) => return Ok(ApiResponse::error("未找到工作流实例")),
+      Err(err) => return Ok(ApiResponse::error(format!("读取工作流实例失败: {}", err))),
+    },
+  };
+
+  let new_download_ids = match create_retry_download_records(
+    &context,
+    &task_id,
+    &workflow_instance_id,
+    std::slice::from_ref(&record),
+  ) {
+    Ok(ids) => ids,
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+  // 先入队再改状态，入队失败时任务保持原状态，不会停在 PENDING
+  if let Err(err) =
+    crate::commands::download::requeue_integrated_downloads(&state, &new_download_ids).await
+  {
+    return Ok(ApiResponse::error(format!("重新加入下载队列失败: {}", err)));
+  }
+  if let Err(err) = update_submission_status(&context, &task_id, "PENDING") {
+    return Ok(ApiResponse::error(format!("更新任务状态失败: {}", err)));
+  }
+  let _ = update_workflow_status(&context, &task_id, "PENDING", None, 0.0);
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "submission_retry_integrated_download task_id={} download_id={} new_download_id={}",
+      task_id,
+      download_id,
+      new_download_ids.first().copied().unwrap_or(0)
+    ),
+  );
+  Ok(ApiResponse::success(
+    "已重新创建下载任务，下载完成后自动继续投稿".to_string(),
+  ))
+}
+
+#[tauri::command]
+pub async fn submission_upload_execute(
+  state: State<'_, AppState>,
+  task_id: String,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(&state);
+  let task_id = task_id.trim().to_string();
+  if task_id.is_empty() {
+    return Ok(ApiResponse::error("任务ID不能为空"));
+  }
+  match queue_submission_upload(&context, &task_id) {
+    Ok(()) => Ok(ApiResponse::success("投稿任务已加入队列".to_string())),
+    Err(err) => Ok(ApiResponse::error(err)),
+  }
+}
+
+/// 批量重新投稿，逐个沿用单任务的状态与分段文件校验，失败的任务不影响其余任务。
+#[tauri::command]
+pub async fn submission_bulk_retry(
+  state: State<'_, AppState>,
+  task_ids: Vec<String>,
+) -> Result<ApiResponse<SubmissionBulkResult>, String> {
+  let context = SubmissionContext::new(&state);
+  let task_ids = normalize_bulk_task_ids(task_ids);
+  if task_ids.is_empty() {
+    return Ok(ApiResponse::error("请至少选择一个任务"));
+  }
+  let items = task_ids
+    .into_iter()
+    .map(|task_id| {
+      let error = queue_submission_upload(&context, &task_id).err();
+      SubmissionBulkItemResult {
+        task_id,
+        success: error.is_none(),
+        error,
+      }
+    })
+    .collect::<Vec<_>>();
+  let result = SubmissionBulkResult::from_items(items);
   append_log(
     &state.app_log_path,
+    &format!(
+      "submission_bulk_retry_done succeeded={} failed={}",
+      result.succeeded, result.failed
+    ),
+  );
+  Ok(ApiResponse::success(result))
+}
+
+fn queue_submission_upload(context: &SubmissionContext, task_id: &str) -> Result<(), String> {
+  let status = load_task_status(context, task_id).map_err(|err| format!("读取任务状态失败: {}", err))?;
+  append_log(
+    &context.app_log_path,
     &format!("submission_upload_request task_id={} status={}", task_id, status),
   );
   if status == "UPLOADING" {
     append_log(
-      &state.app_log_path,
+      &context.app_log_path,
       &format!("submission_upload_reject task_id={} reason=uploading", task_id),
     );
-    return Ok(ApiResponse::error("任务正在投稿中"));
+    return Err("任务正在投稿中".to_string());
+  }
+  if status == SUBMISSION_DEAD_LETTER_STATUS {
+    return Err("任务上传失败次数已达上限，请先重置重试次数".to_string());
   }
   if status != "WAITING_UPLOAD" && status != "FAILED" {
     append_log(
-      &state.app_log_path,
+      &context.app_log_path,
       &format!(
         "submission_upload_reject task_id={} reason=invalid_status status={}",
         task_id, status
       ),
     );
-    return Ok(ApiResponse::error("任务状态不支持投稿"));
+    return Err("任务状态不支持投稿".to_string());
   }
 
-  if let Err(err) = update_submission_status(&context, &task_id, "WAITING_UPLOAD") {
-    return Ok(ApiResponse::error(format!("提交到投稿队列失败: {}", err)));
+  let precheck =
+    check_segment_files(context, task_id).map_err(|err| format!("校验分段文件失败: {}", err))?;
+  if !precheck.ready {
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "submission_upload_reject task_id={} reason=segment_files issues={}",
+        task_id,
+        precheck.issues.len()
+      ),
+    );
+    return Err(format_segment_file_issues(&precheck.issues));
   }
 
-  Ok(ApiResponse::success("投稿任务已加入队列".to_string()))
+  clear_upload_cancel(task_id);
+  let _ = update_task_auto_retry_count(context, task_id, 0);
+  set_upload_paused(context, task_id, false)
+    .and_then(|_| update_submission_status(context, task_id, "WAITING_UPLOAD"))
+    .map_err(|err| format!("提交到投稿队列失败: {}", err))
 }
 
 #[tauri::command]
-pub async fn submission_retry_segment_upload(
+pub async fn submission_preflight(
   state: State<'_, AppState>,
-  segment_id: String,
-) -> Result<ApiResponse<String>, String> {
-  let context = SubmissionContext::new(&state);
-  let segment_id = segment_id.trim().to_string();
-  if segment_id.is_empty() {
-    return Ok(ApiResponse::error("分段ID不能为空"));
+  source_videos: Vec<SourceVideoInput>,
+) -> Result<ApiResponse<SubmissionPreflightResult>, String> {
+  if source_videos.is_empty() {
+    return Ok(ApiResponse::error("请至少添加一个源视频"));
   }
-  let segment = match load_output_segment_by_id(&context, &segment_id) {
-    Ok(Some(segment)) => segment,
-    Ok(None) => return Ok(ApiResponse::error("未找到分段信息")),
-    Err(err) => return Ok(ApiResponse::error(err)),
+  let app_log_path = state.app_log_path.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || run_submission_preflight(&source_videos))
+    .await
+    .map_err(|_| "预检失败".to_string())?;
+  append_log(
+    &app_log_path,
+    &format!(
+      "submission_preflight sources={} use_copy={} reason={} total_seconds={:.2}",
+      result.sources.len(),
+      result.use_copy,
+      result.reason.as_deref().unwrap_or(""),
+      result.total_estimated_seconds
+    ),
+  );
+  Ok(ApiResponse::success(result))
+}
+
+#[tauri::command]
+pub async fn bilibili_submission_quota(
+  state: State<'_, AppState>,
+) -> Result<ApiResponse<SubmissionQuota>, String> {
+  let auth = match state.login_store.load_auth_info(&state.db) {
+    Ok(Some(auth)) => auth,
+    Ok(None) => return Ok(ApiResponse::success(SubmissionQuota::logged_out())),
+    Err(err) => return Ok(ApiResponse::error(format!("读取登录信息失败: {}", err))),
   };
-  if segment.upload_status == "SUCCESS" {
-    return Ok(ApiResponse::success("分段已上传成功".to_string()));
-  }
-  let status = match load_task_status(&context, &segment.task_id) {
-    Ok(status) => status,
-    Err(err) => return Ok(ApiResponse::error(format!("读取任务状态失败: {}", err))),
+  let url = "https://member.bilibili.com/x/vupre/web/archive/pre";
+  let params = vec![("lang".to_string(), "cn".to_string())];
+  let data = match state.bilibili.get_json(url, &params, Some(&auth), false).await {
+    Ok(data) => data,
+    Err(err) if err.contains("code: -101") => {
+      append_log(
+        &state.app_log_path,
+        &format!("submission_quota_logged_out err={}", err),
+      );
+      return Ok(ApiResponse::success(SubmissionQuota::logged_out()));
+    }
+    Err(err) => {
+      append_log(
+        &state.app_log_path,
+        &format!("submission_quota_fail err={}", err),
+      );
+      return Ok(ApiResponse::error(format!("查询投稿额度失败: {}", err)));
+    }
   };
-  if status == "UPLOADING" {
-    return Ok(ApiResponse::error("任务正在投稿中，请稍后重试"));
-  }
-
-  let upload_context = UploadContext::new(&state);
-  let auth = match load_auth_or_refresh(&upload_context, "submission_retry_segment").await {
-    Ok(auth) => auth,
-    Err(err) => return Ok(ApiResponse::error(err)),
+  // 额度接口失败不影响等级与封禁状态的展示，剩余次数与冷却保持未知
+  let limit_data = match state
+    .bilibili
+    .get_json(
+      "https://member.bilibili.com/x/vupre/web/archive/limit",
+      &params,
+      Some(&auth),
+      false,
+    )
+    .await
+  {
+    Ok(limit_data) => Some(limit_data),
+    Err(err) => {
+      append_log(
+        &state.app_log_path,
+        &format!("submission_quota_limit_fail err={}", err),
+      );
+      None
+    }
+  };
+  let Some(quota) = parse_submission_quota(&data, limit_data.as_ref()) else {
+    let payload = data.to_string();
+    append_log(
+      &state.app_log_path,
+      &format!(
+        "submission_quota_unknown_payload payload={}",
+        payload.chars().take(512).collect::<String>()
+      ),
+    );
+    return Ok(ApiResponse::error("投稿额度返回格式无法识别"));
   };
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "submission_quota level={} banned={} remaining={} cooldown={}",
+      quota.level.unwrap_or(-1),
+      quota.banned,
+      quota.remaining_daily.unwrap_or(-1),
+      quota.cooldown_message.as_deref().unwrap_or("")
+    ),
+  );
+  Ok(ApiResponse::success(quota))
+}
 
-  update_segment_upload_status(&context, &segment_id, "UPLOADING")?;
-  let client = Client::new();
-  let result = upload_segment_with_retry(
-    &context,
-    &upload_context,
-    &client,
-    &auth,
-    &segment_id,
-    upload_context.app_log_path.as_ref(),
-    UPLOAD_SEGMENT_RETRY_LIMIT,
-  )
-  .await;
+/// 投稿前置信息 `archive/pre` 的 `data.myinfo` 部分，只取账号等级与封禁状态。
+#[derive(Deserialize)]
+struct ArchivePreMyInfo {
+  mid: i64,
+  #[serde(default)]
+  level: Option<i64>,
+  #[serde(default)]
+  banned: bool,
+}
+
+/// 创作中心投稿限制信息 `archive/limit`，给出当日投稿上限、已用次数与冷却剩余秒数。
+#[derive(Deserialize, Default)]
+struct ArchiveLimitInfo {
+  #[serde(default)]
+  limit: Option<i64>,
+  #[serde(default)]
+  used: Option<i64>,
+  #[serde(default)]
+  remain: Option<i64>,
+  #[serde(default)]
+  cooldown: Option<i64>,
+  #[serde(default)]
+  message: Option<String>,
+}
+
+/// 按 `data.myinfo` 的结构解析投稿额度；结构不符时返回 None，由调用方记录原始返回。
+/// 剩余次数与冷却取自 `archive/limit`，未查询到时保持未知。
+fn parse_submission_quota(data: &Value, limit_data: Option<&Value>) -> Option<SubmissionQuota> {
+  let myinfo: ArchivePreMyInfo = serde_json::from_value(data.get("myinfo")?.clone()).ok()?;
+  if myinfo.mid <= 0 {
+    return None;
+  }
+  let limit: ArchiveLimitInfo = limit_data
+    .and_then(|value| serde_json::from_value(value.clone()).ok())
+    .unwrap_or_default();
+  let remaining_daily = limit.remain.or_else(|| match (limit.limit, limit.used) {
+    (Some(total), Some(used)) => Some(total - used),
+    _ => None,
+  });
+  let remaining_daily = remaining_daily.map(|value| value.max(0));
+  let cooldown_seconds = limit.cooldown.filter(|value| *value > 0);
+  let cooldown_message = if myinfo.banned {
+    Some("账号当前处于投稿限制中".to_string())
+  } else if let Some(seconds) = cooldown_seconds {
+    Some(format!("投稿冷却中，约 {} 分钟后可投稿", (seconds + 59) / 60))
+  } else if remaining_daily == Some(0) {
+    Some(
+      limit
+        .message
+        .filter(|message| !message.trim().is_empty())
+        .unwrap_or_else(|| "今日投稿次数已用完".to_string()),
+    )
+  } else {
+    None
+  };
+  Some(SubmissionQuota {
+    logged_in: true,
+    level: myinfo.level,
+    banned: myinfo.banned,
+    remaining_daily,
+    cooldown_message,
+  })
+}
 
+#[tauri::command]
+pub async fn submission_normalize_sources(
+  state: State<'_, AppState>,
+  task_id: String,
+) -> Result<ApiResponse<Vec<SourceTimeAdjustment>>, String> {
+  let context = SubmissionContext::new(&state);
+  let sources = match load_task_source_records(&context, &task_id) {
+    Ok(sources) => sources,
+    Err(err) => return Ok(ApiResponse::error(format!("读取源视频失败: {}", err))),
+  };
+  if sources.is_empty() {
+    return Ok(ApiResponse::error("任务没有源视频"));
+  }
+  let worker_context = context.clone();
+  let worker_task_id = task_id.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    normalize_task_sources(&worker_context, &worker_task_id, &sources)
+  })
+  .await
+  .map_err(|_| "校正源视频时间失败".to_string())?;
   match result {
-    Ok(upload_result) => {
-      update_segment_upload_result(
-        &context,
-        &segment_id,
-        "SUCCESS",
-        Some(upload_result.cid),
-        Some(upload_result.filename),
-      )?;
+    Ok(adjustments) => {
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "submission_normalize_sources task_id={} adjusted={} errors={}",
+          task_id,
+          adjustments.iter().filter(|item| item.error.is_none()).count(),
+          adjustments.iter().filter(|item| item.error.is_some()).count()
+        ),
+      );
+      Ok(ApiResponse::success(adjustments))
+    }
+    Err(err) => Ok(ApiResponse::error(format!("校正源视频时间失败: {}", err))),
+  }
+}
+
+fn load_task_source_records(
+  context: &SubmissionContext,
+  task_id: &str,
+) -> Result<Vec<TaskSourceVideoRecord>, String> {
+  context
+    .db
+    .with_conn(|conn| query_task_source_records(conn, task_id))
+    .map_err(|err| err.to_string())
+}
+
+fn query_task_source_records(
+  conn: &rusqlite::Connection,
+  task_id: &str,
+) -> rusqlite::Result<Vec<TaskSourceVideoRecord>> {
+  let mut stmt = conn.prepare(
+    "SELECT id, task_id, source_file_path, sort_order, start_time, end_time FROM task_source_video WHERE task_id = ?1 ORDER BY sort_order ASC",
+  )?;
+  let rows = stmt.query_map([task_id], |row| {
+    Ok(TaskSourceVideoRecord {
+      id: row.get(0)?,
+      task_id: row.get(1)?,
+      source_file_path: row.get(2)?,
+      sort_order: row.get(3)?,
+      start_time: row.get(4)?,
+      end_time: row.get(5)?,
+    })
+  })?;
+  rows.collect()
+}
+
+/// 与 check_sources_ready 的钳制规则一致：结束时间超出时长时截到时长，
+/// 起始时间越界时归零；未配置的结束时间保持为空。只返回发生变化或探测失败的源。
+fn normalize_task_sources(
+  context: &SubmissionContext,
+  task_id: &str,
+  sources: &[TaskSourceVideoRecord],
+) -> Result<Vec<SourceTimeAdjustment>, String> {
+  let mut adjustments = Vec::new();
+  for source in sources {
+    let mut adjustment = SourceTimeAdjustment {
+      source_id: source.id.clone(),
+      source_file_path: source.source_file_path.clone(),
+      sort_order: source.sort_order,
+      duration_seconds: None,
+      old_start_time: source.start_time.clone(),
+      old_end_time: source.end_time.clone(),
+      new_start_time: source.start_time.clone(),
+      new_end_time: source.end_time.clone(),
+      error: None,
+    };
+    let duration = match probe_duration_seconds(Path::new(&source.source_file_path)) {
+      Ok(duration) if duration > 0.0 => duration,
+      Ok(_) => {
+        adjustment.error = Some("无法获取视频时长".to_string());
+        adjustments.push(adjustment);
+        continue;
+      }
+      Err(err) => {
+        adjustment.error = Some(err);
+        adjustments.push(adjustment);
+        continue;
+      }
+    };
+    adjustment.duration_seconds = Some(duration);
+    let start_config = source.start_time.as_deref().and_then(parse_time_to_seconds);
+    let end_config = source.end_time.as_deref().and_then(parse_time_to_seconds);
+    let end = match end_config {
+      Some(end) if end > 0.0 && end <= duration => end,
+      _ => duration,
+    };
+    if let Some(config_end) = end_config {
+      if config_end <= 0.0 || config_end > duration {
+        adjustment.new_end_time = Some(format_timecode_seconds(end));
+      }
+    }
+    if let Some(start) = start_config {
+      if start < 0.0 || start >= end {
+        adjustment.new_start_time = Some("00:00:00".to_string());
+      }
+    }
+    if adjustment.new_start_time == adjustment.old_start_time
+      && adjustment.new_end_time == adjustment.old_end_time
+    {
+      continue;
+    }
+    context
+      .db
+      .with_conn(|conn| {
+        conn.execute(
+          "UPDATE task_source_video SET start_time = ?1, end_time = ?2 WHERE id = ?3",
+          (&adjustment.new_start_time, &adjustment.new_end_time, &source.id),
+        )
+      })
+      .map_err(|err| err.to_string())?;
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "submission_source_time_normalized task_id={} input={} start={} end={} duration={}",
+        task_id,
+        source.source_file_path,
+        adjustment.new_start_time.as_deref().unwrap_or(""),
+        adjustment.new_end_time.as_deref().unwrap_or(""),
+        duration
+      ),
+    );
+    adjustments.push(adjustment);
+  }
+  Ok(adjustments)
+}
+
+fn run_submission_preflight(source_videos: &[SourceVideoInput]) -> SubmissionPreflightResult {
+  let mut sources = Vec::with_capacity(source_videos.len());
+  let mut estimates = Vec::with_capacity(source_videos.len());
+  for source in source_videos {
+    let start_seconds = source
+      .start_time
+      .as_deref()
+      .and_then(parse_time_to_seconds)
+      .unwrap_or(0.0)
+      .max(0.0);
+    let end_config = source.end_time.as_deref().and_then(parse_time_to_seconds);
+    let (duration_seconds, error) = match probe_duration_seconds(Path::new(&source.source_file_path)) {
+      Ok(duration) => (Some(duration), None),
+      Err(err) => (None, Some(err)),
+    };
+    // 与 check_sources_ready 一致：结束时间超出时长时截断到时长，未配置时取整段。
+    let end_seconds = match (end_config, duration_seconds) {
+      (Some(end), Some(duration)) if end > 0.0 => Some(end.min(duration)),
+      (_, Some(duration)) => Some(duration),
+      (Some(end), None) if end > 0.0 => Some(end),
+      _ => None,
+    };
+    let estimated_seconds = end_seconds.map(|end| {
+      if start_seconds < end {
+        end - start_seconds
+      } else {
+        end
+      }
+    });
+    sources.push(ClipSource {
+      input_path: source.source_file_path.clone(),
+      start_time: source.start_time.clone(),
+      end_time: source.end_time.clone(),
+      order: source.sort_order,
+    });
+    estimates.push(PreflightSourceEstimate {
+      source_file_path: source.source_file_path.clone(),
+      sort_order: source.sort_order,
+      duration_seconds,
+      start_seconds,
+      end_seconds,
+      estimated_seconds,
+      error,
+    });
+  }
+
+  let decision = decide_clip_copy(&sources).unwrap_or_else(|err| crate::processing::ClipCopyDecision {
+    use_copy: false,
+    reason: Some(format!("timestamp_probe_failed err={}", err)),
+  });
+  let total_estimated_seconds = estimates
+    .iter()
+    .filter_map(|estimate| estimate.estimated_seconds)
+    .sum();
+  SubmissionPreflightResult {
+    use_copy: decision.use_copy,
+    reason: decision.reason,
+    sources: estimates,
+    total_estimated_seconds,
+  }
+}
+
+#[tauri::command]
+pub fn submission_upload_precheck(
+  state: State<'_, AppState>,
+  task_id: String,
+) -> ApiResponse<SubmissionUploadPrecheck> {
+  let context = SubmissionContext::new(&state);
+  let task_id = task_id.trim().to_string();
+  if task_id.is_empty() {
+    return ApiResponse::error("任务ID不能为空");
+  }
+  match check_segment_files(&context, &task_id) {
+    Ok(precheck) => ApiResponse::success(precheck),
+    Err(err) => ApiResponse::error(format!("校验分段文件失败: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn submission_stats(
+  state: State<'_, AppState>,
+  task_id: String,
+) -> ApiResponse<Option<TaskStatsRecord>> {
+  let context = SubmissionContext::new(&state);
+  let task_id = task_id.trim().to_string();
+  if task_id.is_empty() {
+    return ApiResponse::error("任务ID不能为空");
+  }
+  match load_task_stats(&context, &task_id) {
+    Ok(stats) => ApiResponse::success(stats),
+    Err(err) => ApiResponse::error(format!("加载任务统计失败: {}", err)),
+  }
+}
+
+/// 检查待上传分段文件是否存在、非空，且与已记录的上传总字节数一致；已上传成功的分段不再需要本地文件。
+fn check_segment_files(
+  context: &SubmissionContext,
+  task_id: &str,
+) -> Result<SubmissionUploadPrecheck, String> {
+  let rows = context
+    .db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT segment_id, part_name, part_order, segment_file_path, upload_status, \
+         COALESCE(upload_total_bytes, 0), file_cleaned \
+         FROM task_output_segment WHERE task_id = ?1 ORDER BY part_order ASC",
+      )?;
+      let rows = stmt
+        .query_map([task_id], |row| {
+          Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, i64>(5)?,
+            row.get::<_, i64>(6)? != 0,
+          ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok(rows)
+    })
+    .map_err(|err| err.to_string())?;
+
+  let segment_count = rows.len();
+  let mut issues = Vec::new();
+  for (segment_id, part_name, part_order, file_path, upload_status, total_bytes, file_cleaned) in rows
+  {
+    if upload_status == "SUCCESS" {
+      continue;
+    }
+    let expected_size = if total_bytes > 0 {
+      Some(total_bytes as u64)
+    } else {
+      None
+    };
+    let actual_size = std::fs::metadata(&file_path)
+      .ok()
+      .filter(|meta| meta.is_file())
+      .map(|meta| meta.len());
+    let reason = match (actual_size, expected_size) {
+      (None, _) if file_cleaned => "CLEANED",
+      (None, _) => "MISSING",
+      (Some(0), _) => "EMPTY",
+      (Some(actual), Some(expected)) if actual != expected => "SIZE_CHANGED",
+      _ => continue,
+    };
+    issues.push(SegmentFileIssue {
+      segment_id,
+      part_name,
+      part_order,
+      file_path,
+      reason: reason.to_string(),
+      expected_size,
+      actual_size,
+    });
+  }
+  Ok(SubmissionUploadPrecheck {
+    ready: issues.is_empty(),
+    segment_count,
+    issues,
+  })
+}
+
+fn format_segment_file_issues(issues: &[SegmentFileIssue]) -> String {
+  let details = issues
+    .iter()
+    .map(|issue| {
+      let reason = match issue.reason.as_str() {
+        "MISSING" => "文件不存在",
+        "CLEANED" => "文件已被自动清理",
+        "EMPTY" => "文件为空",
+        _ => "文件大小已变化",
+      };
+      format!("{}({})", issue.part_name, reason)
+    })
+    .collect::<Vec<_>>()
+    .join("、");
+  format!("分段文件缺失或已变更，请重新分段后再投稿: {}", details)
+}
+
+#[tauri::command]
+pub fn submission_cancel_upload(
+  state: State<'_, AppState>,
+  task_id: String,
+) -> ApiResponse<String> {
+  let context = SubmissionContext::new(&state);
+  let task_id = task_id.trim().to_string();
+  if task_id.is_empty() {
+    return ApiResponse::error("任务ID不能为空");
+  }
+  let status = match load_task_status(&context, &task_id) {
+    Ok(status) => status,
+    Err(err) => return ApiResponse::error(format!("读取任务状态失败: {}", err)),
+  };
+  append_log(
+    &state.app_log_path,
+    &format!("submission_cancel_upload_request task_id={} status={}", task_id, status),
+  );
+  match status.as_str() {
+    "UPLOADING" => {
+      request_upload_cancel(&task_id);
+      ApiResponse::success("正在取消上传".to_string())
+    }
+    "WAITING_UPLOAD" => match mark_upload_cancelled(&context, &task_id) {
+      Ok(()) => ApiResponse::success("已移出投稿队列".to_string()),
+      Err(err) => ApiResponse::error(format!("取消上传失败: {}", err)),
+    },
+    _ => ApiResponse::success("当前没有正在上传的任务".to_string()),
+  }
+}
+
+#[tauri::command]
+pub async fn submission_retry_segment_upload(
+  state: State<'_, AppState>,
+  segment_id: String,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(&state);
+  let segment_id = segment_id.trim().to_string();
+  if segment_id.is_empty() {
+    return Ok(ApiResponse::error("分段ID不能为空"));
+  }
+  let segment = match load_output_segment_by_id(&context, &segment_id) {
+    Ok(Some(segment)) => segment,
+    Ok(None) => return Ok(ApiResponse::error("未找到分段信息")),
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+  if segment.upload_status == "SUCCESS" {
+    return Ok(ApiResponse::success("分段已上传成功".to_string()));
+  }
+  let status = match load_task_status(&context, &segment.task_id) {
+    Ok(status) => status,
+    Err(err) => return Ok(ApiResponse::error(format!("读取任务状态失败: {}", err))),
+  };
+  if status == "UPLOADING" {
+    return Ok(ApiResponse::error("任务正在投稿中，请稍后重试"));
+  }
+
+  let upload_context = UploadContext::new(&state);
+  let auth = match load_auth_or_refresh(&upload_context, "submission_retry_segment").await {
+    Ok(auth) => auth,
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+
+  clear_upload_cancel(&segment.task_id);
+  update_segment_upload_status(&context, &segment_id, "UPLOADING")?;
+  let client = crate::config::build_http_client();
+  let result = upload_segment_with_retry(
+    &context,
+    &upload_context,
+    &client,
+    &auth,
+    &segment_id,
+    upload_context.app_log_path.as_ref(),
+    UPLOAD_SEGMENT_RETRY_LIMIT,
+  )
+  .await;
+
+  match result {
+    Ok(upload_result) => {
+      update_segment_upload_result(
+        &context,
+        &segment_id,
+        "SUCCESS",
+        Some(upload_result.cid),
+        Some(upload_result.filename),
+      )?;
       let remaining = count_incomplete_segments(&context, &segment.task_id)?;
       if remaining == 0 {
         if let Ok(status) = load_task_status(&context, &segment.task_id) {
@@ -2070,15 +3937,485 @@ pub async fn submission_retry_segment_upload(
           }
         }
       }
-      Ok(ApiResponse::success("分段上传成功".to_string()))
+      Ok(ApiResponse::success("分段上传成功".to_string()))
+    }
+    Err(err) => {
+      update_segment_upload_status(&context, &segment_id, "FAILED")?;
+      Ok(ApiResponse::error(err))
+    }
+  }
+}
+
+#[tauri::command]
+pub fn submission_set_segment_result(
+  state: State<'_, AppState>,
+  segment_id: String,
+  cid: i64,
+  filename: String,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(&state);
+  let segment_id = segment_id.trim().to_string();
+  if segment_id.is_empty() {
+    return Ok(ApiResponse::error("分段ID不能为空"));
+  }
+  if cid <= 0 {
+    return Ok(ApiResponse::error("CID无效"));
+  }
+  let filename = filename.trim().to_string();
+  if filename.is_empty() {
+    return Ok(ApiResponse::error("文件名不能为空"));
+  }
+  let segment = match load_output_segment_by_id(&context, &segment_id) {
+    Ok(Some(segment)) => segment,
+    Ok(None) => return Ok(ApiResponse::error("未找到分段信息")),
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+  if is_segment_upload_active(&segment_id) {
+    return Ok(ApiResponse::error("分段正在上传中，无法手动设置结果"));
+  }
+  let status = match load_task_status(&context, &segment.task_id) {
+    Ok(status) => status,
+    Err(err) => return Ok(ApiResponse::error(format!("读取任务状态失败: {}", err))),
+  };
+  if status == "UPLOADING" {
+    return Ok(ApiResponse::error("任务正在投稿中，请稍后重试"));
+  }
+
+  update_segment_upload_result(
+    &context,
+    &segment_id,
+    "SUCCESS",
+    Some(cid),
+    Some(filename.clone()),
+  )?;
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "submission_segment_result_set task_id={} segment_id={} cid={} filename={} prev_status={}",
+      segment.task_id, segment_id, cid, filename, segment.upload_status
+    ),
+  );
+  let remaining = count_incomplete_segments(&context, &segment.task_id)?;
+  if remaining == 0 && status == "FAILED" {
+    update_submission_status(&context, &segment.task_id, "WAITING_UPLOAD")?;
+  }
+  Ok(ApiResponse::success("分段结果已更新".to_string()))
+}
+
+#[tauri::command]
+pub async fn submission_merged_hash(
+  state: State<'_, AppState>,
+  task_id: String,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(&state);
+  let merged = match load_latest_merged_video(&context, &task_id) {
+    Ok(Some(merged)) => merged,
+    Ok(None) => return Ok(ApiResponse::error("未找到合并视频")),
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+  let video_path = merged.video_path.clone().unwrap_or_default();
+  if video_path.trim().is_empty() || !Path::new(&video_path).exists() {
+    return Ok(ApiResponse::error("合并视频文件不存在"));
+  }
+
+  let hash_path = PathBuf::from(&video_path);
+  let result = tauri::async_runtime::spawn_blocking(move || sha256_file(&hash_path))
+    .await
+    .map_err(|_| "计算文件哈希失败".to_string())?;
+  let hash = match result {
+    Ok(hash) => hash,
+    Err(err) => {
+      append_log(
+        &state.app_log_path,
+        &format!(
+          "submission_merged_hash_fail task_id={} path={} err={}",
+          task_id, video_path, err
+        ),
+      );
+      return Ok(ApiResponse::error(err));
+    }
+  };
+
+  let now = now_rfc3339();
+  context
+    .db
+    .with_conn(|conn| {
+      conn.execute(
+        "UPDATE merged_video SET content_hash = ?1, update_time = ?2 WHERE id = ?3",
+        (&hash, &now, merged.id),
+      )?;
+      Ok(())
+    })
+    .map_err(|err| err.to_string())?;
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "submission_merged_hash task_id={} merged_id={} path={} sha256={}",
+      task_id, merged.id, video_path, hash
+    ),
+  );
+  Ok(ApiResponse::success(hash))
+}
+
+#[tauri::command]
+pub fn submission_clean_temp(
+  state: State<'_, AppState>,
+  task_id: String,
+) -> ApiResponse<SubmissionCleanTempResult> {
+  let context = SubmissionContext::new(&state);
+  let task_id = task_id.trim().to_string();
+  if task_id.is_empty() {
+    return ApiResponse::error("任务ID不能为空");
+  }
+  let status = match load_task_status(&context, &task_id) {
+    Ok(status) => status,
+    Err(err) => return ApiResponse::error(format!("读取任务状态失败: {}", err)),
+  };
+  if matches!(
+    status.as_str(),
+    "CLIPPING" | "MERGING" | "SEGMENTING" | "UPLOADING"
+  ) {
+    return ApiResponse::error("任务处理中，暂不支持清理临时文件");
+  }
+  let referenced = match load_task_referenced_paths(&context, &task_id) {
+    Ok(paths) => paths,
+    Err(err) => return ApiResponse::error(format!("读取任务文件记录失败: {}", err)),
+  };
+  let base_dir = resolve_submission_base_dir(&context, &task_id);
+  let mut candidates = Vec::new();
+  collect_temp_artifacts(&base_dir, &referenced, &mut candidates);
+
+  let mut result = SubmissionCleanTempResult {
+    freed_bytes: 0,
+    removed_files: Vec::new(),
+  };
+  for (path, size) in candidates {
+    let path_str = path.to_string_lossy().to_string();
+    match fs::remove_file(&path) {
+      Ok(()) => {
+        append_log(
+          &state.app_log_path,
+          &format!(
+            "submission_clean_temp_remove task_id={} path={} size={}",
+            task_id, path_str, size
+          ),
+        );
+        result.freed_bytes += size;
+        result.removed_files.push(path_str);
+      }
+      Err(err) => {
+        append_log(
+          &state.app_log_path,
+          &format!(
+            "submission_clean_temp_fail task_id={} path={} err={}",
+            task_id, path_str, err
+          ),
+        );
+      }
+    }
+  }
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "submission_clean_temp_done task_id={} removed={} freed_bytes={}",
+      task_id,
+      result.removed_files.len(),
+      result.freed_bytes
+    ),
+  );
+  ApiResponse::success(result)
+}
+
+/// 统计任务占用空间：任务目录下的 cut/merge/output（其余子目录计入 other），
+/// 以及任务引用的源视频和下载文件；不存在的目录或文件按 0 计并标记 missing。
+#[tauri::command]
+pub fn submission_disk_usage(
+  state: State<'_, AppState>,
+  task_id: String,
+) -> ApiResponse<SubmissionDiskUsage> {
+  let context = SubmissionContext::new(&state);
+  let task_id = task_id.trim().to_string();
+  if task_id.is_empty() {
+    return ApiResponse::error("任务ID不能为空");
+  }
+  if let Err(err) = load_task_status(&context, &task_id) {
+    return ApiResponse::error(format!("读取任务失败: {}", err));
+  }
+  let external_files = match load_task_external_files(&context, &task_id) {
+    Ok(files) => files,
+    Err(err) => return ApiResponse::error(format!("读取任务文件记录失败: {}", err)),
+  };
+  let base_dir = resolve_submission_base_dir(&context, &task_id);
+  let mut entries = Vec::new();
+  let folders = ["cut", "merge", "output"];
+  for folder in folders {
+    let path = base_dir.join(folder);
+    let (bytes, file_count) = directory_size(&path);
+    entries.push(SubmissionDiskUsageEntry {
+      category: folder.to_string(),
+      path: path.to_string_lossy().to_string(),
+      bytes,
+      file_count,
+      missing: !path.exists(),
+    });
+  }
+  let mut other_bytes = 0;
+  let mut other_count = 0;
+  if let Ok(children) = fs::read_dir(&base_dir) {
+    for child in children.flatten() {
+      let name = child.file_name();
+      if folders.iter().any(|folder| name == *folder) {
+        continue;
+      }
+      let (bytes, file_count) = directory_size(&child.path());
+      other_bytes += bytes;
+      other_count += file_count;
+    }
+  }
+  entries.push(SubmissionDiskUsageEntry {
+    category: "other".to_string(),
+    path: base_dir.to_string_lossy().to_string(),
+    bytes: other_bytes,
+    file_count: other_count,
+    missing: !base_dir.exists(),
+  });
+  let mut seen = HashSet::new();
+  for (category, path) in external_files {
+    // 已计入任务目录的文件不重复统计
+    if path.starts_with(&base_dir) || !seen.insert(path.clone()) {
+      continue;
+    }
+    let metadata = fs::metadata(&path).ok();
+    entries.push(SubmissionDiskUsageEntry {
+      category: category.to_string(),
+      path: path.to_string_lossy().to_string(),
+      bytes: metadata.as_ref().map(|metadata| metadata.len()).unwrap_or(0),
+      file_count: usize::from(metadata.is_some()),
+      missing: metadata.is_none(),
+    });
+  }
+  let total_bytes = entries.iter().map(|entry| entry.bytes).sum();
+  ApiResponse::success(SubmissionDiskUsage {
+    task_id,
+    base_dir: base_dir.to_string_lossy().to_string(),
+    total_bytes,
+    entries,
+  })
+}
+
+fn load_task_external_files(
+  context: &SubmissionContext,
+  task_id: &str,
+) -> Result<Vec<(&'static str, PathBuf)>, String> {
+  let queries = [
+    (
+      "source",
+      "SELECT source_file_path FROM task_source_video WHERE task_id = ?1 ORDER BY sort_order",
+    ),
+    (
+      "download",
+      "SELECT vd.local_path FROM task_relations tr \
+       JOIN video_download vd ON tr.download_task_id = vd.id \
+       WHERE tr.submission_task_id = ?1",
+    ),
+  ];
+  context
+    .db
+    .with_conn(|conn| {
+      let mut files = Vec::new();
+      for (category, sql) in queries {
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map([task_id], |row| row.get::<_, Option<String>>(0))?;
+        for value in rows {
+          if let Some(value) = value? {
+            let trimmed = value.trim();
+            if !trimmed.is_empty() {
+              files.push((category, PathBuf::from(trimmed)));
+            }
+          }
+        }
+      }
+      Ok(files)
+    })
+    .map_err(|err| err.to_string())
+}
+
+/// 递归统计目录大小，返回（字节数, 文件数）；路径不存在时返回 0。
+fn directory_size(path: &Path) -> (u64, usize) {
+  let Ok(metadata) = fs::symlink_metadata(path) else {
+    return (0, 0);
+  };
+  if metadata.is_file() {
+    return (metadata.len(), 1);
+  }
+  if !metadata.is_dir() {
+    return (0, 0);
+  }
+  let Ok(entries) = fs::read_dir(path) else {
+    return (0, 0);
+  };
+  entries.flatten().fold((0, 0), |(bytes, count), entry| {
+    let (child_bytes, child_count) = directory_size(&entry.path());
+    (bytes + child_bytes, count + child_count)
+  })
+}
+
+/// 按传入顺序重写源视频的 sort_order（从 1 开始），列表必须完整覆盖该任务的全部源视频。
+#[tauri::command]
+pub fn submission_reorder_sources(
+  state: State<'_, AppState>,
+  task_id: String,
+  ordered_source_ids: Vec<String>,
+) -> ApiResponse<String> {
+  let context = SubmissionContext::new(&state);
+  let task_id = task_id.trim().to_string();
+  if task_id.is_empty() {
+    return ApiResponse::error("任务ID不能为空");
+  }
+  let status = match load_task_status(&context, &task_id) {
+    Ok(status) => status,
+    Err(err) => return ApiResponse::error(format!("读取任务状态失败: {}", err)),
+  };
+  if matches!(
+    status.as_str(),
+    "CLIPPING" | "MERGING" | "SEGMENTING" | "UPLOADING"
+  ) {
+    return ApiResponse::error("任务处理中，暂不支持调整源视频顺序");
+  }
+  let ordered_ids: Vec<String> = ordered_source_ids
+    .iter()
+    .map(|value| value.trim().to_string())
+    .collect();
+  let result = context.db.with_conn_mut(|conn| {
+    let tx = conn.transaction()?;
+    let existing_ids = {
+      let mut stmt = tx.prepare("SELECT id FROM task_source_video WHERE task_id = ?1")?;
+      let rows = stmt.query_map([task_id.as_str()], |row| row.get::<_, String>(0))?;
+      rows.collect::<Result<HashSet<_>, _>>()?
+    };
+    let mut seen = HashSet::new();
+    for source_id in &ordered_ids {
+      if !existing_ids.contains(source_id) {
+        return Ok(Err(format!("源视频不属于该任务: {}", source_id)));
+      }
+      if !seen.insert(source_id.as_str()) {
+        return Ok(Err(format!("源视频重复: {}", source_id)));
+      }
+    }
+    if seen.len() != existing_ids.len() {
+      return Ok(Err(format!(
+        "源视频列表不完整，需包含全部 {} 个源视频",
+        existing_ids.len()
+      )));
+    }
+    for (index, source_id) in ordered_ids.iter().enumerate() {
+      tx.execute(
+        "UPDATE task_source_video SET sort_order = ?1 WHERE id = ?2 AND task_id = ?3",
+        (index as i64 + 1, source_id, task_id.as_str()),
+      )?;
+    }
+    tx.execute(
+      "UPDATE submission_task SET updated_at = ?1 WHERE task_id = ?2",
+      (now_rfc3339(), task_id.as_str()),
+    )?;
+    tx.commit()?;
+    Ok(Ok(()))
+  });
+  match result {
+    Ok(Ok(())) => {
+      append_log(
+        &state.app_log_path,
+        &format!(
+          "submission_reorder_sources task_id={} count={}",
+          task_id,
+          ordered_ids.len()
+        ),
+      );
+      ApiResponse::success("源视频顺序已更新".to_string())
+    }
+    Ok(Err(err)) => ApiResponse::error(err),
+    Err(err) => ApiResponse::error(format!("调整源视频顺序失败: {}", err)),
+  }
+}
+
+fn load_task_referenced_paths(
+  context: &SubmissionContext,
+  task_id: &str,
+) -> Result<HashSet<PathBuf>, String> {
+  let queries = [
+    "SELECT source_file_path FROM task_source_video WHERE task_id = ?1",
+    "SELECT clip_path FROM video_clip WHERE task_id = ?1",
+    "SELECT video_path FROM merged_video WHERE task_id = ?1",
+    "SELECT segment_file_path FROM task_output_segment WHERE task_id = ?1",
+    "SELECT segment_file_path FROM edit_upload_segment WHERE task_id = ?1",
+    "SELECT cover_url FROM submission_task WHERE task_id = ?1",
+  ];
+  context
+    .db
+    .with_conn(|conn| {
+      let mut paths = HashSet::new();
+      for sql in queries {
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map([task_id], |row| row.get::<_, Option<String>>(0))?;
+        for value in rows {
+          if let Some(value) = value? {
+            let trimmed = value.trim();
+            if !trimmed.is_empty() {
+              paths.insert(PathBuf::from(trimmed));
+            }
+          }
+        }
+      }
+      Ok(paths)
+    })
+    .map_err(|err| err.to_string())
+}
+
+// 只收集 concat 列表和零字节的剪辑/合并残留，源文件与产物由 DB 记录保护
+fn collect_temp_artifacts(
+  dir: &Path,
+  referenced: &HashSet<PathBuf>,
+  output: &mut Vec<(PathBuf, u64)>,
+) {
+  let entries = match fs::read_dir(dir) {
+    Ok(entries) => entries,
+    Err(_) => return,
+  };
+  for entry in entries.flatten() {
+    let path = entry.path();
+    let metadata = match entry.metadata() {
+      Ok(metadata) => metadata,
+      Err(_) => continue,
+    };
+    if metadata.is_dir() {
+      collect_temp_artifacts(&path, referenced, output);
+      continue;
     }
-    Err(err) => {
-      update_segment_upload_status(&context, &segment_id, "FAILED")?;
-      Ok(ApiResponse::error(err))
+    if referenced.contains(&path) {
+      continue;
+    }
+    let ext = path
+      .extension()
+      .and_then(|value| value.to_str())
+      .map(|value| value.to_ascii_lowercase())
+      .unwrap_or_default();
+    let is_concat_list = ext == "txt" && is_concat_list_file(&path);
+    let is_empty_media =
+      metadata.len() == 0 && SOURCE_DIRECTORY_VIDEO_EXTENSIONS.contains(&ext.as_str());
+    if is_concat_list || is_empty_media {
+      output.push((path, metadata.len()));
     }
   }
 }
 
+fn is_concat_list_file(path: &Path) -> bool {
+  let Ok(content) = fs::read_to_string(path) else {
+    return false;
+  };
+  let mut lines = content.lines().filter(|line| !line.trim().is_empty()).peekable();
+  lines.peek().is_some() && lines.all(|line| line.trim_start().starts_with("file "))
+}
+
 #[tauri::command]
 pub fn workflow_status(
   state: State<'_, AppState>,
@@ -2091,6 +4428,18 @@ pub fn workflow_status(
   }
 }
 
+#[tauri::command]
+pub fn workflow_logs(
+  state: State<'_, AppState>,
+  task_id: String,
+) -> ApiResponse<Vec<WorkflowLogEntry>> {
+  let context = SubmissionContext::new(&state);
+  match load_workflow_logs(&context, &task_id) {
+    Ok(logs) => ApiResponse::success(logs),
+    Err(err) => ApiResponse::error(format!("Failed to load workflow logs: {}", err)),
+  }
+}
+
 #[tauri::command]
 pub fn workflow_pause(state: State<'_, AppState>, task_id: String) -> ApiResponse<String> {
   let context = SubmissionContext::new(&state);
@@ -2139,26 +4488,319 @@ pub fn workflow_cancel(state: State<'_, AppState>, task_id: String) -> ApiRespon
   }
 }
 
-fn load_tasks(
-  context: &SubmissionContext,
-  status: Option<String>,
-  page: i64,
-  page_size: i64,
-) -> Result<PaginatedSubmissionTasks, String> {
-  context
-    .db
+#[tauri::command]
+pub fn submission_video_types() -> ApiResponse<Vec<SubmissionVideoType>> {
+  ApiResponse::success(supported_video_types())
+}
+
+fn supported_video_types() -> Vec<SubmissionVideoType> {
+  vec![
+    SubmissionVideoType {
+      value: "ORIGINAL".to_string(),
+      name: "原创".to_string(),
+      copyright: 1,
+    },
+    // 转载来源由分区规则要求写在简介中，投稿时不单独提交来源字段
+    SubmissionVideoType {
+      value: "REPOST".to_string(),
+      name: "转载".to_string(),
+      copyright: 2,
+    },
+  ]
+}
+
+fn resolve_video_type_copyright(video_type: &str) -> i64 {
+  supported_video_types()
+    .into_iter()
+    .find(|item| item.value == video_type)
+    .map(|item| item.copyright)
+    .unwrap_or(2)
+}
+
+#[tauri::command]
+pub fn submission_partition_rules(
+  state: State<'_, AppState>,
+) -> ApiResponse<Vec<SubmissionPartitionRule>> {
+  ApiResponse::success(load_partition_rules(&state.db))
+}
+
+#[tauri::command]
+pub fn submission_partition_rules_update(
+  state: State<'_, AppState>,
+  rules: Vec<SubmissionPartitionRule>,
+) -> ApiResponse<Vec<SubmissionPartitionRule>> {
+  for rule in &rules {
+    if rule.partition_ids.iter().any(|id| *id <= 0) {
+      return ApiResponse::error("分区规则包含无效的分区ID");
+    }
+    if rule.min_tags > SUBMISSION_MAX_TAGS {
+      return ApiResponse::error(format!("分区规则标签数量不能超过 {}", SUBMISSION_MAX_TAGS));
+    }
+  }
+  let value = match serde_json::to_string(&rules) {
+    Ok(value) => value,
+    Err(err) => return ApiResponse::error(format!("序列化分区规则失败: {}", err)),
+  };
+  let now = now_rfc3339();
+  let result = state.db.with_conn(|conn| {
+    upsert_setting(conn, PARTITION_RULES_SETTING_KEY, &value, &now)?;
+    Ok(())
+  });
+  if let Err(err) = result {
+    return ApiResponse::error(format!("保存分区规则失败: {}", err));
+  }
+  append_log(
+    &state.app_log_path,
+    &format!("submission_partition_rules_update count={}", rules.len()),
+  );
+  ApiResponse::success(rules)
+}
+
+fn default_partition_rules() -> Vec<SubmissionPartitionRule> {
+  vec![
+    SubmissionPartitionRule {
+      partition_ids: Vec::new(),
+      video_types: Vec::new(),
+      min_tags: 1,
+      require_description: false,
+      message: None,
+    },
+    SubmissionPartitionRule {
+      partition_ids: Vec::new(),
+      video_types: vec!["REPOST".to_string()],
+      min_tags: 0,
+      require_description: true,
+      message: Some("转载视频需在简介中注明转载来源".to_string()),
+    },
+    SubmissionPartitionRule {
+      partition_ids: vec![17, 19, 65, 121, 136, 171, 172, 173],
+      video_types: vec!["REPOST".to_string()],
+      min_tags: 2,
+      require_description: true,
+      message: Some("游戏类分区转载需填写来源简介且至少 2 个标签".to_string()),
+    },
+  ]
+}
+
+fn load_partition_rules(db: &Db) -> Vec<SubmissionPartitionRule> {
+  let value: Option<String> = db
     .with_conn(|conn| {
-      let total = if status.is_some() {
-        conn.query_row(
-          "SELECT COUNT(*) FROM submission_task WHERE status = ?1",
-          [status.clone().unwrap_or_default()],
+      conn
+        .query_row(
+          "SELECT value FROM app_settings WHERE key = ?1",
+          [PARTITION_RULES_SETTING_KEY],
           |row| row.get(0),
-        )?
-      } else {
-        conn.query_row("SELECT COUNT(*) FROM submission_task", [], |row| row.get(0))?
-      };
-      let offset = (page - 1).saturating_mul(page_size);
-      let order_by = "ORDER BY \
+        )
+        .optional()
+    })
+    .ok()
+    .flatten();
+  value
+    .and_then(|value| serde_json::from_str::<Vec<SubmissionPartitionRule>>(&value).ok())
+    .unwrap_or_else(default_partition_rules)
+}
+
+fn validate_scheduled_publish_at(scheduled_publish_at: i64) -> Result<(), String> {
+  let now = Utc::now().timestamp();
+  if scheduled_publish_at < now + SCHEDULED_PUBLISH_MIN_LEAD_SECS {
+    return Err("定时发布时间需晚于当前时间 2 小时".to_string());
+  }
+  if scheduled_publish_at > now + SCHEDULED_PUBLISH_MAX_LEAD_SECS {
+    return Err("定时发布时间不能晚于 15 天后".to_string());
+  }
+  Ok(())
+}
+
+/// 提交时使用的定时发布时间：上传耗时导致不足 2 小时的顺延到最早允许时间并留出余量，超过 15 天仍报错。
+fn resolve_submit_publish_at(scheduled_publish_at: i64) -> Result<i64, String> {
+  let now = Utc::now().timestamp();
+  if scheduled_publish_at > now + SCHEDULED_PUBLISH_MAX_LEAD_SECS {
+    return Err("定时发布时间不能晚于 15 天后".to_string());
+  }
+  if scheduled_publish_at < now + SCHEDULED_PUBLISH_MIN_LEAD_SECS {
+    return Ok(now + SCHEDULED_PUBLISH_MIN_LEAD_SECS + SCHEDULED_PUBLISH_CLAMP_MARGIN_SECS);
+  }
+  Ok(scheduled_publish_at)
+}
+
+struct NormalizedTags {
+  tags: Vec<String>,
+  dropped: Vec<String>,
+  truncated: Vec<String>,
+}
+
+impl NormalizedTags {
+  fn joined(&self) -> String {
+    self.tags.join(",")
+  }
+
+  /// 有标签被舍弃或截断时返回提示，避免投稿接口整体拒绝。
+  fn violation(&self) -> Option<String> {
+    let mut parts = Vec::new();
+    if !self.dropped.is_empty() {
+      parts.push(format!(
+        "最多 {} 个标签，超出的标签将被舍弃: {}",
+        SUBMISSION_MAX_TAGS,
+        self.dropped.join("、")
+      ));
+    }
+    if !self.truncated.is_empty() {
+      parts.push(format!(
+        "单个标签不能超过 {} 个字符: {}",
+        SUBMISSION_MAX_TAG_CHARS,
+        self.truncated.join("、")
+      ));
+    }
+    if parts.is_empty() {
+      None
+    } else {
+      Some(format!("投稿标签不符合要求，{}", parts.join("；")))
+    }
+  }
+}
+
+/// 按逗号拆分标签，去除空白与重复项（忽略大小写），并按B站的数量与长度上限处理。
+fn normalize_tags(raw: &str) -> NormalizedTags {
+  let mut seen = HashSet::new();
+  let mut result = NormalizedTags {
+    tags: Vec::new(),
+    dropped: Vec::new(),
+    truncated: Vec::new(),
+  };
+  for tag in raw.split([',', '，']).map(|tag| tag.trim()) {
+    if tag.is_empty() || !seen.insert(tag.to_lowercase()) {
+      continue;
+    }
+    if result.tags.len() >= SUBMISSION_MAX_TAGS {
+      result.dropped.push(tag.to_string());
+      continue;
+    }
+    if tag.chars().count() > SUBMISSION_MAX_TAG_CHARS {
+      result.truncated.push(tag.to_string());
+      result
+        .tags
+        .push(tag.chars().take(SUBMISSION_MAX_TAG_CHARS).collect());
+      continue;
+    }
+    result.tags.push(tag.to_string());
+  }
+  result
+}
+
+fn count_submission_tags(tags: Option<&str>) -> usize {
+  tags
+    .unwrap_or("")
+    .split([',', '，'])
+    .map(|tag| tag.trim())
+    .filter(|tag| !tag.is_empty())
+    .collect::<HashSet<_>>()
+    .len()
+}
+
+fn validate_partition_rules(
+  db: &Db,
+  partition_id: i64,
+  video_type: &str,
+  tags: Option<&str>,
+  description: Option<&str>,
+) -> Result<(), String> {
+  let tag_count = count_submission_tags(tags);
+  let has_description = description
+    .map(|value| !value.trim().is_empty())
+    .unwrap_or(false);
+  for rule in load_partition_rules(db) {
+    if !rule.partition_ids.is_empty() && !rule.partition_ids.contains(&partition_id) {
+      continue;
+    }
+    if !rule.video_types.is_empty() && !rule.video_types.iter().any(|value| value == video_type) {
+      continue;
+    }
+    let violation = if tag_count < rule.min_tags {
+      Some(format!("当前分区要求至少 {} 个投稿标签", rule.min_tags))
+    } else if rule.require_description && !has_description {
+      Some("当前分区要求填写视频简介".to_string())
+    } else {
+      None
+    };
+    if let Some(violation) = violation {
+      return Err(rule.message.clone().unwrap_or(violation));
+    }
+  }
+  Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum SubmissionListSort {
+  Priority,
+  Created,
+  Updated,
+  Title,
+}
+
+impl SubmissionListSort {
+  fn parse(value: Option<&str>) -> Result<Self, String> {
+    match value.map(|value| value.trim().to_lowercase()).as_deref() {
+      None | Some("") => Ok(Self::Priority),
+      Some("created") => Ok(Self::Created),
+      Some("updated") => Ok(Self::Updated),
+      Some("title") => Ok(Self::Title),
+      Some(other) => Err(format!("不支持的排序方式: {}", other)),
+    }
+  }
+}
+
+fn escape_like_pattern(value: &str) -> String {
+  let mut escaped = String::with_capacity(value.len() + 2);
+  escaped.push('%');
+  for ch in value.chars() {
+    if matches!(ch, '%' | '_' | '\\') {
+      escaped.push('\\');
+    }
+    escaped.push(ch);
+  }
+  escaped.push('%');
+  escaped
+}
+
+fn build_task_list_filter(
+  status: Option<String>,
+  query: Option<String>,
+) -> (String, Vec<rusqlite::types::Value>) {
+  let query = query
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty());
+  let mut conditions = Vec::new();
+  let mut params: Vec<rusqlite::types::Value> = Vec::new();
+  if let Some(status) = status {
+    params.push(status.into());
+    conditions.push(format!("st.status = ?{}", params.len()));
+  }
+  if let Some(query) = query {
+    params.push(escape_like_pattern(&query).into());
+    let index = params.len();
+    conditions.push(format!(
+      "(st.title LIKE ?{index} ESCAPE '\\' OR st.bvid LIKE ?{index} ESCAPE '\\' \
+       OR st.tags LIKE ?{index} ESCAPE '\\')"
+    ));
+  }
+  let where_clause = if conditions.is_empty() {
+    String::new()
+  } else {
+    format!("WHERE {}", conditions.join(" AND "))
+  };
+  (where_clause, params)
+}
+
+fn build_task_count_sql(where_clause: &str) -> String {
+  format!("SELECT COUNT(*) FROM submission_task st {}", where_clause)
+}
+
+fn build_task_list_sql(where_clause: &str, sort: SubmissionListSort, param_count: usize) -> String {
+  let order_by = match sort {
+    SubmissionListSort::Created => "ORDER BY st.created_at DESC",
+    SubmissionListSort::Updated => "ORDER BY st.updated_at DESC, st.created_at DESC",
+    SubmissionListSort::Title => "ORDER BY st.title COLLATE NOCASE ASC, st.created_at DESC",
+    SubmissionListSort::Priority => "ORDER BY \
         CASE \
           WHEN st.status <> 'COMPLETED' THEN 0 \
           WHEN st.status = 'COMPLETED' AND (wi.status IS NULL OR wi.status <> 'COMPLETED') THEN 1 \
@@ -2185,35 +4827,52 @@ fn load_tasks(
             END \
           ELSE 9 \
         END, \
-        st.created_at DESC";
-      let sql = if status.is_some() {
-        format!(
-          "SELECT st.task_id, st.status, st.title, st.description, st.cover_url, st.partition_id, st.tags, st.video_type, st.collection_id, st.bvid, st.aid, st.remote_state, st.reject_reason, st.created_at, st.updated_at, st.segment_prefix, st.baidu_sync_enabled, st.baidu_sync_path, st.baidu_sync_filename, \
-                  CASE WHEN EXISTS (SELECT 1 FROM task_relations tr WHERE tr.submission_task_id = st.task_id) THEN 1 ELSE 0 END, \
-                  wi.status, wi.current_step, wi.progress \
-           FROM submission_task st \
-           LEFT JOIN workflow_instances wi ON wi.task_id = st.task_id \
-           WHERE st.status = ?1 {} LIMIT ?2 OFFSET ?3",
-          order_by
-        )
-      } else {
-        format!(
-          "SELECT st.task_id, st.status, st.title, st.description, st.cover_url, st.partition_id, st.tags, st.video_type, st.collection_id, st.bvid, st.aid, st.remote_state, st.reject_reason, st.created_at, st.updated_at, st.segment_prefix, st.baidu_sync_enabled, st.baidu_sync_path, st.baidu_sync_filename, \
-                  CASE WHEN EXISTS (SELECT 1 FROM task_relations tr WHERE tr.submission_task_id = st.task_id) THEN 1 ELSE 0 END, \
-                  wi.status, wi.current_step, wi.progress \
-           FROM submission_task st \
-           LEFT JOIN workflow_instances wi ON wi.task_id = st.task_id \
-           {} LIMIT ?1 OFFSET ?2",
-          order_by
-        )
-      };
+        st.created_at DESC",
+  };
+  format!(
+    "SELECT st.task_id, st.status, st.title, st.description, st.cover_url, st.partition_id, st.tags, st.video_type, st.collection_id, st.bvid, st.aid, st.remote_state, st.reject_reason, st.created_at, st.updated_at, st.segment_prefix, st.baidu_sync_enabled, st.baidu_sync_path, st.baidu_sync_filename, \
+            CASE WHEN EXISTS (SELECT 1 FROM task_relations tr WHERE tr.submission_task_id = st.task_id) THEN 1 ELSE 0 END, \
+            wi.status, wi.current_step, wi.progress, st.scheduled_publish_at, \
+            COALESCE(st.upload_failure_count, 0), st.last_upload_error, COALESCE(st.upload_paused, 0) \
+     FROM submission_task st \
+     LEFT JOIN workflow_instances wi ON wi.task_id = st.task_id \
+     {} {} LIMIT ?{} OFFSET ?{}",
+    where_clause,
+    order_by,
+    param_count + 1,
+    param_count + 2
+  )
+}
+
+fn load_tasks(
+  context: &SubmissionContext,
+  status: Option<String>,
+  query: Option<String>,
+  sort: Option<String>,
+  page: i64,
+  page_size: i64,
+) -> Result<PaginatedSubmissionTasks, String> {
+  let sort = SubmissionListSort::parse(sort.as_deref())?;
+  let (where_clause, params) = build_task_list_filter(status, query);
+  context
+    .db
+    .with_conn(|conn| {
+      let total = conn.query_row(
+        &build_task_count_sql(&where_clause),
+        rusqlite::params_from_iter(params.iter()),
+        |row| row.get(0),
+      )?;
+      let offset = (page - 1).saturating_mul(page_size);
+      let sql = build_task_list_sql(&where_clause, sort, params.len());
 
       let mut stmt = conn.prepare(&sql)?;
-      let rows = if let Some(status) = status {
-        stmt.query_map((status, page_size, offset), map_submission_task)?
-      } else {
-        stmt.query_map((page_size, offset), map_submission_task)?
-      };
+      let mut query_params = params.clone();
+      query_params.push(page_size.into());
+      query_params.push(offset.into());
+      let rows = stmt.query_map(
+        rusqlite::params_from_iter(query_params.iter()),
+        map_submission_task,
+      )?;
 
       let list = rows.collect::<Result<Vec<_>, _>>()?;
       Ok(PaginatedSubmissionTasks {
@@ -2237,6 +4896,10 @@ fn map_submission_task(row: &rusqlite::Row<'_>) -> rusqlite::Result<SubmissionTa
     progress: workflow_progress.unwrap_or(0.0),
   });
 
+  let remote_state: Option<i64> = row.get(11)?;
+  let remote_state_label = remote_state
+    .map(|code| RemoteAuditState::from_code(code).label().to_string());
+
   Ok(SubmissionTaskRecord {
     task_id: row.get(0)?,
     status: row.get(1)?,
@@ -2249,7 +4912,8 @@ fn map_submission_task(row: &rusqlite::Row<'_>) -> rusqlite::Result<SubmissionTa
     collection_id: row.get(8)?,
     bvid: row.get(9)?,
     aid: row.get(10)?,
-    remote_state: row.get(11)?,
+    remote_state,
+    remote_state_label,
     reject_reason: row.get(12)?,
     created_at: row.get(13)?,
     updated_at: row.get(14)?,
@@ -2259,6 +4923,10 @@ fn map_submission_task(row: &rusqlite::Row<'_>) -> rusqlite::Result<SubmissionTa
     baidu_sync_filename: row.get(18)?,
     has_integrated_downloads: has_integrated_downloads != 0,
     workflow_status,
+    scheduled_publish_at: row.get(23)?,
+    upload_failure_count: row.get(24)?,
+    last_upload_error: row.get(25)?,
+    upload_paused: row.get::<_, i64>(26)? != 0,
   })
 }
 
@@ -2272,7 +4940,8 @@ fn load_task_detail(
       let task = conn.query_row(
         "SELECT st.task_id, st.status, st.title, st.description, st.cover_url, st.partition_id, st.tags, st.video_type, st.collection_id, st.bvid, st.aid, st.remote_state, st.reject_reason, st.created_at, st.updated_at, st.segment_prefix, st.baidu_sync_enabled, st.baidu_sync_path, st.baidu_sync_filename, \
                 CASE WHEN EXISTS (SELECT 1 FROM task_relations tr WHERE tr.submission_task_id = st.task_id) THEN 1 ELSE 0 END, \
-                wi.status, wi.current_step, wi.progress \
+                wi.status, wi.current_step, wi.progress, st.scheduled_publish_at, \
+                COALESCE(st.upload_failure_count, 0), st.last_upload_error, COALESCE(st.upload_paused, 0) \
          FROM submission_task st \
          LEFT JOIN workflow_instances wi ON wi.task_id = st.task_id \
          WHERE st.task_id = ?1",
@@ -2280,26 +4949,13 @@ fn load_task_detail(
         map_submission_task,
       )?;
 
-      let mut source_stmt = conn.prepare(
-        "SELECT id, task_id, source_file_path, sort_order, start_time, end_time FROM task_source_video WHERE task_id = ?1 ORDER BY sort_order ASC",
-      )?;
-      let source_videos = source_stmt
-        .query_map([task_id], |row| {
-          Ok(TaskSourceVideoRecord {
-            id: row.get(0)?,
-            task_id: row.get(1)?,
-            source_file_path: row.get(2)?,
-            sort_order: row.get(3)?,
-            start_time: row.get(4)?,
-            end_time: row.get(5)?,
-          })
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
+      let source_videos = query_task_source_records(conn, task_id)?;
 
       let mut segment_stmt = conn.prepare(
         "SELECT segment_id, task_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, \
                 upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, \
-                upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index \
+                upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index, \
+                upload_speed_bps, upload_eta_seconds \
          FROM task_output_segment WHERE task_id = ?1 ORDER BY part_order ASC",
       )?;
       let output_segments = segment_stmt
@@ -2323,6 +4979,8 @@ fn load_task_detail(
             upload_uri: row.get(15)?,
             upload_chunk_size: row.get(16)?,
             upload_last_part_index: row.get(17)?,
+            upload_speed_bps: row.get(18)?,
+            upload_eta_seconds: row.get(19)?,
           })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -2331,7 +4989,7 @@ fn load_task_detail(
         "SELECT id, task_id, file_name, video_path, duration, status, \
                 upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_cid, upload_file_name, \
                 upload_session_id, upload_biz_id, upload_endpoint, upload_auth, upload_uri, upload_chunk_size, \
-                upload_last_part_index, create_time, update_time \
+                upload_last_part_index, create_time, update_time, content_hash \
          FROM merged_video WHERE task_id = ?1 ORDER BY id DESC",
       )?;
       let merged_videos = merged_stmt
@@ -2355,6 +5013,7 @@ fn load_task_detail(
             upload_uri: row.get(15)?,
             upload_chunk_size: row.get(16)?,
             upload_last_part_index: row.get(17)?,
+            content_hash: row.get(20)?,
             create_time: row.get(18)?,
             update_time: row.get(19)?,
           })
@@ -2390,6 +5049,8 @@ pub fn create_workflow_instance_for_task_with_type(
   config: &Value,
   workflow_type: &str,
 ) -> Result<(String, String), String> {
+  parse_encoder_args(config)?;
+  parse_output_container(config)?;
   let config_json = serde_json::to_string(config).map_err(|err| err.to_string())?;
   let now = now_rfc3339();
   let instance_id = uuid::Uuid::new_v4().to_string();
@@ -2436,10 +5097,164 @@ const SOURCE_READY_STABLE_DELAY_SECS: u64 = 2;
 const SOURCE_READY_MAX_RETRIES: u32 = 30;
 const SOURCE_READY_MAX_WAIT_SECS: u64 = 30;
 
+const SOURCE_DIRECTORY_VIDEO_EXTENSIONS: [&str; 8] =
+  ["mp4", "flv", "mkv", "mov", "ts", "m4v", "avi", "webm"];
+
 struct SourceReadyInfo {
   source: ClipSource,
   path: String,
   size: u64,
+  db_order: Option<i64>,
+}
+
+struct ExpandedSource {
+  source: ClipSource,
+  db_order: Option<i64>,
+}
+
+fn is_source_glob_pattern(value: &str) -> bool {
+  value.contains('*') || value.contains('?')
+}
+
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+  let (mut p, mut n) = (0usize, 0usize);
+  let mut star: Option<usize> = None;
+  let mut star_n = 0usize;
+  while n < name.len() {
+    if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+      p += 1;
+      n += 1;
+    } else if p < pattern.len() && pattern[p] == '*' {
+      star = Some(p);
+      star_n = n;
+      p += 1;
+    } else if let Some(star_p) = star {
+      p = star_p + 1;
+      star_n += 1;
+      n = star_n;
+    } else {
+      return false;
+    }
+  }
+  while p < pattern.len() && pattern[p] == '*' {
+    p += 1;
+  }
+  p == pattern.len()
+}
+
+fn list_source_directory(dir: &Path) -> Result<Vec<PathBuf>, String> {
+  let entries = fs::read_dir(dir)
+    .map_err(|err| format!("读取源目录失败 input={} err={}", dir.to_string_lossy(), err))?;
+  let mut files = Vec::new();
+  for entry in entries.flatten() {
+    let path = entry.path();
+    if !path.is_file() {
+      continue;
+    }
+    let is_video = path
+      .extension()
+      .and_then(|value| value.to_str())
+      .map(|ext| {
+        SOURCE_DIRECTORY_VIDEO_EXTENSIONS
+          .iter()
+          .any(|item| ext.eq_ignore_ascii_case(item))
+      })
+      .unwrap_or(false);
+    if is_video {
+      files.push(path);
+    }
+  }
+  files.sort();
+  Ok(files)
+}
+
+fn list_source_glob(pattern: &str) -> Result<Vec<PathBuf>, String> {
+  let pattern_path = Path::new(pattern);
+  let parent = pattern_path
+    .parent()
+    .filter(|value| !value.as_os_str().is_empty())
+    .unwrap_or_else(|| Path::new("."));
+  if is_source_glob_pattern(&parent.to_string_lossy()) {
+    return Err(format!("仅支持在文件名中使用通配符 input={}", pattern));
+  }
+  let name_pattern: Vec<char> = pattern_path
+    .file_name()
+    .map(|value| value.to_string_lossy().chars().collect())
+    .unwrap_or_default();
+  let entries = fs::read_dir(parent)
+    .map_err(|err| format!("读取源目录失败 input={} err={}", pattern, err))?;
+  let mut files = Vec::new();
+  for entry in entries.flatten() {
+    let path = entry.path();
+    if !path.is_file() {
+      continue;
+    }
+    let name: Vec<char> = entry.file_name().to_string_lossy().chars().collect();
+    if wildcard_match(&name_pattern, &name) {
+      files.push(path);
+    }
+  }
+  files.sort();
+  Ok(files)
+}
+
+fn expand_source_entries(
+  context: &SubmissionContext,
+  task_id: &str,
+  sources: &[ClipSource],
+) -> Result<Vec<ExpandedSource>, String> {
+  let mut expanded = Vec::with_capacity(sources.len());
+  let mut has_expansion = false;
+  for source in sources {
+    let path = Path::new(&source.input_path);
+    let (mode, files) = if path.is_dir() {
+      ("dir", list_source_directory(path)?)
+    } else if !path.exists() && is_source_glob_pattern(&source.input_path) {
+      ("glob", list_source_glob(&source.input_path)?)
+    } else {
+      expanded.push(ExpandedSource {
+        source: source.clone(),
+        db_order: Some(source.order),
+      });
+      continue;
+    };
+    if files.is_empty() {
+      return Err(format!("源路径未匹配到视频文件 input={}", source.input_path));
+    }
+    for file in &files {
+      fs::File::open(file)
+        .map_err(|err| format!("源文件不可读 input={} err={}", file.to_string_lossy(), err))?;
+    }
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "submission_source_expand task_id={} input={} mode={} count={}",
+        task_id,
+        source.input_path,
+        mode,
+        files.len()
+      ),
+    );
+    has_expansion = true;
+    for file in files {
+      expanded.push(ExpandedSource {
+        source: ClipSource {
+          input_path: file.to_string_lossy().to_string(),
+          start_time: None,
+          end_time: None,
+          order: source.order,
+        },
+        db_order: None,
+      });
+    }
+  }
+  if has_expansion {
+    // 剪辑输出按序号命名，展开后的条目需要重新分配连续且不重复的序号
+    for (index, item) in expanded.iter_mut().enumerate() {
+      item.source.order = index as i64 + 1;
+    }
+  }
+  Ok(expanded)
 }
 
 fn format_timecode_seconds(seconds: f64) -> String {
@@ -2459,8 +5274,9 @@ async fn check_sources_ready(
   task_id: &str,
   sources: &[ClipSource],
 ) -> Result<Vec<ClipSource>, String> {
-  let mut infos = Vec::with_capacity(sources.len());
-  for source in sources {
+  let expanded = expand_source_entries(context, task_id, sources)?;
+  let mut infos = Vec::with_capacity(expanded.len());
+  for ExpandedSource { source, db_order } in expanded {
     let path = Path::new(&source.input_path);
     let metadata =
       fs::metadata(path).map_err(|err| format!("源文件不存在 input={} err={}", source.input_path, err))?;
@@ -2469,9 +5285,10 @@ async fn check_sources_ready(
       return Err(format!("源文件大小为0 input={}", source.input_path));
     }
     infos.push(SourceReadyInfo {
-      source: source.clone(),
       path: source.input_path.clone(),
+      source,
       size,
+      db_order,
     });
   }
 
@@ -2516,12 +5333,15 @@ async fn check_sources_ready(
           ),
         );
         let end_time = format_timecode_seconds(duration);
-        let update_result = context.db.with_conn(|conn| {
-          conn.execute(
-            "UPDATE task_source_video SET end_time = ?1 WHERE task_id = ?2 AND source_file_path = ?3 AND sort_order = ?4",
-            (&end_time, task_id, &info.path, info.source.order),
-          )
-        });
+        let update_result = match info.db_order {
+          Some(db_order) => context.db.with_conn(|conn| {
+            conn.execute(
+              "UPDATE task_source_video SET end_time = ?1 WHERE task_id = ?2 AND source_file_path = ?3 AND sort_order = ?4",
+              (&end_time, task_id, &info.path, db_order),
+            )
+          }),
+          None => Ok(0),
+        };
         if let Err(err) = update_result {
           append_log(
             &context.app_log_path,
@@ -2610,6 +5430,16 @@ async fn run_submission_workflow(
   context: SubmissionContext,
   task_id: String,
 ) -> Result<(), String> {
+  let result = execute_submission_workflow(context.clone(), task_id.clone()).await;
+  notify_submission_terminal(&context, &task_id, result.as_ref().err().map(String::as_str));
+  result
+}
+
+async fn execute_submission_workflow(
+  context: SubmissionContext,
+  task_id: String,
+) -> Result<(), String> {
+  clear_upload_cancel(&task_id);
   let workflow_type = load_latest_workflow_type(&context, &task_id)?
     .unwrap_or_else(|| "VIDEO_SUBMISSION".to_string());
   let is_update_workflow = workflow_type == "VIDEO_UPDATE";
@@ -2627,6 +5457,14 @@ async fn run_submission_workflow(
     update_submission_status(&context, &task_id, "FAILED")?;
     return Err("No source videos".to_string());
   }
+  if let Some(reason) = crate::commands::system::ffmpeg_unavailable_reason() {
+    append_log(
+      &context.app_log_path,
+      &format!("submission_workflow_blocked task_id={} reason={}", task_id, reason),
+    );
+    update_submission_status(&context, &task_id, "FAILED")?;
+    return Err(reason);
+  }
 
   let sources = ensure_sources_ready(&context, &task_id, &sources).await?;
   let _ = wait_for_workflow_ready(&context, &task_id).await?;
@@ -2641,16 +5479,37 @@ async fn run_submission_workflow(
     base_dir.clone()
   };
   let clip_dir = workflow_dir.join("cut");
-  let copy_decision = match decide_clip_copy(&sources) {
-    Ok(decision) => decision,
-    Err(err) => {
-      append_log(
-        &context.app_log_path,
-        &format!("submission_clip_copy_check_err task_id={} err={}", task_id, err),
-      );
-      crate::processing::ClipCopyDecision {
-        use_copy: false,
-        reason: Some(format!("timestamp_probe_failed err={}", err)),
+  let workflow_settings = load_workflow_settings(&context, &task_id);
+  let encoder_args = workflow_settings.encoder_args.clone();
+  let normalize_config = workflow_settings.normalize_before_merge;
+  let copy_decision = if normalize_config.is_some() {
+    crate::processing::ClipCopyDecision {
+      use_copy: false,
+      reason: Some("normalize_before_merge".to_string()),
+    }
+  } else if encoder_args.is_some() {
+    crate::processing::ClipCopyDecision {
+      use_copy: false,
+      reason: Some("encoder_args".to_string()),
+    }
+  } else if workflow_settings.frame_accurate {
+    // 复制流只能在关键帧处切开，精确剪辑需重编码，耗时明显高于复制
+    crate::processing::ClipCopyDecision {
+      use_copy: false,
+      reason: Some("frame_accurate tradeoff=reencode_slower_than_copy".to_string()),
+    }
+  } else {
+    match decide_clip_copy(&sources) {
+      Ok(decision) => decision,
+      Err(err) => {
+        append_log(
+          &context.app_log_path,
+          &format!("submission_clip_copy_check_err task_id={} err={}", task_id, err),
+        );
+        crate::processing::ClipCopyDecision {
+          use_copy: false,
+          reason: Some(format!("timestamp_probe_failed err={}", err)),
+        }
       }
     }
   };
@@ -2664,6 +5523,31 @@ async fn run_submission_workflow(
       ),
     );
   }
+  let output_container = workflow_settings.output_container;
+  if use_copy {
+    // 直接复制流时原始编码会进入最终封装，提前提示不兼容的组合
+    if let Some(source) = sources.first() {
+      let probe_path = PathBuf::from(&source.input_path);
+      let warning = tauri::async_runtime::spawn_blocking(move || {
+        container_stream_warning(output_container, &probe_path)
+      })
+      .await
+      .ok()
+      .and_then(|result| result.ok())
+      .flatten();
+      if let Some(warning) = warning {
+        append_log(
+          &context.app_log_path,
+          &format!(
+            "submission_container_warning task_id={} container={} warning={}",
+            task_id,
+            output_container.extension(),
+            warning
+          ),
+        );
+      }
+    }
+  }
   append_log(
     &context.app_log_path,
     &format!(
@@ -2689,8 +5573,43 @@ async fn run_submission_workflow(
   }
   let sources_clone = sources.clone();
   let clip_dir_clone = clip_dir.clone();
+  let clip_encoder_args = encoder_args.clone();
+  let clip_log_path = context.app_log_path.clone();
+  let clip_task_id = task_id.clone();
+  let clip_concurrency = load_download_settings_from_db(context.db.as_ref())
+    .map(|settings| settings.clip_concurrency)
+    .unwrap_or(DEFAULT_CLIP_CONCURRENCY)
+    .max(1) as usize;
+  let clip_started_at = Instant::now();
   let clip_outputs = match tauri::async_runtime::spawn_blocking(move || {
-    clip_sources(&sources_clone, &clip_dir_clone, use_copy)
+    let normalize_target = match normalize_config {
+      Some(config) => {
+        let target = resolve_normalize_target(&sources_clone, &config)?;
+        append_log(
+          &clip_log_path,
+          &format!(
+            "submission_clip_normalize task_id={} width={} height={} fps={}",
+            clip_task_id, target.width, target.height, target.fps
+          ),
+        );
+        Some(target)
+      }
+      None => None,
+    };
+    clip_sources(
+      &sources_clone,
+      &clip_dir_clone,
+      use_copy,
+      clip_encoder_args.as_deref(),
+      normalize_target.as_ref(),
+      clip_concurrency,
+      |command| {
+        append_log(
+          &clip_log_path,
+          &format!("submission_clip_command task_id={} args={}", clip_task_id, command),
+        );
+      },
+    )
   })
   .await
   {
@@ -2710,13 +5629,15 @@ async fn run_submission_workflow(
       return Err("Failed to clip videos".to_string());
     }
   };
+  let clip_duration = clip_started_at.elapsed();
   append_log(
     &context.app_log_path,
     &format!(
-      "submission_clip_done task_id={} outputs={} output_dir={}",
+      "submission_clip_done task_id={} outputs={} output_dir={} elapsed_ms={}",
       task_id,
       clip_outputs.len(),
-      clip_dir.to_string_lossy()
+      clip_dir.to_string_lossy(),
+      clip_duration.as_millis()
     ),
   );
 
@@ -2733,16 +5654,31 @@ async fn run_submission_workflow(
   let _ = update_workflow_status(&context, &task_id, "RUNNING", Some("MERGING"), 40.0);
   let merge_output = workflow_dir
     .join("merge")
-    .join(format!("{}_merged.mp4", sanitize_filename(&task_id)));
+    .join(format!(
+      "{}_merged.{}",
+      sanitize_filename(&task_id),
+      output_container.extension()
+    ));
   let merge_list_path = merge_output.with_extension("txt");
+  // 统一规格后的片段已经一致，合并时直接 concat copy，避免二次编码
+  let merge_encoder_args = if normalize_config.is_some() {
+    None
+  } else {
+    encoder_args.clone()
+  };
   append_log(
     &context.app_log_path,
     &format!(
-      "submission_merge_start task_id={} inputs={} output={} list={} mode=concat_copy",
+      "submission_merge_start task_id={} inputs={} output={} list={} mode={}",
       task_id,
       clip_outputs.len(),
       merge_output.to_string_lossy(),
-      merge_list_path.to_string_lossy()
+      merge_list_path.to_string_lossy(),
+      if merge_encoder_args.is_some() {
+        "concat_encode"
+      } else {
+        "concat_copy"
+      }
     ),
   );
   for path in &clip_outputs {
@@ -2756,20 +5692,47 @@ async fn run_submission_workflow(
     );
   }
   let merge_output_clone = merge_output.clone();
-  tauri::async_runtime::spawn_blocking(move || merge_files(&clip_outputs, &merge_output_clone))
-    .await
-    .map_err(|_| "Failed to merge videos".to_string())??;
+  let merge_log_path = context.app_log_path.clone();
+  let merge_task_id = task_id.clone();
+  let merge_started_at = Instant::now();
+  let merge_fallback = tauri::async_runtime::spawn_blocking(move || {
+    merge_files(
+      &clip_outputs,
+      &merge_output_clone,
+      Some(output_container),
+      merge_encoder_args.as_deref(),
+      |command| {
+        append_log(
+          &merge_log_path,
+          &format!("submission_merge_command task_id={} args={}", merge_task_id, command),
+        );
+      },
+    )
+  })
+  .await
+  .map_err(|_| "Failed to merge videos".to_string())??;
+  let merge_duration = merge_started_at.elapsed();
+  if let Some(reason) = merge_fallback {
+    append_log(
+      &context.app_log_path,
+      &format!("submission_merge_reencode task_id={} reason={}", task_id, reason),
+    );
+  }
   append_log(
     &context.app_log_path,
     &format!(
-      "submission_merge_done task_id={} output={}",
+      "submission_merge_done task_id={} output={} elapsed_ms={}",
       task_id,
-      merge_output.to_string_lossy()
+      merge_output.to_string_lossy(),
+      merge_duration.as_millis()
     ),
   );
 
   let _ = wait_for_workflow_ready(&context, &task_id).await?;
   save_merged_video(&context, &task_id, &merge_output)?;
+  if !is_update_workflow {
+    ensure_workflow_cover(&context, &task_id, &merge_output, &workflow_dir).await;
+  }
   if let Err(err) = baidu_sync::enqueue_submission_sync(
     context.db.as_ref(),
     context.app_log_path.as_ref(),
@@ -2781,7 +5744,8 @@ async fn run_submission_workflow(
     );
   }
 
-  let workflow_settings = load_workflow_settings(&context, &task_id);
+  let mut segment_duration = Duration::ZERO;
+  let mut segment_count: usize = 1;
   if workflow_settings.enable_segmentation {
     let _ = wait_for_workflow_ready(&context, &task_id).await?;
     update_submission_status(&context, &task_id, "SEGMENTING")?;
@@ -2799,22 +5763,54 @@ async fn run_submission_workflow(
       ),
     );
     let segment_dir_clone = segment_dir.clone();
-    let segment_outputs = tauri::async_runtime::spawn_blocking(move || {
+    let min_last_segment_seconds = load_download_settings_from_db(context.db.as_ref())
+      .map(|settings| settings.min_last_segment_seconds)
+      .unwrap_or(DEFAULT_MIN_LAST_SEGMENT_SECONDS);
+    let segment_started_at = Instant::now();
+    let segment_result = tauri::async_runtime::spawn_blocking(move || {
       segment_file(
         &merge_output_segment,
         &segment_dir_clone,
         workflow_settings.segment_duration_seconds,
+        min_last_segment_seconds,
+        output_container,
       )
     })
     .await
     .map_err(|_| "Failed to segment video".to_string())??;
+    if let Some(absorbed) = segment_result.absorbed_tail_seconds {
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "submission_segment_tail_absorbed task_id={} tail_seconds={:.2} min_seconds={}",
+          task_id, absorbed, min_last_segment_seconds
+        ),
+      );
+    }
+    for (index, segment) in segment_result.segments.iter().enumerate() {
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "submission_segment_boundary task_id={} index={} start={:.2} end={:.2} path={}",
+          task_id,
+          index + 1,
+          segment.start_seconds,
+          segment.end_seconds,
+          segment.path.to_string_lossy()
+        ),
+      );
+    }
+    let segment_outputs = segment_result.paths();
+    segment_duration = segment_started_at.elapsed();
+    segment_count = segment_outputs.len();
     append_log(
       &context.app_log_path,
       &format!(
-        "submission_segment_done task_id={} outputs={} output_dir={}",
+        "submission_segment_done task_id={} outputs={} output_dir={} elapsed_ms={}",
         task_id,
         segment_outputs.len(),
-        segment_dir.to_string_lossy()
+        segment_dir.to_string_lossy(),
+        segment_duration.as_millis()
       ),
     );
 
@@ -2835,7 +5831,12 @@ async fn run_submission_workflow(
         name_start_index,
       )?;
     } else {
-      save_output_segments(&context, &task_id, &segment_outputs)?;
+      save_output_segments(
+        &context,
+        &task_id,
+        &segment_outputs,
+        workflow_settings.segment_prefix.as_deref(),
+      )?;
     }
   }
   if is_update_workflow && !workflow_settings.enable_segmentation {
@@ -2855,6 +5856,19 @@ async fn run_submission_workflow(
       name_start_index,
     )?;
   }
+  if let Err(err) = save_workflow_stats(
+    &context,
+    &task_id,
+    clip_duration,
+    merge_duration,
+    segment_duration,
+    segment_count,
+  ) {
+    append_log(
+      &context.app_log_path,
+      &format!("submission_stats_save_fail task_id={} err={}", task_id, err),
+    );
+  }
 
   update_submission_status(&context, &task_id, "WAITING_UPLOAD")?;
   let workflow_status = match load_integrated_download_stats(&context, &task_id)? {
@@ -2875,6 +5889,7 @@ pub fn start_submission_workflow(
     db,
     app_log_path,
     edit_upload_state,
+    rate_limit_gate: upload_rate_limit_gate(),
   };
   tauri::async_runtime::spawn(async move {
     let _ = run_submission_workflow(context, task_id).await;
@@ -2886,6 +5901,7 @@ struct PreuploadInfo {
   biz_id: i64,
   chunk_size: u64,
   endpoint: String,
+  endpoints: Vec<String>,
   upos_uri: String,
 }
 
@@ -2907,6 +5923,51 @@ struct UploadProgressSnapshot {
   total_bytes: u64,
   progress: f64,
   last_part_index: u64,
+  speed_bps: u64,
+  eta_seconds: Option<u64>,
+}
+
+struct UploadSpeedTracker {
+  samples: VecDeque<(Instant, u64)>,
+}
+
+impl UploadSpeedTracker {
+  fn new() -> Self {
+    Self {
+      samples: VecDeque::with_capacity(UPLOAD_SPEED_WINDOW_CHUNKS + 1),
+    }
+  }
+
+  fn record(&mut self, uploaded_bytes: u64) {
+    self.samples.push_back((Instant::now(), uploaded_bytes));
+    while self.samples.len() > UPLOAD_SPEED_WINDOW_CHUNKS + 1 {
+      self.samples.pop_front();
+    }
+  }
+
+  fn reset(&mut self) {
+    self.samples.clear();
+  }
+
+  fn speed_bps(&self) -> u64 {
+    let (Some((first_at, first_bytes)), Some((last_at, last_bytes))) =
+      (self.samples.front(), self.samples.back())
+    else {
+      return 0;
+    };
+    let elapsed = last_at.duration_since(*first_at).as_secs_f64();
+    if elapsed <= 0.0 {
+      return 0;
+    }
+    (last_bytes.saturating_sub(*first_bytes) as f64 / elapsed) as u64
+  }
+
+  fn apply(&self, snapshot: &mut UploadProgressSnapshot) {
+    let speed = self.speed_bps();
+    snapshot.speed_bps = speed;
+    let remaining = snapshot.total_bytes.saturating_sub(snapshot.uploaded_bytes);
+    snapshot.eta_seconds = (speed > 0).then(|| remaining.div_ceil(speed));
+  }
 }
 
 struct UploadProgressLimiter {
@@ -2947,12 +6008,73 @@ impl UploadProgressLimiter {
   }
 }
 
+#[derive(Clone)]
 enum UploadTarget {
   Segment(String),
   Merged(i64),
   EditSegment(String),
 }
 
+impl UploadTarget {
+  fn key(&self) -> String {
+    match self {
+      UploadTarget::Segment(segment_id) => format!("segment:{}", segment_id),
+      UploadTarget::Merged(merged_id) => format!("merged:{}", merged_id),
+      UploadTarget::EditSegment(segment_id) => format!("edit:{}", segment_id),
+    }
+  }
+}
+
+/// 内存中的最新上传断点（已连续完成的分片），进度落库有节流，退出时据此补写。
+fn active_upload_sessions() -> &'static Mutex<HashMap<String, (UploadTarget, UploadSessionInfo)>> {
+  static SESSIONS: std::sync::OnceLock<Mutex<HashMap<String, (UploadTarget, UploadSessionInfo)>>> =
+    std::sync::OnceLock::new();
+  SESSIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+struct ActiveUploadGuard {
+  key: String,
+}
+
+impl Drop for ActiveUploadGuard {
+  fn drop(&mut self) {
+    if let Ok(mut sessions) = active_upload_sessions().lock() {
+      sessions.remove(&self.key);
+    }
+  }
+}
+
+/// 退出前补写上传断点，并把 UPLOADING 任务转回 WAITING_UPLOAD，返回处理的任务数。
+pub fn checkpoint_uploads_for_shutdown(state: &State<'_, AppState>) -> usize {
+  let context = SubmissionContext::new(state);
+  let sessions = active_upload_sessions()
+    .lock()
+    .map(|sessions| sessions.values().cloned().collect::<Vec<_>>())
+    .unwrap_or_default();
+  for (target, session) in &sessions {
+    if let Err(err) = update_upload_session(&context, target, session) {
+      append_log(
+        &context.app_log_path,
+        &format!("shutdown_upload_checkpoint_fail target={} err={}", target.key(), err),
+      );
+    }
+  }
+  let uploading_ids = load_task_ids_by_status(&context, "UPLOADING").unwrap_or_default();
+  for task_id in &uploading_ids {
+    reconcile_interrupted_upload_sessions(&context, task_id);
+    let _ = update_submission_status(&context, task_id, "WAITING_UPLOAD");
+  }
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "shutdown_upload_checkpoint sessions={} tasks={}",
+      sessions.len(),
+      uploading_ids.len()
+    ),
+  );
+  uploading_ids.len()
+}
+
 struct UploadFileResult {
   cid: i64,
   filename: String,
@@ -2999,6 +6121,72 @@ const UPLOAD_RETRY_MAX_DELAY_SECS: u64 = 30;
 const PREUPLOAD_PARSE_RETRY_BASE_SECS: u64 = 60;
 const PREUPLOAD_PARSE_RETRY_MAX_SECS: u64 = 30 * 60;
 const PREUPLOAD_PARSE_RETRY_LIMIT: u32 = 6;
+const UPLOAD_CANCELLED_MESSAGE: &str = "上传已取消";
+const PARTITION_RULES_SETTING_KEY: &str = "submission_partition_rules";
+const SUBMISSION_MAX_TAGS: usize = 12;
+const SUBMISSION_MAX_TAG_CHARS: usize = 20;
+// B站定时发布要求发布时间在当前时间 2 小时后、15 天内。
+const SCHEDULED_PUBLISH_MIN_LEAD_SECS: i64 = 2 * 60 * 60;
+const SCHEDULED_PUBLISH_MAX_LEAD_SECS: i64 = 15 * 24 * 60 * 60;
+const SCHEDULED_PUBLISH_CLAMP_MARGIN_SECS: i64 = 5 * 60;
+const UPLOAD_SPEED_WINDOW_CHUNKS: usize = 5;
+const UPLOAD_ENDPOINT_PROBE_TIMEOUT_SECS: u64 = 3;
+const UPLOAD_CANCEL_POLL_MILLIS: u64 = 500;
+const SUBMISSION_QUEUE_POLL_SECS: u64 = 2;
+// 暂停只影响队列挑选新任务，进行中的上传照常完成；不持久化，重启后恢复运行
+static SUBMISSION_QUEUE_PAUSED: AtomicBool = AtomicBool::new(false);
+const SUBMISSION_AUTO_RETRY_BASE_DELAY_SECS: u64 = 60;
+const SUBMISSION_AUTO_RETRY_MAX_DELAY_SECS: u64 = 30 * 60;
+const SUBMISSION_DEAD_LETTER_STATUS: &str = "FAILED_PERMANENT";
+const WEBHOOK_MAX_ATTEMPTS: u32 = 3;
+const WEBHOOK_RETRY_DELAY_SECS: u64 = 5;
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+/// 所有上传共享的限流窗口：任一请求收到带 Retry-After 的 406 后，
+/// 其余并发上传在窗口结束前都暂停发请求，避免连锁触发限流。
+struct UploadRateLimitGate {
+  blocked_until: Mutex<Option<Instant>>,
+}
+
+impl UploadRateLimitGate {
+  fn new() -> Self {
+    Self {
+      blocked_until: Mutex::new(None),
+    }
+  }
+
+  /// 延长共享窗口，返回窗口剩余时长（可能长于本次的 wait）。
+  fn block_for(&self, wait: Duration) -> Duration {
+    let now = Instant::now();
+    let until = now + wait;
+    let Ok(mut guard) = self.blocked_until.lock() else {
+      return wait;
+    };
+    let next = match *guard {
+      Some(current) if current > until => current,
+      _ => until,
+    };
+    *guard = Some(next);
+    next.saturating_duration_since(now)
+  }
+
+  fn remaining(&self) -> Option<Duration> {
+    let guard = self.blocked_until.lock().ok()?;
+    let remaining = (*guard)?.saturating_duration_since(Instant::now());
+    if remaining.is_zero() {
+      None
+    } else {
+      Some(remaining)
+    }
+  }
+}
+
+fn upload_rate_limit_gate() -> Arc<UploadRateLimitGate> {
+  static GATE: std::sync::OnceLock<Arc<UploadRateLimitGate>> = std::sync::OnceLock::new();
+  GATE
+    .get_or_init(|| Arc::new(UploadRateLimitGate::new()))
+    .clone()
+}
 
 struct UploadRateLimiter {
   consecutive_406: u32,
@@ -3045,9 +6233,156 @@ fn is_preupload_parse_error(err: &str) -> bool {
   err.contains("预上传解析失败") || err.contains("error decoding response body")
 }
 
+fn upload_cancel_requests() -> &'static Mutex<HashSet<String>> {
+  use std::sync::OnceLock;
+  static REQUESTS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+  REQUESTS.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+fn request_upload_cancel(task_id: &str) {
+  if let Ok(mut requests) = upload_cancel_requests().lock() {
+    requests.insert(task_id.to_string());
+  }
+}
+
+fn clear_upload_cancel(task_id: &str) {
+  if let Ok(mut requests) = upload_cancel_requests().lock() {
+    requests.remove(task_id);
+  }
+}
+
+fn is_upload_cancel_requested(task_id: &str) -> bool {
+  upload_cancel_requests()
+    .lock()
+    .map(|requests| requests.contains(task_id))
+    .unwrap_or(false)
+}
+
+fn active_segment_uploads() -> &'static Mutex<HashSet<String>> {
+  use std::sync::OnceLock;
+  static ACTIVE: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+  ACTIVE.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+fn is_segment_upload_active(segment_id: &str) -> bool {
+  active_segment_uploads()
+    .lock()
+    .map(|active| active.contains(segment_id))
+    .unwrap_or(false)
+}
+
+struct ActiveSegmentUpload {
+  segment_id: String,
+}
+
+impl ActiveSegmentUpload {
+  fn begin(segment_id: &str) -> Self {
+    if let Ok(mut active) = active_segment_uploads().lock() {
+      active.insert(segment_id.to_string());
+    }
+    Self {
+      segment_id: segment_id.to_string(),
+    }
+  }
+}
+
+impl Drop for ActiveSegmentUpload {
+  fn drop(&mut self) {
+    if let Ok(mut active) = active_segment_uploads().lock() {
+      active.remove(&self.segment_id);
+    }
+  }
+}
+
+fn is_upload_cancelled_error(err: &str) -> bool {
+  err == UPLOAD_CANCELLED_MESSAGE
+}
+
+async fn wait_for_upload_cancel(task_id: Option<&str>) {
+  let Some(task_id) = task_id else {
+    return std::future::pending::<()>().await;
+  };
+  while !is_upload_cancel_requested(task_id) {
+    sleep(Duration::from_millis(UPLOAD_CANCEL_POLL_MILLIS)).await;
+  }
+}
+
+fn load_upload_target_task_id(context: &SubmissionContext, target: &UploadTarget) -> Option<String> {
+  match target {
+    UploadTarget::Segment(segment_id) => load_output_segment_by_id(context, segment_id)
+      .ok()
+      .flatten()
+      .map(|segment| segment.task_id),
+    UploadTarget::Merged(merged_id) => context
+      .db
+      .with_conn(|conn| {
+        conn
+          .query_row(
+            "SELECT task_id FROM merged_video WHERE id = ?1",
+            [merged_id],
+            |row| row.get::<_, String>(0),
+          )
+          .optional()
+      })
+      .ok()
+      .flatten(),
+    UploadTarget::EditSegment(segment_id) => load_edit_upload_segment(context, segment_id)
+      .ok()
+      .flatten()
+      .map(|segment| segment.task_id),
+  }
+}
+
+fn finish_cancelled_upload(context: &SubmissionContext, task_id: &str) -> Result<(), String> {
+  let detail = load_task_detail(context, task_id)?;
+  for segment in detail
+    .output_segments
+    .iter()
+    .filter(|segment| segment.upload_status != "SUCCESS")
+  {
+    let target = UploadTarget::Segment(segment.segment_id.clone());
+    clear_upload_session(context, &target)?;
+    update_segment_upload_status(context, &segment.segment_id, "PENDING")?;
+  }
+  if let Some(merged) = load_latest_merged_video(context, task_id)? {
+    if merged.upload_cid.is_none() {
+      clear_upload_session(context, &UploadTarget::Merged(merged.id))?;
+    }
+  }
+  mark_upload_cancelled(context, task_id)?;
+  clear_upload_cancel(task_id);
+  append_log(
+    &context.app_log_path,
+    &format!("submission_upload_cancelled task_id={}", task_id),
+  );
+  Ok(())
+}
+
+/// 取消后的任务回到 WAITING_UPLOAD 并标记暂停，队列不再自动选取，重新投稿时恢复。
+fn mark_upload_cancelled(context: &SubmissionContext, task_id: &str) -> Result<(), String> {
+  set_upload_paused(context, task_id, true)
+}
+
+fn set_upload_paused(context: &SubmissionContext, task_id: &str, paused: bool) -> Result<(), String> {
+  let now = now_rfc3339();
+  let sql = if paused {
+    "UPDATE submission_task SET status = 'WAITING_UPLOAD', upload_paused = 1, updated_at = ?1 \
+     WHERE task_id = ?2"
+  } else {
+    "UPDATE submission_task SET upload_paused = 0, updated_at = ?1 WHERE task_id = ?2"
+  };
+  context
+    .db
+    .with_conn(|conn| {
+      conn.execute(sql, (&now, task_id))?;
+      Ok(())
+    })
+    .map_err(|err| err.to_string())
+}
+
 fn build_uploaded_parts(
   detail: &SubmissionTaskDetail,
-  is_update_workflow: bool,
+  padding: usize,
 ) -> Result<Vec<UploadedVideoPart>, String> {
   let mut parts = Vec::with_capacity(detail.output_segments.len());
   for (index, segment) in detail.output_segments.iter().enumerate() {
@@ -3061,18 +6396,188 @@ fn build_uploaded_parts(
       .file_name
       .clone()
       .ok_or_else(|| format!("分段缺少文件名 segment_id={}", segment.segment_id))?;
-    let title = if is_update_workflow {
-      resolve_existing_part_title(&detail.task, &segment.part_name, index + 1)
-    } else {
-      build_part_title(detail.task.segment_prefix.as_deref(), index + 1)
-    };
+    // 分P标题在分段时已写入记录，上传时沿用，避免补零设置变化后与本地文件名不一致
+    let title = resolve_existing_part_title(&detail.task, &segment.part_name, index + 1, padding);
     parts.push(UploadedVideoPart {
       filename,
       cid,
       title,
     });
   }
-  Ok(parts)
+  Ok(parts)
+}
+
+const WORKFLOW_COVER_POSITION_RATIO: f64 = 0.1;
+
+async fn ensure_workflow_cover(
+  context: &SubmissionContext,
+  task_id: &str,
+  merge_output: &Path,
+  workflow_dir: &Path,
+) {
+  let cover_url = context
+    .db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT cover_url FROM submission_task WHERE task_id = ?1",
+          [task_id],
+          |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+    })
+    .ok()
+    .flatten()
+    .flatten()
+    .unwrap_or_default();
+  if !cover_url.trim().is_empty() {
+    return;
+  }
+
+  let cover_path = workflow_dir.join("cover").join("cover.jpg");
+  let frame_source = merge_output.to_path_buf();
+  let frame_output = cover_path.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let duration = probe_duration_seconds(&frame_source)?;
+    extract_frame(
+      &frame_source,
+      duration * WORKFLOW_COVER_POSITION_RATIO,
+      &frame_output,
+    )
+  })
+  .await
+  .map_err(|_| "spawn_blocking_failed".to_string())
+  .and_then(|result| result);
+  let timestamp = match result {
+    Ok(timestamp) => timestamp,
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("submission_cover_extract_fail task_id={} err={}", task_id, err),
+      );
+      return;
+    }
+  };
+
+  let cover_value = cover_path.to_string_lossy().to_string();
+  let now = now_rfc3339();
+  let update_result = context.db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE submission_task SET cover_url = ?1, updated_at = ?2 WHERE task_id = ?3",
+      (&cover_value, &now, task_id),
+    )
+  });
+  match update_result {
+    Ok(_) => append_log(
+      &context.app_log_path,
+      &format!(
+        "submission_cover_extract_ok task_id={} timestamp={:.3} path={}",
+        task_id, timestamp, cover_value
+      ),
+    ),
+    Err(err) => append_log(
+      &context.app_log_path,
+      &format!("submission_cover_update_fail task_id={} err={}", task_id, err),
+    ),
+  }
+}
+
+fn is_remote_cover_url(value: &str) -> bool {
+  let lower = value.trim().to_lowercase();
+  lower.starts_with("http://") || lower.starts_with("https://") || lower.starts_with("//")
+}
+
+async fn upload_local_cover(
+  context: &UploadContext,
+  submission_context: &SubmissionContext,
+  auth: &AuthInfo,
+  csrf: &str,
+  task_id: &str,
+  cover_url: Option<String>,
+) -> Option<String> {
+  let cover = cover_url?;
+  if cover.trim().is_empty() || is_remote_cover_url(&cover) {
+    return Some(cover);
+  }
+
+  let bytes = match tokio::fs::read(&cover).await {
+    Ok(bytes) => bytes,
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "submission_cover_upload_skip task_id={} path={} err={}",
+          task_id, cover, err
+        ),
+      );
+      return None;
+    }
+  };
+  let Some(mime) = crate::commands::video::detect_image_mime(&bytes) else {
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "submission_cover_upload_skip task_id={} path={} err=unrecognized_image",
+        task_id, cover
+      ),
+    );
+    return None;
+  };
+  let encoded = STANDARD.encode(&bytes);
+  let form = vec![
+    (
+      "cover".to_string(),
+      format!("data:{};base64,{}", mime, encoded),
+    ),
+    ("csrf".to_string(), csrf.to_string()),
+  ];
+  let url = "https://member.bilibili.com/x/vu/web/cover/up";
+  let uploaded = context
+    .bilibili
+    .post_form(url, &[], &form, Some(auth))
+    .await
+    .and_then(|data| {
+      data
+        .get("url")
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string())
+        .ok_or_else(|| "封面上传响应缺少URL".to_string())
+    });
+  let remote_url = match uploaded {
+    Ok(remote_url) => remote_url,
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "submission_cover_upload_fail task_id={} path={} err={}",
+          task_id, cover, err
+        ),
+      );
+      return None;
+    }
+  };
+
+  let now = now_rfc3339();
+  let persist_result = submission_context.db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE submission_task SET cover_url = ?1, updated_at = ?2 WHERE task_id = ?3",
+      (&remote_url, &now, task_id),
+    )
+  });
+  if let Err(err) = persist_result {
+    append_log(
+      &context.app_log_path,
+      &format!("submission_cover_update_fail task_id={} err={}", task_id, err),
+    );
+  }
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "submission_cover_upload_ok task_id={} path={} url={}",
+      task_id, cover, remote_url
+    ),
+  );
+  Some(remote_url)
 }
 
 async fn run_submission_upload(
@@ -3083,6 +6588,7 @@ async fn run_submission_upload(
     db: context.db.clone(),
     app_log_path: context.app_log_path.clone(),
     edit_upload_state: context.edit_upload_state.clone(),
+    rate_limit_gate: context.rate_limit_gate.clone(),
   };
   append_log(
     &context.app_log_path,
@@ -3113,25 +6619,64 @@ async fn run_submission_upload(
     }
   };
 
-  let detail = load_task_detail(&submission_context, &task_id)?;
-  let tags = detail.task.tags.clone().unwrap_or_default();
-  if tags.trim().is_empty() {
+  let mut detail = load_task_detail(&submission_context, &task_id)?;
+  let normalized_tags = normalize_tags(detail.task.tags.as_deref().unwrap_or(""));
+  if normalized_tags.tags.is_empty() {
     update_submission_status(&submission_context, &task_id, "FAILED")?;
     return Err("投稿标签不能为空".to_string());
   }
+  if let Some(violation) = normalized_tags.violation() {
+    update_submission_status(&submission_context, &task_id, "FAILED")?;
+    return Err(violation);
+  }
+  // 排队期间定时发布时间可能已不足 2 小时，与提交时一样顺延；只有超过 15 天才判定失败
+  if let Some(scheduled_publish_at) = detail.task.scheduled_publish_at {
+    match resolve_submit_publish_at(scheduled_publish_at) {
+      Ok(adjusted) if adjusted != scheduled_publish_at => {
+        append_log(
+          &context.app_log_path,
+          &format!(
+            "submission_upload_schedule_adjusted task_id={} from={} to={}",
+            task_id, scheduled_publish_at, adjusted
+          ),
+        );
+      }
+      Ok(_) => {}
+      Err(err) => {
+        append_log(
+          &context.app_log_path,
+          &format!("submission_upload_schedule_invalid task_id={} err={}", task_id, err),
+        );
+        update_submission_status(&submission_context, &task_id, "FAILED")?;
+        return Err(err);
+      }
+    }
+  }
+  detail.task.tags = Some(normalized_tags.joined());
+  detail.task.cover_url = upload_local_cover(
+    &context,
+    &submission_context,
+    &auth,
+    &csrf,
+    &task_id,
+    detail.task.cover_url.take(),
+  )
+  .await;
   let workflow_type = load_latest_workflow_type(&submission_context, &task_id)?
     .unwrap_or_else(|| "VIDEO_SUBMISSION".to_string());
   let is_update_workflow = workflow_type == "VIDEO_UPDATE";
 
   update_submission_status(&submission_context, &task_id, "UPLOADING")?;
+  let upload_started_at = Instant::now();
 
   let settings = load_workflow_settings(&submission_context, &task_id);
   let upload_concurrency = load_download_settings_from_db(&submission_context.db)
     .map(|settings| settings.upload_concurrency)
     .unwrap_or(DEFAULT_UPLOAD_CONCURRENCY)
     .max(1) as usize;
-  let client = Client::new();
+  let client = crate::config::build_http_client();
   let mut parts: Vec<UploadedVideoPart> = Vec::new();
+  let upload_bytes: u64;
 
   if is_update_workflow || settings.enable_segmentation {
     if detail.output_segments.is_empty() {
@@ -3140,6 +6685,10 @@ async fn run_submission_upload(
     }
     let mut preupload_retry_round: u32 = 0;
     loop {
+      if is_upload_cancel_requested(&task_id) {
+        finish_cancelled_upload(&submission_context, &task_id)?;
+        return Err(UPLOAD_CANCELLED_MESSAGE.to_string());
+      }
       let detail = load_task_detail(&submission_context, &task_id)?;
       if detail.output_segments.is_empty() {
         update_submission_status(&submission_context, &task_id, "FAILED")?;
@@ -3162,9 +6711,17 @@ async fn run_submission_upload(
         .map(|(index, segment)| (index, segment.segment_id.clone()))
         .collect();
       if pending.is_empty() {
-        match build_uploaded_parts(&detail, is_update_workflow) {
+        match build_uploaded_parts(
+          &detail,
+          load_segment_index_padding(context.db.as_ref()),
+        ) {
           Ok(list) => {
             parts = list;
+            upload_bytes = detail
+              .output_segments
+              .iter()
+              .map(|segment| segment.upload_total_bytes.max(0) as u64)
+              .sum();
             break;
           }
           Err(err) => {
@@ -3211,6 +6768,7 @@ async fn run_submission_upload(
       }
       let mut has_preupload_parse_error = false;
       let mut has_other_error = false;
+      let mut has_cancelled = false;
       while let Some((segment_id, result)) = futures.next().await {
         match result {
           Ok(upload_result) => {
@@ -3223,6 +6781,10 @@ async fn run_submission_upload(
             )?;
           }
           Err(err) => {
+            if is_upload_cancelled_error(&err) {
+              has_cancelled = true;
+              continue;
+            }
             if is_preupload_parse_error(&err) {
               let _ = clear_upload_session(
                 &submission_context,
@@ -3244,6 +6806,10 @@ async fn run_submission_upload(
           }
         }
       }
+      if has_cancelled {
+        finish_cancelled_upload(&submission_context, &task_id)?;
+        return Err(UPLOAD_CANCELLED_MESSAGE.to_string());
+      }
       if has_other_error {
         update_submission_status(&submission_context, &task_id, "FAILED")?;
         return Err("存在分段上传失败，请重试失败分P".to_string());
@@ -3295,6 +6861,10 @@ async fn run_submission_upload(
       {
         Ok(result) => break Ok(result),
         Err(err) => {
+          if is_upload_cancelled_error(&err) {
+            finish_cancelled_upload(&submission_context, &task_id)?;
+            break Err(err);
+          }
           if is_auth_error(&err) {
             match refresh_auth(&context, "upload_merged").await {
               Ok(auth) => {
@@ -3314,10 +6884,17 @@ async fn run_submission_upload(
       Some(result.cid),
       Some(result.filename.clone()),
     )?;
+    upload_bytes = fs::metadata(&merged_path)
+      .map(|meta| meta.len())
+      .unwrap_or(0);
     parts.push(UploadedVideoPart {
       filename: result.filename,
       cid: result.cid,
-      title: build_part_title(detail.task.segment_prefix.as_deref(), 1),
+      title: build_part_title(
+        detail.task.segment_prefix.as_deref(),
+        1,
+        load_segment_index_padding(context.db.as_ref()),
+      ),
     });
   }
 
@@ -3325,6 +6902,23 @@ async fn run_submission_upload(
     update_submission_status(&submission_context, &task_id, "FAILED")?;
     return Err("投稿文件为空".to_string());
   }
+  let upload_duration = upload_started_at.elapsed();
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "submission_upload_files_done task_id={} bytes={} elapsed_ms={}",
+      task_id,
+      upload_bytes,
+      upload_duration.as_millis()
+    ),
+  );
+  if let Err(err) = save_upload_stats(&submission_context, &task_id, upload_bytes, upload_duration)
+  {
+    append_log(
+      &context.app_log_path,
+      &format!("submission_stats_save_fail task_id={} err={}", task_id, err),
+    );
+  }
 
   if is_update_workflow {
     let mut aid = detail.task.aid.unwrap_or(0);
@@ -3346,6 +6940,7 @@ async fn run_submission_upload(
     match submit_result {
       Ok(()) => {
         update_submission_status(&submission_context, &task_id, "COMPLETED")?;
+        auto_cleanup_completed_task(&submission_context, &task_id);
         append_log(
           &context.app_log_path,
           &format!(
@@ -3398,6 +6993,7 @@ async fn run_submission_upload(
           }
         }
         update_submission_status(&submission_context, &task_id, "COMPLETED")?;
+        auto_cleanup_completed_task(&submission_context, &task_id);
         append_log(
           &context.app_log_path,
           &format!(
@@ -3419,47 +7015,481 @@ async fn run_submission_upload(
   }
 }
 
-async fn submission_queue_loop(context: SubmissionQueueContext) {
-  let submission_context = SubmissionContext {
-    db: context.db.clone(),
-    app_log_path: context.app_log_path.clone(),
-    edit_upload_state: context.edit_upload_state.clone(),
+#[tauri::command]
+pub fn submission_queue_pause(state: State<'_, AppState>) -> ApiResponse<bool> {
+  if !SUBMISSION_QUEUE_PAUSED.swap(true, Ordering::SeqCst) {
+    append_log(&state.app_log_path, "submission_queue_pause");
+  }
+  ApiResponse::success(true)
+}
+
+#[tauri::command]
+pub fn submission_queue_resume(state: State<'_, AppState>) -> ApiResponse<bool> {
+  if SUBMISSION_QUEUE_PAUSED.swap(false, Ordering::SeqCst) {
+    append_log(&state.app_log_path, "submission_queue_resume");
+  }
+  ApiResponse::success(false)
+}
+
+pub fn is_submission_queue_paused() -> bool {
+  SUBMISSION_QUEUE_PAUSED.load(Ordering::SeqCst)
+}
+
+async fn submission_queue_loop(context: SubmissionQueueContext) {
+  let submission_context = SubmissionContext {
+    db: context.db.clone(),
+    app_log_path: context.app_log_path.clone(),
+    edit_upload_state: context.edit_upload_state.clone(),
+    rate_limit_gate: upload_rate_limit_gate(),
+  };
+  // 正在处理的任务不会再次被选中，避免同一任务并发上传
+  let mut running_ids: HashSet<String> = HashSet::new();
+  let mut running = FuturesUnordered::new();
+  loop {
+    let max_tasks = load_download_settings_from_db(&context.db)
+      .map(|settings| settings.max_concurrent_submission_tasks)
+      .unwrap_or(DEFAULT_MAX_CONCURRENT_SUBMISSION_TASKS)
+      .clamp(1, MAX_CONCURRENT_SUBMISSION_TASKS) as usize;
+    while running.len() < max_tasks
+      && !crate::shutdown::is_requested()
+      && !is_submission_queue_paused()
+    {
+      let task_id = match load_next_queued_task(&submission_context, &running_ids) {
+        Ok(Some(task_id)) => task_id,
+        Ok(None) => break,
+        Err(err) => {
+          append_log(
+            &context.app_log_path,
+            &format!("submission_queue_load_fail err={}", err),
+          );
+          break;
+        }
+      };
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "submission_queue_pick task_id={} running={} max={}",
+          task_id,
+          running.len() + 1,
+          max_tasks
+        ),
+      );
+      running_ids.insert(task_id.clone());
+      running.push(run_queued_submission(
+        context.clone(),
+        submission_context.clone(),
+        task_id,
+      ));
+    }
+    if running.is_empty() {
+      sleep(jittered_duration(Duration::from_secs(SUBMISSION_QUEUE_POLL_SECS))).await;
+      continue;
+    }
+    tokio::select! {
+      Some(task_id) = running.next() => {
+        running_ids.remove(&task_id);
+      }
+      _ = sleep(jittered_duration(Duration::from_secs(SUBMISSION_QUEUE_POLL_SECS))) => {}
+    }
+  }
+}
+
+async fn run_queued_submission(
+  context: SubmissionQueueContext,
+  submission_context: SubmissionContext,
+  task_id: String,
+) -> String {
+  let upload_context = UploadContext {
+    db: context.db.clone(),
+    bilibili: context.bilibili.clone(),
+    login_store: context.login_store.clone(),
+    app_log_path: context.app_log_path.clone(),
+    edit_upload_state: context.edit_upload_state.clone(),
+    rate_limit_gate: upload_rate_limit_gate(),
+  };
+  let result = run_submission_upload(upload_context, task_id.clone()).await;
+  let dead_lettered = match result.as_ref() {
+    Err(err) if err != UPLOAD_CANCELLED_MESSAGE => {
+      record_submission_upload_failure(&submission_context, &task_id, err)
+    }
+    _ => false,
+  };
+  // 用户取消不是终态，任务回到队列等待重新投稿，不发送通知
+  if !matches!(result.as_ref(), Err(err) if err == UPLOAD_CANCELLED_MESSAGE) {
+    notify_submission_terminal(
+      &submission_context,
+      &task_id,
+      result.as_ref().err().map(String::as_str),
+    );
+  }
+  match result {
+    Ok(()) => {
+      let _ = update_task_auto_retry_count(&submission_context, &task_id, 0);
+      let _ = clear_submission_upload_failures(&submission_context, &task_id);
+    }
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("submission_queue_upload_fail task_id={} err={}", task_id, err),
+      );
+      if !dead_lettered && err != UPLOAD_CANCELLED_MESSAGE {
+        schedule_submission_auto_retry(&submission_context, &task_id, &err);
+      }
+    }
+  }
+  task_id
+}
+
+/// 上传失败次数上限：首次上传加上自动重试设置允许的次数。
+fn max_upload_failures(context: &SubmissionContext) -> i64 {
+  let max_attempts = load_download_settings_from_db(&context.db)
+    .map(|settings| settings.auto_retry_submission_max_attempts)
+    .unwrap_or(DEFAULT_SUBMISSION_AUTO_RETRY_MAX_ATTEMPTS);
+  max_attempts + 1
+}
+
+/// 累加上传失败次数并记录最后一次错误，达到上限时转为 FAILED_PERMANENT，队列不再选取。
+/// 返回任务是否已转为 FAILED_PERMANENT。
+fn record_submission_upload_failure(context: &SubmissionContext, task_id: &str, err: &str) -> bool {
+  let now = now_rfc3339();
+  let count = context
+    .db
+    .with_conn(|conn| {
+      conn.execute(
+        "UPDATE submission_task SET upload_failure_count = COALESCE(upload_failure_count, 0) + 1, \
+         last_upload_error = ?1, updated_at = ?2 WHERE task_id = ?3",
+        (err, &now, task_id),
+      )?;
+      conn.query_row(
+        "SELECT COALESCE(upload_failure_count, 0) FROM submission_task WHERE task_id = ?1",
+        [task_id],
+        |row| row.get::<_, i64>(0),
+      )
+    })
+    .map_err(|err| err.to_string());
+  let count = match count {
+    Ok(count) => count,
+    Err(update_err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("submission_upload_failure_record_fail task_id={} err={}", task_id, update_err),
+      );
+      return false;
+    }
+  };
+  let max_failures = max_upload_failures(context);
+  if count < max_failures {
+    return false;
+  }
+  mark_submission_dead_letter(context, task_id, count, max_failures)
+}
+
+fn mark_submission_dead_letter(
+  context: &SubmissionContext,
+  task_id: &str,
+  count: i64,
+  max_failures: i64,
+) -> bool {
+  match update_submission_status(context, task_id, SUBMISSION_DEAD_LETTER_STATUS) {
+    Ok(()) => {
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "submission_queue_dead_letter task_id={} failures={} max={}",
+          task_id, count, max_failures
+        ),
+      );
+      true
+    }
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("submission_queue_dead_letter_fail task_id={} err={}", task_id, err),
+      );
+      false
+    }
+  }
+}
+
+fn clear_submission_upload_failures(context: &SubmissionContext, task_id: &str) -> Result<(), String> {
+  context
+    .db
+    .with_conn(|conn| {
+      conn.execute(
+        "UPDATE submission_task SET upload_failure_count = 0, last_upload_error = NULL WHERE task_id = ?1",
+        [task_id],
+      )?;
+      Ok(())
+    })
+    .map_err(|err| err.to_string())
+}
+
+/// 清空上传失败计数；FAILED_PERMANENT 的任务恢复为 FAILED，可重新投稿。
+#[tauri::command]
+pub async fn submission_reset_retries(
+  state: State<'_, AppState>,
+  task_id: String,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(&state);
+  let task_id = task_id.trim().to_string();
+  if task_id.is_empty() {
+    return Ok(ApiResponse::error("任务ID不能为空"));
+  }
+  let status = match load_task_status(&context, &task_id) {
+    Ok(status) => status,
+    Err(err) => return Ok(ApiResponse::error(format!("读取任务状态失败: {}", err))),
+  };
+  if let Err(err) = clear_submission_upload_failures(&context, &task_id) {
+    return Ok(ApiResponse::error(format!("重置重试次数失败: {}", err)));
+  }
+  let _ = update_task_auto_retry_count(&context, &task_id, 0);
+  if status == SUBMISSION_DEAD_LETTER_STATUS {
+    if let Err(err) = update_submission_status(&context, &task_id, "FAILED") {
+      return Ok(ApiResponse::error(format!("更新任务状态失败: {}", err)));
+    }
+  }
+  append_log(
+    &state.app_log_path,
+    &format!("submission_reset_retries task_id={} status={}", task_id, status),
+  );
+  Ok(ApiResponse::success("已重置重试次数".to_string()))
+}
+
+#[derive(Serialize)]
+struct SubmissionWebhookPayload {
+  task_id: String,
+  title: String,
+  status: String,
+  bvid: Option<String>,
+  error: Option<String>,
+}
+
+/// 任务进入 COMPLETED/FAILED 时向设置的 webhook 推送通知，后台投递不阻塞流程。
+fn notify_submission_terminal(context: &SubmissionContext, task_id: &str, error: Option<&str>) {
+  let webhook_url = match load_download_settings_from_db(&context.db) {
+    Ok(settings) => settings.notify_webhook_url,
+    Err(_) => return,
+  };
+  if webhook_url.is_empty() {
+    return;
+  }
+  let row = context
+    .db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT title, status, bvid FROM submission_task WHERE task_id = ?1",
+          [task_id],
+          |row| {
+            Ok((
+              row.get::<_, String>(0)?,
+              row.get::<_, String>(1)?,
+              row.get::<_, Option<String>>(2)?,
+            ))
+          },
+        )
+        .optional()
+    })
+    .ok()
+    .flatten();
+  let Some((title, status, bvid)) = row else {
+    return;
+  };
+  if status != "COMPLETED" && status != "FAILED" && status != SUBMISSION_DEAD_LETTER_STATUS {
+    return;
+  }
+  let payload = SubmissionWebhookPayload {
+    task_id: task_id.to_string(),
+    title,
+    error: if status != "COMPLETED" {
+      error.map(|value| value.to_string())
+    } else {
+      None
+    },
+    status,
+    bvid: bvid.filter(|value| !value.trim().is_empty()),
+  };
+  let app_log_path = context.app_log_path.clone();
+  tauri::async_runtime::spawn(async move {
+    deliver_submission_webhook(&app_log_path, &webhook_url, &payload).await;
+  });
+}
+
+async fn deliver_submission_webhook(
+  app_log_path: &Path,
+  webhook_url: &str,
+  payload: &SubmissionWebhookPayload,
+) {
+  let client = crate::config::build_http_client();
+  let mut last_err = String::new();
+  for attempt in 1..=WEBHOOK_MAX_ATTEMPTS {
+    let response = client
+      .post(webhook_url)
+      .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+      .json(payload)
+      .send()
+      .await;
+    match response {
+      Ok(response) if response.status().is_success() => {
+        append_log(
+          app_log_path,
+          &format!(
+            "webhook_notify_ok task_id={} status={} attempt={}",
+            payload.task_id, payload.status, attempt
+          ),
+        );
+        return;
+      }
+      Ok(response) => last_err = format!("http_status={}", response.status().as_u16()),
+      Err(err) => last_err = err.to_string(),
+    }
+    if attempt < WEBHOOK_MAX_ATTEMPTS {
+      sleep(Duration::from_secs(WEBHOOK_RETRY_DELAY_SECS * attempt as u64)).await;
+    }
+  }
+  append_log(
+    app_log_path,
+    &format!(
+      "webhook_notify_fail task_id={} status={} attempts={} err={}",
+      payload.task_id, payload.status, WEBHOOK_MAX_ATTEMPTS, last_err
+    ),
+  );
+}
+
+fn schedule_submission_auto_retry(context: &SubmissionContext, task_id: &str, err: &str) {
+  let settings = match load_download_settings_from_db(&context.db) {
+    Ok(settings) => settings,
+    Err(_) => return,
   };
-  loop {
-    let task_id = match load_next_queued_task(&submission_context) {
-      Ok(task_id) => task_id,
-      Err(err) => {
-        append_log(
-          &context.app_log_path,
-          &format!("submission_queue_load_fail err={}", err),
-        );
-        sleep(Duration::from_secs(2)).await;
-        continue;
-      }
-    };
-    let Some(task_id) = task_id else {
-      sleep(Duration::from_secs(2)).await;
-      continue;
-    };
+  if !settings.auto_retry_submission || !is_transient_upload_error(err) {
+    return;
+  }
+  if load_task_status(context, task_id).ok().as_deref() != Some("FAILED") {
+    return;
+  }
+  let attempts = load_task_auto_retry_count(context, task_id).unwrap_or(0);
+  let max_attempts = settings.auto_retry_submission_max_attempts;
+  if attempts >= max_attempts {
     append_log(
       &context.app_log_path,
-      &format!("submission_queue_pick task_id={}", task_id),
+      &format!(
+        "submission_auto_retry_exhausted task_id={} attempts={} max={}",
+        task_id, attempts, max_attempts
+      ),
     );
-    let upload_context = UploadContext {
-      db: context.db.clone(),
-      bilibili: context.bilibili.clone(),
-      login_store: context.login_store.clone(),
-      app_log_path: context.app_log_path.clone(),
-      edit_upload_state: context.edit_upload_state.clone(),
-    };
-    let result = run_submission_upload(upload_context, task_id.clone()).await;
-    if let Err(err) = result {
+    return;
+  }
+  let attempt = attempts + 1;
+  if let Err(update_err) = update_task_auto_retry_count(context, task_id, attempt) {
+    append_log(
+      &context.app_log_path,
+      &format!("submission_auto_retry_fail task_id={} err={}", task_id, update_err),
+    );
+    return;
+  }
+  let delay_secs = submission_auto_retry_delay_secs(attempt as u32);
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "submission_auto_retry task_id={} attempt={} max={} delay_secs={} err={}",
+      task_id, attempt, max_attempts, delay_secs, err
+    ),
+  );
+  let context = context.clone();
+  let task_id = task_id.to_string();
+  tauri::async_runtime::spawn(async move {
+    sleep(Duration::from_secs(delay_secs)).await;
+    // 等待期间用户可能已手动处理，只在仍为 FAILED 时重新入队
+    if load_task_status(&context, &task_id).ok().as_deref() != Some("FAILED") {
       append_log(
         &context.app_log_path,
-        &format!("submission_queue_upload_fail task_id={} err={}", task_id, err),
+        &format!("submission_auto_retry_skip task_id={} reason=status_changed", task_id),
       );
+      return;
     }
-  }
+    let _ = reset_failed_segment_uploads(&context, &task_id);
+    clear_upload_cancel(&task_id);
+    match update_submission_status(&context, &task_id, "WAITING_UPLOAD") {
+      Ok(()) => append_log(
+        &context.app_log_path,
+        &format!("submission_auto_retry_enqueue task_id={} attempt={}", task_id, attempt),
+      ),
+      Err(err) => append_log(
+        &context.app_log_path,
+        &format!("submission_auto_retry_fail task_id={} err={}", task_id, err),
+      ),
+    }
+  });
+}
+
+fn submission_auto_retry_delay_secs(attempt: u32) -> u64 {
+  let exponent = attempt.saturating_sub(1).min(10);
+  SUBMISSION_AUTO_RETRY_BASE_DELAY_SECS
+    .saturating_mul(1u64 << exponent)
+    .min(SUBMISSION_AUTO_RETRY_MAX_DELAY_SECS)
+}
+
+// 登录态与投稿信息校验类错误重试也不会成功，只对网络/接口类错误自动重试
+fn is_transient_upload_error(err: &str) -> bool {
+  if err == UPLOAD_CANCELLED_MESSAGE {
+    return false;
+  }
+  const NON_TRANSIENT_MARKERS: [&str; 9] = [
+    "登录",
+    "CSRF",
+    "csrf",
+    "-101",
+    "不能为空",
+    "未找到",
+    "格式错误",
+    "无效",
+    "不支持",
+  ];
+  !NON_TRANSIENT_MARKERS.iter().any(|marker| err.contains(marker))
+}
+
+fn load_task_auto_retry_count(context: &SubmissionContext, task_id: &str) -> Result<i64, String> {
+  context
+    .db
+    .with_conn(|conn| {
+      conn.query_row(
+        "SELECT COALESCE(auto_retry_count, 0) FROM submission_task WHERE task_id = ?1",
+        [task_id],
+        |row| row.get(0),
+      )
+    })
+    .map_err(|err| err.to_string())
+}
+
+fn update_task_auto_retry_count(
+  context: &SubmissionContext,
+  task_id: &str,
+  count: i64,
+) -> Result<(), String> {
+  context
+    .db
+    .with_conn(|conn| {
+      conn.execute(
+        "UPDATE submission_task SET auto_retry_count = ?1 WHERE task_id = ?2",
+        (count, task_id),
+      )?;
+      Ok(())
+    })
+    .map_err(|err| err.to_string())
+}
+
+fn reset_failed_segment_uploads(context: &SubmissionContext, task_id: &str) -> Result<(), String> {
+  context
+    .db
+    .with_conn(|conn| {
+      conn.execute(
+        "UPDATE task_output_segment SET upload_status = 'PENDING', upload_speed_bps = 0, \
+         upload_eta_seconds = NULL WHERE task_id = ?1 AND upload_status = 'FAILED'",
+        [task_id],
+      )?;
+      Ok(())
+    })
+    .map_err(|err| err.to_string())
 }
 
 #[derive(Clone)]
@@ -3480,7 +7510,7 @@ async fn submission_remote_refresh_loop(context: SubmissionQueueContext) {
         &format!("submission_remote_refresh_fail err={}", err),
       );
     }
-    sleep(Duration::from_secs((interval_minutes as u64) * 60)).await;
+    sleep(jittered_duration(Duration::from_secs((interval_minutes as u64) * 60))).await;
   }
 }
 
@@ -3502,6 +7532,19 @@ async fn refresh_submission_remote_state(
   if task_bvids.is_empty() {
     return Ok(());
   }
+  let previous_states = load_task_remote_states(context)?;
+  let transitions: Vec<(String, String, i64, Option<String>)> = task_bvids
+    .iter()
+    .filter_map(|(task_id, bvid)| {
+      let state = remote_map.get(bvid).map(|info| info.state).unwrap_or(0);
+      let (title, previous) = previous_states.get(task_id)?;
+      if *previous == Some(state) {
+        return None;
+      }
+      let reject_reason = remote_map.get(bvid).and_then(|info| info.reject_reason.clone());
+      Some((task_id.clone(), title.clone(), state, reject_reason))
+    })
+    .collect();
   let missing_bvids: Vec<String> = task_bvids
     .iter()
     .filter(|(_, bvid)| !remote_map.contains_key(bvid))
@@ -3581,9 +7624,165 @@ async fn refresh_submission_remote_state(
       Ok(())
     })
     .map_err(|err| err.to_string())?;
+  for (task_id, title, state, reject_reason) in transitions {
+    notify_remote_state_change(context, &task_id, title, state, reject_reason.as_deref());
+  }
   Ok(())
 }
 
+fn notify_remote_state_change(
+  context: &SubmissionQueueContext,
+  task_id: &str,
+  title: String,
+  state: i64,
+  reject_reason: Option<&str>,
+) {
+  let remote_state = RemoteAuditState::from_code(state);
+  let (event, notify_title) = if remote_state == RemoteAuditState::Open {
+    ("published", "稿件已发布")
+  } else if remote_state.is_rejected() {
+    ("rejected", "稿件未通过审核")
+  } else {
+    return;
+  };
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "submission_remote_state_change task_id={} event={} state={} reject_reason={}",
+      task_id,
+      event,
+      state,
+      reject_reason.unwrap_or("")
+    ),
+  );
+  let body = match reject_reason {
+    Some(reason) if !reason.trim().is_empty() => format!("{}\n{}", title, reason),
+    _ => title,
+  };
+  desktop_notify::notify(
+    &context.db,
+    &context.app_log_path,
+    NotifyCategory::Submission,
+    notify_title,
+    &body,
+  );
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionRemoteStateResult {
+  pub task_id: String,
+  pub bvid: String,
+  pub remote_state: i64,
+  pub remote_state_label: String,
+  pub reject_reason: Option<String>,
+}
+
+/// 只刷新单个任务的审核状态，不做整个账号的稿件列表扫描。
+#[tauri::command]
+pub async fn submission_refresh_remote(
+  state: State<'_, AppState>,
+  task_id: String,
+) -> Result<ApiResponse<SubmissionRemoteStateResult>, String> {
+  let task_id = task_id.trim().to_string();
+  if task_id.is_empty() {
+    return Ok(ApiResponse::error("任务ID不能为空"));
+  }
+  let context = build_submission_queue_context(&state);
+  let row = context
+    .db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT title, bvid, remote_state FROM submission_task WHERE task_id = ?1",
+          [&task_id],
+          |row| {
+            Ok((
+              row.get::<_, String>(0)?,
+              row.get::<_, Option<String>>(1)?,
+              row.get::<_, Option<i64>>(2)?,
+            ))
+          },
+        )
+        .optional()
+    })
+    .map_err(|err| err.to_string())?;
+  let Some((title, bvid, previous_state)) = row else {
+    return Ok(ApiResponse::error("任务不存在"));
+  };
+  let Some(bvid) = bvid
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty())
+  else {
+    return Ok(ApiResponse::error("任务尚未投稿，没有BV号"));
+  };
+  let auth = match load_auth_from_queue_context(&context) {
+    Ok(auth) => auth,
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+  let info = match fetch_remote_audit_info(&context, &auth, &bvid).await {
+    Ok(Some(info)) => info,
+    Ok(None) => return Ok(ApiResponse::error("稿件列表中未找到该稿件")),
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("submission_refresh_remote_fail task_id={} err={}", task_id, err),
+      );
+      return Ok(ApiResponse::error(format!("获取审核状态失败: {}", err)));
+    }
+  };
+  if let Err(err) = context.db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE submission_task SET remote_state = ?1, reject_reason = ?2 WHERE task_id = ?3",
+      (info.state, info.reject_reason.as_deref(), &task_id),
+    )
+  }) {
+    return Ok(ApiResponse::error(format!("更新审核状态失败: {}", err)));
+  }
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "submission_refresh_remote_ok task_id={} bvid={} state={}",
+      task_id, bvid, info.state
+    ),
+  );
+  if previous_state != Some(info.state) {
+    notify_remote_state_change(
+      &context,
+      &task_id,
+      title,
+      info.state,
+      info.reject_reason.as_deref(),
+    );
+  }
+  Ok(ApiResponse::success(SubmissionRemoteStateResult {
+    task_id,
+    bvid,
+    remote_state: info.state,
+    remote_state_label: RemoteAuditState::from_code(info.state).label().to_string(),
+    reject_reason: info.reject_reason,
+  }))
+}
+
+fn load_task_remote_states(
+  context: &SubmissionQueueContext,
+) -> Result<HashMap<String, (String, Option<i64>)>, String> {
+  context
+    .db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT task_id, title, remote_state FROM submission_task \
+         WHERE bvid IS NOT NULL AND TRIM(bvid) != ''",
+      )?;
+      let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, (row.get(1)?, row.get(2)?)))
+      })?;
+      let map = rows.collect::<Result<HashMap<_, _>, _>>()?;
+      Ok(map)
+    })
+    .map_err(|err| err.to_string())
+}
+
 fn load_task_bvids(context: &SubmissionQueueContext) -> Result<Vec<(String, String)>, String> {
   context
     .db
@@ -3646,44 +7845,9 @@ async fn fetch_remote_audit_map(
       .cloned()
       .unwrap_or_default();
     for item in arc_audits.iter() {
-      let archive = match item.get("Archive") {
-        Some(value) => value,
-        None => continue,
-      };
-      let bvid = archive
-        .get("bvid")
-        .and_then(|value| value.as_str())
-        .unwrap_or("")
-        .trim()
-        .to_string();
-      if bvid.is_empty() {
-        continue;
+      if let Some((bvid, info)) = parse_remote_audit_item(item) {
+        result.insert(bvid, info);
       }
-      let state = archive.get("state").and_then(|value| value.as_i64()).unwrap_or(0);
-      let reject_reason = item
-        .get("problem_detail")
-        .and_then(|value| value.as_array())
-        .and_then(|items| {
-          items.iter().find_map(|detail| {
-            detail
-              .get("reject_reason")
-              .and_then(|value| value.as_str())
-          })
-        })
-        .or_else(|| {
-          archive
-            .get("reject_reason")
-            .and_then(|value| value.as_str())
-        })
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty());
-      result.insert(
-        bvid,
-        RemoteAuditInfo {
-          state,
-          reject_reason,
-        },
-      );
     }
 
     let total_count = data
@@ -3700,68 +7864,356 @@ async fn fetch_remote_audit_map(
     if arc_audits.is_empty() {
       break;
     }
-    page += 1;
+    page += 1;
+  }
+
+  Ok(result)
+}
+
+fn parse_remote_audit_item(item: &Value) -> Option<(String, RemoteAuditInfo)> {
+  let archive = item.get("Archive")?;
+  let bvid = archive
+    .get("bvid")
+    .and_then(|value| value.as_str())
+    .unwrap_or("")
+    .trim()
+    .to_string();
+  if bvid.is_empty() {
+    return None;
+  }
+  let state = archive.get("state").and_then(|value| value.as_i64()).unwrap_or(0);
+  let reject_reason = item
+    .get("problem_detail")
+    .and_then(|value| value.as_array())
+    .and_then(|items| {
+      items.iter().find_map(|detail| {
+        detail
+          .get("reject_reason")
+          .and_then(|value| value.as_str())
+      })
+    })
+    .or_else(|| {
+      archive
+        .get("reject_reason")
+        .and_then(|value| value.as_str())
+    })
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty());
+  Some((
+    bvid,
+    RemoteAuditInfo {
+      state,
+      reject_reason,
+    },
+  ))
+}
+
+/// 按 BV 号在稿件管理列表中检索单个稿件，只请求一页。
+async fn fetch_remote_audit_info(
+  context: &SubmissionQueueContext,
+  auth: &AuthInfo,
+  bvid: &str,
+) -> Result<Option<RemoteAuditInfo>, String> {
+  let params = vec![
+    ("status".to_string(), REMOTE_AUDIT_STATUS.to_string()),
+    ("pn".to_string(), "1".to_string()),
+    ("ps".to_string(), "10".to_string()),
+    ("keyword".to_string(), bvid.to_string()),
+    ("coop".to_string(), "1".to_string()),
+    ("interactive".to_string(), "1".to_string()),
+  ];
+  let data = context
+    .bilibili
+    .get_json(
+      "https://member.bilibili.com/x/web/archives",
+      &params,
+      Some(auth),
+      false,
+    )
+    .await?;
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "submission_remote_fetch_single_response bvid={} data={}",
+      bvid,
+      truncate_log_value(&data)
+    ),
+  );
+  let info = data
+    .get("arc_audits")
+    .and_then(|value| value.as_array())
+    .and_then(|items| {
+      items
+        .iter()
+        .filter_map(parse_remote_audit_item)
+        .find(|(item_bvid, _)| item_bvid == bvid)
+    })
+    .map(|(_, info)| info);
+  Ok(info)
+}
+
+async fn recover_submission_tasks(context: SubmissionQueueContext) {
+  let submission_context = SubmissionContext {
+    db: context.db.clone(),
+    app_log_path: context.app_log_path.clone(),
+    edit_upload_state: context.edit_upload_state.clone(),
+    rate_limit_gate: upload_rate_limit_gate(),
+  };
+  let mut processing_ids = Vec::new();
+  for status in ["PENDING", "CLIPPING", "MERGING", "SEGMENTING"] {
+    if let Ok(list) = load_task_ids_by_status(&submission_context, status) {
+      processing_ids.extend(list);
+    }
+  }
+  let uploading_ids = load_task_ids_by_status(&submission_context, "UPLOADING").unwrap_or_default();
+
+  for task_id in uploading_ids {
+    reconcile_interrupted_upload_sessions(&submission_context, &task_id);
+    let _ = update_submission_status(&submission_context, &task_id, "WAITING_UPLOAD");
+    append_log(
+      &context.app_log_path,
+      &format!("submission_recover_uploading task_id={}", task_id),
+    );
+  }
+
+  resume_edit_upload_segments(&context, &submission_context).await;
+
+  for task_id in processing_ids {
+    // 复制出来、尚未添加源视频的任务保持待处理，不自动执行工作流。
+    if !task_has_source_videos(&submission_context, &task_id) {
+      append_log(
+        &context.app_log_path,
+        &format!("submission_recover_skip task_id={} reason=no_sources", task_id),
+      );
+      continue;
+    }
+    let _ = update_submission_status(&submission_context, &task_id, "PENDING");
+    let _ = set_workflow_instance_status(&submission_context, &task_id, "PENDING");
+    let context_clone = submission_context.clone();
+    let task_id_clone = task_id.clone();
+    append_log(
+      &context.app_log_path,
+      &format!("submission_recover_workflow task_id={}", task_id),
+    );
+    tauri::async_runtime::spawn(async move {
+      let _ = run_submission_workflow(context_clone, task_id_clone).await;
+    });
+  }
+}
+
+/// 应用重启后检查被中断的上传：已保存的会话仍与本地文件大小一致时保留，
+/// 重新排队后 run_submission_upload 会从断点继续；否则清除会话从头上传。
+fn reconcile_interrupted_upload_sessions(context: &SubmissionContext, task_id: &str) {
+  let detail = match load_task_detail(context, task_id) {
+    Ok(detail) => detail,
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("submission_recover_session_fail task_id={} err={}", task_id, err),
+      );
+      return;
+    }
+  };
+  for segment in detail
+    .output_segments
+    .iter()
+    .filter(|segment| segment.upload_status != "SUCCESS" && segment.upload_status != "FAILED")
+  {
+    let target = UploadTarget::Segment(segment.segment_id.clone());
+    let session = build_upload_session_from_segment(segment);
+    reconcile_upload_session(
+      context,
+      task_id,
+      &target,
+      &segment.segment_id,
+      &segment.segment_file_path,
+      session,
+    );
+    let _ = update_segment_upload_status(context, &segment.segment_id, "PENDING");
+  }
+  if let Ok(Some(merged)) = load_latest_merged_video(context, task_id) {
+    if merged.upload_cid.is_none() {
+      let target = UploadTarget::Merged(merged.id);
+      let session = build_upload_session_from_merged(&merged);
+      reconcile_upload_session(
+        context,
+        task_id,
+        &target,
+        &merged.id.to_string(),
+        merged.video_path.as_deref().unwrap_or(""),
+        session,
+      );
+    }
   }
+}
 
-  Ok(result)
+fn reconcile_upload_session(
+  context: &SubmissionContext,
+  task_id: &str,
+  target: &UploadTarget,
+  target_id: &str,
+  file_path: &str,
+  session: Option<UploadSessionInfo>,
+) {
+  let Some(session) = session else {
+    return;
+  };
+  let file_size = fs::metadata(file_path).map(|metadata| metadata.len()).ok();
+  let resumable = file_size
+    .and_then(|size| sanitize_upload_session(Some(session.clone()), size))
+    .map(|valid| valid.uploaded_bytes <= valid.total_bytes)
+    .unwrap_or(false);
+  if resumable {
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "submission_recover_upload_resume task_id={} target={} uploaded={} total={} last_part={}",
+        task_id, target_id, session.uploaded_bytes, session.total_bytes, session.last_part_index
+      ),
+    );
+    return;
+  }
+  let _ = clear_upload_session(context, target);
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "submission_recover_upload_reset task_id={} target={} file_size={} session_total={}",
+      task_id,
+      target_id,
+      file_size.map(|size| size as i64).unwrap_or(-1),
+      session.total_bytes
+    ),
+  );
 }
 
-async fn recover_submission_tasks(context: SubmissionQueueContext) {
-  let submission_context = SubmissionContext {
+fn task_has_source_videos(context: &SubmissionContext, task_id: &str) -> bool {
+  context
+    .db
+    .with_conn(|conn| {
+      conn.query_row(
+        "SELECT COUNT(*) FROM task_source_video WHERE task_id = ?1",
+        [task_id],
+        |row| row.get::<_, i64>(0),
+      )
+    })
+    .map(|count| count > 0)
+    .unwrap_or(true)
+}
+
+async fn resume_edit_upload_segments(
+  context: &SubmissionQueueContext,
+  submission_context: &SubmissionContext,
+) {
+  let segment_ids = match load_uploading_edit_upload_segment_ids(submission_context) {
+    Ok(ids) => ids,
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("submission_recover_edit_segments_fail err={}", err),
+      );
+      return;
+    }
+  };
+  if segment_ids.is_empty() {
+    return;
+  }
+  let upload_context = UploadContext {
     db: context.db.clone(),
+    bilibili: context.bilibili.clone(),
+    login_store: context.login_store.clone(),
     app_log_path: context.app_log_path.clone(),
     edit_upload_state: context.edit_upload_state.clone(),
+    rate_limit_gate: upload_rate_limit_gate(),
   };
-  let mut processing_ids = Vec::new();
-  for status in ["PENDING", "CLIPPING", "MERGING", "SEGMENTING"] {
-    if let Ok(list) = load_task_ids_by_status(&submission_context, status) {
-      processing_ids.extend(list);
+  let auth = match load_auth_or_refresh(&upload_context, "submission_recover_edit_segments").await {
+    Ok(auth) => auth,
+    Err(err) => {
+      for segment_id in &segment_ids {
+        let _ = update_edit_upload_segment(submission_context, segment_id, |segment| {
+          segment.upload_status = "FAILED".to_string();
+        });
+      }
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "submission_recover_edit_segments_fail count={} err={}",
+          segment_ids.len(),
+          err
+        ),
+      );
+      return;
     }
-  }
-  let uploading_ids = load_task_ids_by_status(&submission_context, "UPLOADING").unwrap_or_default();
-
-  for task_id in uploading_ids {
-    let _ = update_submission_status(&submission_context, &task_id, "WAITING_UPLOAD");
+  };
+  for segment_id in segment_ids {
     append_log(
       &context.app_log_path,
-      &format!("submission_recover_uploading task_id={}", task_id),
+      &format!("submission_recover_edit_segment segment_id={}", segment_id),
     );
-  }
-
-  for task_id in processing_ids {
-    let _ = update_submission_status(&submission_context, &task_id, "PENDING");
-    let _ = set_workflow_instance_status(&submission_context, &task_id, "PENDING");
     let context_clone = submission_context.clone();
-    let task_id_clone = task_id.clone();
-    append_log(
-      &context.app_log_path,
-      &format!("submission_recover_workflow task_id={}", task_id),
-    );
+    let upload_context_clone = upload_context.clone();
+    let auth = auth.clone();
     tauri::async_runtime::spawn(async move {
-      let _ = run_submission_workflow(context_clone, task_id_clone).await;
+      let client = crate::config::build_http_client();
+      let result = upload_edit_segment_with_retry(
+        &context_clone,
+        &upload_context_clone,
+        &client,
+        &auth,
+        &segment_id,
+        upload_context_clone.app_log_path.as_ref(),
+        UPLOAD_SEGMENT_RETRY_LIMIT,
+      )
+      .await;
+      match result {
+        Ok(upload_result) => {
+          let _ = update_edit_upload_segment(&context_clone, &segment_id, |segment| {
+            segment.upload_status = "SUCCESS".to_string();
+            segment.cid = Some(upload_result.cid);
+            segment.file_name = Some(upload_result.filename);
+          });
+        }
+        Err(err) => {
+          let _ = update_edit_upload_segment(&context_clone, &segment_id, |segment| {
+            segment.upload_status = "FAILED".to_string();
+          });
+          append_log(
+            upload_context_clone.app_log_path.as_ref(),
+            &format!(
+              "submission_recover_edit_segment_fail segment_id={} err={}",
+              segment_id, err
+            ),
+          );
+        }
+      }
     });
   }
 }
 
-fn build_part_title(prefix: Option<&str>, index: usize) -> String {
+/// `padding` 为序号补零后的最少位数，0 表示不补零。
+fn build_part_title(prefix: Option<&str>, index: usize, padding: usize) -> String {
   let prefix = prefix.unwrap_or("").trim();
-  if prefix.is_empty() {
-    return format!("P{}", index);
-  }
-  format!("{}{}", prefix, index)
+  let prefix = if prefix.is_empty() { "P" } else { prefix };
+  format!("{}{:0width$}", prefix, index, width = padding)
+}
+
+fn load_segment_index_padding(db: &Db) -> usize {
+  load_download_settings_from_db(db)
+    .map(|settings| settings.segment_index_padding.max(0) as usize)
+    .unwrap_or(0)
 }
 
 fn resolve_existing_part_title(
   task: &SubmissionTaskRecord,
   part_name: &str,
   index: usize,
+  padding: usize,
 ) -> String {
   let trimmed = part_name.trim();
   if trimmed.is_empty() {
-    return build_part_title(task.segment_prefix.as_deref(), index);
+    return build_part_title(task.segment_prefix.as_deref(), index, padding);
   }
   if trimmed == format!("Part {}", index) {
-    return build_part_title(task.segment_prefix.as_deref(), index);
+    return build_part_title(task.segment_prefix.as_deref(), index, padding);
   }
   trimmed.to_string()
 }
@@ -3781,6 +8233,8 @@ fn build_progress_snapshot(
     total_bytes,
     progress: progress.min(100.0).max(0.0),
     last_part_index,
+    speed_bps: 0,
+    eta_seconds: None,
   }
 }
 
@@ -3862,16 +8316,50 @@ async fn wait_on_rate_limit(
   retry_after: Option<u64>,
   stage: &str,
 ) {
-  let wait_secs = limiter.next_wait_seconds(retry_after);
+  let mut wait = Duration::from_secs(limiter.next_wait_seconds(retry_after));
+  let shared = retry_after.unwrap_or(0) > 0;
+  if shared {
+    wait = context.rate_limit_gate.block_for(wait).max(wait);
+  } else if let Some(remaining) = context.rate_limit_gate.remaining() {
+    wait = wait.max(remaining);
+  }
+  let _ = update_upload_status_for_target(context, target, "RATE_LIMITED");
+  append_log(
+    log_path,
+    &format!(
+      "upload_rate_limited stage={} wait_secs={} count={} shared={}",
+      stage,
+      wait.as_secs(),
+      limiter.consecutive_406,
+      shared
+    ),
+  );
+  sleep(wait).await;
+  let _ = restore_upload_status_after_rate_limit(context, target);
+}
+
+/// 其他上传触发的共享限流窗口未结束时，先等待再发请求。
+async fn wait_for_shared_rate_limit(
+  context: &SubmissionContext,
+  target: &UploadTarget,
+  log_path: &Path,
+  stage: &str,
+) {
+  let Some(remaining) = context.rate_limit_gate.remaining() else {
+    return;
+  };
   let _ = update_upload_status_for_target(context, target, "RATE_LIMITED");
   append_log(
     log_path,
     &format!(
-      "upload_rate_limited stage={} wait_secs={} count={}",
-      stage, wait_secs, limiter.consecutive_406
+      "upload_rate_limit_shared_wait stage={} wait_secs={}",
+      stage,
+      remaining.as_secs()
     ),
   );
-  sleep(Duration::from_secs(wait_secs)).await;
+  while let Some(remaining) = context.rate_limit_gate.remaining() {
+    sleep(remaining).await;
+  }
   let _ = restore_upload_status_after_rate_limit(context, target);
 }
 
@@ -3916,12 +8404,20 @@ async fn upload_file_with_session(
       biz_id: session.biz_id,
       chunk_size: session.chunk_size,
       endpoint: session.endpoint.clone(),
+      endpoints: vec![session.endpoint.clone()],
       upos_uri: session.upos_uri.clone(),
     };
     update_upload_session(context, target, &session)?;
+    append_log(
+      log_path,
+      &format!(
+        "upload_session_resume file={} uploaded={} total={} last_part={}",
+        file_name, session.uploaded_bytes, session.total_bytes, session.last_part_index
+      ),
+    );
     (preupload, session.upload_id.clone(), resume_session)
   } else {
-    let preupload = preupload_video(
+    let mut preupload = preupload_video(
       context,
       target,
       client,
@@ -3932,6 +8428,7 @@ async fn upload_file_with_session(
       &mut limiter,
     )
     .await?;
+    preupload.endpoint = select_upload_endpoint(client, &preupload, log_path).await;
     let upload_id =
       post_video_meta(context, target, client, auth, &preupload, file_size, log_path, &mut limiter)
         .await?;
@@ -4012,7 +8509,7 @@ async fn upload_single_file(
   let session = sanitize_upload_session(resume_session, file_size);
 
   if session.is_some() {
-    if let Ok(result) = upload_file_with_session(
+    match upload_file_with_session(
       context,
       target,
       client,
@@ -4025,7 +8522,9 @@ async fn upload_single_file(
     )
     .await
     {
-      return Ok(result);
+      Ok(result) => return Ok(result),
+      Err(err) if is_upload_cancelled_error(&err) => return Err(err),
+      Err(_) => {}
     }
     let _ = clear_upload_session(context, target);
   }
@@ -4053,6 +8552,7 @@ async fn upload_segment_with_retry(
   log_path: &PathBuf,
   max_retries: u32,
 ) -> Result<UploadFileResult, String> {
+  let _active = ActiveSegmentUpload::begin(segment_id);
   let mut attempt: u32 = 0;
   let mut current_auth = auth.clone();
   loop {
@@ -4079,6 +8579,9 @@ async fn upload_segment_with_retry(
     {
       Ok(result) => return Ok(result),
       Err(err) => {
+        if is_upload_cancelled_error(&err) {
+          return Err(err);
+        }
         if is_auth_error(&err) {
           match refresh_auth(upload_context, "upload_segment").await {
             Ok(auth) => {
@@ -4140,6 +8643,9 @@ async fn upload_edit_segment_with_retry(
     {
       Ok(result) => return Ok(result),
       Err(err) => {
+        if is_upload_cancelled_error(&err) {
+          return Err(err);
+        }
         if is_auth_error(&err) {
           match refresh_auth(upload_context, "upload_edit_segment").await {
             Ok(auth) => {
@@ -4186,6 +8692,7 @@ async fn preupload_video(
   ];
 
   loop {
+    wait_for_shared_rate_limit(context, target, log_path, "preupload").await;
     let headers = build_headers(Some(&auth.cookie))?;
     let response = client
       .get(url)
@@ -4218,6 +8725,20 @@ async fn preupload_video(
       }
     }
     limiter.reset();
+    let endpoint = value
+      .get("endpoint")
+      .and_then(|val| val.as_str())
+      .ok_or_else(|| "预上传缺少endpoint".to_string())?
+      .to_string();
+    let mut endpoints = vec![endpoint.clone()];
+    if let Some(list) = value.get("endpoints").and_then(|val| val.as_array()) {
+      for item in list.iter().filter_map(|val| val.as_str()) {
+        let item = item.trim();
+        if !item.is_empty() && !endpoints.iter().any(|existing| existing == item) {
+          endpoints.push(item.to_string());
+        }
+      }
+    }
     return Ok(PreuploadInfo {
       auth: value
         .get("auth")
@@ -4232,11 +8753,8 @@ async fn preupload_video(
         .get("chunk_size")
         .and_then(|val| val.as_u64())
         .ok_or_else(|| "预上传缺少chunk_size".to_string())?,
-      endpoint: value
-        .get("endpoint")
-        .and_then(|val| val.as_str())
-        .ok_or_else(|| "预上传缺少endpoint".to_string())?
-        .to_string(),
+      endpoint,
+      endpoints,
       upos_uri: value
         .get("upos_uri")
         .and_then(|val| val.as_str())
@@ -4266,6 +8784,7 @@ async fn post_video_meta(
     ("biz_id", preupload.biz_id.to_string()),
   ];
   loop {
+    wait_for_shared_rate_limit(context, target, log_path, "post_meta").await;
     let mut headers = build_headers(Some(&auth.cookie))?;
     headers.insert(
       "X-Upos-Auth",
@@ -4310,6 +8829,80 @@ async fn post_video_meta(
   }
 }
 
+struct PendingChunk {
+  index: u64,
+  start: u64,
+  buffer: Vec<u8>,
+}
+
+enum ChunkPutOutcome {
+  Done,
+  RateLimited(Option<u64>),
+}
+
+/// 同一文件所有分片共用的上传参数。
+struct ChunkUploadContext<'a> {
+  client: &'a Client,
+  upload_url: &'a str,
+  cookie: &'a str,
+  upos_auth: &'a str,
+  upload_id: &'a str,
+  total_chunks: u64,
+  file_size: u64,
+}
+
+async fn put_upload_chunk(
+  context: &ChunkUploadContext<'_>,
+  chunk: PendingChunk,
+) -> (PendingChunk, Result<ChunkPutOutcome, String>) {
+  let result: Result<ChunkPutOutcome, String> = async {
+    let mut headers = build_headers(Some(context.cookie))?;
+    headers.insert(
+      "X-Upos-Auth",
+      HeaderValue::from_str(context.upos_auth).map_err(|_| "无效的X-Upos-Auth".to_string())?,
+    );
+    headers.insert(
+      "Content-Type",
+      HeaderValue::from_static("application/octet-stream"),
+    );
+    let current_size = chunk.buffer.len() as u64;
+    let params = vec![
+      ("partNumber", (chunk.index + 1).to_string()),
+      ("uploadId", context.upload_id.to_string()),
+      ("chunk", chunk.index.to_string()),
+      ("chunks", context.total_chunks.to_string()),
+      ("size", current_size.to_string()),
+      ("start", chunk.start.to_string()),
+      ("end", (chunk.start + current_size).to_string()),
+      ("total", context.file_size.to_string()),
+    ];
+    let response = context
+      .client
+      .put(context.upload_url)
+      .headers(headers)
+      .query(&params)
+      .body(chunk.buffer.clone())
+      .send()
+      .await
+      .map_err(|err| format!("上传分片失败: {}", err))?;
+    if response.status() == StatusCode::NOT_ACCEPTABLE {
+      return Ok(ChunkPutOutcome::RateLimited(retry_after_seconds(
+        response.headers(),
+      )));
+    }
+    let text = response
+      .text()
+      .await
+      .map_err(|err| format!("读取分片响应失败: {}", err))?;
+    if !text.contains("MULTIPART_PUT_SUCCESS") {
+      return Err("分片上传失败".to_string());
+    }
+    Ok(ChunkPutOutcome::Done)
+  }
+  .await;
+  (chunk, result)
+}
+
 async fn upload_video_chunks(
   context: &SubmissionContext,
   target: &UploadTarget,
@@ -4349,7 +8942,14 @@ async fn upload_video_chunks(
       .map_err(|err| format!("跳转文件位置失败: {}", err))?;
   }
 
+  let cancel_task_id = load_upload_target_task_id(context, target);
+  let session_key = target.key();
+  let _active_guard = ActiveUploadGuard {
+    key: session_key.clone(),
+  };
   let mut progress_limiter = UploadProgressLimiter::new();
+  let mut speed_tracker = UploadSpeedTracker::new();
+  speed_tracker.record(offset);
   if offset > 0 {
     let snapshot = build_progress_snapshot(offset, file_size, start_index.saturating_sub(1));
     if update_upload_progress(context, target, &snapshot).is_ok() {
@@ -4362,68 +8962,139 @@ async fn upload_video_chunks(
     }
   }
 
-  let mut index = start_index;
-  while index < total_chunks {
-    let remaining = file_size.saturating_sub(offset);
-    if remaining == 0 {
+  let concurrency = load_download_settings_from_db(&context.db)
+    .map(|settings| settings.chunk_concurrency)
+    .unwrap_or(DEFAULT_CHUNK_CONCURRENCY)
+    .max(1) as usize;
+  let mut next_index = start_index;
+  let mut contiguous_done = start_index;
+  let mut completed_parts: HashSet<u64> = HashSet::new();
+  let mut completed_bytes = offset;
+  let mut retry_queue: VecDeque<PendingChunk> = VecDeque::new();
+  let chunk_context = ChunkUploadContext {
+    client,
+    upload_url: &upload_url,
+    cookie: &auth.cookie,
+    upos_auth: &preupload.auth,
+    upload_id,
+    total_chunks,
+    file_size,
+  };
+  let mut in_flight = FuturesUnordered::new();
+
+  loop {
+    wait_for_shared_rate_limit(context, target, log_path, "upload_chunk").await;
+    while in_flight.len() < concurrency {
+      if cancel_task_id
+        .as_deref()
+        .map(is_upload_cancel_requested)
+        .unwrap_or(false)
+      {
+        append_log(
+          log_path,
+          &format!("upload_chunks_cancelled part={} total={}", next_index, total_chunks),
+        );
+        return Err(UPLOAD_CANCELLED_MESSAGE.to_string());
+      }
+      let chunk = if let Some(chunk) = retry_queue.pop_front() {
+        chunk
+      } else if next_index < total_chunks {
+        let start = next_index.saturating_mul(chunk_size);
+        let remaining = file_size.saturating_sub(start);
+        if remaining == 0 {
+          next_index = total_chunks;
+          break;
+        }
+        let current_size = std::cmp::min(chunk_size, remaining) as usize;
+        let mut buffer = vec![0u8; current_size];
+        file
+          .read_exact(&mut buffer)
+          .await
+          .map_err(|err| format!("读取分片失败: {}", err))?;
+        let chunk = PendingChunk {
+          index: next_index,
+          start,
+          buffer,
+        };
+        next_index = next_index.saturating_add(1);
+        chunk
+      } else {
+        break;
+      };
+      in_flight.push(put_upload_chunk(&chunk_context, chunk));
+    }
+    if in_flight.is_empty() {
       break;
     }
-    let current_size = std::cmp::min(chunk_size, remaining) as usize;
-    let mut buffer = vec![0u8; current_size];
-    file
-      .read_exact(&mut buffer)
-      .await
-      .map_err(|err| format!("读取分片失败: {}", err))?;
-    let start = offset;
-    let end = offset + current_size as u64;
-    let params = vec![
-      ("partNumber", (index + 1).to_string()),
-      ("uploadId", upload_id.to_string()),
-      ("chunk", index.to_string()),
-      ("chunks", total_chunks.to_string()),
-      ("size", current_size.to_string()),
-      ("start", start.to_string()),
-      ("end", end.to_string()),
-      ("total", file_size.to_string()),
-    ];
-
-    loop {
-      let mut headers = build_headers(Some(&auth.cookie))?;
-      headers.insert(
-        "X-Upos-Auth",
-        HeaderValue::from_str(&preupload.auth).map_err(|_| "无效的X-Upos-Auth".to_string())?,
-      );
-      headers.insert(
-        "Content-Type",
-        HeaderValue::from_static("application/octet-stream"),
-      );
 
-      let response = client
-        .put(upload_url.clone())
-        .headers(headers)
-        .query(&params)
-        .body(buffer.clone())
-        .send()
-        .await
-        .map_err(|err| format!("上传分片失败: {}", err))?;
-      if response.status() == StatusCode::NOT_ACCEPTABLE {
-        let retry_after = retry_after_seconds(response.headers());
+    let (chunk, result) = tokio::select! {
+      Some(item) = in_flight.next() => item,
+      _ = wait_for_upload_cancel(cancel_task_id.as_deref()) => {
+        append_log(
+          log_path,
+          &format!(
+            "upload_chunks_cancelled part={} total={} in_flight={}",
+            contiguous_done,
+            total_chunks,
+            in_flight.len()
+          ),
+        );
+        return Err(UPLOAD_CANCELLED_MESSAGE.to_string());
+      }
+    };
+    let contiguous_offset = contiguous_done.saturating_mul(chunk_size).min(file_size);
+    match result? {
+      ChunkPutOutcome::RateLimited(retry_after) => {
+        // 退避期间停止派发新分片，让限流等待对所有在途分片生效
+        let snapshot = build_progress_snapshot(
+          contiguous_offset,
+          file_size,
+          contiguous_done.saturating_sub(1),
+        );
+        let _ = update_upload_progress(context, target, &snapshot);
+        speed_tracker.reset();
         wait_on_rate_limit(context, target, limiter, log_path, retry_after, "upload_chunk").await;
+        speed_tracker.record(completed_bytes);
+        retry_queue.push_back(chunk);
         continue;
       }
-      let text = response
-        .text()
-        .await
-        .map_err(|err| format!("读取分片响应失败: {}", err))?;
-      if !text.contains("MULTIPART_PUT_SUCCESS") {
-        return Err("分片上传失败".to_string());
+      ChunkPutOutcome::Done => {
+        limiter.reset();
       }
-      limiter.reset();
-      break;
     }
 
-    offset = end;
-    let snapshot = build_progress_snapshot(offset, file_size, index);
+    completed_bytes = completed_bytes.saturating_add(chunk.buffer.len() as u64);
+    completed_parts.insert(chunk.index);
+    while completed_parts.remove(&contiguous_done) {
+      contiguous_done = contiguous_done.saturating_add(1);
+    }
+    speed_tracker.record(completed_bytes);
+    if contiguous_done == 0 {
+      continue;
+    }
+    // 只持久化连续完成的前缀，按 last_part_index 续传时不会跳过中间未完成的分片
+    let contiguous_offset = contiguous_done.saturating_mul(chunk_size).min(file_size);
+    let mut snapshot = build_progress_snapshot(contiguous_offset, file_size, contiguous_done - 1);
+    speed_tracker.apply(&mut snapshot);
+    if let Ok(mut sessions) = active_upload_sessions().lock() {
+      sessions.insert(
+        session_key.clone(),
+        (
+          target.clone(),
+          UploadSessionInfo {
+            upload_id: upload_id.to_string(),
+            biz_id: preupload.biz_id,
+            chunk_size,
+            endpoint: preupload.endpoint.clone(),
+            auth: preupload.auth.clone(),
+            upos_uri: preupload.upos_uri.clone(),
+            uploaded_bytes: contiguous_offset,
+            total_bytes: file_size,
+            last_part_index: contiguous_done - 1,
+          },
+        ),
+      );
+    }
     if progress_limiter.should_persist(&snapshot) {
       if update_upload_progress(context, target, &snapshot).is_ok() {
         progress_limiter.mark_saved(&snapshot);
@@ -4432,12 +9103,11 @@ async fn upload_video_chunks(
           log_path,
           &format!(
             "upload_progress_skip offset={} file_size={} part={}",
-            offset, file_size, index
+            contiguous_offset, file_size, chunk.index
           ),
         );
       }
     }
-    index = index.saturating_add(1);
   }
 
   Ok(total_chunks)
@@ -4472,6 +9142,7 @@ async fn end_upload(
   }
   let body = serde_json::json!({ "parts": parts });
   loop {
+    wait_for_shared_rate_limit(context, target, log_path, "end_upload").await;
     let mut headers = build_headers(Some(&auth.cookie))?;
     headers.insert(
       "X-Upos-Auth",
@@ -4527,6 +9198,62 @@ fn remove_file_extension(name: &str) -> String {
   }
 }
 
+async fn select_upload_endpoint(
+  client: &Client,
+  preupload: &PreuploadInfo,
+  log_path: &Path,
+) -> String {
+  let primary = preupload.endpoint.clone();
+  if preupload.endpoints.len() <= 1 {
+    return primary;
+  }
+  let mut probes = preupload
+    .endpoints
+    .iter()
+    .map(|endpoint| {
+      let endpoint = endpoint.clone();
+      let url = build_upload_url(&endpoint, &preupload.upos_uri);
+      async move {
+        let started = Instant::now();
+        let result = client
+          .request(reqwest::Method::OPTIONS, url)
+          .timeout(Duration::from_secs(UPLOAD_ENDPOINT_PROBE_TIMEOUT_SECS))
+          .send()
+          .await;
+        (endpoint, result.map(|_| started.elapsed()))
+      }
+    })
+    .collect::<FuturesUnordered<_>>();
+  while let Some((endpoint, result)) = probes.next().await {
+    match result {
+      Ok(elapsed) => {
+        append_log(
+          log_path,
+          &format!(
+            "upload_endpoint_selected endpoint={} primary={} elapsed_ms={} candidates={}",
+            endpoint,
+            primary,
+            elapsed.as_millis(),
+            preupload.endpoints.len()
+          ),
+        );
+        return endpoint;
+      }
+      Err(err) => {
+        append_log(
+          log_path,
+          &format!("upload_endpoint_probe_fail endpoint={} err={}", endpoint, err),
+        );
+      }
+    }
+  }
+  append_log(
+    log_path,
+    &format!("upload_endpoint_fallback endpoint={}", primary),
+  );
+  primary
+}
+
 fn build_upload_url(endpoint: &str, upos_uri: &str) -> String {
   let mut path = upos_uri.trim_start_matches("upos://").to_string();
   if !path.starts_with('/') {
@@ -4693,14 +9420,17 @@ async fn submit_video_add(
   parts: &[UploadedVideoPart],
   csrf: &str,
 ) -> Result<SubmissionSubmitResult, String> {
-  let payload = build_add_payload(task, parts);
+  let payload = build_add_payload(task, parts)?;
+  let dtime = payload.get("dtime").and_then(|value| value.as_i64());
   append_log(
     &context.app_log_path,
     &format!(
-      "submission_submit_start title={} season_id={} parts={}",
+      "submission_submit_start title={} season_id={} parts={} dtime={} dtime_clamped={}",
       task.title,
       task.collection_id.unwrap_or(0),
-      parts.len()
+      parts.len(),
+      dtime.unwrap_or(0),
+      dtime != task.scheduled_publish_at
     ),
   );
   let params = vec![
@@ -4771,9 +9501,20 @@ fn build_submission_videos(parts: &[UploadedVideoPart]) -> Vec<Value> {
     .collect()
 }
 
-fn build_add_payload(task: &SubmissionTaskRecord, parts: &[UploadedVideoPart]) -> Value {
-  let copyright = if task.video_type == "ORIGINAL" { 1 } else { 2 };
-  let tags = task.tags.clone().unwrap_or_default();
+fn build_add_payload(
+  task: &SubmissionTaskRecord,
+  parts: &[UploadedVideoPart],
+) -> Result<Value, String> {
+  let copyright = resolve_video_type_copyright(&task.video_type);
+  let normalized_tags = normalize_tags(task.tags.as_deref().unwrap_or(""));
+  if let Some(violation) = normalized_tags.violation() {
+    return Err(violation);
+  }
+  let tags = normalized_tags.joined();
+  let scheduled_publish_at = task
+    .scheduled_publish_at
+    .map(resolve_submit_publish_at)
+    .transpose()?;
   let desc = task.description.clone().unwrap_or_default();
   let cover = task.cover_url.clone().unwrap_or_default();
   let videos = build_submission_videos(parts);
@@ -4809,13 +9550,16 @@ fn build_add_payload(task: &SubmissionTaskRecord, parts: &[UploadedVideoPart]) -
       payload["season_id"] = serde_json::json!(collection_id);
     }
   }
+  if let Some(scheduled_publish_at) = scheduled_publish_at {
+    payload["dtime"] = serde_json::json!(scheduled_publish_at);
+  }
 
-  payload
+  Ok(payload)
 }
 
 fn build_edit_payload(task: &SubmissionTaskRecord, parts: &[UploadedVideoPart], aid: i64) -> Value {
-  let copyright = if task.video_type == "ORIGINAL" { 1 } else { 2 };
-  let tags = task.tags.clone().unwrap_or_default();
+  let copyright = resolve_video_type_copyright(&task.video_type);
+  let tags = normalize_tags(task.tags.as_deref().unwrap_or("")).joined();
   let desc = task.description.clone().unwrap_or_default();
   let cover = task.cover_url.clone().unwrap_or_default();
   let videos = build_submission_videos(parts);
@@ -4916,9 +9660,8 @@ async fn add_video_to_collection(
   if aid <= 0 || cid <= 0 {
     return Err("合集绑定缺少AID或CID".to_string());
   }
-  let section_id = fetch_collection_section_id(context, auth, season_id)
-    .await
-    .unwrap_or(0);
+  let section_id =
+    crate::commands::video::resolve_collection_section_id(&context.bilibili, auth, season_id).await?;
   append_log(
     &context.app_log_path,
     &format!(
@@ -4967,9 +9710,8 @@ async fn switch_video_collection(
   if season_id <= 0 || aid <= 0 {
     return Err("合集切换缺少season_id或aid".to_string());
   }
-  let section_id = fetch_collection_section_id(context, auth, season_id)
-    .await
-    .unwrap_or(0);
+  let section_id =
+    crate::commands::video::resolve_collection_section_id(&context.bilibili, auth, season_id).await?;
   append_log(
     &context.app_log_path,
     &format!(
@@ -5000,47 +9742,12 @@ async fn switch_video_collection(
   Ok(())
 }
 
-async fn fetch_collection_section_id(
-  context: &UploadContext,
-  auth: &AuthInfo,
-  season_id: i64,
-) -> Option<i64> {
-  let url = "https://member.bilibili.com/x2/creative/web/seasons";
-  let params = vec![
-    ("pn".to_string(), "1".to_string()),
-    ("ps".to_string(), "100".to_string()),
-    ("order".to_string(), "desc".to_string()),
-    ("sort".to_string(), "mtime".to_string()),
-    ("filter".to_string(), "1".to_string()),
-  ];
-  let data = context.bilibili.get_json(url, &params, Some(auth), false).await.ok()?;
-  let seasons = data.get("seasons").and_then(|value| value.as_array())?;
-  for item in seasons {
-    let season = item.get("season")?;
-    let id = season.get("id").and_then(|value| value.as_i64())?;
-    if id != season_id {
-      continue;
-    }
-    let sections = item
-      .get("sections")
-      .and_then(|value| value.get("sections"))
-      .and_then(|value| value.as_array())
-      .and_then(|list| list.first())
-      .and_then(|section| section.get("id"))
-      .and_then(|value| value.as_i64());
-    return Some(sections.unwrap_or(0));
-  }
-  None
-}
-
-
 fn build_headers(cookie: Option<&str>) -> Result<HeaderMap, String> {
   let mut headers = HeaderMap::new();
   headers.insert(
     USER_AGENT,
-    HeaderValue::from_static(
-      "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/132.0.0.0 Safari/537.36 Edg/132.0.0.0",
-    ),
+    HeaderValue::from_str(&crate::config::user_agent_or(crate::config::DEFAULT_USER_AGENT))
+      .map_err(|_| "无效的User-Agent".to_string())?,
   );
   headers.insert(ACCEPT, HeaderValue::from_static("application/json, text/javascript, */*; q=0.01"));
   headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("zh-CN"));
@@ -5388,15 +10095,28 @@ fn append_output_segments(
   part_order_start: i64,
   name_start_index: usize,
 ) -> Result<(), String> {
+  let padding = load_segment_index_padding(context.db.as_ref());
+  let segments: Vec<(PathBuf, String)> = segments
+    .iter()
+    .enumerate()
+    .map(|(index, segment)| {
+      let part_name = build_part_title(prefix, name_start_index + index, padding);
+      let segment = if padding > 0 {
+        rename_segment_to_part_title(context, task_id, segment, &part_name)
+      } else {
+        segment.clone()
+      };
+      (segment, part_name)
+    })
+    .collect();
   context
     .db
     .with_conn(|conn| {
-      for (index, segment) in segments.iter().enumerate() {
+      for (index, (segment, part_name)) in segments.iter().enumerate() {
         let segment_id = uuid::Uuid::new_v4().to_string();
         let file_name = segment.file_name().and_then(|name| name.to_str()).unwrap_or("segment.mp4");
         let total_bytes = fs::metadata(segment).map(|meta| meta.len()).unwrap_or(0);
         let part_order = part_order_start + index as i64;
-        let part_name = build_part_title(prefix, name_start_index + index);
         conn.execute(
           "INSERT INTO task_output_segment (segment_id, task_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index) \
            VALUES (?1, ?2, ?3, ?4, ?5, 'PENDING', NULL, ?6, 0, 0, ?7, NULL, 0, NULL, NULL, NULL, 0, 0)",
@@ -5416,11 +10136,59 @@ fn append_output_segments(
     .map_err(|err| err.to_string())
 }
 
+/// 开启补零时把本地分段文件改名为分P标题，保证上传文件名与分P标题序号一致；
+/// 目标已存在或改名失败时保留原文件名。
+fn rename_segment_to_part_title(
+  context: &SubmissionContext,
+  task_id: &str,
+  segment: &Path,
+  part_name: &str,
+) -> PathBuf {
+  let extension = segment
+    .extension()
+    .and_then(|value| value.to_str())
+    .unwrap_or("mp4");
+  let target = segment.with_file_name(format!("{}.{}", sanitize_filename(part_name), extension));
+  if target == segment {
+    return segment.to_path_buf();
+  }
+  if target.exists() {
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "submission_segment_rename_skip task_id={} from={} to={} reason=target_exists",
+        task_id,
+        segment.to_string_lossy(),
+        target.to_string_lossy()
+      ),
+    );
+    return segment.to_path_buf();
+  }
+  match fs::rename(segment, &target) {
+    Ok(()) => target,
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "submission_segment_rename_fail task_id={} from={} to={} err={}",
+          task_id,
+          segment.to_string_lossy(),
+          target.to_string_lossy(),
+          err
+        ),
+      );
+      segment.to_path_buf()
+    }
+  }
+}
+
 fn save_output_segments(
   context: &SubmissionContext,
   task_id: &str,
   segments: &[PathBuf],
+  prefix: Option<&str>,
 ) -> Result<(), String> {
+  let padding = load_segment_index_padding(context.db.as_ref());
   context
     .db
     .with_conn(|conn| {
@@ -5435,7 +10203,7 @@ fn save_output_segments(
           (
             segment_id,
             task_id,
-            format!("Part {}", index + 1),
+            build_part_title(prefix, index + 1, padding),
             segment.to_string_lossy().to_string(),
             (index + 1) as i64,
             file_name,
@@ -5453,17 +10221,19 @@ fn update_upload_progress(
   target: &UploadTarget,
   snapshot: &UploadProgressSnapshot,
 ) -> Result<(), String> {
-  match target {
+  let result = match target {
     UploadTarget::Segment(segment_id) => context
       .db
       .with_conn(|conn| {
         conn.execute(
-          "UPDATE task_output_segment SET upload_progress = ?1, upload_uploaded_bytes = ?2, upload_total_bytes = ?3, upload_last_part_index = ?4 WHERE segment_id = ?5",
+          "UPDATE task_output_segment SET upload_progress = ?1, upload_uploaded_bytes = ?2, upload_total_bytes = ?3, upload_last_part_index = ?4, upload_speed_bps = ?5, upload_eta_seconds = ?6 WHERE segment_id = ?7",
           (
             snapshot.progress,
             snapshot.uploaded_bytes as i64,
             snapshot.total_bytes as i64,
             snapshot.last_part_index as i64,
+            snapshot.speed_bps as i64,
+            snapshot.eta_seconds.map(|value| value as i64),
             segment_id,
           ),
         )?;
@@ -5494,9 +10264,15 @@ fn update_upload_progress(
         segment.upload_uploaded_bytes = snapshot.uploaded_bytes as i64;
         segment.upload_total_bytes = snapshot.total_bytes as i64;
         segment.upload_last_part_index = snapshot.last_part_index as i64;
+        segment.upload_speed_bps = snapshot.speed_bps as i64;
+        segment.upload_eta_seconds = snapshot.eta_seconds.map(|value| value as i64);
       },
     ),
+  };
+  if result.is_ok() {
+    emit_upload_event(context, target, None, Some(snapshot));
   }
+  result
 }
 
 fn update_upload_status_for_target(
@@ -5504,7 +10280,7 @@ fn update_upload_status_for_target(
   target: &UploadTarget,
   status: &str,
 ) -> Result<(), String> {
-  match target {
+  let result = match target {
     UploadTarget::Segment(segment_id) => update_segment_upload_status(context, segment_id, status),
     UploadTarget::Merged(_) => Ok(()),
     UploadTarget::EditSegment(segment_id) => update_edit_upload_segment(
@@ -5514,6 +10290,39 @@ fn update_upload_status_for_target(
         segment.upload_status = status.to_string();
       },
     ),
+  };
+  if result.is_ok() {
+    emit_upload_event(context, target, Some(status), None);
+  }
+  result
+}
+
+fn emit_upload_event(
+  context: &SubmissionContext,
+  target: &UploadTarget,
+  status: Option<&str>,
+  snapshot: Option<&UploadProgressSnapshot>,
+) {
+  let (target_type, segment_id) = match target {
+    UploadTarget::Segment(segment_id) => ("segment", segment_id.clone()),
+    UploadTarget::Merged(merged_id) => ("merged", merged_id.to_string()),
+    UploadTarget::EditSegment(segment_id) => ("editSegment", segment_id.clone()),
+  };
+  let payload = UploadProgressEvent {
+    target_type,
+    segment_id,
+    upload_status: status.map(|value| value.to_string()),
+    upload_progress: snapshot.map(|value| value.progress),
+    upload_uploaded_bytes: snapshot.map(|value| value.uploaded_bytes as i64),
+    upload_total_bytes: snapshot.map(|value| value.total_bytes as i64),
+    upload_speed_bps: snapshot.map(|value| value.speed_bps as i64),
+    upload_eta_seconds: snapshot.and_then(|value| value.eta_seconds.map(|eta| eta as i64)),
+  };
+  if let Err(err) = app_events::emit(UPLOAD_PROGRESS_EVENT, payload) {
+    append_log(
+      &context.app_log_path,
+      &format!("upload_progress_emit_fail target={} err={}", target_type, err),
+    );
   }
 }
 
@@ -5626,7 +10435,7 @@ fn clear_upload_session(context: &SubmissionContext, target: &UploadTarget) -> R
       .db
       .with_conn(|conn| {
         conn.execute(
-          "UPDATE task_output_segment SET upload_session_id = NULL, upload_biz_id = 0, upload_endpoint = NULL, upload_auth = NULL, upload_uri = NULL, upload_chunk_size = 0, upload_uploaded_bytes = 0, upload_total_bytes = 0, upload_progress = 0, upload_last_part_index = 0 WHERE segment_id = ?1",
+          "UPDATE task_output_segment SET upload_session_id = NULL, upload_biz_id = 0, upload_endpoint = NULL, upload_auth = NULL, upload_uri = NULL, upload_chunk_size = 0, upload_uploaded_bytes = 0, upload_total_bytes = 0, upload_progress = 0, upload_last_part_index = 0, upload_speed_bps = 0, upload_eta_seconds = NULL WHERE segment_id = ?1",
           [segment_id],
         )?;
         Ok(())
@@ -5656,6 +10465,8 @@ fn clear_upload_session(context: &SubmissionContext, target: &UploadTarget) -> R
         segment.upload_total_bytes = 0;
         segment.upload_progress = 0.0;
         segment.upload_last_part_index = 0;
+        segment.upload_speed_bps = 0;
+        segment.upload_eta_seconds = None;
       },
     ),
   }
@@ -5672,7 +10483,7 @@ fn update_segment_upload_result(
     .db
     .with_conn(|conn| {
       conn.execute(
-        "UPDATE task_output_segment SET upload_status = ?1, cid = ?2, file_name = ?3 WHERE segment_id = ?4",
+        "UPDATE task_output_segment SET upload_status = ?1, cid = ?2, file_name = ?3, upload_speed_bps = 0, upload_eta_seconds = NULL WHERE segment_id = ?4",
         (status, cid, file_name, segment_id),
       )?;
       Ok(())
@@ -5707,7 +10518,10 @@ fn update_segment_upload_status(
     .db
     .with_conn(|conn| {
       conn.execute(
-        "UPDATE task_output_segment SET upload_status = ?1 WHERE segment_id = ?2",
+        "UPDATE task_output_segment SET upload_status = ?1, \
+         upload_speed_bps = CASE WHEN ?1 = 'UPLOADING' THEN upload_speed_bps ELSE 0 END, \
+         upload_eta_seconds = CASE WHEN ?1 = 'UPLOADING' THEN upload_eta_seconds ELSE NULL END \
+         WHERE segment_id = ?2",
         (status, segment_id),
       )?;
       Ok(())
@@ -5742,7 +10556,8 @@ fn load_output_segment_by_id(
       let mut stmt = conn.prepare(
         "SELECT segment_id, task_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, \
                 upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, \
-                upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index \
+                upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index, \
+                upload_speed_bps, upload_eta_seconds \
          FROM task_output_segment WHERE segment_id = ?1",
       )?;
       let result = stmt
@@ -5766,6 +10581,8 @@ fn load_output_segment_by_id(
             upload_uri: row.get(15)?,
             upload_chunk_size: row.get(16)?,
             upload_last_part_index: row.get(17)?,
+            upload_speed_bps: row.get(18)?,
+            upload_eta_seconds: row.get(19)?,
           })
         })
         .ok();
@@ -6037,6 +10854,108 @@ struct IntegratedDownloadStats {
   failed: i64,
 }
 
+fn save_workflow_stats(
+  context: &SubmissionContext,
+  task_id: &str,
+  clip_duration: Duration,
+  merge_duration: Duration,
+  segment_duration: Duration,
+  segment_count: usize,
+) -> Result<(), String> {
+  let now = now_rfc3339();
+  context
+    .db
+    .with_conn(|conn| {
+      conn.execute(
+        "INSERT INTO task_stats (task_id, clip_duration_ms, merge_duration_ms, segment_duration_ms, segment_count, created_at, updated_at) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6) \
+         ON CONFLICT(task_id) DO UPDATE SET \
+         clip_duration_ms = excluded.clip_duration_ms, \
+         merge_duration_ms = excluded.merge_duration_ms, \
+         segment_duration_ms = excluded.segment_duration_ms, \
+         segment_count = excluded.segment_count, \
+         updated_at = excluded.updated_at",
+        (
+          task_id,
+          clip_duration.as_millis() as i64,
+          merge_duration.as_millis() as i64,
+          segment_duration.as_millis() as i64,
+          segment_count as i64,
+          &now,
+        ),
+      )?;
+      Ok(())
+    })
+    .map_err(|err| err.to_string())
+}
+
+fn save_upload_stats(
+  context: &SubmissionContext,
+  task_id: &str,
+  upload_bytes: u64,
+  upload_duration: Duration,
+) -> Result<(), String> {
+  let now = now_rfc3339();
+  context
+    .db
+    .with_conn(|conn| {
+      conn.execute(
+        "INSERT INTO task_stats (task_id, upload_bytes, upload_duration_ms, created_at, updated_at) \
+         VALUES (?1, ?2, ?3, ?4, ?4) \
+         ON CONFLICT(task_id) DO UPDATE SET \
+         upload_bytes = excluded.upload_bytes, \
+         upload_duration_ms = excluded.upload_duration_ms, \
+         updated_at = excluded.updated_at",
+        (
+          task_id,
+          upload_bytes as i64,
+          upload_duration.as_millis() as i64,
+          &now,
+        ),
+      )?;
+      Ok(())
+    })
+    .map_err(|err| err.to_string())
+}
+
+fn load_task_stats(
+  context: &SubmissionContext,
+  task_id: &str,
+) -> Result<Option<TaskStatsRecord>, String> {
+  context
+    .db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT task_id, clip_duration_ms, merge_duration_ms, segment_duration_ms, segment_count, \
+           upload_bytes, upload_duration_ms, updated_at FROM task_stats WHERE task_id = ?1",
+          [task_id],
+          |row| {
+            let upload_bytes: i64 = row.get(5)?;
+            let upload_duration_ms: i64 = row.get(6)?;
+            let upload_speed_bps = if upload_duration_ms > 0 {
+              upload_bytes.saturating_mul(1000) / upload_duration_ms
+            } else {
+              0
+            };
+            Ok(TaskStatsRecord {
+              task_id: row.get(0)?,
+              clip_duration_ms: row.get(1)?,
+              merge_duration_ms: row.get(2)?,
+              segment_duration_ms: row.get(3)?,
+              segment_count: row.get(4)?,
+              upload_bytes,
+              upload_duration_ms,
+              upload_speed_bps,
+              updated_at: row.get(7)?,
+            })
+          },
+        )
+        .optional()
+    })
+    .map_err(|err| err.to_string())
+}
+
 fn load_integrated_download_stats(
   context: &SubmissionContext,
   task_id: &str,
@@ -6173,7 +11092,7 @@ fn load_latest_merged_video(
         "SELECT id, task_id, file_name, video_path, duration, status, \
                 upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_cid, upload_file_name, \
                 upload_session_id, upload_biz_id, upload_endpoint, upload_auth, upload_uri, upload_chunk_size, \
-                upload_last_part_index, create_time, update_time \
+                upload_last_part_index, create_time, update_time, content_hash \
          FROM merged_video WHERE task_id = ?1 ORDER BY id DESC LIMIT 1",
       )?;
       let result = stmt
@@ -6197,6 +11116,7 @@ fn load_latest_merged_video(
             upload_uri: row.get(15)?,
             upload_chunk_size: row.get(16)?,
             upload_last_part_index: row.get(17)?,
+            content_hash: row.get(20)?,
             create_time: row.get(18)?,
             update_time: row.get(19)?,
           })
@@ -6226,16 +11146,39 @@ fn update_submission_status(
 }
 
 fn resolve_submission_base_dir(context: &SubmissionContext, task_id: &str) -> PathBuf {
-  let configured = load_download_settings_from_db(&context.db)
-    .map(|settings| settings.download_path)
+  resolve_download_root(&context.db).join(task_id)
+}
+
+fn load_task_id_by_client_request_id(
+  context: &SubmissionContext,
+  client_request_id: &str,
+) -> Result<Option<String>, String> {
+  context
+    .db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT task_id FROM submission_task WHERE client_request_id = ?1",
+          [client_request_id],
+          |row| row.get(0),
+        )
+        .optional()
+    })
+    .map_err(|err| err.to_string())
+}
+
+fn load_existing_creation_result(context: &SubmissionContext, task_id: String) -> TaskCreationResult {
+  let workflow_instance_id = load_workflow_instance_id(context, &task_id).ok().flatten();
+  let workflow_status = load_workflow_status(context, &task_id)
     .ok()
-    .unwrap_or_default();
-  let base = if configured.trim().is_empty() {
-    default_download_dir()
-  } else {
-    PathBuf::from(configured.trim())
-  };
-  base.join(task_id)
+    .flatten()
+    .map(|record| record.status);
+  TaskCreationResult {
+    task_id,
+    workflow_instance_id,
+    workflow_status,
+    workflow_error: None,
+  }
 }
 
 fn load_workflow_status(
@@ -6279,6 +11222,22 @@ fn load_latest_workflow_type(
     .map_err(|err| err.to_string())
 }
 
+fn load_workflow_instance_id(
+  context: &SubmissionContext,
+  task_id: &str,
+) -> Result<Option<String>, String> {
+  context
+    .db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT instance_id FROM workflow_instances WHERE task_id = ?1 ORDER BY created_at DESC LIMIT 1",
+      )?;
+      let result = stmt.query_row([task_id], |row| row.get(0)).optional()?;
+      Ok(result)
+    })
+    .map_err(|err| err.to_string())
+}
+
 fn reset_workflow_instances(
   context: &SubmissionContext,
   task_id: &str,
@@ -6313,10 +11272,24 @@ fn set_workflow_instance_status(
   let updated = context
     .db
     .with_conn(|conn| {
+      let previous = load_latest_workflow_state(conn, task_id)?;
       let updated = conn.execute(
         "UPDATE workflow_instances SET status = ?1, updated_at = ?2 WHERE task_id = ?3",
         (status, &now, task_id),
       )?;
+      if let Some(previous) = previous {
+        if previous.status != status {
+          let current_step = previous.current_step.clone();
+          insert_workflow_transition_log(
+            conn,
+            &previous,
+            status,
+            current_step.as_deref(),
+            previous.progress,
+            &now,
+          )?;
+        }
+      }
       Ok(updated)
     })
     .map_err(|err| err.to_string())?;
@@ -6352,6 +11325,10 @@ struct WorkflowSettings {
   enable_segmentation: bool,
   segment_duration_seconds: i64,
   segment_prefix: Option<String>,
+  encoder_args: Option<Vec<String>>,
+  normalize_before_merge: Option<ClipNormalizeConfig>,
+  output_container: OutputContainer,
+  frame_accurate: bool,
 }
 
 fn load_workflow_settings(context: &SubmissionContext, task_id: &str) -> WorkflowSettings {
@@ -6385,10 +11362,22 @@ fn parse_workflow_settings(config: Option<Value>) -> WorkflowSettings {
       .map(|value| value.trim().to_string())
       .filter(|value| !value.is_empty());
 
+    let encoder_args = parse_encoder_args(&config).ok().flatten();
+    let normalize_before_merge = parse_normalize_before_merge(config.get("normalizeBeforeMerge"));
+    let output_container = parse_output_container(&config).unwrap_or_default();
+    let frame_accurate = config
+      .get("frameAccurate")
+      .and_then(|value| value.as_bool())
+      .unwrap_or(false);
+
     return WorkflowSettings {
       enable_segmentation,
       segment_duration_seconds,
       segment_prefix,
+      encoder_args,
+      normalize_before_merge,
+      output_container,
+      frame_accurate,
     };
   }
 
@@ -6396,7 +11385,80 @@ fn parse_workflow_settings(config: Option<Value>) -> WorkflowSettings {
     enable_segmentation: false,
     segment_duration_seconds: 133,
     segment_prefix: None,
+    encoder_args: None,
+    normalize_before_merge: None,
+    output_container: OutputContainer::default(),
+    frame_accurate: false,
+  }
+}
+
+fn parse_output_container(config: &Value) -> Result<OutputContainer, String> {
+  match config.get("outputContainer") {
+    Some(Value::String(text)) if text.trim().is_empty() => Ok(OutputContainer::default()),
+    Some(Value::String(text)) => OutputContainer::parse(text)
+      .ok_or_else(|| format!("不支持的输出格式: {}", text.trim())),
+    Some(Value::Null) | None => Ok(OutputContainer::default()),
+    Some(_) => Err("输出格式配置错误".to_string()),
+  }
+}
+
+fn parse_normalize_before_merge(value: Option<&Value>) -> Option<ClipNormalizeConfig> {
+  match value {
+    Some(Value::Bool(true)) => Some(ClipNormalizeConfig::default()),
+    Some(Value::String(text)) if text.trim().eq_ignore_ascii_case("auto") => {
+      Some(ClipNormalizeConfig::default())
+    }
+    Some(Value::Object(map)) => {
+      let enabled = map
+        .get("enabled")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(true);
+      if !enabled {
+        return None;
+      }
+      let (mut width, mut height) = map
+        .get("resolution")
+        .and_then(|value| value.as_str())
+        .and_then(|value| value.trim().split_once(['x', 'X']))
+        .map(|(w, h)| (w.trim().parse::<i64>().ok(), h.trim().parse::<i64>().ok()))
+        .unwrap_or((None, None));
+      if let Some(value) = map.get("width").and_then(|value| value.as_i64()) {
+        width = Some(value);
+      }
+      if let Some(value) = map.get("height").and_then(|value| value.as_i64()) {
+        height = Some(value);
+      }
+      let fps = map.get("fps").and_then(|value| value.as_f64());
+      Some(ClipNormalizeConfig {
+        width: width.filter(|value| *value > 0),
+        height: height.filter(|value| *value > 0),
+        fps: fps.filter(|value| *value > 0.0),
+      })
+    }
+    _ => None,
+  }
+}
+
+fn parse_encoder_args(config: &Value) -> Result<Option<Vec<String>>, String> {
+  let list = match config.get("encoderArgs") {
+    Some(Value::Array(list)) => list,
+    Some(Value::Null) | None => return Ok(None),
+    Some(_) => return Err("编码参数格式错误".to_string()),
+  };
+  if list.is_empty() {
+    return Ok(None);
   }
+  let args = list
+    .iter()
+    .map(|item| {
+      item
+        .as_str()
+        .map(|value| value.trim().to_string())
+        .ok_or_else(|| "编码参数格式错误".to_string())
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+  validate_encoder_args(&args)?;
+  Ok(Some(args))
 }
 
 fn build_resegment_workflow_config(
@@ -6460,12 +11522,131 @@ fn update_workflow_status(
   context
     .db
     .with_conn(|conn| {
+      let previous = load_latest_workflow_state(conn, task_id)?;
       conn.execute(
         "UPDATE workflow_instances SET status = ?1, current_step = ?2, progress = ?3, updated_at = ?4 WHERE task_id = ?5",
         (status, current_step, progress, &now, task_id),
       )?;
+      if let Some(previous) = previous {
+        if previous.status != status || previous.current_step.as_deref() != current_step {
+          insert_workflow_transition_log(conn, &previous, status, current_step, progress, &now)?;
+        }
+      }
       Ok(())
     })
+    .map_err(|err| err.to_string())?;
+  let payload = WorkflowStatusEvent {
+    task_id: task_id.to_string(),
+    status: status.to_string(),
+    current_step: current_step.map(|value| value.to_string()),
+    progress,
+  };
+  if let Err(err) = app_events::emit(WORKFLOW_STATUS_EVENT, payload) {
+    append_log(
+      &context.app_log_path,
+      &format!("workflow_status_emit_fail task_id={} err={}", task_id, err),
+    );
+  }
+  Ok(())
+}
+
+struct WorkflowInstanceState {
+  instance_id: String,
+  status: String,
+  current_step: Option<String>,
+  progress: f64,
+}
+
+fn load_latest_workflow_state(
+  conn: &rusqlite::Connection,
+  task_id: &str,
+) -> Result<Option<WorkflowInstanceState>, rusqlite::Error> {
+  conn
+    .query_row(
+      "SELECT instance_id, status, current_step, progress FROM workflow_instances \
+       WHERE task_id = ?1 ORDER BY created_at DESC LIMIT 1",
+      [task_id],
+      |row| {
+        let progress: Option<f64> = row.get(3)?;
+        Ok(WorkflowInstanceState {
+          instance_id: row.get(0)?,
+          status: row.get(1)?,
+          current_step: row.get(2)?,
+          progress: progress.unwrap_or(0.0),
+        })
+      },
+    )
+    .optional()
+}
+
+/// 仅在状态或步骤变化时写入，进度刷新不记录。
+fn insert_workflow_transition_log(
+  conn: &rusqlite::Connection,
+  previous: &WorkflowInstanceState,
+  status: &str,
+  current_step: Option<&str>,
+  progress: f64,
+  now: &str,
+) -> Result<(), rusqlite::Error> {
+  let log_level = if status == "FAILED" { "ERROR" } else { "INFO" };
+  let message = format!(
+    "{}/{} -> {}/{}",
+    previous.status,
+    previous.current_step.as_deref().unwrap_or("-"),
+    status,
+    current_step.unwrap_or("-")
+  );
+  let log_data = serde_json::json!({
+    "status": status,
+    "currentStep": current_step,
+    "previousStatus": previous.status,
+    "previousStep": previous.current_step,
+    "progress": progress,
+  })
+  .to_string();
+  conn.execute(
+    "INSERT INTO workflow_execution_logs (instance_id, log_level, log_message, log_data, source_component, created_at) \
+     VALUES (?1, ?2, ?3, ?4, 'workflow_status', ?5)",
+    (&previous.instance_id, log_level, message, log_data, now),
+  )?;
+  Ok(())
+}
+
+fn load_workflow_logs(
+  context: &SubmissionContext,
+  task_id: &str,
+) -> Result<Vec<WorkflowLogEntry>, String> {
+  context
+    .db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT l.log_id, l.instance_id, l.log_level, l.log_message, l.log_data, l.created_at \
+         FROM workflow_execution_logs l \
+         JOIN workflow_instances wi ON wi.instance_id = l.instance_id \
+         WHERE wi.task_id = ?1 ORDER BY l.log_id ASC",
+      )?;
+      let rows = stmt.query_map([task_id], |row| {
+        let log_data: Option<String> = row.get(4)?;
+        let data = log_data
+          .as_deref()
+          .and_then(|value| serde_json::from_str::<Value>(value).ok())
+          .unwrap_or(Value::Null);
+        let text_field = |key: &str| data.get(key).and_then(|value| value.as_str()).map(str::to_string);
+        Ok(WorkflowLogEntry {
+          log_id: row.get(0)?,
+          instance_id: row.get(1)?,
+          log_level: row.get(2)?,
+          message: row.get(3)?,
+          status: text_field("status"),
+          current_step: text_field("currentStep"),
+          previous_status: text_field("previousStatus"),
+          previous_step: text_field("previousStep"),
+          progress: data.get("progress").and_then(|value| value.as_f64()),
+          created_at: row.get(5)?,
+        })
+      })?;
+      rows.collect::<Result<Vec<_>, _>>()
+    })
     .map_err(|err| err.to_string())
 }
 
@@ -6486,18 +11667,220 @@ fn load_task_ids_by_status(
     .map_err(|err| err.to_string())
 }
 
-fn load_next_queued_task(context: &SubmissionContext) -> Result<Option<String>, String> {
-  context
+fn load_next_queued_task(
+  context: &SubmissionContext,
+  running: &HashSet<String>,
+) -> Result<Option<String>, String> {
+  let tasks = context
     .db
     .with_conn(|conn| {
-      let result = conn
-        .query_row(
-          "SELECT task_id FROM submission_task WHERE status = 'WAITING_UPLOAD' ORDER BY updated_at ASC LIMIT 1",
-          [],
-          |row| row.get(0),
-        )
-        .ok();
-      Ok(result)
+      let mut stmt = conn.prepare(
+        "SELECT task_id, COALESCE(upload_failure_count, 0) FROM submission_task \
+         WHERE status = 'WAITING_UPLOAD' AND COALESCE(upload_paused, 0) = 0 \
+         ORDER BY updated_at ASC",
+      )?;
+      let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+      rows.collect::<Result<Vec<_>, _>>()
     })
-    .map_err(|err| err.to_string())
+    .map_err(|err| err.to_string())?;
+  let max_failures = max_upload_failures(context);
+  for (task_id, failures) in tasks {
+    if running.contains(&task_id) || is_upload_cancel_requested(&task_id) {
+      continue;
+    }
+    // 失败次数已达上限的任务被重新置为排队时直接转入 FAILED_PERMANENT，避免反复上传
+    if failures >= max_failures {
+      mark_submission_dead_letter(context, &task_id, failures, max_failures);
+      continue;
+    }
+    return Ok(Some(task_id));
+  }
+  Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn test_context() -> (SubmissionContext, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("reaction-cut-test-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    let db = Db::new(dir.join("test.db")).unwrap();
+    let context = SubmissionContext {
+      db: Arc::new(db),
+      app_log_path: Arc::new(dir.join("app.log")),
+      edit_upload_state: Arc::new(Mutex::new(EditUploadState::default())),
+      rate_limit_gate: upload_rate_limit_gate(),
+    };
+    (context, dir)
+  }
+
+  fn insert_interrupted_segment(context: &SubmissionContext, file_path: &Path, total_bytes: i64) {
+    let now = now_rfc3339();
+    context
+      .db
+      .with_conn(|conn| {
+        conn.execute(
+          "INSERT INTO submission_task (task_id, status, title, partition_id, video_type, created_at, updated_at) \
+           VALUES ('task-1', 'UPLOADING', '测试', 1, 'ORIGINAL', ?1, ?1)",
+          [&now],
+        )?;
+        conn.execute(
+          "INSERT INTO task_output_segment (segment_id, task_id, part_name, segment_file_path, part_order, upload_status, file_name, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index) \
+           VALUES ('seg-1', 'task-1', 'P1', ?1, 1, 'UPLOADING', 'seg.mp4', 8, ?2, 'upload-1', 42, '//upos.example.com', 'auth', 'upos://bucket/seg.mp4', 4, 2)",
+          (file_path.to_string_lossy().to_string(), total_bytes),
+        )?;
+        Ok(())
+      })
+      .unwrap();
+  }
+
+  #[test]
+  fn interrupted_upload_session_resumes_from_last_part_index() {
+    let (context, dir) = test_context();
+    let file_path = dir.join("seg.mp4");
+    fs::write(&file_path, [0u8; 16]).unwrap();
+    insert_interrupted_segment(&context, &file_path, 16);
+
+    reconcile_interrupted_upload_sessions(&context, "task-1");
+
+    let detail = load_task_detail(&context, "task-1").unwrap();
+    let segment = &detail.output_segments[0];
+    assert_eq!(segment.upload_status, "PENDING");
+    let session = sanitize_upload_session(build_upload_session_from_segment(segment), 16).unwrap();
+    assert_eq!(session.upload_id, "upload-1");
+    assert_eq!(session.last_part_index, 2);
+    assert_eq!(session.uploaded_bytes, 8);
+    let _ = fs::remove_dir_all(dir);
+  }
+
+  #[test]
+  fn interrupted_upload_session_resets_when_file_size_changed() {
+    let (context, dir) = test_context();
+    let file_path = dir.join("seg.mp4");
+    fs::write(&file_path, [0u8; 10]).unwrap();
+    insert_interrupted_segment(&context, &file_path, 16);
+
+    reconcile_interrupted_upload_sessions(&context, "task-1");
+
+    let detail = load_task_detail(&context, "task-1").unwrap();
+    let segment = &detail.output_segments[0];
+    assert_eq!(segment.upload_status, "PENDING");
+    assert!(build_upload_session_from_segment(segment).is_none());
+    assert_eq!(segment.upload_last_part_index, 0);
+    let _ = fs::remove_dir_all(dir);
+  }
+
+  fn query_plan(context: &SubmissionContext, sql: &str, params: &[rusqlite::types::Value]) -> String {
+    context
+      .db
+      .with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {}", sql))?;
+        let rows = stmt
+          .query_map(rusqlite::params_from_iter(params.iter()), |row| row.get::<_, String>(3))?
+          .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows.join("\n"))
+      })
+      .unwrap()
+  }
+
+  fn list_params(mut params: Vec<rusqlite::types::Value>) -> Vec<rusqlite::types::Value> {
+    params.push(20i64.into());
+    params.push(0i64.into());
+    params
+  }
+
+  #[test]
+  fn task_count_by_status_uses_status_index() {
+    let (context, dir) = test_context();
+    let (where_clause, params) = build_task_list_filter(Some("COMPLETED".to_string()), None);
+    let plan = query_plan(&context, &build_task_count_sql(&where_clause), &params);
+    assert!(plan.contains("idx_submission_task_status_created_at"), "{}", plan);
+    let _ = fs::remove_dir_all(dir);
+  }
+
+  #[test]
+  fn task_list_by_status_uses_status_created_index() {
+    let (context, dir) = test_context();
+    let (where_clause, params) = build_task_list_filter(Some("COMPLETED".to_string()), None);
+    let sql = build_task_list_sql(&where_clause, SubmissionListSort::Created, params.len());
+    let plan = query_plan(&context, &sql, &list_params(params));
+    assert!(plan.contains("idx_submission_task_status_created_at"), "{}", plan);
+    assert!(!plan.contains("TEMP B-TREE FOR ORDER BY"), "{}", plan);
+    let _ = fs::remove_dir_all(dir);
+  }
+
+  #[test]
+  fn task_list_by_created_uses_created_index() {
+    let (context, dir) = test_context();
+    let (where_clause, params) = build_task_list_filter(None, None);
+    let sql = build_task_list_sql(&where_clause, SubmissionListSort::Created, params.len());
+    let plan = query_plan(&context, &sql, &list_params(params));
+    assert!(plan.contains("idx_submission_task_created_at"), "{}", plan);
+    assert!(!plan.contains("TEMP B-TREE FOR ORDER BY"), "{}", plan);
+    let _ = fs::remove_dir_all(dir);
+  }
+
+  #[test]
+  fn submission_quota_parses_myinfo() {
+    let data = serde_json::json!({
+      "myinfo": { "mid": 42, "uname": "测试", "level": 5, "banned": false }
+    });
+    let limit = serde_json::json!({ "limit": 10, "used": 3, "cooldown": 0 });
+    let quota = parse_submission_quota(&data, Some(&limit)).unwrap();
+    assert!(quota.logged_in);
+    assert_eq!(quota.level, Some(5));
+    assert!(!quota.banned);
+    assert_eq!(quota.remaining_daily, Some(7));
+    assert_eq!(quota.cooldown_message, None);
+
+    let quota = parse_submission_quota(&data, None).unwrap();
+    assert_eq!(quota.remaining_daily, None);
+    assert_eq!(quota.cooldown_message, None);
+
+    let banned = serde_json::json!({ "myinfo": { "mid": 42, "level": 2, "banned": true } });
+    let quota = parse_submission_quota(&banned, Some(&limit)).unwrap();
+    assert!(quota.banned);
+    assert!(quota.cooldown_message.is_some());
+  }
+
+  #[test]
+  fn submission_quota_reports_cooldown_and_exhausted_limit() {
+    let data = serde_json::json!({ "myinfo": { "mid": 42, "level": 4, "banned": false } });
+    let cooling = serde_json::json!({ "remain": 2, "cooldown": 90 });
+    let quota = parse_submission_quota(&data, Some(&cooling)).unwrap();
+    assert_eq!(quota.remaining_daily, Some(2));
+    assert_eq!(quota.cooldown_message.as_deref(), Some("投稿冷却中，约 2 分钟后可投稿"));
+
+    let exhausted = serde_json::json!({ "limit": 5, "used": 6 });
+    let quota = parse_submission_quota(&data, Some(&exhausted)).unwrap();
+    assert_eq!(quota.remaining_daily, Some(0));
+    assert_eq!(quota.cooldown_message.as_deref(), Some("今日投稿次数已用完"));
+  }
+
+  #[test]
+  fn remote_audit_state_code_round_trips() {
+    for code in [0, -1, -2, -3, -4, -6, -7, -11, -15, -16, -20, -30, -40, -100, -99] {
+      assert_eq!(RemoteAuditState::from_code(code).to_code(), code);
+    }
+    assert_eq!(RemoteAuditState::Reviewing.to_code(), -30);
+  }
+
+  #[test]
+  fn submission_quota_rejects_unknown_payload() {
+    assert!(parse_submission_quota(&serde_json::json!({ "typelist": [] }), None).is_none());
+    assert!(parse_submission_quota(&serde_json::json!({ "myinfo": { "level": 3 } }), None).is_none());
+    assert!(parse_submission_quota(&serde_json::json!({ "myinfo": { "mid": 0 } }), None).is_none());
+  }
+
+  #[test]
+  fn csv_field_escapes_formulas_and_separators() {
+    assert_eq!(escape_csv_field("普通标题"), "普通标题");
+    assert_eq!(escape_csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+    assert_eq!(escape_csv_field("+1"), "'+1");
+    assert_eq!(escape_csv_field("-1"), "'-1");
+    assert_eq!(escape_csv_field("@cmd"), "'@cmd");
+    assert_eq!(escape_csv_field("a,b"), "\"a,b\"");
+    assert_eq!(escape_csv_field(""), "");
+  }
 }
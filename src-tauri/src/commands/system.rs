@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::api::ApiResponse;
+use crate::config::{
+  resolve_aria2c_candidates, resolve_baidu_pcs_candidates, resolve_ffmpeg_path,
+  resolve_ffprobe_path,
+};
+use crate::utils::{append_log, now_rfc3339};
+use crate::AppState;
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinaryVersionInfo {
+  pub name: String,
+  pub configured_path: String,
+  pub resolved_path: Option<String>,
+  pub version: Option<String>,
+  pub raw: Option<String>,
+  pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStatus {
+  pub active_downloads: i64,
+  pub active_recordings: Vec<String>,
+  pub submission_status_counts: HashMap<String, i64>,
+  pub queued_submissions: i64,
+  pub pending_baidu_syncs: i64,
+  pub uploading_baidu_syncs: i64,
+  pub last_heartbeat: Option<String>,
+  pub login_valid: bool,
+  pub login_user_id: Option<i64>,
+  pub submission_queue_paused: bool,
+  pub hwaccel_methods: Vec<String>,
+  pub active_hwaccel: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedRowGroup {
+  pub table: String,
+  pub reason: String,
+  pub count: usize,
+  pub sample_ids: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbRepairReport {
+  pub groups: Vec<OrphanedRowGroup>,
+  pub total: usize,
+  pub deleted: bool,
+}
+
+const ORPHAN_SAMPLE_LIMIT: usize = 50;
+
+/// (表名, 主键列, 原因, 孤儿行条件)
+const ORPHAN_CHECKS: [(&str, &str, &str, &str); 6] = [
+  (
+    "task_relations",
+    "id",
+    "download_missing",
+    "NOT EXISTS (SELECT 1 FROM video_download vd WHERE vd.id = task_relations.download_task_id)",
+  ),
+  (
+    "task_relations",
+    "id",
+    "submission_missing",
+    "NOT EXISTS (SELECT 1 FROM submission_task st WHERE st.task_id = task_relations.submission_task_id)",
+  ),
+  (
+    "task_output_segment",
+    "segment_id",
+    "submission_missing",
+    "NOT EXISTS (SELECT 1 FROM submission_task st WHERE st.task_id = task_output_segment.task_id)",
+  ),
+  (
+    "merged_video",
+    "id",
+    "submission_missing",
+    "NOT EXISTS (SELECT 1 FROM submission_task st WHERE st.task_id = merged_video.task_id)",
+  ),
+  (
+    "workflow_instances",
+    "instance_id",
+    "submission_missing",
+    "NOT EXISTS (SELECT 1 FROM submission_task st WHERE st.task_id = workflow_instances.task_id)",
+  ),
+  (
+    "workflow_execution_logs",
+    "log_id",
+    "instance_missing",
+    "NOT EXISTS (SELECT 1 FROM workflow_instances wi WHERE wi.instance_id = workflow_execution_logs.instance_id)",
+  ),
+];
+
+static BINARY_VERSION_CACHE: OnceLock<Mutex<Option<Vec<BinaryVersionInfo>>>> = OnceLock::new();
+static LAST_HEARTBEAT: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+
+fn binary_version_cache() -> &'static Mutex<Option<Vec<BinaryVersionInfo>>> {
+  BINARY_VERSION_CACHE.get_or_init(|| Mutex::new(None))
+}
+
+fn last_heartbeat() -> &'static Mutex<Option<String>> {
+  LAST_HEARTBEAT.get_or_init(|| Mutex::new(None))
+}
+
+pub fn record_heartbeat() {
+  if let Ok(mut guard) = last_heartbeat().lock() {
+    *guard = Some(now_rfc3339());
+  }
+}
+
+#[tauri::command]
+pub fn app_status(state: State<'_, AppState>) -> ApiResponse<AppStatus> {
+  let active_downloads = state
+    .download_runtime
+    .active_count
+    .lock()
+    .map(|value| *value)
+    .unwrap_or(0);
+  let mut active_recordings = state.live_runtime.active_room_ids();
+  active_recordings.sort();
+  let counts = state.db.with_conn(|conn| {
+    let mut stmt = conn.prepare("SELECT status, COUNT(*) FROM submission_task GROUP BY status")?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+    let submission_status_counts = rows.collect::<Result<HashMap<_, _>, _>>()?;
+    let mut stmt = conn.prepare("SELECT status, COUNT(*) FROM baidu_sync_task GROUP BY status")?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+    let baidu_status_counts = rows.collect::<Result<HashMap<_, _>, _>>()?;
+    Ok((submission_status_counts, baidu_status_counts))
+  });
+  let (submission_status_counts, baidu_status_counts) = match counts {
+    Ok(counts) => counts,
+    Err(err) => return ApiResponse::error(format!("读取任务状态失败: {}", err)),
+  };
+  let auth_info = state.login_store.load_auth_info(&state.db).ok().flatten();
+  // 有 Cookie 且记录的过期时间未到才视为登录有效；过期时间未知时以 Cookie 为准
+  let login_expired = crate::login_refresh::load_login_expire_time(&state.db)
+    .ok()
+    .flatten()
+    .map(|expire_time| expire_time <= chrono::Utc::now())
+    .unwrap_or(false);
+  ApiResponse::success(AppStatus {
+    active_downloads,
+    active_recordings,
+    queued_submissions: submission_status_counts
+      .get("WAITING_UPLOAD")
+      .copied()
+      .unwrap_or(0),
+    submission_status_counts,
+    pending_baidu_syncs: baidu_status_counts.get("PENDING").copied().unwrap_or(0),
+    uploading_baidu_syncs: baidu_status_counts.get("UPLOADING").copied().unwrap_or(0),
+    last_heartbeat: last_heartbeat().lock().ok().and_then(|guard| guard.clone()),
+    login_valid: auth_info.is_some() && !login_expired,
+    login_user_id: auth_info.and_then(|auth| auth.user_id),
+    submission_queue_paused: crate::commands::submission::is_submission_queue_paused(),
+    hwaccel_methods: crate::ffmpeg::available_hwaccels().to_vec(),
+    active_hwaccel: crate::ffmpeg::active_hwaccel().map(|method| method.to_string()),
+  })
+}
+
+#[tauri::command]
+pub async fn app_binary_versions(
+  refresh: Option<bool>,
+) -> Result<ApiResponse<Vec<BinaryVersionInfo>>, String> {
+  if !refresh.unwrap_or(false) {
+    if let Ok(cache) = binary_version_cache().lock() {
+      if let Some(cached) = cache.as_ref() {
+        return Ok(ApiResponse::success(cached.clone()));
+      }
+    }
+  }
+  let versions = tauri::async_runtime::spawn_blocking(refresh_binary_versions)
+    .await
+    .map_err(|_| "读取工具版本失败".to_string())?;
+  Ok(ApiResponse::success(versions))
+}
+
+/// 扫描指向已删除下载/投稿的关联行；`delete` 为 true 时在同一事务内删除。
+/// 工作流实例先于日志处理，删除实例后遗留的日志会一并清理。
+#[tauri::command]
+pub fn db_repair(state: State<'_, AppState>, delete: Option<bool>) -> ApiResponse<DbRepairReport> {
+  let delete = delete.unwrap_or(false);
+  let result = state.db.with_conn_mut(|conn| {
+    let tx = conn.transaction()?;
+    let mut groups = Vec::new();
+    for (table, id_column, reason, condition) in ORPHAN_CHECKS {
+      let ids = {
+        let mut stmt = tx.prepare(&format!(
+          "SELECT CAST({} AS TEXT) FROM {} WHERE {}",
+          id_column, table, condition
+        ))?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+      };
+      if ids.is_empty() {
+        continue;
+      }
+      if delete {
+        tx.execute(&format!("DELETE FROM {} WHERE {}", table, condition), [])?;
+      }
+      groups.push(OrphanedRowGroup {
+        table: table.to_string(),
+        reason: reason.to_string(),
+        count: ids.len(),
+        sample_ids: ids.into_iter().take(ORPHAN_SAMPLE_LIMIT).collect(),
+      });
+    }
+    tx.commit()?;
+    Ok(groups)
+  });
+  match result {
+    Ok(groups) => {
+      let total = groups.iter().map(|group| group.count).sum();
+      let summary = groups
+        .iter()
+        .map(|group| format!("{}:{}={}", group.table, group.reason, group.count))
+        .collect::<Vec<_>>()
+        .join(",");
+      append_log(
+        &state.app_log_path,
+        &format!("db_repair delete={} total={} groups={}", delete, total, summary),
+      );
+      ApiResponse::success(DbRepairReport {
+        groups,
+        total,
+        deleted: delete,
+      })
+    }
+    Err(err) => ApiResponse::error(format!("检查数据库失败: {}", err)),
+  }
+}
+
+/// 重新探测全部外部工具并刷新缓存。
+pub(crate) fn refresh_binary_versions() -> Vec<BinaryVersionInfo> {
+  let versions = collect_binary_versions();
+  if let Ok(mut cache) = binary_version_cache().lock() {
+    *cache = Some(versions.clone());
+  }
+  versions
+}
+
+/// 启动时探测一次并记录版本，ffmpeg/ffprobe 不可用时单独记录。
+pub fn run_startup_binary_check(app_log_path: &Path) {
+  for info in refresh_binary_versions() {
+    append_log(
+      app_log_path,
+      &format!(
+        "bin_check name={} path={} version={} err={}",
+        info.name,
+        info.resolved_path.as_deref().unwrap_or(""),
+        info.version.as_deref().unwrap_or(""),
+        info.error.as_deref().unwrap_or("")
+      ),
+    );
+  }
+  if let Some(reason) = ffmpeg_unavailable_reason() {
+    append_log(app_log_path, &format!("bin_check_ffmpeg_unavailable reason={}", reason));
+  } else {
+    crate::ffmpeg::init_hwaccels(app_log_path);
+  }
+}
+
+/// 根据最近一次探测结果判断 ffmpeg/ffprobe 是否可用，未探测过时视为可用。
+/// 当前解析出的路径与探测时不同（例如路径配置已调整）时先重新探测。
+pub fn ffmpeg_unavailable_reason() -> Option<String> {
+  if ffmpeg_paths_changed() {
+    refresh_binary_versions();
+  }
+  let cache = binary_version_cache().lock().ok()?;
+  let versions = cache.as_ref()?;
+  versions
+    .iter()
+    .filter(|info| info.name == "ffmpeg" || info.name == "ffprobe")
+    .find_map(|info| {
+      if info.resolved_path.is_none() {
+        Some(format!(
+          "{} 不可用: {}",
+          info.name,
+          info.error.as_deref().unwrap_or("未找到可执行文件")
+        ))
+      } else {
+        info
+          .error
+          .as_ref()
+          .map(|err| format!("{} 运行异常: {}", info.name, err))
+      }
+    })
+}
+
+fn ffmpeg_paths_changed() -> bool {
+  let ffmpeg_path = resolve_ffmpeg_path().to_string_lossy().to_string();
+  let ffprobe_path = resolve_ffprobe_path().to_string_lossy().to_string();
+  let Ok(cache) = binary_version_cache().lock() else {
+    return false;
+  };
+  let Some(versions) = cache.as_ref() else {
+    return false;
+  };
+  versions.iter().any(|info| match info.name.as_str() {
+    "ffmpeg" => info.configured_path != ffmpeg_path,
+    "ffprobe" => info.configured_path != ffprobe_path,
+    _ => false,
+  })
+}
+
+fn collect_binary_versions() -> Vec<BinaryVersionInfo> {
+  let ffmpeg_path = resolve_ffmpeg_path().to_string_lossy().to_string();
+  let ffprobe_path = resolve_ffprobe_path().to_string_lossy().to_string();
+  vec![
+    probe_binary_version("ffmpeg", &[ffmpeg_path], "-version"),
+    probe_binary_version("ffprobe", &[ffprobe_path], "-version"),
+    probe_binary_version("aria2c", &resolve_aria2c_candidates(), "--version"),
+    probe_binary_version("baidu_pcs", &resolve_baidu_pcs_candidates(), "--version"),
+  ]
+}
+
+pub(crate) fn probe_binary_version(
+  name: &str,
+  candidates: &[String],
+  flag: &str,
+) -> BinaryVersionInfo {
+  let configured_path = candidates.first().cloned().unwrap_or_default();
+  let mut last_error = None;
+  for candidate in candidates {
+    match Command::new(candidate).arg(flag).output() {
+      Ok(output) => {
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let text = if stdout.trim().is_empty() {
+          String::from_utf8_lossy(&output.stderr).to_string()
+        } else {
+          stdout
+        };
+        let first_line = text
+          .lines()
+          .map(|line| line.trim())
+          .find(|line| !line.is_empty())
+          .unwrap_or("")
+          .to_string();
+        return BinaryVersionInfo {
+          name: name.to_string(),
+          configured_path,
+          resolved_path: Some(candidate.clone()),
+          version: parse_version_line(&first_line),
+          raw: if first_line.is_empty() { None } else { Some(first_line) },
+          error: if output.status.success() {
+            None
+          } else {
+            Some(format!("退出码异常: {}", output.status))
+          },
+        };
+      }
+      Err(err) => {
+        last_error = Some(format!("{}: {}", candidate, err));
+      }
+    }
+  }
+  BinaryVersionInfo {
+    name: name.to_string(),
+    configured_path,
+    resolved_path: None,
+    version: None,
+    raw: None,
+    error: Some(last_error.unwrap_or_else(|| "未找到可执行文件".to_string())),
+  }
+}
+
+// "ffmpeg version 6.1.1 Copyright ..." / "aria2 version 1.37.0" / "BaiduPCS-Go version v3.9.5"
+fn parse_version_line(line: &str) -> Option<String> {
+  let mut tokens = line.split_whitespace();
+  while let Some(token) = tokens.next() {
+    if token.eq_ignore_ascii_case("version") {
+      return tokens
+        .next()
+        .map(|value| value.trim_start_matches(['v', 'V']).trim_end_matches(',').to_string())
+        .filter(|value| !value.is_empty());
+    }
+  }
+  line
+    .split_whitespace()
+    .find(|token| token.chars().next().is_some_and(|ch| ch.is_ascii_digit()))
+    .map(|value| value.to_string())
+}
@@ -1,11 +1,14 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use chrono::Utc;
 use serde::Deserialize;
 use tauri::State;
 
 use crate::api::ApiResponse;
-use crate::ffmpeg::run_ffmpeg;
+use crate::commands::system::{refresh_binary_versions, BinaryVersionInfo};
+use crate::ffmpeg::{run_ffmpeg, run_ffprobe_json};
+use crate::processing::{clip_source_to_file, decide_clip_copy, parse_clip_range, ClipSource};
 use crate::utils;
 use crate::AppState;
 
@@ -16,6 +19,41 @@ pub struct RemuxPayload {
   pub target_path: String,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractAudioPayload {
+  pub input_path: String,
+  pub format: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrimPayload {
+  pub input_path: String,
+  pub start_time: Option<String>,
+  pub end_time: Option<String>,
+  pub output_path: Option<String>,
+}
+
+#[tauri::command]
+pub async fn toolbox_check_binaries(
+  state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<BinaryVersionInfo>>, String> {
+  let versions = tauri::async_runtime::spawn_blocking(refresh_binary_versions)
+    .await
+    .map_err(|_| "检测工具失败".to_string())?;
+  let missing = versions
+    .iter()
+    .filter(|info| info.resolved_path.is_none())
+    .map(|info| info.name.as_str())
+    .collect::<Vec<_>>();
+  utils::append_log(
+    state.app_log_path.as_ref(),
+    &format!("toolbox_check_binaries missing={}", missing.join(",")),
+  );
+  Ok(ApiResponse::success(versions))
+}
+
 #[tauri::command]
 pub async fn toolbox_remux(
   state: State<'_, AppState>,
@@ -82,3 +120,250 @@ pub async fn toolbox_remux(
     }
   }
 }
+
+#[tauri::command]
+pub async fn toolbox_extract_audio(
+  state: State<'_, AppState>,
+  payload: ExtractAudioPayload,
+) -> Result<ApiResponse<String>, String> {
+  let input = payload.input_path.trim();
+  if input.is_empty() {
+    return Ok(ApiResponse::error("请选择源文件"));
+  }
+
+  let input_path = Path::new(input);
+  if !input_path.exists() {
+    return Ok(ApiResponse::error("源文件不存在"));
+  }
+  if !input_path.is_file() {
+    return Ok(ApiResponse::error("源文件不是文件"));
+  }
+
+  let format = payload.format.trim().to_lowercase();
+  let (encoder, extension) = match format.as_str() {
+    "mp3" => ("libmp3lame", "mp3"),
+    "aac" => ("aac", "aac"),
+    _ => return Ok(ApiResponse::error("仅支持导出 mp3 或 aac 音频")),
+  };
+
+  let log_path = state.app_log_path.clone();
+  let probe_path = input_path.to_path_buf();
+  let source_codec = tauri::async_runtime::spawn_blocking(move || probe_audio_codec(&probe_path))
+    .await
+    .map_err(|_| "读取音频信息失败".to_string())?;
+  let source_codec = match source_codec {
+    Ok(Some(codec)) => codec,
+    Ok(None) => return Ok(ApiResponse::error("源文件不包含音频流")),
+    Err(err) => {
+      utils::append_log(
+        log_path.as_ref(),
+        &format!("toolbox_extract_audio_probe_fail input={} err={}", input, err),
+      );
+      return Ok(ApiResponse::error(err));
+    }
+  };
+
+  let output_path = build_audio_output_path(input_path, extension);
+  let output = output_path.to_string_lossy().to_string();
+  let copy_stream = source_codec == format;
+  let mut args = vec![
+    "-hide_banner".to_string(),
+    "-loglevel".to_string(),
+    "error".to_string(),
+    "-y".to_string(),
+    "-i".to_string(),
+    input.to_string(),
+    "-vn".to_string(),
+    "-acodec".to_string(),
+  ];
+  if copy_stream {
+    args.push("copy".to_string());
+  } else {
+    args.push(encoder.to_string());
+    args.push("-b:a".to_string());
+    args.push("192k".to_string());
+  }
+  args.push(output.clone());
+
+  utils::append_log(
+    log_path.as_ref(),
+    &format!(
+      "toolbox_extract_audio_start input={} output={} source_codec={} copy={} args={}",
+      input,
+      output,
+      source_codec,
+      copy_stream,
+      args.join(" ")
+    ),
+  );
+
+  let result = tauri::async_runtime::spawn_blocking(move || run_ffmpeg(&args))
+    .await
+    .map_err(|_| "提取音频执行失败".to_string())?;
+
+  match result {
+    Ok(()) => {
+      utils::append_log(
+        log_path.as_ref(),
+        &format!("toolbox_extract_audio_done status=ok output={}", output),
+      );
+      Ok(ApiResponse::success(output))
+    }
+    Err(err) => {
+      utils::append_log(
+        log_path.as_ref(),
+        &format!("toolbox_extract_audio_done status=err err={}", err),
+      );
+      Ok(ApiResponse::error(err))
+    }
+  }
+}
+
+fn probe_audio_codec(path: &Path) -> Result<Option<String>, String> {
+  let args = vec![
+    "-v".to_string(),
+    "error".to_string(),
+    "-select_streams".to_string(),
+    "a:0".to_string(),
+    "-show_entries".to_string(),
+    "stream=codec_name".to_string(),
+    "-of".to_string(),
+    "json".to_string(),
+    path.to_string_lossy().to_string(),
+  ];
+  let data = run_ffprobe_json(&args)?;
+  let codec = data
+    .get("streams")
+    .and_then(|value| value.as_array())
+    .and_then(|streams| streams.first())
+    .and_then(|stream| stream.get("codec_name"))
+    .and_then(|value| value.as_str())
+    .map(|value| value.to_lowercase());
+  Ok(codec)
+}
+
+fn build_audio_output_path(input_path: &Path, extension: &str) -> PathBuf {
+  let stem = input_path
+    .file_stem()
+    .map(|value| value.to_string_lossy().to_string())
+    .unwrap_or_else(|| "audio".to_string());
+  let dir = input_path.parent().unwrap_or_else(|| Path::new(""));
+  let candidate = dir.join(format!("{}.{}", stem, extension));
+  if candidate != input_path {
+    return candidate;
+  }
+  dir.join(format!("{}_audio.{}", stem, extension))
+}
+
+/// 按时间段裁剪本地文件，复用投稿剪辑的 copy/重编码判断，不写入投稿相关表。
+#[tauri::command]
+pub async fn toolbox_trim(
+  state: State<'_, AppState>,
+  payload: TrimPayload,
+) -> Result<ApiResponse<String>, String> {
+  let input = payload.input_path.trim();
+  if input.is_empty() {
+    return Ok(ApiResponse::error("请选择源文件"));
+  }
+  let input_path = Path::new(input);
+  if !input_path.exists() {
+    return Ok(ApiResponse::error("源文件不存在"));
+  }
+  if !input_path.is_file() {
+    return Ok(ApiResponse::error("源文件不是文件"));
+  }
+
+  let (start_time, end_time) =
+    match parse_clip_range(payload.start_time.as_deref(), payload.end_time.as_deref()) {
+      Ok(value) => value,
+      Err(err) => return Ok(ApiResponse::error(err)),
+    };
+  if start_time.is_none() && end_time.is_none() {
+    return Ok(ApiResponse::error("请填写开始或结束时间"));
+  }
+
+  let output_path = match payload
+    .output_path
+    .as_deref()
+    .map(|value| value.trim())
+    .filter(|value| !value.is_empty())
+  {
+    Some(value) => PathBuf::from(value),
+    None => build_trim_output_path(input_path),
+  };
+  let is_mp4 = output_path
+    .extension()
+    .map(|ext| ext.to_string_lossy().eq_ignore_ascii_case("mp4"))
+    .unwrap_or(false);
+  if !is_mp4 {
+    return Ok(ApiResponse::error("裁剪仅支持输出 mp4 文件"));
+  }
+  if output_path == input_path {
+    return Ok(ApiResponse::error("输出路径不能与源文件相同"));
+  }
+  let output_dir = output_path
+    .parent()
+    .map(|parent| parent.to_path_buf())
+    .unwrap_or_else(|| PathBuf::from("."));
+  if let Err(err) = fs::create_dir_all(&output_dir) {
+    return Ok(ApiResponse::error(format!("创建输出目录失败: {}", err)));
+  }
+
+  let output = output_path.to_string_lossy().to_string();
+  let log_path = state.app_log_path.clone();
+  let source = ClipSource {
+    input_path: input.to_string(),
+    start_time: start_time.map(|(value, _)| value),
+    end_time: end_time.map(|(value, _)| value),
+    order: 1,
+  };
+  let work_dir = output_dir.join(format!(".trim_{}", Utc::now().timestamp_millis()));
+  let task_log_path = log_path.clone();
+  let task_output_path = output_path.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let decision = decide_clip_copy(std::slice::from_ref(&source))?;
+    utils::append_log(
+      task_log_path.as_ref(),
+      &format!(
+        "toolbox_trim_start input={} use_copy={} reason={}",
+        source.input_path,
+        decision.use_copy,
+        decision.reason.as_deref().unwrap_or("")
+      ),
+    );
+    clip_source_to_file(&source, &work_dir, &task_output_path, decision.use_copy, |command| {
+      utils::append_log(
+        task_log_path.as_ref(),
+        &format!("toolbox_trim_command args={}", command),
+      );
+    })
+  })
+  .await
+  .map_err(|_| "裁剪执行失败".to_string())?;
+
+  match result {
+    Ok(()) => {
+      utils::append_log(
+        log_path.as_ref(),
+        &format!("toolbox_trim_done status=ok output={}", output),
+      );
+      Ok(ApiResponse::success(output))
+    }
+    Err(err) => {
+      utils::append_log(
+        log_path.as_ref(),
+        &format!("toolbox_trim_done status=err err={}", err),
+      );
+      Ok(ApiResponse::error(err))
+    }
+  }
+}
+
+fn build_trim_output_path(input_path: &Path) -> PathBuf {
+  let stem = input_path
+    .file_stem()
+    .map(|value| value.to_string_lossy().to_string())
+    .unwrap_or_else(|| "trim".to_string());
+  let dir = input_path.parent().unwrap_or_else(|| Path::new(""));
+  dir.join(format!("{}_trim.mp4", stem))
+}
@@ -1,3 +1,9 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::Cursor;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
 use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, USER_AGENT};
@@ -7,17 +13,86 @@ use tauri::State;
 use chrono::Utc;
 
 use crate::api::ApiResponse;
+use crate::bilibili::client::BilibiliClient;
+use crate::commands::download::{cap_quality, pick_video_stream};
+use crate::commands::settings::{load_download_settings_from_db, DEFAULT_CREATE_OPTIONS_TTL_SECS};
 use crate::login_store::AuthInfo;
 use crate::utils::append_log;
 use crate::AppState;
 
-#[derive(Serialize)]
+
+const PARTITION_CACHE_TTL_SECS: u64 = 6 * 60 * 60;
+static CREATE_OPTIONS_CACHE: OnceLock<Mutex<Option<CreateOptionsCacheEntry>>> = OnceLock::new();
+static PARTITION_CACHE: OnceLock<PartitionCache> = OnceLock::new();
+const COLLECTION_SECTION_CACHE_TTL_SECS: u64 = 30 * 60;
+static COLLECTION_SECTION_CACHE: OnceLock<CollectionSectionCache> = OnceLock::new();
+
+type PartitionCache = TtlCache<(), Vec<Partition>>;
+type CollectionSectionCache = TtlCache<i64, Vec<CollectionSection>>;
+
+/// 按 key 缓存加载结果，超过 TTL 的条目视为不存在。
+struct TtlCache<K, V> {
+  ttl_secs: u64,
+  entries: Mutex<HashMap<K, (Instant, V)>>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+  fn new(ttl_secs: u64) -> Self {
+    Self {
+      ttl_secs,
+      entries: Mutex::new(HashMap::new()),
+    }
+  }
+
+  fn get(&self, key: &K) -> Option<V> {
+    let guard = self.entries.lock().ok()?;
+    let (loaded_at, value) = guard.get(key)?;
+    if loaded_at.elapsed().as_secs() < self.ttl_secs {
+      Some(value.clone())
+    } else {
+      None
+    }
+  }
+
+  fn insert(&self, key: K, value: V) {
+    if let Ok(mut guard) = self.entries.lock() {
+      guard.insert(key, (Instant::now(), value));
+    }
+  }
+
+  fn remove(&self, key: &K) {
+    if let Ok(mut guard) = self.entries.lock() {
+      guard.remove(key);
+    }
+  }
+}
+
+fn partition_cache() -> &'static PartitionCache {
+  PARTITION_CACHE.get_or_init(|| TtlCache::new(PARTITION_CACHE_TTL_SECS))
+}
+
+fn collection_section_cache() -> &'static CollectionSectionCache {
+  COLLECTION_SECTION_CACHE.get_or_init(|| TtlCache::new(COLLECTION_SECTION_CACHE_TTL_SECS))
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Partition {
   pub tid: i64,
   pub name: String,
+  pub parent_tid: Option<i64>,
 }
 
-#[derive(Serialize)]
+/// 按父分区分组的投稿分区，没有子分区的一级分区本身可选。
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartitionGroup {
+  pub tid: i64,
+  pub name: String,
+  pub children: Vec<Partition>,
+}
+
+#[derive(Clone, Serialize)]
 pub struct Collection {
   pub season_id: i64,
   pub name: String,
@@ -25,6 +100,28 @@ pub struct Collection {
   pub description: Option<String>,
 }
 
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionSection {
+  pub section_id: i64,
+  pub title: String,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionCreateOptions {
+  pub collections: Vec<Collection>,
+  pub partitions: Vec<PartitionGroup>,
+  pub fetched_at: String,
+  pub from_cache: bool,
+}
+
+struct CreateOptionsCacheEntry {
+  mid: i64,
+  loaded_at: Instant,
+  options: SubmissionCreateOptions,
+}
+
 #[tauri::command]
 pub async fn video_detail(
   state: State<'_, AppState>,
@@ -60,11 +157,15 @@ pub async fn video_playurl(
   fnval: Option<String>,
   fnver: Option<String>,
   fourk: Option<String>,
+  preferred_codec: Option<String>,
+  max_resolution: Option<i64>,
 ) -> Result<ApiResponse<Value>, String> {
+  let qn = cap_quality(&qn.unwrap_or_else(|| "112".to_string()), max_resolution);
+  let requested_qn = qn.clone();
   let params = vec![
     ("bvid".to_string(), bvid),
     ("cid".to_string(), cid),
-    ("qn".to_string(), qn.unwrap_or_else(|| "112".to_string())),
+    ("qn".to_string(), qn),
     ("fnval".to_string(), fnval.unwrap_or_else(|| "4048".to_string())),
     ("fnver".to_string(), fnver.unwrap_or_else(|| "0".to_string())),
     ("fourk".to_string(), fourk.unwrap_or_else(|| "1".to_string())),
@@ -73,7 +174,24 @@ pub async fn video_playurl(
   let auth = load_auth(&state);
   let url = format!("{}/x/player/wbi/playurl", state.bilibili.base_url());
   match state.bilibili.get_json(&url, &params, auth.as_ref(), true).await {
-    Ok(data) => Ok(ApiResponse::success(data)),
+    Ok(mut data) => {
+      // 按与下载相同的规则标注首选流，前端可直接使用
+      let selected = data.get("dash").and_then(|dash| {
+        pick_video_stream(
+          dash,
+          Some(requested_qn.as_str()),
+          preferred_codec.as_deref(),
+          max_resolution,
+        )
+      });
+      if let (Some((resolution, codec)), Some(object)) = (selected, data.as_object_mut()) {
+        object.insert(
+          "selectedStream".to_string(),
+          serde_json::json!({ "resolution": resolution, "codec": codec }),
+        );
+      }
+      Ok(ApiResponse::success(data))
+    }
     Err(err) => Ok(ApiResponse::error(format!("Failed to load playurl: {}", err))),
   }
 }
@@ -106,35 +224,109 @@ pub async fn video_playurl_by_aid(
 }
 
 #[tauri::command]
-pub async fn video_proxy_image(url: String) -> Result<ApiResponse<String>, String> {
+pub async fn video_proxy_image(
+  state: State<'_, AppState>,
+  url: String,
+  transcode: Option<String>,
+) -> Result<ApiResponse<String>, String> {
   let trimmed = url.trim();
   if trimmed.is_empty() {
     return Ok(ApiResponse::error("图片地址不能为空"));
   }
+  let target = match transcode.as_deref().map(|value| value.trim().to_lowercase()) {
+    None => None,
+    Some(value) if value.is_empty() => None,
+    Some(value) if value == "jpeg" || value == "jpg" => Some(image::ImageFormat::Jpeg),
+    Some(value) if value == "png" => Some(image::ImageFormat::Png),
+    Some(value) => return Ok(ApiResponse::error(format!("不支持的转码格式: {}", value))),
+  };
 
+  let (mut content_type, mut bytes) = match fetch_proxy_image(trimmed).await {
+    Ok(value) => value,
+    Err(err) => {
+      append_log(
+        &state.app_log_path,
+        &format!("video_proxy_image_fail url={} err={}", trimmed, err),
+      );
+      return Ok(ApiResponse::error(err));
+    }
+  };
+
+  if let Some(target) = target {
+    // 当前 image 依赖未启用 AVIF 解码，AVIF 改为向 CDN 请求 JPEG 版本；取不到时报错而不是原样返回 AVIF
+    if detect_image_mime(&bytes) == Some("image/avif") {
+      let Some(fallback_url) = build_jpeg_fallback_url(trimmed) else {
+        append_log(
+          &state.app_log_path,
+          &format!("video_proxy_image_avif_fallback_skip url={} reason=unsupported_host", trimmed),
+        );
+        return Ok(ApiResponse::error("暂不支持转码 AVIF 图片"));
+      };
+      match fetch_proxy_image(&fallback_url).await {
+        Ok((fallback_type, fallback_bytes)) => {
+          content_type = fallback_type;
+          bytes = fallback_bytes;
+        }
+        Err(err) => {
+          append_log(
+            &state.app_log_path,
+            &format!(
+              "video_proxy_image_avif_fallback_fail url={} fallback={} err={}",
+              trimmed, fallback_url, err
+            ),
+          );
+          return Ok(ApiResponse::error(format!("AVIF 图片转码失败: {}", err)));
+        }
+      }
+      if detect_image_mime(&bytes) == Some("image/avif") {
+        return Ok(ApiResponse::error("暂不支持转码 AVIF 图片"));
+      }
+    }
+    if detect_image_mime(&bytes) == Some("image/webp") {
+      let source = bytes.clone();
+      let result = tauri::async_runtime::spawn_blocking(move || transcode_image(&source, target))
+        .await
+        .map_err(|_| "图片转码失败".to_string())
+        .and_then(|result| result);
+      match result {
+        Ok(output) => {
+          bytes = output;
+          content_type = target.to_mime_type().to_string();
+        }
+        Err(err) => return Ok(ApiResponse::error(err)),
+      }
+    }
+  }
+
+  let encoded = STANDARD.encode(bytes);
+  let data_url = format!("data:{};base64,{}", content_type, encoded);
+  Ok(ApiResponse::success(data_url))
+}
+
+async fn fetch_proxy_image(url: &str) -> Result<(String, Vec<u8>), String> {
   let mut headers = HeaderMap::new();
+  let user_agent =
+    crate::config::user_agent_or("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36");
   headers.insert(
     USER_AGENT,
-    HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36"),
+    HeaderValue::from_str(&user_agent).map_err(|_| "无效的User-Agent".to_string())?,
   );
   headers.insert(
     "Referer",
-    HeaderValue::from_static("https://www.bilibili.com"),
+    HeaderValue::from_str(&crate::config::web_referer_or("https://www.bilibili.com"))
+      .map_err(|_| "无效的Referer".to_string())?,
   );
 
-  let client = reqwest::Client::new();
-  let response = match client.get(trimmed).headers(headers).send().await {
-    Ok(response) => response,
-    Err(err) => {
-      return Ok(ApiResponse::error(format!("获取图片失败: {}", err)));
-    }
-  };
+  let client = crate::config::build_http_client();
+  let response = client
+    .get(url)
+    .headers(headers)
+    .send()
+    .await
+    .map_err(|err| format!("获取图片失败: {}", err))?;
 
   if !response.status().is_success() {
-    return Ok(ApiResponse::error(format!(
-      "获取图片失败: {}",
-      response.status()
-    )));
+    return Err(format!("获取图片失败: {}", response.status()));
   }
 
   let content_type = response
@@ -144,16 +336,64 @@ pub async fn video_proxy_image(url: String) -> Result<ApiResponse<String>, Strin
     .unwrap_or("image/jpeg")
     .to_string();
 
-  let bytes = match response.bytes().await {
-    Ok(bytes) => bytes,
-    Err(err) => {
-      return Ok(ApiResponse::error(format!("读取图片失败: {}", err)));
-    }
+  let bytes = response
+    .bytes()
+    .await
+    .map_err(|err| format!("读取图片失败: {}", err))?;
+  Ok((content_type, bytes.to_vec()))
+}
+
+/// CDN 的 content-type 不总是准确，只按文件头判断图片格式，无法识别时返回 None。
+pub(crate) fn detect_image_mime(bytes: &[u8]) -> Option<&'static str> {
+  if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+    return Some("image/jpeg");
+  }
+  if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+    return Some("image/png");
+  }
+  if bytes.starts_with(b"GIF8") {
+    return Some("image/gif");
+  }
+  if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+    return Some("image/webp");
+  }
+  if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" && matches!(&bytes[8..12], b"avif" | b"avis") {
+    return Some("image/avif");
+  }
+  None
+}
+
+/// hdslb 图片地址支持 `@参数.格式` 后缀，改为 `.jpg` 即可让 CDN 输出 JPEG。
+fn build_jpeg_fallback_url(url: &str) -> Option<String> {
+  let parsed = url::Url::parse(url).ok()?;
+  if !parsed.host_str()?.ends_with("hdslb.com") {
+    return None;
+  }
+  let path = parsed.path();
+  let next_path = if let Some(at) = path.rfind('@') {
+    let params = &path[at + 1..];
+    let params = params.rsplit_once('.').map(|(head, _)| head).unwrap_or(params);
+    format!("{}@{}.jpg", &path[..at], params)
+  } else {
+    format!("{}@.jpg", path)
   };
+  let mut next = parsed.clone();
+  next.set_path(&next_path);
+  Some(next.to_string())
+}
 
-  let encoded = STANDARD.encode(bytes);
-  let data_url = format!("data:{};base64,{}", content_type, encoded);
-  Ok(ApiResponse::success(data_url))
+fn transcode_image(bytes: &[u8], target: image::ImageFormat) -> Result<Vec<u8>, String> {
+  let decoded = image::load_from_memory(bytes).map_err(|err| format!("图片解码失败: {}", err))?;
+  let decoded = if target == image::ImageFormat::Jpeg {
+    image::DynamicImage::ImageRgb8(decoded.to_rgb8())
+  } else {
+    decoded
+  };
+  let mut output = Cursor::new(Vec::new());
+  decoded
+    .write_to(&mut output, target)
+    .map_err(|err| format!("图片编码失败: {}", err))?;
+  Ok(output.into_inner())
 }
 
 #[tauri::command]
@@ -161,14 +401,122 @@ pub async fn bilibili_collections(
   state: State<'_, AppState>,
   mid: i64,
 ) -> Result<ApiResponse<Vec<Collection>>, String> {
-  let auth = load_auth(&state);
+  match fetch_collections(&state, mid).await {
+    Ok(collections) => Ok(ApiResponse::success(collections)),
+    Err(err) => Ok(ApiResponse::error(err)),
+  }
+}
+
+#[tauri::command]
+pub async fn bilibili_collection_sections(
+  state: State<'_, AppState>,
+  season_id: i64,
+  force: Option<bool>,
+) -> Result<ApiResponse<Vec<CollectionSection>>, String> {
+  let Some(auth) = load_auth(&state) else {
+    return Ok(ApiResponse::error("请先登录"));
+  };
+  if force.unwrap_or(false) {
+    invalidate_collection_sections(season_id);
+  }
+  match load_collection_sections(&state.bilibili, &auth, season_id).await {
+    Ok(sections) => Ok(ApiResponse::success(sections)),
+    Err(err) => {
+      append_log(
+        &state.app_log_path,
+        &format!("collection_sections_fail season_id={} err={}", season_id, err),
+      );
+      Ok(ApiResponse::error(err))
+    }
+  }
+}
+
+#[tauri::command]
+pub async fn bilibili_partitions(
+  state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<Partition>>, String> {
+  Ok(ApiResponse::success(fetch_partitions(&state).await))
+}
+
+#[tauri::command]
+pub async fn submission_create_options(
+  state: State<'_, AppState>,
+  mid: Option<i64>,
+  force: Option<bool>,
+) -> Result<ApiResponse<SubmissionCreateOptions>, String> {
+  let mid = mid.unwrap_or(0);
+  let force = force.unwrap_or(false);
+  let ttl_secs = load_create_options_ttl_secs(&state);
+  let cache = CREATE_OPTIONS_CACHE.get_or_init(|| Mutex::new(None));
+  if !force && ttl_secs > 0 {
+    if let Ok(guard) = cache.lock() {
+      if let Some(entry) = guard.as_ref() {
+        if entry.mid == mid && entry.loaded_at.elapsed().as_secs() < ttl_secs {
+          let mut options = entry.options.clone();
+          options.from_cache = true;
+          return Ok(ApiResponse::success(options));
+        }
+      }
+    }
+  }
+
+  if force {
+    partition_cache().remove(&());
+  }
+  let collections = match fetch_collections(&state, mid).await {
+    Ok(collections) => collections,
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+  // 分区接口失败时返回内置列表，但不写入缓存，下次打开时重新拉取。
+  let remote_partitions = load_remote_partitions(&state).await;
+  let cacheable = remote_partitions.is_some();
+  let partitions = remote_partitions.unwrap_or_else(default_partitions);
+  let options = SubmissionCreateOptions {
+    collections,
+    partitions: group_partitions(&partitions),
+    fetched_at: Utc::now().to_rfc3339(),
+    from_cache: false,
+  };
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "submission_create_options_refresh mid={} force={} collections={} partitions={} cached={}",
+      mid,
+      force,
+      options.collections.len(),
+      options.partitions.len(),
+      cacheable && ttl_secs > 0
+    ),
+  );
+  if !cacheable {
+    return Ok(ApiResponse::success(options));
+  }
+  if let Ok(mut guard) = cache.lock() {
+    *guard = Some(CreateOptionsCacheEntry {
+      mid,
+      loaded_at: Instant::now(),
+      options: options.clone(),
+    });
+  }
+  Ok(ApiResponse::success(options))
+}
+
+fn load_create_options_ttl_secs(state: &State<'_, AppState>) -> u64 {
+  load_download_settings_from_db(&state.db)
+    .map(|settings| settings.submission_create_options_ttl_secs)
+    .unwrap_or(DEFAULT_CREATE_OPTIONS_TTL_SECS)
+    .max(0) as u64
+}
+
+async fn fetch_collections(state: &State<'_, AppState>, mid: i64) -> Result<Vec<Collection>, String> {
+  let auth = load_auth(state);
   append_log(
     &state.app_log_path,
     &format!("collections_start mid={} has_auth={}", mid, auth.is_some()),
   );
   if auth.is_none() {
     append_log(&state.app_log_path, &format!("collections_no_auth mid={}", mid));
-    return Ok(ApiResponse::error("Login required"));
+    return Err("Login required".to_string());
   }
 
   let params = vec![
@@ -191,7 +539,7 @@ pub async fn bilibili_collections(
         &state.app_log_path,
         &format!("collections_api_error mid={} err={}", mid, err),
       );
-      return Ok(ApiResponse::error(format!("Failed to load collections: {}", err)));
+      return Err(format!("Failed to load collections: {}", err));
     }
   };
 
@@ -223,14 +571,131 @@ pub async fn bilibili_collections(
     &format!("collections_ok mid={} count={}", mid, collections.len()),
   );
 
-  Ok(ApiResponse::success(collections))
+  Ok(collections)
 }
 
-#[tauri::command]
-pub async fn bilibili_partitions(
-  state: State<'_, AppState>,
-) -> Result<ApiResponse<Vec<Partition>>, String> {
-  let auth = load_auth(&state);
+/// 读取合集的小节列表，结果按 season_id 缓存 30 分钟；合集不存在或没有小节时返回错误。
+pub async fn load_collection_sections(
+  bilibili: &BilibiliClient,
+  auth: &AuthInfo,
+  season_id: i64,
+) -> Result<Vec<CollectionSection>, String> {
+  if season_id <= 0 {
+    return Err("合集ID无效".to_string());
+  }
+  if let Some(sections) = collection_section_cache().get(&season_id) {
+    return Ok(sections);
+  }
+
+  let url = "https://member.bilibili.com/x2/creative/web/seasons";
+  let params = vec![
+    ("pn".to_string(), "1".to_string()),
+    ("ps".to_string(), "100".to_string()),
+    ("order".to_string(), "desc".to_string()),
+    ("sort".to_string(), "mtime".to_string()),
+    ("filter".to_string(), "1".to_string()),
+  ];
+  let data = bilibili
+    .get_json(url, &params, Some(auth), false)
+    .await
+    .map_err(|err| format!("读取合集小节失败: {}", err))?;
+  let item = data
+    .get("seasons")
+    .and_then(|value| value.as_array())
+    .and_then(|seasons| {
+      seasons.iter().find(|item| {
+        item
+          .get("season")
+          .and_then(|season| season.get("id"))
+          .and_then(|value| value.as_i64())
+          == Some(season_id)
+      })
+    })
+    .ok_or_else(|| format!("未找到合集 {}", season_id))?;
+  let sections = item
+    .get("sections")
+    .and_then(|value| value.get("sections"))
+    .and_then(|value| value.as_array())
+    .map(|list| {
+      list
+        .iter()
+        .filter_map(|section| {
+          let section_id = section.get("id").and_then(|value| value.as_i64())?;
+          Some(CollectionSection {
+            section_id,
+            title: section
+              .get("title")
+              .and_then(|value| value.as_str())
+              .unwrap_or_default()
+              .to_string(),
+          })
+        })
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+  if sections.is_empty() {
+    return Err(format!("合集 {} 没有可用的小节", season_id));
+  }
+  collection_section_cache().insert(season_id, sections.clone());
+  Ok(sections)
+}
+
+/// 投稿加入合集时使用第一个小节。
+pub async fn resolve_collection_section_id(
+  bilibili: &BilibiliClient,
+  auth: &AuthInfo,
+  season_id: i64,
+) -> Result<i64, String> {
+  let sections = load_collection_sections(bilibili, auth, season_id).await?;
+  sections
+    .first()
+    .map(|section| section.section_id)
+    .ok_or_else(|| format!("合集 {} 没有可用的小节", season_id))
+}
+
+pub fn invalidate_collection_sections(season_id: i64) {
+  collection_section_cache().remove(&season_id);
+}
+
+async fn fetch_partitions(state: &State<'_, AppState>) -> Vec<Partition> {
+  load_remote_partitions(state)
+    .await
+    .unwrap_or_else(default_partitions)
+}
+
+/// 校验投稿分区，分区列表来自带 TTL 的缓存；接口不可用时不拦截，交由投稿接口判断。
+pub async fn validate_partition_id(
+  state: &State<'_, AppState>,
+  partition_id: i64,
+) -> Result<(), String> {
+  let Some(partitions) = load_remote_partitions(state).await else {
+    append_log(
+      &state.app_log_path,
+      &format!("partition_validate_skip partition_id={} reason=list_unavailable", partition_id),
+    );
+    return Ok(());
+  };
+  if partitions.iter().any(|partition| partition.tid == partition_id) {
+    return Ok(());
+  }
+  append_log(
+    &state.app_log_path,
+    &format!("partition_validate_reject partition_id={}", partition_id),
+  );
+  Err("分区不存在或不可用".to_string())
+}
+
+async fn load_remote_partitions(state: &State<'_, AppState>) -> Option<Vec<Partition>> {
+  if let Some(partitions) = partition_cache().get(&()) {
+    return Some(partitions);
+  }
+  let partitions = fetch_remote_partitions(state).await?;
+  partition_cache().insert((), partitions.clone());
+  Some(partitions)
+}
+
+async fn fetch_remote_partitions(state: &State<'_, AppState>) -> Option<Vec<Partition>> {
+  let auth = load_auth(state);
   let params = vec![("t".to_string(), format!("{}", Utc::now().timestamp_millis()))];
   let url = "https://member.bilibili.com/x/vupre/web/archive/human/type2/list";
 
@@ -240,30 +705,80 @@ pub async fn bilibili_partitions(
     .await
   {
     Ok(data) => data,
-    Err(_) => return Ok(ApiResponse::success(default_partitions())),
+    Err(_) => return None,
   };
 
   let list = data.get("type_list").and_then(|value| value.as_array());
   let mut partitions = Vec::new();
   if let Some(list) = list {
     for item in list {
-      if let (Some(id), Some(name)) = (
-        item.get("id").and_then(|value| value.as_i64()),
-        item.get("name").and_then(|value| value.as_str()),
-      ) {
-        partitions.push(Partition {
-          tid: id,
-          name: name.to_string(),
-        });
-      }
+      push_remote_partition(&mut partitions, item, None);
     }
   }
 
   if partitions.is_empty() {
-    Ok(ApiResponse::success(default_partitions()))
+    None
   } else {
-    Ok(ApiResponse::success(partitions))
+    Some(partitions)
+  }
+}
+
+/// 分区条目可能以 `children` 嵌套子分区，也可能在子分区上用 `parent` 标明父分区。
+fn push_remote_partition(partitions: &mut Vec<Partition>, item: &Value, parent_tid: Option<i64>) {
+  let (Some(id), Some(name)) = (
+    item.get("id").and_then(|value| value.as_i64()),
+    item.get("name").and_then(|value| value.as_str()),
+  ) else {
+    return;
+  };
+  let parent_tid = parent_tid.or_else(|| {
+    item
+      .get("parent")
+      .and_then(|value| value.as_i64())
+      .filter(|value| *value > 0 && *value != id)
+  });
+  partitions.push(Partition {
+    tid: id,
+    name: name.to_string(),
+    parent_tid,
+  });
+  if let Some(children) = item.get("children").and_then(|value| value.as_array()) {
+    for child in children {
+      push_remote_partition(partitions, child, Some(id));
+    }
+  }
+}
+
+/// 按父分区整理成两级结构，保持接口返回的顺序；父分区不在列表中时以其 tid 建组。
+fn group_partitions(partitions: &[Partition]) -> Vec<PartitionGroup> {
+  let mut groups: Vec<PartitionGroup> = Vec::new();
+  for partition in partitions.iter().filter(|partition| partition.parent_tid.is_none()) {
+    if !groups.iter().any(|group| group.tid == partition.tid) {
+      groups.push(PartitionGroup {
+        tid: partition.tid,
+        name: partition.name.clone(),
+        children: Vec::new(),
+      });
+    }
+  }
+  for partition in partitions {
+    let Some(parent_tid) = partition.parent_tid else {
+      continue;
+    };
+    let index = match groups.iter().position(|group| group.tid == parent_tid) {
+      Some(index) => index,
+      None => {
+        groups.push(PartitionGroup {
+          tid: parent_tid,
+          name: format!("分区 {}", parent_tid),
+          children: Vec::new(),
+        });
+        groups.len() - 1
+      }
+    };
+    groups[index].children.push(partition.clone());
   }
+  groups
 }
 
 fn default_partitions() -> Vec<Partition> {
@@ -271,18 +786,22 @@ fn default_partitions() -> Vec<Partition> {
     Partition {
       tid: 1,
       name: "Animation".to_string(),
+      parent_tid: None,
     },
     Partition {
       tid: 4,
       name: "Game".to_string(),
+      parent_tid: None,
     },
     Partition {
       tid: 36,
       name: "Knowledge".to_string(),
+      parent_tid: None,
     },
     Partition {
       tid: 188,
       name: "Technology".to_string(),
+      parent_tid: None,
     },
   ]
 }
@@ -2,6 +2,7 @@ use std::env;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
+use std::sync::RwLock;
 
 use tauri::path::BaseDirectory;
 use tauri::AppHandle;
@@ -17,6 +18,134 @@ const ENV_FFPROBE_PATH: &str = "REACTION_CUT_FFPROBE_PATH";
 const ENV_ARIA2C_PATH: &str = "REACTION_CUT_ARIA2C_PATH";
 const ENV_BAIDU_PCS_PATH: &str = "REACTION_CUT_BAIDU_PCS_PATH";
 const ENV_BAIDU_PCS_CONFIG_DIR: &str = "BAIDUPCS_GO_CONFIG_DIR";
+const PROXY_SCHEMES: [&str; 4] = ["http", "https", "socks5", "socks5h"];
+pub const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/132.0.0.0 Safari/537.36 Edg/132.0.0.0";
+
+static PROXY_URL: RwLock<Option<String>> = RwLock::new(None);
+static CUSTOM_USER_AGENT: RwLock<Option<String>> = RwLock::new(None);
+static CUSTOM_LIVE_REFERER: RwLock<Option<String>> = RwLock::new(None);
+static CUSTOM_WEB_REFERER: RwLock<Option<String>> = RwLock::new(None);
+
+fn normalize_override(value: Option<&str>) -> Option<String> {
+  value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+/// 设置自定义 User-Agent 与直播/主站 Referer，空值表示使用各请求内置的默认值。
+pub fn set_request_identity(
+  user_agent: Option<&str>,
+  live_referer: Option<&str>,
+  web_referer: Option<&str>,
+) {
+  if let Ok(mut guard) = CUSTOM_USER_AGENT.write() {
+    *guard = normalize_override(user_agent);
+  }
+  if let Ok(mut guard) = CUSTOM_LIVE_REFERER.write() {
+    *guard = normalize_override(live_referer);
+  }
+  if let Ok(mut guard) = CUSTOM_WEB_REFERER.write() {
+    *guard = normalize_override(web_referer);
+  }
+}
+
+pub fn user_agent_or(default: &str) -> String {
+  CUSTOM_USER_AGENT
+    .read()
+    .ok()
+    .and_then(|guard| guard.clone())
+    .unwrap_or_else(|| default.to_string())
+}
+
+/// live.bilibili.com 相关请求使用的 Referer。
+pub fn live_referer_or(default: &str) -> String {
+  CUSTOM_LIVE_REFERER
+    .read()
+    .ok()
+    .and_then(|guard| guard.clone())
+    .unwrap_or_else(|| default.to_string())
+}
+
+/// www.bilibili.com 相关请求使用的 Referer。
+pub fn web_referer_or(default: &str) -> String {
+  CUSTOM_WEB_REFERER
+    .read()
+    .ok()
+    .and_then(|guard| guard.clone())
+    .unwrap_or_else(|| default.to_string())
+}
+
+pub fn validate_header_override(label: &str, value: &str) -> Result<(), String> {
+  reqwest::header::HeaderValue::from_str(value.trim())
+    .map(|_| ())
+    .map_err(|_| format!("{}包含无效字符", label))
+}
+
+/// 设置对外请求使用的代理，空字符串表示直连。
+pub fn set_proxy_url(url: Option<&str>) {
+  if let Ok(mut guard) = PROXY_URL.write() {
+    *guard = normalize_override(url);
+  }
+}
+
+pub fn current_proxy_url() -> Option<String> {
+  PROXY_URL.read().ok().and_then(|guard| guard.clone())
+}
+
+pub fn validate_proxy_url(value: &str) -> Result<(), String> {
+  let parsed = url::Url::parse(value.trim()).map_err(|_| "代理地址格式不正确".to_string())?;
+  if !PROXY_SCHEMES.contains(&parsed.scheme()) {
+    return Err("代理仅支持 http、https、socks5、socks5h".to_string());
+  }
+  if parsed.host_str().unwrap_or("").is_empty() {
+    return Err("代理地址缺少主机名".to_string());
+  }
+  reqwest::Proxy::all(value.trim()).map_err(|err| format!("代理地址无效: {}", err))?;
+  Ok(())
+}
+
+/// aria2c 的 --all-proxy 只支持 HTTP 代理。
+pub fn current_http_proxy_url() -> Option<String> {
+  current_proxy_url().filter(|value| value.starts_with("http://") || value.starts_with("https://"))
+}
+
+/// 每次请求时读取当前代理配置，修改设置后已创建的客户端也立即生效。
+/// 本机与局域网地址（例如本地 webhook）始终直连。
+pub fn http_proxy() -> reqwest::Proxy {
+  reqwest::Proxy::custom(|url| {
+    if url.host().is_some_and(|host| is_local_host(&host)) {
+      return None;
+    }
+    current_proxy_url()
+  })
+}
+
+fn is_local_host(host: &url::Host<&str>) -> bool {
+  match host {
+    url::Host::Domain(domain) => {
+      let domain = domain.to_ascii_lowercase();
+      domain == "localhost" || domain.ends_with(".localhost") || domain.ends_with(".local")
+    }
+    url::Host::Ipv4(ip) => ip.is_loopback() || ip.is_private() || ip.is_link_local(),
+    url::Host::Ipv6(ip) => {
+      let first = ip.segments()[0];
+      // fc00::/7 唯一本地地址，fe80::/10 链路本地地址
+      ip.is_loopback() || (first & 0xfe00) == 0xfc00 || (first & 0xffc0) == 0xfe80
+    }
+  }
+}
+
+pub fn build_http_client() -> reqwest::Client {
+  reqwest::Client::builder()
+    .proxy(http_proxy())
+    .build()
+    .unwrap_or_else(|_| reqwest::Client::new())
+}
+
+pub fn build_blocking_http_client() -> reqwest::blocking::Client {
+  reqwest::blocking::Client::builder()
+    .proxy(http_proxy())
+    .build()
+    .unwrap_or_else(|_| reqwest::blocking::Client::new())
+}
 
 fn resolve_home_dir() -> Option<PathBuf> {
   if cfg!(target_os = "windows") {
@@ -46,6 +46,38 @@ impl Db {
       "ALTER TABLE live_settings ADD COLUMN title_split_min_seconds INTEGER DEFAULT 1800",
       [],
     );
+    let _ = conn.execute(
+      "ALTER TABLE live_settings ADD COLUMN split_by_size_mb INTEGER NOT NULL DEFAULT 0",
+      [],
+    );
+    let _ = conn.execute(
+      "ALTER TABLE live_settings ADD COLUMN record_backup_quality INTEGER NOT NULL DEFAULT 0",
+      [],
+    );
+    let _ = conn.execute(
+      "ALTER TABLE live_settings ADD COLUMN danmaku_format TEXT NOT NULL DEFAULT 'json'",
+      [],
+    );
+    let _ = conn.execute(
+      "ALTER TABLE live_settings ADD COLUMN min_free_space_mb INTEGER NOT NULL DEFAULT 1024",
+      [],
+    );
+    let _ = conn.execute(
+      "ALTER TABLE live_settings ADD COLUMN max_concurrent_recordings INTEGER NOT NULL DEFAULT 0",
+      [],
+    );
+    let _ = conn.execute(
+      "ALTER TABLE live_settings ADD COLUMN remux_fragmented_mp4 INTEGER NOT NULL DEFAULT 0",
+      [],
+    );
+    let _ = conn.execute(
+      "ALTER TABLE live_settings ADD COLUMN danmaku_filter_keywords TEXT NOT NULL DEFAULT ''",
+      [],
+    );
+    let _ = conn.execute(
+      "ALTER TABLE live_settings ADD COLUMN danmaku_max_per_second INTEGER NOT NULL DEFAULT 0",
+      [],
+    );
     let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN aid INTEGER", []);
     let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN remote_state INTEGER", []);
     let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN reject_reason TEXT", []);
@@ -55,6 +87,26 @@ impl Db {
     );
     let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN baidu_sync_path TEXT", []);
     let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN baidu_sync_filename TEXT", []);
+    let _ = conn.execute(
+      "ALTER TABLE submission_task ADD COLUMN auto_retry_count INTEGER DEFAULT 0",
+      [],
+    );
+    let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN client_request_id TEXT", []);
+    let _ = conn.execute(
+      "ALTER TABLE submission_task ADD COLUMN upload_failure_count INTEGER DEFAULT 0",
+      [],
+    );
+    let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN last_upload_error TEXT", []);
+    let _ = conn.execute(
+      "ALTER TABLE submission_task ADD COLUMN upload_paused INTEGER NOT NULL DEFAULT 0",
+      [],
+    );
+    let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN scheduled_publish_at INTEGER", []);
+    let _ = conn.execute(
+      "CREATE UNIQUE INDEX IF NOT EXISTS idx_submission_task_client_request_id \
+       ON submission_task (client_request_id)",
+      [],
+    );
     let _ = conn.execute("ALTER TABLE video_download ADD COLUMN cid INTEGER", []);
     let _ = conn.execute("ALTER TABLE video_download ADD COLUMN content TEXT", []);
     let _ = conn.execute(
@@ -65,6 +117,25 @@ impl Db {
       "ALTER TABLE video_download ADD COLUMN progress_done INTEGER DEFAULT 0",
       [],
     );
+    let _ = conn.execute(
+      "ALTER TABLE video_download ADD COLUMN priority INTEGER DEFAULT 0",
+      [],
+    );
+    let _ = conn.execute("ALTER TABLE video_download ADD COLUMN max_resolution INTEGER", []);
+    let _ = conn.execute("ALTER TABLE video_download ADD COLUMN status_message TEXT", []);
+    // 早期版本把全部取消记为 status = 5，统一并入失败/已取消
+    let _ = conn.execute(
+      "UPDATE video_download SET status = 3, status_message = '下载已取消' WHERE status = 5",
+      [],
+    );
+    let _ = conn.execute(
+      "ALTER TABLE baidu_sync_task ADD COLUMN uploaded_bytes INTEGER DEFAULT 0",
+      [],
+    );
+    let _ = conn.execute(
+      "ALTER TABLE baidu_sync_task ADD COLUMN total_bytes INTEGER DEFAULT 0",
+      [],
+    );
     let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_progress REAL DEFAULT 0.0", []);
     let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_uploaded_bytes INTEGER DEFAULT 0", []);
     let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_total_bytes INTEGER DEFAULT 0", []);
@@ -77,6 +148,7 @@ impl Db {
     let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_uri TEXT", []);
     let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_chunk_size INTEGER DEFAULT 0", []);
     let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_last_part_index INTEGER DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN content_hash TEXT", []);
     let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_progress REAL DEFAULT 0.0", []);
     let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_uploaded_bytes INTEGER DEFAULT 0", []);
     let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_total_bytes INTEGER DEFAULT 0", []);
@@ -87,11 +159,31 @@ impl Db {
     let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_uri TEXT", []);
     let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_chunk_size INTEGER DEFAULT 0", []);
     let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_last_part_index INTEGER DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_speed_bps INTEGER DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_eta_seconds INTEGER", []);
+    let _ = conn.execute(
+      "ALTER TABLE task_output_segment ADD COLUMN file_cleaned INTEGER NOT NULL DEFAULT 0",
+      [],
+    );
+    let _ = conn.execute("ALTER TABLE edit_upload_segment ADD COLUMN upload_speed_bps INTEGER DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE edit_upload_segment ADD COLUMN upload_eta_seconds INTEGER", []);
     let _ = conn.execute("ALTER TABLE live_room_settings ADD COLUMN baidu_sync_path TEXT", []);
     let _ = conn.execute(
       "ALTER TABLE live_room_settings ADD COLUMN baidu_sync_enabled INTEGER DEFAULT 0",
       [],
     );
+    let _ = conn.execute(
+      "ALTER TABLE live_room_settings ADD COLUMN max_daily_recording_hours REAL DEFAULT 0",
+      [],
+    );
+    let _ = conn.execute(
+      "ALTER TABLE live_room_settings ADD COLUMN settings_override TEXT",
+      [],
+    );
+    let _ = conn.execute(
+      "ALTER TABLE live_record_task ADD COLUMN remux_retry_count INTEGER DEFAULT 0",
+      [],
+    );
 
     Ok(Self {
       conn: Mutex::new(conn),
@@ -49,7 +49,28 @@ CREATE TABLE IF NOT EXISTS submission_task (
   segment_prefix TEXT,
   baidu_sync_enabled INTEGER DEFAULT 0,
   baidu_sync_path TEXT,
-  baidu_sync_filename TEXT
+  baidu_sync_filename TEXT,
+  auto_retry_count INTEGER DEFAULT 0,
+  client_request_id TEXT,
+  upload_failure_count INTEGER DEFAULT 0,
+  last_upload_error TEXT,
+  scheduled_publish_at INTEGER,
+  upload_paused INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_submission_task_status_created_at ON submission_task (status, created_at);
+CREATE INDEX IF NOT EXISTS idx_submission_task_created_at ON submission_task (created_at);
+
+CREATE TABLE IF NOT EXISTS task_stats (
+  task_id TEXT PRIMARY KEY,
+  clip_duration_ms INTEGER DEFAULT 0,
+  merge_duration_ms INTEGER DEFAULT 0,
+  segment_duration_ms INTEGER DEFAULT 0,
+  segment_count INTEGER DEFAULT 0,
+  upload_bytes INTEGER DEFAULT 0,
+  upload_duration_ms INTEGER DEFAULT 0,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL
 );
 
 CREATE TABLE IF NOT EXISTS merged_video (
@@ -71,6 +92,7 @@ CREATE TABLE IF NOT EXISTS merged_video (
   upload_uri TEXT,
   upload_chunk_size INTEGER DEFAULT 0,
   upload_last_part_index INTEGER DEFAULT 0,
+  content_hash TEXT,
   create_time TEXT NOT NULL,
   update_time TEXT NOT NULL,
   FOREIGN KEY (task_id) REFERENCES submission_task (task_id) ON DELETE CASCADE
@@ -95,11 +117,41 @@ CREATE TABLE IF NOT EXISTS task_output_segment (
   upload_uri TEXT,
   upload_chunk_size INTEGER DEFAULT 0,
   upload_last_part_index INTEGER DEFAULT 0,
+  upload_speed_bps INTEGER DEFAULT 0,
+  upload_eta_seconds INTEGER,
+  file_cleaned INTEGER NOT NULL DEFAULT 0,
   FOREIGN KEY (task_id) REFERENCES submission_task (task_id) ON DELETE CASCADE
 );
 
 CREATE INDEX IF NOT EXISTS idx_task_output_segment_task_id ON task_output_segment (task_id);
 
+CREATE TABLE IF NOT EXISTS edit_upload_segment (
+  segment_id TEXT PRIMARY KEY,
+  task_id TEXT NOT NULL,
+  part_name TEXT NOT NULL,
+  segment_file_path TEXT NOT NULL,
+  part_order INTEGER NOT NULL,
+  upload_status TEXT NOT NULL,
+  cid INTEGER,
+  file_name TEXT,
+  upload_progress REAL DEFAULT 0.0,
+  upload_uploaded_bytes INTEGER DEFAULT 0,
+  upload_total_bytes INTEGER DEFAULT 0,
+  upload_session_id TEXT,
+  upload_biz_id INTEGER DEFAULT 0,
+  upload_endpoint TEXT,
+  upload_auth TEXT,
+  upload_uri TEXT,
+  upload_chunk_size INTEGER DEFAULT 0,
+  upload_last_part_index INTEGER DEFAULT 0,
+  upload_speed_bps INTEGER DEFAULT 0,
+  upload_eta_seconds INTEGER,
+  update_time TEXT NOT NULL,
+  FOREIGN KEY (task_id) REFERENCES submission_task (task_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_edit_upload_segment_task_id ON edit_upload_segment (task_id);
+
 CREATE TABLE IF NOT EXISTS task_source_video (
   id TEXT PRIMARY KEY,
   task_id TEXT NOT NULL,
@@ -147,7 +199,10 @@ CREATE TABLE IF NOT EXISTS video_download (
   part_count INTEGER,
   current_part INTEGER,
   cid INTEGER,
-  content TEXT
+  content TEXT,
+  priority INTEGER DEFAULT 0,
+  max_resolution INTEGER,
+  status_message TEXT
 );
 
 CREATE INDEX IF NOT EXISTS idx_video_download_status ON video_download (status);
@@ -342,7 +397,15 @@ CREATE TABLE IF NOT EXISTS live_settings (
   cutting_number INTEGER NOT NULL,
   cutting_by_title INTEGER NOT NULL,
   title_split_min_seconds INTEGER NOT NULL DEFAULT 1800,
+  split_by_size_mb INTEGER NOT NULL DEFAULT 0,
+  record_backup_quality INTEGER NOT NULL DEFAULT 0,
+  min_free_space_mb INTEGER NOT NULL DEFAULT 1024,
+  max_concurrent_recordings INTEGER NOT NULL DEFAULT 0,
+  remux_fragmented_mp4 INTEGER NOT NULL DEFAULT 0,
   danmaku_transport INTEGER NOT NULL,
+  danmaku_format TEXT NOT NULL DEFAULT 'json',
+  danmaku_filter_keywords TEXT NOT NULL DEFAULT '',
+  danmaku_max_per_second INTEGER NOT NULL DEFAULT 0,
   record_danmaku INTEGER NOT NULL,
   record_danmaku_raw INTEGER NOT NULL,
   record_danmaku_superchat INTEGER NOT NULL,
@@ -365,6 +428,8 @@ CREATE TABLE IF NOT EXISTS live_room_settings (
   auto_record INTEGER NOT NULL DEFAULT 1,
   baidu_sync_enabled INTEGER NOT NULL DEFAULT 0,
   baidu_sync_path TEXT,
+  max_daily_recording_hours REAL DEFAULT 0,
+  settings_override TEXT,
   update_time TEXT NOT NULL
 );
 
@@ -379,6 +444,7 @@ CREATE TABLE IF NOT EXISTS live_record_task (
   file_size INTEGER DEFAULT 0,
   title TEXT,
   error_message TEXT,
+  remux_retry_count INTEGER DEFAULT 0,
   create_time TEXT NOT NULL,
   update_time TEXT NOT NULL
 );
@@ -395,6 +461,9 @@ INSERT OR IGNORE INTO app_settings (key, value, updated_at) VALUES
   ('download_threads', '3', datetime('now')),
   ('download_queue_size', '10', datetime('now')),
   ('submission_upload_concurrency', '3', datetime('now')),
+  ('submission_chunk_concurrency', '1', datetime('now')),
+  ('submission_auto_retry_enabled', '0', datetime('now')),
+  ('submission_auto_retry_max_attempts', '3', datetime('now')),
   ('baidu_sync_enabled', '1', datetime('now')),
   ('baidu_sync_exec_path', '', datetime('now')),
   ('baidu_sync_target_path', '/录播', datetime('now')),
@@ -439,6 +508,8 @@ CREATE TABLE IF NOT EXISTS baidu_sync_task (
   error TEXT,
   retry_count INTEGER DEFAULT 0,
   policy TEXT,
+  uploaded_bytes INTEGER DEFAULT 0,
+  total_bytes INTEGER DEFAULT 0,
   created_at TEXT NOT NULL,
   updated_at TEXT NOT NULL
 );
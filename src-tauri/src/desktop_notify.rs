@@ -0,0 +1,47 @@
+use std::path::Path;
+
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::settings::load_download_settings_from_db;
+use crate::db::Db;
+use crate::utils::append_log;
+
+#[derive(Clone, Copy)]
+pub enum NotifyCategory {
+  Recording,
+  Download,
+  Submission,
+}
+
+impl NotifyCategory {
+  fn as_str(self) -> &'static str {
+    match self {
+      NotifyCategory::Recording => "recording",
+      NotifyCategory::Download => "download",
+      NotifyCategory::Submission => "submission",
+    }
+  }
+}
+
+/// 发送系统通知，按分类读取设置开关；通知失败只记日志。
+pub fn notify(db: &Db, app_log_path: &Path, category: NotifyCategory, title: &str, body: &str) {
+  let Some(app_handle) = crate::app_events::app_handle() else {
+    return;
+  };
+  let enabled = load_download_settings_from_db(db)
+    .map(|settings| match category {
+      NotifyCategory::Recording => settings.notify_recording,
+      NotifyCategory::Download => settings.notify_download,
+      NotifyCategory::Submission => settings.notify_submission,
+    })
+    .unwrap_or(true);
+  if !enabled {
+    return;
+  }
+  if let Err(err) = app_handle.notification().builder().title(title).body(body).show() {
+    append_log(
+      app_log_path,
+      &format!("desktop_notify_fail category={} err={}", category.as_str(), err),
+    );
+  }
+}
@@ -1,9 +1,103 @@
 use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
 
 use serde_json::Value;
 
 use crate::config::{resolve_ffmpeg_path, resolve_ffprobe_path};
+use crate::utils::append_log;
+
+/// 按优先级挑选解码加速方式；vdpau/opencl/vulkan 等需要额外滤镜配合，不自动使用。
+const PREFERRED_HWACCELS: [&str; 6] = ["videotoolbox", "cuda", "qsv", "d3d11va", "dxva2", "vaapi"];
+
+pub const SOFTWARE_H264_ENCODER: &str = "libx264";
+
+static HWACCEL_ENABLED: AtomicBool = AtomicBool::new(false);
+static AVAILABLE_HWACCELS: OnceLock<Vec<String>> = OnceLock::new();
+
+pub fn set_hwaccel_enabled(enabled: bool) {
+  HWACCEL_ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// 启动时执行一次 `ffmpeg -hwaccels` 并缓存结果，检测失败视为不支持。
+pub fn init_hwaccels(log_path: &Path) {
+  let methods = match detect_hwaccels() {
+    Ok(methods) => methods,
+    Err(err) => {
+      append_log(log_path, &format!("ffmpeg_hwaccel_detect_fail err={}", err));
+      Vec::new()
+    }
+  };
+  append_log(
+    log_path,
+    &format!(
+      "ffmpeg_hwaccels available={} preferred={} enabled={}",
+      if methods.is_empty() { "-".to_string() } else { methods.join(",") },
+      pick_hwaccel(&methods).unwrap_or("-"),
+      HWACCEL_ENABLED.load(Ordering::SeqCst)
+    ),
+  );
+  let _ = AVAILABLE_HWACCELS.set(methods);
+}
+
+pub fn available_hwaccels() -> &'static [String] {
+  AVAILABLE_HWACCELS.get().map(Vec::as_slice).unwrap_or(&[])
+}
+
+/// 用户开启且检测到可用加速时返回 `-hwaccel` 的取值。
+pub fn active_hwaccel() -> Option<&'static str> {
+  if !HWACCEL_ENABLED.load(Ordering::SeqCst) {
+    return None;
+  }
+  pick_hwaccel(available_hwaccels())
+}
+
+/// 解码加速对应的 H.264 硬件编码器；vaapi/d3d11va/dxva2 需要额外的上传滤镜或厂商编码器，使用软件编码。
+pub fn hwaccel_h264_encoder(method: &str) -> Option<&'static str> {
+  match method {
+    "videotoolbox" => Some("h264_videotoolbox"),
+    "cuda" => Some("h264_nvenc"),
+    "qsv" => Some("h264_qsv"),
+    _ => None,
+  }
+}
+
+/// 重编码默认使用的 H.264 编码器，未启用或未检测到可用加速时为 libx264。
+pub fn default_h264_encoder() -> &'static str {
+  active_hwaccel()
+    .and_then(hwaccel_h264_encoder)
+    .unwrap_or(SOFTWARE_H264_ENCODER)
+}
+
+fn pick_hwaccel(methods: &[String]) -> Option<&'static str> {
+  PREFERRED_HWACCELS
+    .iter()
+    .copied()
+    .find(|candidate| methods.iter().any(|method| method == candidate))
+}
+
+fn detect_hwaccels() -> Result<Vec<String>, String> {
+  let output = Command::new(resolve_ffmpeg_path())
+    .args(["-hide_banner", "-hwaccels"])
+    .output()
+    .map_err(|err| format!("Failed to start FFmpeg: {}", err))?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(format!("FFmpeg failed: {}", stderr.trim()));
+  }
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  Ok(
+    stdout
+      .lines()
+      .map(str::trim)
+      .filter(|line| !line.is_empty() && !line.ends_with(':'))
+      .map(|line| line.to_string())
+      .collect(),
+  )
+}
 
 pub fn run_ffmpeg(args: &[String]) -> Result<(), String> {
   let ffmpeg_path = resolve_ffmpeg_path();
@@ -20,13 +114,66 @@ pub fn run_ffmpeg(args: &[String]) -> Result<(), String> {
   Err(format!("FFmpeg failed: {}", stderr.trim()))
 }
 
-pub fn run_ffmpeg_with_progress<F>(
+/// 与 `run_ffmpeg` 相同，但会轮询 `cancel`，置位后终止 FFmpeg 进程。
+pub fn run_ffmpeg_cancellable(args: &[String], cancel: &AtomicBool) -> Result<(), String> {
+  run_ffmpeg_until(args, || cancel.load(Ordering::SeqCst))
+}
+
+/// 轮询 `should_stop`，返回 true 时终止 FFmpeg 进程。
+pub fn run_ffmpeg_until<S>(args: &[String], should_stop: S) -> Result<(), String>
+where
+  S: Fn() -> bool,
+{
+  let ffmpeg_path = resolve_ffmpeg_path();
+  let mut child = Command::new(ffmpeg_path)
+    .args(args)
+    .stdout(Stdio::null())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|err| format!("Failed to start FFmpeg: {}", err))?;
+
+  let mut stderr = child
+    .stderr
+    .take()
+    .ok_or_else(|| "Failed to capture FFmpeg stderr".to_string())?;
+  let (stderr_tx, stderr_rx) = std::sync::mpsc::channel();
+  std::thread::spawn(move || {
+    let mut buffer = String::new();
+    let _ = stderr.read_to_string(&mut buffer);
+    let _ = stderr_tx.send(buffer);
+  });
+
+  let status = loop {
+    if should_stop() {
+      let _ = child.kill();
+      let _ = child.wait();
+      return Err("FFmpeg cancelled".to_string());
+    }
+    match child.try_wait() {
+      Ok(Some(status)) => break status,
+      Ok(None) => std::thread::sleep(Duration::from_millis(200)),
+      Err(err) => return Err(format!("Failed to wait FFmpeg: {}", err)),
+    }
+  };
+
+  if status.success() {
+    return Ok(());
+  }
+
+  let stderr = stderr_rx.recv().unwrap_or_default();
+  Err(format!("FFmpeg failed: {}", stderr.trim()))
+}
+
+/// `should_stop` 在每行进度输出后检查，返回 true 时终止 FFmpeg 进程。
+pub fn run_ffmpeg_with_progress<F, S>(
   args: &[String],
   duration_ms: Option<i64>,
   mut on_progress: F,
+  should_stop: S,
 ) -> Result<(), String>
 where
   F: FnMut(i64),
+  S: Fn() -> bool,
 {
   let ffmpeg_path = resolve_ffmpeg_path();
   let mut child = Command::new(ffmpeg_path)
@@ -55,7 +202,12 @@ where
   let total_ms = duration_ms.unwrap_or(0);
   let mut last_progress = -1;
   let reader = BufReader::new(stdout);
-  for line in reader.lines().flatten() {
+  for line in reader.lines().map_while(Result::ok) {
+    if should_stop() {
+      let _ = child.kill();
+      let _ = child.wait();
+      return Err("FFmpeg cancelled".to_string());
+    }
     if total_ms <= 0 {
       continue;
     }
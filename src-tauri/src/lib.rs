@@ -6,20 +6,26 @@ use tokio::time::{sleep, Duration};
 use tauri::Manager;
 
 mod api;
+mod app_events;
 mod app_log;
 mod baidu_sync;
 mod bilibili;
 mod commands;
 mod config;
 mod db;
+mod desktop_notify;
 mod ffmpeg;
 mod live_recorder;
 mod login_refresh;
 mod login_store;
 mod processing;
+mod shutdown;
 mod utils;
 
+const HEARTBEAT_INTERVAL_SECS: u64 = 30;
+
 struct AppState {
+    app_handle: tauri::AppHandle,
     db: Arc<db::Db>,
     bilibili: Arc<bilibili::client::BilibiliClient>,
     login_store: Arc<login_store::LoginStore>,
@@ -35,6 +41,13 @@ struct AppState {
 struct DownloadRuntime {
     active_count: Mutex<i64>,
     progress_state: Mutex<HashMap<i64, HashMap<String, (u64, u64)>>>,
+    telemetry_state: Mutex<HashMap<i64, DownloadTelemetry>>,
+}
+
+#[derive(Default)]
+struct DownloadTelemetry {
+    speeds: HashMap<String, u64>,
+    last_emit: Option<std::time::Instant>,
 }
 
 impl DownloadRuntime {
@@ -42,6 +55,7 @@ impl DownloadRuntime {
         Self {
             active_count: Mutex::new(0),
             progress_state: Mutex::new(HashMap::new()),
+            telemetry_state: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -64,6 +78,7 @@ pub fn run() {
     let app = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .on_window_event(|window, event| {
             let state = window.app_handle().state::<AppState>();
             match event {
@@ -78,19 +93,67 @@ pub fn run() {
         })
         .setup(|app| {
             config::init_resource_bins(&app.handle());
+            app_events::init(app.handle().clone());
             let app_dir = app.path().app_data_dir()?;
             let db_path = app_dir.join("reaction-cut-rust.sqlite3");
             let db = Arc::new(db::Db::new(db_path)?);
             let login_path = app_dir.join("bilibili_login_info.json");
-            let download_dir = commands::settings::load_download_settings_from_db(&db)
+            let stored_download_settings = commands::settings::load_download_settings_from_db(&db).ok();
+            utils::set_structured_logs(
+                stored_download_settings
+                    .as_ref()
+                    .map(|settings| settings.structured_logs)
+                    .unwrap_or(false),
+            );
+            utils::set_max_log_size_mb(
+                stored_download_settings
+                    .as_ref()
+                    .map(|settings| settings.max_log_size_mb)
+                    .unwrap_or(utils::DEFAULT_MAX_LOG_SIZE_MB),
+            );
+            config::set_proxy_url(
+                stored_download_settings
+                    .as_ref()
+                    .map(|settings| settings.proxy_url.as_str()),
+            );
+            ffmpeg::set_hwaccel_enabled(
+                stored_download_settings
+                    .as_ref()
+                    .map(|settings| settings.hw_decode)
+                    .unwrap_or(false),
+            );
+            config::set_request_identity(
+                stored_download_settings
+                    .as_ref()
+                    .map(|settings| settings.custom_user_agent.as_str()),
+                stored_download_settings
+                    .as_ref()
+                    .map(|settings| settings.custom_live_referer.as_str()),
+                stored_download_settings
+                    .as_ref()
+                    .map(|settings| settings.custom_web_referer.as_str()),
+            );
+            let custom_user_agent = stored_download_settings
+                .as_ref()
+                .map(|settings| !settings.custom_user_agent.is_empty())
+                .unwrap_or(false);
+            let download_dir = stored_download_settings
                 .map(|settings| settings.download_path)
-                .unwrap_or_else(|_| config::default_download_dir().to_string_lossy().to_string());
+                .unwrap_or_else(|| config::default_download_dir().to_string_lossy().to_string());
             let log_dir = commands::settings::ensure_log_dir(&db, std::path::Path::new(&download_dir));
             let log_dir = std::path::PathBuf::from(log_dir);
             let log_path = log_dir.join("auth_debug.log");
             let app_log_path = log_dir.join("app_debug.log");
             let panic_log_path = log_dir.join("panic_debug.log");
             utils::append_log(&app_log_path, "app_start");
+            utils::append_log(
+                &app_log_path,
+                &format!(
+                    "request_user_agent custom={} ua={}",
+                    custom_user_agent,
+                    config::user_agent_or(config::DEFAULT_USER_AGENT)
+                ),
+            );
             if let Some(resource_dir) = config::resolve_resource_bin_dir(&app.handle()) {
                 utils::append_log(
                     &app_log_path,
@@ -113,15 +176,24 @@ pub fn run() {
                     baidu_pcs_candidates.join(",")
                 ),
             );
+            let bin_check_log_path = app_log_path.clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                commands::system::run_startup_binary_check(&bin_check_log_path);
+            });
             init_panic_log(Arc::new(panic_log_path));
             let heartbeat_path = app_log_path.clone();
             tauri::async_runtime::spawn(async move {
                 loop {
                     utils::append_log(&heartbeat_path, "heartbeat");
-                    sleep(Duration::from_secs(30)).await;
+                    commands::system::record_heartbeat();
+                    sleep(utils::jittered_duration(Duration::from_secs(
+                        HEARTBEAT_INTERVAL_SECS,
+                    )))
+                    .await;
                 }
             });
             let state = AppState {
+                app_handle: app.handle().clone(),
                 db,
                 bilibili: Arc::new(bilibili::client::BilibiliClient::new()),
                 login_store: Arc::new(login_store::LoginStore::new(login_path)),
@@ -175,6 +247,7 @@ pub fn run() {
             commands::file_scanner::scan_path,
             commands::file_scanner::validate_directory,
             commands::file_scanner::video_duration,
+            commands::file_scanner::media_probe,
             commands::auth::auth_qrcode_generate,
             commands::auth::auth_qrcode_poll,
             commands::auth::auth_sms_login,
@@ -186,32 +259,59 @@ pub fn run() {
             commands::auth::auth_perform_qrcode_login,
             commands::settings::get_download_settings,
             commands::settings::update_download_settings,
+            commands::settings::update_network_settings,
+            commands::settings::update_notification_settings,
+            commands::settings::update_logging_settings,
             commands::settings::get_live_settings,
             commands::settings::update_live_settings,
+            commands::settings::validate_settings,
+            commands::system::app_binary_versions,
+            commands::system::app_status,
+            commands::system::db_repair,
+            commands::storage::storage_scan_orphans,
+            commands::storage::storage_delete_orphans,
             commands::anchor::anchor_subscribe,
             commands::anchor::anchor_list,
             commands::anchor::anchor_unsubscribe,
             commands::anchor::anchor_check,
+            commands::anchor::anchor_export,
+            commands::anchor::anchor_import,
             commands::live::live_record_start,
             commands::live::live_record_stop,
+            commands::live::live_record_debug,
+            commands::live::live_record_status,
+            commands::live::live_record_merge,
+            commands::live::live_test_stream,
             commands::live::live_room_auto_record_update,
             commands::live::live_room_baidu_sync_update,
             commands::live::live_room_baidu_sync_toggle,
+            commands::live::live_room_daily_limit_update,
+            commands::live::live_room_settings_override_update,
             commands::video::video_detail,
             commands::video::video_playurl,
             commands::video::video_playurl_by_aid,
             commands::video::video_proxy_image,
             commands::video::bilibili_collections,
+            commands::video::bilibili_collection_sections,
             commands::video::bilibili_partitions,
+            commands::video::submission_create_options,
             commands::download::download_video,
+            commands::download::download_clip,
             commands::download::download_get,
             commands::download::download_list_by_status,
             commands::download::download_delete,
             commands::download::download_retry,
             commands::download::download_resume,
+            commands::download::download_pause,
+            commands::download::download_cancel_all,
+            commands::download::download_set_priority,
             commands::process::process_create,
             commands::process::process_status,
+            commands::process::process_extract_frame,
+            commands::toolbox::toolbox_check_binaries,
             commands::toolbox::toolbox_remux,
+            commands::toolbox::toolbox_extract_audio,
+            commands::toolbox::toolbox_trim,
             commands::baidu_sync::baidu_sync_settings,
             commands::baidu_sync::baidu_sync_status,
             commands::baidu_sync::baidu_sync_login,
@@ -226,16 +326,21 @@ pub fn run() {
             commands::baidu_sync::baidu_sync_create_dir,
             commands::baidu_sync::baidu_sync_rename_dir,
             commands::baidu_sync::baidu_sync_retry,
+            commands::baidu_sync::baidu_sync_verify,
+            commands::baidu_sync::baidu_sync_check,
             commands::baidu_sync::baidu_sync_cancel,
             commands::baidu_sync::baidu_sync_pause,
             commands::baidu_sync::baidu_sync_delete,
             commands::baidu_sync::baidu_sync_update_settings,
             commands::submission::submission_create,
+            commands::submission::submission_duplicate,
             commands::submission::submission_update,
             commands::submission::submission_repost,
             commands::submission::submission_resegment,
             commands::submission::submission_list,
             commands::submission::submission_list_by_status,
+            commands::submission::submission_export_csv,
+            commands::submission::submission_refresh_remote,
             commands::submission::submission_task_dir,
             commands::submission::submission_detail,
             commands::submission::submission_edit_prepare,
@@ -244,12 +349,34 @@ pub fn run() {
             commands::submission::submission_edit_upload_status,
             commands::submission::submission_edit_upload_clear,
             commands::submission::submission_edit_submit,
+            commands::submission::submission_resubmit_rejected,
             commands::submission::submission_delete,
+            commands::submission::submission_bulk_delete,
             commands::submission::submission_execute,
             commands::submission::submission_integrated_execute,
+            commands::submission::submission_retry_integrated_download,
             commands::submission::submission_upload_execute,
+            commands::submission::submission_bulk_retry,
+            commands::submission::submission_queue_pause,
+            commands::submission::submission_queue_resume,
+            commands::submission::submission_upload_precheck,
+            commands::submission::submission_preflight,
+            commands::submission::bilibili_submission_quota,
+            commands::submission::submission_normalize_sources,
+            commands::submission::submission_stats,
+            commands::submission::submission_cancel_upload,
             commands::submission::submission_retry_segment_upload,
+            commands::submission::submission_reset_retries,
+            commands::submission::submission_set_segment_result,
+            commands::submission::submission_merged_hash,
+            commands::submission::submission_clean_temp,
+            commands::submission::submission_disk_usage,
+            commands::submission::submission_reorder_sources,
+            commands::submission::submission_video_types,
+            commands::submission::submission_partition_rules,
+            commands::submission::submission_partition_rules_update,
             commands::submission::workflow_status,
+            commands::submission::workflow_logs,
             commands::submission::workflow_pause,
             commands::submission::workflow_resume,
             commands::submission::workflow_cancel,
@@ -263,11 +390,22 @@ pub fn run() {
                 tauri::RunEvent::Ready => {
                     utils::append_log(&state.app_log_path, "run_ready");
                 }
-                tauri::RunEvent::ExitRequested { code, .. } => {
+                tauri::RunEvent::ExitRequested { code, api, .. } => {
                     utils::append_log(
                         &state.app_log_path,
                         &format!("run_exit_requested code={:?}", code),
                     );
+                    // 第一次退出请求先完成收尾，再由收尾线程重新发起退出
+                    if shutdown::begin() {
+                        api.prevent_exit();
+                        let app_handle = app_handle.clone();
+                        std::thread::spawn(move || {
+                            if let Some(state) = app_handle.try_state::<AppState>() {
+                                shutdown::run(&state);
+                            }
+                            app_handle.exit(code.unwrap_or(0));
+                        });
+                    }
                 }
                 tauri::RunEvent::Exit => {
                     utils::append_log(&state.app_log_path, "run_exit");
@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{File, OpenOptions};
-use std::io::{Read, Write};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::{
@@ -9,12 +9,13 @@ use std::sync::{
 };
 use std::time::{Duration, Instant, SystemTime};
 
-use chrono::Utc;
+use chrono::{DateTime, Local, Utc};
 use reqwest::blocking::Client;
 use reqwest::header::{
   HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, REFERER, USER_AGENT,
 };
 use rusqlite::OptionalExtension;
+use serde::Serialize;
 use serde_json::Value;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio_tungstenite::tungstenite::Message;
@@ -23,17 +24,24 @@ use url::Url;
 
 use crate::bilibili::client::BilibiliClient;
 use crate::commands::settings::{
-  load_download_settings_from_db, load_live_settings_from_db, LiveSettings,
+  load_download_settings_from_db, load_live_settings_from_db, normalize_danmaku_format,
+  LiveSettings, LiveSettingsOverride,
 };
 use crate::config::{default_download_dir, resolve_ffmpeg_path};
 use crate::db::Db;
+use crate::desktop_notify::{self, NotifyCategory};
 use crate::ffmpeg::run_ffmpeg;
+use crate::processing::merge_files;
 use crate::login_store::{AuthInfo, LoginStore};
 use crate::baidu_sync;
-use crate::utils::{append_log, now_rfc3339, sanitize_filename};
+use crate::utils::{
+  append_log, available_disk_space, jittered_duration, now_rfc3339, sanitize_filename,
+};
 
 pub struct LiveRuntime {
   records: Mutex<HashMap<String, LiveRecordHandle>>,
+  /// 因磁盘空间不足停止的房间及其录制目录，空间恢复到阈值加余量后才自动重新录制。
+  low_disk_rooms: Mutex<HashMap<String, PathBuf>>,
 }
 
 pub struct LiveRecordHandle {
@@ -42,15 +50,115 @@ pub struct LiveRecordHandle {
   pub title_split_flag: Arc<AtomicBool>,
   pub last_title: Arc<Mutex<String>>,
   pub current_file: Arc<Mutex<String>>,
+  pub debug_state: Arc<Mutex<LiveRecordDebugState>>,
+  pub status_state: Arc<Mutex<LiveRecordStatusState>>,
   pub start_time: String,
   pub start_date: String,
 }
 
+#[derive(Default)]
+pub struct LiveRecordDebugState {
+  connections: u64,
+  header_parsed: bool,
+  tags_parsed: u64,
+  bytes_received: u64,
+  bytes_since_last_tag: u64,
+  buffered_bytes: usize,
+  last_tag_timestamp: Option<u32>,
+  stagnant_count: usize,
+  last_data_at: Option<Instant>,
+  last_progress_at: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct LiveRecordStatusState {
+  stream_type: String,
+  segment_index: i64,
+  title: String,
+  bytes_written: u64,
+  segment_started_at: Option<Instant>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveRecordStatus {
+  pub room_id: String,
+  pub file_path: String,
+  pub start_time: String,
+  pub stream_type: String,
+  pub segment_index: i64,
+  pub current_title: String,
+  pub elapsed_seconds: i64,
+  pub segment_elapsed_seconds: u64,
+  pub bytes_written: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveRecordDebugSnapshot {
+  pub room_id: String,
+  pub file_path: String,
+  pub start_time: String,
+  pub connections: u64,
+  pub header_parsed: bool,
+  pub tags_parsed: u64,
+  pub bytes_received: u64,
+  pub bytes_since_last_tag: u64,
+  pub buffered_bytes: usize,
+  pub last_tag_timestamp: Option<u32>,
+  pub stagnant_count: usize,
+  pub secs_since_last_data: Option<u64>,
+  pub secs_since_last_progress: Option<u64>,
+}
+
 pub struct LiveRecordInfo {
   pub file_path: String,
   pub start_time: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveRecordMergeResult {
+  pub record_id: i64,
+  pub room_id: String,
+  pub file_path: String,
+  pub segment_count: usize,
+  pub file_size: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveStreamTestUrl {
+  pub url: String,
+  pub host: Option<String>,
+  pub format: String,
+  pub expire_at: Option<u64>,
+  pub expires_in_secs: Option<i64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveStreamTestResult {
+  pub room_id: String,
+  pub live_status: i64,
+  pub logged_in: bool,
+  pub recording_quality: String,
+  pub requested_qn: i64,
+  pub quality_fallback: bool,
+  pub quality_error: Option<String>,
+  pub urls: Vec<LiveStreamTestUrl>,
+}
+
+struct RecordSegmentRow {
+  id: i64,
+  room_id: String,
+  status: String,
+  file_path: String,
+  start_time: String,
+  end_time: Option<String>,
+  title: Option<String>,
+}
+
 #[derive(Clone)]
 pub struct LiveContext {
   pub db: Arc<Db>,
@@ -75,10 +183,25 @@ const INVALID_STREAM_TAG_LIMIT: usize = 300;
 const INVALID_STREAM_STALL_SECS: u64 = 10;
 const STREAM_URL_REFRESH_LEAD_SECS: u64 = 30;
 const MISSING_SEGMENT_WINDOW_SECS: u64 = 60;
+const RECORD_MERGE_OVERLAP_TOLERANCE_SECS: i64 = 5;
+const RECORD_MERGE_GAP_TOLERANCE_SECS: i64 = 30;
+const ROOM_INFO_FETCH_ATTEMPTS: u32 = 3;
+const ROOM_INFO_RETRY_BASE_DELAY_MS: u64 = 500;
+const ROOM_INFO_RETRY_MAX_DELAY_MS: u64 = 4000;
+const ROOM_INFO_UNREACHABLE_THRESHOLD: u32 = 5;
+const BACKUP_STREAM_NOTE: &str = "主流为空，使用备用流";
+const DISK_SPACE_STOP_REASON: &str = "磁盘空间不足";
+const DISK_SPACE_CHECK_INTERVAL_SECS: u64 = 30;
+const DISK_SPACE_RESUME_MARGIN_MB: u64 = 512;
+const FLV_TIMESTAMP_BACKWARD_TOLERANCE_MS: i64 = 1000;
+const FLV_TIMESTAMP_FORWARD_JUMP_MS: i64 = 10_000;
+const FLV_TIMESTAMP_REBASE_GAP_MS: i64 = 40;
+pub const RECORDING_LIMIT_ERROR: &str = "已达到最大同时录制数";
 
 pub fn new_live_runtime() -> LiveRuntime {
   LiveRuntime {
     records: Mutex::new(HashMap::new()),
+    low_disk_rooms: Mutex::new(HashMap::new()),
   }
 }
 
@@ -87,6 +210,39 @@ impl LiveRuntime {
     self.records.lock().map(|map| map.contains_key(room_id)).unwrap_or(false)
   }
 
+  pub fn active_room_ids(&self) -> Vec<String> {
+    self
+      .records
+      .lock()
+      .map(|map| map.keys().cloned().collect())
+      .unwrap_or_default()
+  }
+
+  pub fn active_recording_count(&self) -> usize {
+    self.records.lock().map(|map| map.len()).unwrap_or(0)
+  }
+
+  fn mark_low_disk(&self, room_id: &str, base_dir: &Path) {
+    if let Ok(mut map) = self.low_disk_rooms.lock() {
+      map.insert(room_id.to_string(), base_dir.to_path_buf());
+    }
+  }
+
+  fn low_disk_dir(&self, room_id: &str) -> Option<PathBuf> {
+    self.low_disk_rooms.lock().ok()?.get(room_id).cloned()
+  }
+
+  fn clear_low_disk(&self, room_id: &str) {
+    if let Ok(mut map) = self.low_disk_rooms.lock() {
+      map.remove(room_id);
+    }
+  }
+
+  /// `limit` 为 0 表示不限制。
+  pub fn recording_limit_reached(&self, limit: i64) -> bool {
+    limit > 0 && self.active_recording_count() as i64 >= limit
+  }
+
   pub fn get_record_info(&self, room_id: &str) -> Option<LiveRecordInfo> {
     let map = self.records.lock().ok()?;
     let handle = map.get(room_id)?;
@@ -97,6 +253,58 @@ impl LiveRuntime {
     })
   }
 
+  pub fn get_record_debug(&self, room_id: &str) -> Option<LiveRecordDebugSnapshot> {
+    let map = self.records.lock().ok()?;
+    let handle = map.get(room_id)?;
+    let file_path = handle.current_file.lock().ok()?.clone();
+    let state = handle.debug_state.lock().ok()?;
+    Some(LiveRecordDebugSnapshot {
+      room_id: room_id.to_string(),
+      file_path,
+      start_time: handle.start_time.clone(),
+      connections: state.connections,
+      header_parsed: state.header_parsed,
+      tags_parsed: state.tags_parsed,
+      bytes_received: state.bytes_received,
+      bytes_since_last_tag: state.bytes_since_last_tag,
+      buffered_bytes: state.buffered_bytes,
+      last_tag_timestamp: state.last_tag_timestamp,
+      stagnant_count: state.stagnant_count,
+      secs_since_last_data: state.last_data_at.map(|at| at.elapsed().as_secs()),
+      secs_since_last_progress: state.last_progress_at.map(|at| at.elapsed().as_secs()),
+    })
+  }
+
+  pub fn get_record_status(&self, room_id: &str) -> Option<LiveRecordStatus> {
+    let map = self.records.lock().ok()?;
+    let handle = map.get(room_id)?;
+    let file_path = handle.current_file.lock().ok()?.clone();
+    let state = handle.status_state.lock().ok()?;
+    let elapsed_seconds = DateTime::parse_from_rfc3339(&handle.start_time)
+      .map(|start| (Utc::now() - start.with_timezone(&Utc)).num_seconds().max(0))
+      .unwrap_or(0);
+    // HLS 输出文件由 ffmpeg 写入，大小直接从磁盘读取
+    let bytes_written = if state.stream_type == "HLS" {
+      std::fs::metadata(&file_path).map(|meta| meta.len()).unwrap_or(0)
+    } else {
+      state.bytes_written
+    };
+    Some(LiveRecordStatus {
+      room_id: room_id.to_string(),
+      file_path,
+      start_time: handle.start_time.clone(),
+      stream_type: state.stream_type.clone(),
+      segment_index: state.segment_index,
+      current_title: state.title.clone(),
+      elapsed_seconds,
+      segment_elapsed_seconds: state
+        .segment_started_at
+        .map(|at| at.elapsed().as_secs())
+        .unwrap_or(0),
+      bytes_written,
+    })
+  }
+
   pub fn mark_split(&self, room_id: &str) {
     if let Ok(map) = self.records.lock() {
       if let Some(handle) = map.get(room_id) {
@@ -117,8 +325,11 @@ impl LiveRuntime {
 const STALE_RECORD_REMUX_MAX_AGE_SECS: u64 = 36 * 60 * 60;
 const STALE_RECORD_IDLE_SECS: u64 = 30 * 60;
 const STALE_RECORD_RECOVERY_INTERVAL_SECS: u64 = 10 * 60;
+const STALE_RECORD_REMUX_RETRY_LIMIT: i64 = 3;
 
 pub fn recover_stale_recordings(context: LiveContext) {
+  retry_failed_remuxes(&context);
+
   let records = context
     .db
     .with_conn(|conn| {
@@ -251,6 +462,116 @@ pub fn recover_stale_recordings(context: LiveContext) {
   }
 }
 
+/// 退出时仍为 RECORDING 的记录改为 STOPPED，下次启动由 `retry_failed_remuxes` 转封装。
+pub fn stop_unfinished_recordings(db: &Db, app_log_path: &Path) -> usize {
+  let records = db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT id, file_path FROM live_record_task WHERE status = 'RECORDING'",
+      )?;
+      let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+      })?;
+      rows.collect::<Result<Vec<(i64, String)>, _>>()
+    })
+    .unwrap_or_default();
+  let end_time = now_rfc3339();
+  let mut stopped = 0;
+  for (record_id, file_path) in records {
+    let file_size = std::fs::metadata(&file_path).map(|meta| meta.len()).unwrap_or(0);
+    let (status, error_message) = if file_size == 0 {
+      ("FAILED", Some("应用退出时录制文件为空"))
+    } else {
+      ("STOPPED", None)
+    };
+    if let Err(err) = update_record_task(
+      db,
+      record_id,
+      status,
+      Some(end_time.clone()),
+      file_size,
+      error_message,
+    ) {
+      append_log(
+        app_log_path,
+        &format!("record_shutdown_update_fail record_id={} err={}", record_id, err),
+      );
+      continue;
+    }
+    let metadata_path = PathBuf::from(&file_path).with_extension("metadata.json");
+    if metadata_path.exists() {
+      let _ = update_metadata_file(metadata_path.to_string_lossy().as_ref(), &end_time, file_size);
+    }
+    append_log(
+      app_log_path,
+      &format!(
+        "record_shutdown_stopped record_id={} status={} size={}",
+        record_id, status, file_size
+      ),
+    );
+    stopped += 1;
+  }
+  stopped
+}
+
+fn retry_failed_remuxes(context: &LiveContext) {
+  let records = context
+    .db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT id, file_path, COALESCE(remux_retry_count, 0) FROM live_record_task \
+         WHERE status = 'STOPPED' AND COALESCE(remux_retry_count, 0) < ?1",
+      )?;
+      let rows = stmt.query_map([STALE_RECORD_REMUX_RETRY_LIMIT], |row| {
+        Ok((
+          row.get::<_, i64>(0)?,
+          row.get::<_, String>(1)?,
+          row.get::<_, i64>(2)?,
+        ))
+      })?;
+      rows.collect::<Result<Vec<(i64, String, i64)>, _>>()
+    })
+    .unwrap_or_default();
+
+  for (record_id, file_path, retry_count) in records {
+    let path = PathBuf::from(&file_path);
+    let is_flv = path
+      .extension()
+      .and_then(|value| value.to_str())
+      .map(|ext| ext.eq_ignore_ascii_case("flv"))
+      .unwrap_or(false);
+    if !is_flv || !path.exists() || path.with_extension("mp4").exists() {
+      continue;
+    }
+    // 与启动恢复一致，超过时限的旧录像不再自动重试，避免每次启动都处理历史文件
+    let recent = std::fs::metadata(&path)
+      .and_then(|meta| meta.modified())
+      .ok()
+      .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+      .map(|age| age <= Duration::from_secs(STALE_RECORD_REMUX_MAX_AGE_SECS))
+      .unwrap_or(false);
+    if !recent {
+      continue;
+    }
+    let attempt = retry_count + 1;
+    if let Err(err) = update_record_remux_retry_count(&context.db, record_id, attempt) {
+      append_log(
+        &context.app_log_path,
+        &format!("record_remux_retry_update_fail record_id={} err={}", record_id, err),
+      );
+      continue;
+    }
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "record_remux_retry record_id={} attempt={} limit={}",
+        record_id, attempt, STALE_RECORD_REMUX_RETRY_LIMIT
+      ),
+    );
+    spawn_segment_remux(context.clone(), record_id, file_path);
+  }
+}
+
 async fn recover_idle_recordings(context: LiveContext) {
   let records = context
     .db
@@ -430,25 +751,71 @@ pub fn start_record_recovery_loop(context: LiveContext) {
   tauri::async_runtime::spawn(async move {
     loop {
       recover_idle_recordings(context.clone()).await;
-      tokio::time::sleep(Duration::from_secs(STALE_RECORD_RECOVERY_INTERVAL_SECS)).await;
+      tokio::time::sleep(jittered_duration(Duration::from_secs(
+        STALE_RECORD_RECOVERY_INTERVAL_SECS,
+      )))
+      .await;
     }
   });
 }
 
 pub fn start_auto_record_loop(context: LiveContext) {
   tauri::async_runtime::spawn(async move {
+    let mut consecutive_failures: HashMap<String, u32> = HashMap::new();
     loop {
       let settings = load_live_settings_from_db(&context.db)
         .unwrap_or_else(|_| crate::commands::settings::default_live_settings());
       let interval_sec = settings.check_interval_sec.max(10);
+      let mut deferred_rooms: Vec<String> = Vec::new();
       if let Ok(rooms) = load_anchor_room_ids(&context.db) {
         for room_id in rooms {
-          match fetch_room_info(&context.bilibili, &room_id).await {
+          match fetch_room_info_with_retry(&context.bilibili, &context.app_log_path, &room_id).await {
             Ok(info) => {
+              if let Some(failures) = consecutive_failures.remove(&room_id) {
+                if failures >= ROOM_INFO_UNREACHABLE_THRESHOLD {
+                  append_log(
+                    &context.app_log_path,
+                    &format!("live_check_recovered room={} failures={}", room_id, failures),
+                  );
+                }
+              }
               let _ = update_anchor_status(&context.db, &room_id, info.live_status);
               let auto_record = load_room_auto_record(&context.db, &room_id).unwrap_or(true);
-              let recording = context.live_runtime.is_recording(&room_id);
-              if info.live_status == 1 && auto_record && !recording {
+              let mut recording = context.live_runtime.is_recording(&room_id);
+              let quota = load_daily_recording_quota(&context.db, &room_id).ok().flatten();
+              let quota_exceeded = quota.as_ref().map(|value| value.exceeded()).unwrap_or(false);
+              if recording && quota_exceeded {
+                if let Some(quota) = quota.as_ref() {
+                  append_log(
+                    &context.app_log_path,
+                    &format!(
+                      "auto_record_stop reason=daily_quota room={} recorded_seconds={} limit_seconds={}",
+                      room_id, quota.recorded_seconds, quota.limit_seconds
+                    ),
+                  );
+                }
+                stop_recording(context.clone(), &room_id, "超出每日录制时长上限");
+                recording = false;
+              }
+              if info.live_status == 1 && auto_record && !recording && quota_exceeded {
+                if let Some(quota) = quota.as_ref() {
+                  log_daily_quota_skip(&context.app_log_path, &room_id, quota);
+                }
+              } else if info.live_status == 1
+                && auto_record
+                && !recording
+                && context
+                  .live_runtime
+                  .recording_limit_reached(settings.max_concurrent_recordings)
+              {
+                deferred_rooms.push(room_id.clone());
+              } else if info.live_status == 1
+                && auto_record
+                && !recording
+                && !low_disk_space_recovered(&context, &room_id, &settings)
+              {
+                // 磁盘空间尚未恢复到阈值加余量，本轮不重新录制。
+              } else if info.live_status == 1 && auto_record && !recording {
                 match start_recording(context.clone(), &room_id, info.clone(), settings.clone()) {
                   Ok(()) => {
                     append_log(&context.app_log_path, &format!("auto_record_start room={}", room_id));
@@ -477,11 +844,34 @@ pub fn start_auto_record_loop(context: LiveContext) {
             }
             Err(err) => {
               append_log(&context.app_log_path, &format!("live_check_error room={} err={}", room_id, err));
+              let failures = consecutive_failures.entry(room_id.clone()).or_insert(0);
+              *failures += 1;
+              if *failures == ROOM_INFO_UNREACHABLE_THRESHOLD {
+                append_log(
+                  &context.app_log_path,
+                  &format!(
+                    "live_check_unreachable room={} consecutive_failures={}",
+                    room_id, failures
+                  ),
+                );
+              }
             }
           }
         }
       }
-      tokio::time::sleep(Duration::from_secs(interval_sec as u64)).await;
+      // 推迟的房间在下一轮检查时重新判断，有录制结束即可启动。
+      if !deferred_rooms.is_empty() {
+        append_log(
+          &context.app_log_path,
+          &format!(
+            "auto_record_deferred rooms={} active={} limit={}",
+            deferred_rooms.join(","),
+            context.live_runtime.active_recording_count(),
+            settings.max_concurrent_recordings
+          ),
+        );
+      }
+      tokio::time::sleep(jittered_duration(Duration::from_secs(interval_sec as u64))).await;
     }
   });
 }
@@ -495,6 +885,9 @@ pub fn start_recording(
   if context.live_runtime.is_recording(room_id) {
     return Ok(());
   }
+  if crate::shutdown::is_requested() {
+    return Err("应用正在退出".to_string());
+  }
 
   if room_info.live_status != 1 {
     return Err("当前未开播".to_string());
@@ -512,6 +905,8 @@ pub fn start_recording(
     title_split_flag: Arc::clone(&title_split_flag),
     last_title: Arc::new(Mutex::new(current_title)),
     current_file: Arc::new(Mutex::new(String::new())),
+    debug_state: Arc::new(Mutex::new(LiveRecordDebugState::default())),
+    status_state: Arc::new(Mutex::new(LiveRecordStatusState::default())),
     start_time: start_time.to_rfc3339(),
     start_date: start_time.format("%Y%m%d").to_string(),
   };
@@ -519,6 +914,18 @@ pub fn start_recording(
   if let Ok(mut map) = context.live_runtime.records.lock() {
     map.insert(room_id.to_string(), handle);
   }
+  let display_name = nickname.clone().unwrap_or_else(|| room_id.to_string());
+  append_log(
+    &context.app_log_path,
+    &format!("record_session_start room={}", room_id),
+  );
+  desktop_notify::notify(
+    &context.db,
+    &context.app_log_path,
+    NotifyCategory::Recording,
+    "开始录制",
+    &format!("{} - {}", display_name, room_info.title),
+  );
 
   let runtime = Arc::clone(&context.live_runtime);
   let room_id_owned = room_id.to_string();
@@ -601,6 +1008,17 @@ pub fn start_recording(
     if let Ok(mut map) = runtime.records.lock() {
       map.remove(&room_id_owned);
     }
+    append_log(
+      &context.app_log_path,
+      &format!("record_session_end room={} retries={}", room_id_owned, retry_count),
+    );
+    desktop_notify::notify(
+      &context.db,
+      &context.app_log_path,
+      NotifyCategory::Recording,
+      "录制结束",
+      &display_name,
+    );
   });
 
   Ok(())
@@ -622,6 +1040,24 @@ fn run_record_loop(
   settings: LiveSettings,
 ) -> Result<(), String> {
   let mut settings = settings;
+  match load_room_settings_override(&context.db, &room_id) {
+    Ok(Some(overrides)) => {
+      overrides.apply(&mut settings);
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "record_settings_override room={} override={}",
+          room_id,
+          serde_json::to_string(&overrides).unwrap_or_default()
+        ),
+      );
+    }
+    Ok(None) => {}
+    Err(err) => append_log(
+      &context.app_log_path,
+      &format!("record_settings_override_load_fail room={} err={}", room_id, err),
+    ),
+  }
   if settings.record_mode == 1 {
     settings.write_metadata = false;
     settings.flv_fix_split_on_missing = false;
@@ -654,6 +1090,18 @@ fn run_record_loop(
       .map(|handle| Arc::clone(&handle.title_split_flag))
       .ok_or_else(|| "Record handle missing".to_string())?
   };
+  let debug_state = {
+    let map = context.live_runtime.records.lock().map_err(|_| "Lock error")?;
+    map.get(&room_id)
+      .map(|handle| Arc::clone(&handle.debug_state))
+      .ok_or_else(|| "Record handle missing".to_string())?
+  };
+  let status_state = {
+    let map = context.live_runtime.records.lock().map_err(|_| "Lock error")?;
+    map.get(&room_id)
+      .map(|handle| Arc::clone(&handle.status_state))
+      .ok_or_else(|| "Record handle missing".to_string())?
+  };
 
   let mut segment_index = 1;
   let mut current_title = room_info.title.clone();
@@ -704,13 +1152,40 @@ fn run_record_loop(
     .connect_timeout(Duration::from_millis(
       settings.stream_connect_timeout_ms.max(1000) as u64,
     ))
+    .proxy(crate::config::http_proxy())
     .build()
     .map_err(|err| format!("Failed to build client: {}", err))?;
   let auth = context.login_store.load_auth_info(&context.db).ok().flatten();
   let mut stream_urls: Vec<String> = Vec::new();
   let mut stream_url_index: usize = 0;
+  // 本次录制中出现过卡流的 CDN 节点，重新获取地址后排在最后
+  let mut failed_stream_hosts: HashSet<String> = HashSet::new();
   let mut force_no_qn_until: Option<i64> = None;
 
+  // 备用流随本次主循环退出而停止，避免主循环重试时重复启动
+  let backup_stop = Arc::new(AtomicBool::new(false));
+  let _backup_guard = BackupStopGuard(Arc::clone(&backup_stop));
+  if settings.record_backup_quality > 0 {
+    let backup_context = context.clone();
+    let backup_room = room_id.clone();
+    let backup_info = room_info.clone();
+    let backup_settings = settings.clone();
+    let backup_auth = auth.clone();
+    let backup_stop_flag = Arc::clone(&stop_flag);
+    let backup_stop_local = Arc::clone(&backup_stop);
+    std::thread::spawn(move || {
+      run_backup_record_loop(
+        backup_context,
+        backup_room,
+        backup_info,
+        backup_settings,
+        backup_auth,
+        backup_stop_flag,
+        backup_stop_local,
+      );
+    });
+  }
+
   loop {
     if stop_flag.load(Ordering::SeqCst) {
       if let Some(mut seg) = segment.take() {
@@ -723,6 +1198,11 @@ fn run_record_loop(
       break;
     }
 
+    if !has_enough_disk_space(&context, &room_id, &base_dir, &settings) {
+      stop_for_low_disk_space(&context, &room_id, &base_dir, &mut segment)?;
+      return Ok(());
+    }
+
     if stream_urls.is_empty() {
       let now = Utc::now().timestamp();
       let use_quality = match force_no_qn_until {
@@ -770,6 +1250,12 @@ fn run_record_loop(
           }
         }
       };
+      prioritize_healthy_stream_urls(
+        &mut stream_urls,
+        &mut failed_stream_hosts,
+        context.app_log_path.as_ref(),
+        room_id.as_str(),
+      );
       stream_url_index = 0;
     }
 
@@ -800,6 +1286,13 @@ fn run_record_loop(
     if is_hls_url(&stream_url) {
       let hls_file_path = normalize_hls_path(&current_file_path);
       update_current_file(&context, &room_id, &hls_file_path);
+      if let Ok(mut state) = status_state.lock() {
+        state.stream_type = "HLS".to_string();
+        state.segment_index = segment_index;
+        state.title = current_title.clone();
+        state.bytes_written = 0;
+        state.segment_started_at = Some(Instant::now());
+      }
       append_log(
         &context.app_log_path,
         &format!("stream_hls_detected room={} path={}", room_id, hls_file_path),
@@ -844,22 +1337,7 @@ fn run_record_loop(
       &context.app_log_path,
       &format!("stream_url_info room={} {}", room_id, summarize_stream_url(&stream_url)),
     );
-    let referer_value = format!("https://live.bilibili.com/{}", room_info.room_id);
-    let mut request = client.get(&stream_url);
-    request = request.header(
-      USER_AGENT,
-      HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36"),
-    );
-    request = request.header(ACCEPT_ENCODING, HeaderValue::from_static("identity"));
-    if let Ok(value) = HeaderValue::from_str(&referer_value) {
-      request = request.header(REFERER, value);
-    }
-    if let Some(auth) = auth.as_ref() {
-      if let Ok(value) = HeaderValue::from_str(&auth.cookie) {
-        request = request.header("Cookie", value);
-      }
-    }
-    let response = request.send();
+    let response = build_stream_request(&client, &stream_url, &room_info.room_id, auth.as_ref()).send();
     let mut response = match response {
       Ok(resp) => resp,
       Err(err) => {
@@ -953,6 +1431,14 @@ fn run_record_loop(
     let mut last_tag_timestamp: Option<u32> = None;
     let mut stagnant_count: usize = 0;
     let mut last_progress_at = Instant::now();
+    let mut last_disk_check_at = Instant::now();
+    if let Ok(mut state) = debug_state.lock() {
+      state.connections += 1;
+      state.header_parsed = false;
+      state.buffered_bytes = 0;
+      state.bytes_since_last_tag = 0;
+      state.stagnant_count = 0;
+    }
 
     loop {
       if stop_flag.load(Ordering::SeqCst) {
@@ -1035,6 +1521,10 @@ fn run_record_loop(
               FlvParsedItem::Header(header) => {
                 cache.set_header(header.clone());
                 if segment.is_none() {
+                  if !has_enough_disk_space(&context, &room_id, &base_dir, &settings) {
+                    stop_for_low_disk_space(&context, &room_id, &base_dir, &mut segment)?;
+                    return Ok(());
+                  }
                   let mut new_segment = open_segment(
                     &context,
                     &room_id,
@@ -1091,6 +1581,10 @@ fn run_record_loop(
 
                 if pending_split && is_video_keyframe(&tag) {
                   if cache.has_header() {
+                    if !has_enough_disk_space(&context, &room_id, &base_dir, &settings) {
+                      stop_for_low_disk_space(&context, &room_id, &base_dir, &mut segment)?;
+                      return Ok(());
+                    }
                     if let Some(mut seg) = segment.take() {
                       let record_id = seg.record_id;
                       let file_path = seg.file_path.clone();
@@ -1133,8 +1627,26 @@ fn run_record_loop(
                   }
                 }
 
+                if last_disk_check_at.elapsed().as_secs() >= DISK_SPACE_CHECK_INTERVAL_SECS {
+                  last_disk_check_at = Instant::now();
+                  if !has_enough_disk_space(&context, &room_id, &base_dir, &settings) {
+                    stop_for_low_disk_space(&context, &room_id, &base_dir, &mut segment)?;
+                    return Ok(());
+                  }
+                }
+
                 if let Some(seg) = segment.as_mut() {
-                  seg.write(&tag.bytes)?;
+                  seg.write_tag(&tag)?;
+                  if seg.take_size_split() {
+                    append_log(
+                      &context.app_log_path,
+                      &format!(
+                        "stream_split_request room={} reason=size bytes={} limit_mb={}",
+                        room_id, seg.bytes_written, settings.split_by_size_mb
+                      ),
+                    );
+                    split_flag.store(true, Ordering::SeqCst);
+                  }
                   if settings.cutting_mode == 1 {
                     let limit = settings.cutting_number.max(1) as u64;
                     if segment_start.elapsed().as_secs() >= limit {
@@ -1184,8 +1696,44 @@ fn run_record_loop(
               }
             }
           }
+          if let Ok(mut state) = debug_state.lock() {
+            state.header_parsed = parser.header_parsed;
+            state.tags_parsed += parser.take_tags_parsed();
+            state.bytes_received += n as u64;
+            state.bytes_since_last_tag = parser.bytes_since_last_tag;
+            state.buffered_bytes = parser.buffer.len();
+            state.last_tag_timestamp = last_tag_timestamp;
+            state.stagnant_count = stagnant_count;
+            state.last_data_at = Some(Instant::now());
+            state.last_progress_at = Some(last_progress_at);
+          }
+          if let Ok(mut state) = status_state.lock() {
+            state.stream_type = "FLV".to_string();
+            state.segment_index = segment_index;
+            state.title = current_title.clone();
+            state.bytes_written = segment.as_ref().map(|seg| seg.bytes_written).unwrap_or(0);
+            state.segment_started_at = Some(segment_start);
+          }
           if invalid_stream {
-            stream_urls.clear();
+            if let Some(host) = stream_url_host(&stream_url) {
+              failed_stream_hosts.insert(host);
+            }
+            // 优先切换到缓存中的其他线路，全部失败后再重新获取
+            match next_healthy_stream_url(&stream_urls, stream_url_index, &failed_stream_hosts) {
+              Some(index) => {
+                append_log(
+                  &context.app_log_path,
+                  &format!(
+                    "stream_switch_alternate room={} from={} to={}",
+                    room_id,
+                    stream_url_host(&stream_url).unwrap_or_default(),
+                    stream_url_host(&stream_urls[index]).unwrap_or_default()
+                  ),
+                );
+                stream_url_index = index;
+              }
+              None => stream_urls.clear(),
+            }
             std::thread::sleep(Duration::from_millis(settings.stream_retry_ms.max(1000) as u64));
             break;
           }
@@ -1245,29 +1793,454 @@ fn run_record_loop(
   Ok(())
 }
 
-struct FlvTag {
-  tag_type: u8,
-  bytes: Vec<u8>,
-  data_offset: usize,
-  data_len: usize,
+/// 可用空间低于 `min_free_space_mb` 时返回 false；设置为 0 或无法查询时视为充足。
+fn has_enough_disk_space(
+  context: &LiveContext,
+  room_id: &str,
+  base_dir: &Path,
+  settings: &LiveSettings,
+) -> bool {
+  if settings.min_free_space_mb <= 0 {
+    return true;
+  }
+  let Some(available) = available_disk_space(base_dir) else {
+    return true;
+  };
+  let free_mb = available / 1024 / 1024;
+  if free_mb >= settings.min_free_space_mb as u64 {
+    return true;
+  }
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "record_disk_space_low room={} free_mb={} min_mb={} path={}",
+      room_id,
+      free_mb,
+      settings.min_free_space_mb,
+      base_dir.to_string_lossy()
+    ),
+  );
+  false
 }
 
-impl FlvTag {
-  fn data(&self) -> &[u8] {
-    &self.bytes[self.data_offset..self.data_offset + self.data_len]
+/// 因磁盘空间不足停止的房间，需可用空间回到 `min_free_space_mb` 加余量后才允许自动重新录制，
+/// 避免在阈值附近反复启停。
+fn low_disk_space_recovered(context: &LiveContext, room_id: &str, settings: &LiveSettings) -> bool {
+  let Some(base_dir) = context.live_runtime.low_disk_dir(room_id) else {
+    return true;
+  };
+  let resume_mb = (settings.min_free_space_mb.max(0) as u64).saturating_add(DISK_SPACE_RESUME_MARGIN_MB);
+  let free_mb = available_disk_space(&base_dir).map(|available| available / 1024 / 1024);
+  if settings.min_free_space_mb <= 0 || free_mb.map(|value| value >= resume_mb).unwrap_or(true) {
+    context.live_runtime.clear_low_disk(room_id);
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "record_disk_space_recovered room={} free_mb={} resume_mb={}",
+        room_id,
+        free_mb.map(|value| value.to_string()).unwrap_or_else(|| "unknown".to_string()),
+        resume_mb
+      ),
+    );
+    return true;
   }
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "auto_record_skip reason=low_disk room={} free_mb={} resume_mb={} path={}",
+      room_id,
+      free_mb.unwrap_or(0),
+      resume_mb,
+      base_dir.to_string_lossy()
+    ),
+  );
+  false
 }
 
-fn is_video_keyframe(tag: &FlvTag) -> bool {
-  if tag.tag_type != 9 {
-    return false;
-  }
-  let data = tag.data();
-  if data.is_empty() {
-    return false;
+fn stop_for_low_disk_space(
+  context: &LiveContext,
+  room_id: &str,
+  base_dir: &Path,
+  segment: &mut Option<SegmentWriter>,
+) -> Result<(), String> {
+  context.live_runtime.mark_low_disk(room_id, base_dir);
+  if let Some(mut seg) = segment.take() {
+    let record_id = seg.record_id;
+    let file_path = seg.file_path.clone();
+    seg.finish("STOPPED", Some(DISK_SPACE_STOP_REASON))?;
+    drop(seg);
+    spawn_segment_remux(context.clone(), record_id, file_path);
   }
-  let frame_type = data[0] >> 4;
-  frame_type == 1
+  stop_recording(context.clone(), room_id, DISK_SPACE_STOP_REASON);
+  Ok(())
+}
+
+struct BackupStopGuard(Arc<AtomicBool>);
+
+impl Drop for BackupStopGuard {
+  fn drop(&mut self) {
+    self.0.store(true, Ordering::SeqCst);
+  }
+}
+
+fn build_stream_request(
+  client: &Client,
+  stream_url: &str,
+  room_id: &str,
+  auth: Option<&AuthInfo>,
+) -> reqwest::blocking::RequestBuilder {
+  let referer_value =
+    crate::config::live_referer_or(&format!("https://live.bilibili.com/{}", room_id));
+  let user_agent =
+    crate::config::user_agent_or("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36");
+  let mut request = client.get(stream_url);
+  if let Ok(value) = HeaderValue::from_str(&user_agent) {
+    request = request.header(USER_AGENT, value);
+  }
+  request = request.header(ACCEPT_ENCODING, HeaderValue::from_static("identity"));
+  if let Ok(value) = HeaderValue::from_str(&referer_value) {
+    request = request.header(REFERER, value);
+  }
+  if let Some(auth) = auth {
+    if let Ok(value) = HeaderValue::from_str(&auth.cookie) {
+      request = request.header("Cookie", value);
+    }
+  }
+  request
+}
+
+/// 以较低画质并行录制一路备用 FLV，写到主文件旁的 `.backup.flv`；
+/// 只有对应的主文件最终为空时才登记为录制记录并转封装，否则删除备用文件。
+fn run_backup_record_loop(
+  context: LiveContext,
+  room_id: String,
+  room_info: LiveRoomInfo,
+  settings: LiveSettings,
+  auth: Option<AuthInfo>,
+  stop_flag: Arc<AtomicBool>,
+  backup_stop: Arc<AtomicBool>,
+) {
+  let qn = settings.record_backup_quality;
+  let retry_delay = Duration::from_millis(settings.stream_retry_ms.max(1000) as u64);
+  let client = match Client::builder()
+    .connect_timeout(Duration::from_millis(
+      settings.stream_connect_timeout_ms.max(1000) as u64,
+    ))
+    .proxy(crate::config::http_proxy())
+    .build()
+  {
+    Ok(client) => client,
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("stream_backup_client_error room={} err={}", room_id, err),
+      );
+      return;
+    }
+  };
+  append_log(
+    &context.app_log_path,
+    &format!("stream_backup_start room={} qn={}", room_id, qn),
+  );
+  let is_stopped = || stop_flag.load(Ordering::SeqCst) || backup_stop.load(Ordering::SeqCst);
+  let mut backup_index: i64 = 0;
+  while !is_stopped() {
+    let urls = match fetch_stream_urls_with_qn(
+      &context.bilibili,
+      &room_info.room_id,
+      Some(qn),
+      auth.as_ref(),
+    ) {
+      Ok(urls) => urls,
+      Err(err) => {
+        append_log(
+          &context.app_log_path,
+          &format!("stream_backup_fetch_error room={} err={}", room_id, err),
+        );
+        std::thread::sleep(retry_delay);
+        continue;
+      }
+    };
+    let Some(stream_url) = urls.into_iter().find(|url| !is_hls_url(url)) else {
+      append_log(
+        &context.app_log_path,
+        &format!("stream_backup_skip room={} reason=hls_only", room_id),
+      );
+      std::thread::sleep(retry_delay);
+      continue;
+    };
+    let mut response = match build_stream_request(&client, &stream_url, &room_info.room_id, auth.as_ref()).send() {
+      Ok(response) if response.status().is_success() => response,
+      Ok(response) => {
+        append_log(
+          &context.app_log_path,
+          &format!(
+            "stream_backup_response_error room={} status={}",
+            room_id,
+            response.status().as_u16()
+          ),
+        );
+        std::thread::sleep(retry_delay);
+        continue;
+      }
+      Err(err) => {
+        append_log(
+          &context.app_log_path,
+          &format!("stream_backup_connect_error room={} err={}", room_id, err),
+        );
+        std::thread::sleep(retry_delay);
+        continue;
+      }
+    };
+    if let Err(err) = write_backup_stream(
+      &context,
+      &room_id,
+      &settings,
+      &mut response,
+      &mut backup_index,
+      &is_stopped,
+    ) {
+      append_log(
+        &context.app_log_path,
+        &format!("stream_backup_read_error room={} err={}", room_id, err),
+      );
+    }
+    if context.live_runtime.get_record_status(&room_id).is_none() {
+      break;
+    }
+    if !is_stopped() {
+      std::thread::sleep(retry_delay);
+    }
+  }
+  append_log(
+    &context.app_log_path,
+    &format!("stream_backup_stop room={}", room_id),
+  );
+}
+
+fn build_backup_path(primary_path: &str, backup_index: i64) -> String {
+  let path = Path::new(primary_path);
+  let stem = path
+    .file_stem()
+    .and_then(|value| value.to_str())
+    .unwrap_or("record");
+  let file_name = if backup_index > 1 {
+    format!("{}.backup{}.flv", stem, backup_index)
+  } else {
+    format!("{}.backup.flv", stem)
+  };
+  path.with_file_name(file_name).to_string_lossy().to_string()
+}
+
+/// 一个已写完的备用 FLV 文件及其对应的主录制文件信息。
+struct BackupFile {
+  primary_path: String,
+  segment_index: i64,
+  title: String,
+  path: String,
+  started_at: String,
+  bytes_written: u64,
+}
+
+/// 备用流与主录制共用 FLV 解析、头信息缓存与时间戳修正，按大小分段时在关键帧处切换文件。
+fn write_backup_stream<F>(
+  context: &LiveContext,
+  room_id: &str,
+  settings: &LiveSettings,
+  response: &mut reqwest::blocking::Response,
+  backup_index: &mut i64,
+  is_stopped: &F,
+) -> Result<(), String>
+where
+  F: Fn() -> bool,
+{
+  let mut parser = FlvStreamParser::new();
+  let mut cache = FlvHeaderCache::new();
+  let mut current: Option<(SegmentWriter, BackupFile)> = None;
+  let mut pending_split = false;
+  let mut buf = vec![0u8; 8192];
+  let result = loop {
+    if is_stopped() {
+      break Ok(());
+    }
+    let n = match response.read(&mut buf) {
+      Ok(0) => break Ok(()),
+      Ok(n) => n,
+      Err(err) => break Err(err.to_string()),
+    };
+    let items = match parser.push(&buf[..n]) {
+      Ok(items) => items,
+      Err(err) => break Err(err),
+    };
+    let written = items.into_iter().try_for_each(|item| match item {
+      FlvParsedItem::Header(header) => {
+        cache.set_header(header);
+        if current.is_none() {
+          current = Some(open_backup_file(context, room_id, settings, &cache, backup_index)?);
+        }
+        Ok(())
+      }
+      FlvParsedItem::Tag(tag) => {
+        cache.update_from_tag(&tag);
+        if pending_split && is_video_keyframe(&tag) {
+          if let Some((seg, file)) = current.take() {
+            finish_backup_file(context, room_id, finish_backup_segment(seg, file));
+          }
+          current = Some(open_backup_file(context, room_id, settings, &cache, backup_index)?);
+          pending_split = false;
+        }
+        if let Some((seg, _)) = current.as_mut() {
+          seg.write_tag(&tag)?;
+          if seg.take_size_split() {
+            pending_split = true;
+          }
+        }
+        Ok(())
+      }
+    });
+    if let Err(err) = written {
+      break Err(err);
+    }
+  };
+  if let Some((seg, file)) = current.take() {
+    finish_backup_file(context, room_id, finish_backup_segment(seg, file));
+  }
+  result
+}
+
+fn open_backup_file(
+  context: &LiveContext,
+  room_id: &str,
+  settings: &LiveSettings,
+  cache: &FlvHeaderCache,
+  backup_index: &mut i64,
+) -> Result<(SegmentWriter, BackupFile), String> {
+  let primary = context
+    .live_runtime
+    .get_record_status(room_id)
+    .ok_or_else(|| "主录制已结束".to_string())?;
+  *backup_index += 1;
+  let path = build_backup_path(&primary.file_path, *backup_index);
+  let mut seg = open_backup_segment(context, &path, settings)?;
+  cache.write_preamble(&mut seg)?;
+  let file = BackupFile {
+    primary_path: primary.file_path,
+    segment_index: primary.segment_index.max(1),
+    title: primary.current_title,
+    path,
+    started_at: now_rfc3339(),
+    bytes_written: 0,
+  };
+  Ok((seg, file))
+}
+
+fn finish_backup_segment(seg: SegmentWriter, mut file: BackupFile) -> BackupFile {
+  file.bytes_written = seg.bytes_written;
+  file
+}
+
+fn finish_backup_file(context: &LiveContext, room_id: &str, file: BackupFile) {
+  let BackupFile {
+    primary_path,
+    segment_index,
+    title,
+    path: backup_path,
+    started_at,
+    bytes_written,
+  } = file;
+  if bytes_written == 0 {
+    let _ = std::fs::remove_file(&backup_path);
+    return;
+  }
+  if !primary_file_is_empty(&primary_path) {
+    // 主流已有内容，备用文件不再需要
+    let removed = std::fs::remove_file(&backup_path).is_ok();
+    append_log(
+      context.app_log_path.as_ref(),
+      &format!(
+        "stream_backup_discard room={} path={} bytes={} removed={}",
+        room_id, backup_path, bytes_written, removed
+      ),
+    );
+    return;
+  }
+  let end_time = now_rfc3339();
+  let record_id = context.db.with_conn(|conn| {
+    conn.execute(
+      "INSERT INTO live_record_task (room_id, status, file_path, segment_index, start_time, end_time, file_size, title, error_message, create_time, update_time) \
+       VALUES (?1, 'COMPLETED', ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+      (
+        room_id,
+        &backup_path,
+        segment_index,
+        &started_at,
+        &end_time,
+        bytes_written as i64,
+        &title,
+        BACKUP_STREAM_NOTE,
+        &end_time,
+        &end_time,
+      ),
+    )?;
+    Ok(conn.last_insert_rowid())
+  });
+  match record_id {
+    Ok(record_id) => {
+      append_log(
+        context.app_log_path.as_ref(),
+        &format!(
+          "stream_backup_promote room={} record_id={} primary={} path={} bytes={}",
+          room_id, record_id, primary_path, backup_path, bytes_written
+        ),
+      );
+      spawn_segment_remux(context.clone(), record_id, backup_path);
+    }
+    Err(err) => {
+      append_log(
+        context.app_log_path.as_ref(),
+        &format!("stream_backup_promote_fail room={} err={}", room_id, err),
+      );
+    }
+  }
+}
+
+fn primary_file_is_empty(primary_path: &str) -> bool {
+  let path = PathBuf::from(primary_path);
+  let candidates = [
+    path.clone(),
+    path.with_extension("mp4"),
+    PathBuf::from(normalize_hls_path(primary_path)),
+  ];
+  !candidates.iter().any(|candidate| {
+    std::fs::metadata(candidate)
+      .map(|meta| meta.len() > 0)
+      .unwrap_or(false)
+  })
+}
+
+struct FlvTag {
+  tag_type: u8,
+  bytes: Vec<u8>,
+  data_offset: usize,
+  data_len: usize,
+}
+
+impl FlvTag {
+  fn data(&self) -> &[u8] {
+    &self.bytes[self.data_offset..self.data_offset + self.data_len]
+  }
+}
+
+fn is_video_keyframe(tag: &FlvTag) -> bool {
+  if tag.tag_type != 9 {
+    return false;
+  }
+  let data = tag.data();
+  if data.is_empty() {
+    return false;
+  }
+  let frame_type = data[0] >> 4;
+  frame_type == 1
 }
 
 enum FlvParsedItem {
@@ -1278,6 +2251,8 @@ enum FlvParsedItem {
 struct FlvStreamParser {
   buffer: Vec<u8>,
   header_parsed: bool,
+  tags_parsed: u64,
+  bytes_since_last_tag: u64,
 }
 
 impl FlvStreamParser {
@@ -1285,12 +2260,19 @@ impl FlvStreamParser {
     Self {
       buffer: Vec::new(),
       header_parsed: false,
+      tags_parsed: 0,
+      bytes_since_last_tag: 0,
     }
   }
 
+  fn take_tags_parsed(&mut self) -> u64 {
+    std::mem::take(&mut self.tags_parsed)
+  }
+
   fn push(&mut self, data: &[u8]) -> Result<Vec<FlvParsedItem>, String> {
     if !data.is_empty() {
       self.buffer.extend_from_slice(data);
+      self.bytes_since_last_tag += data.len() as u64;
     }
     let mut items = Vec::new();
     let mut offset = 0;
@@ -1331,6 +2313,8 @@ impl FlvStreamParser {
         data_len,
       }));
       offset += total;
+      self.tags_parsed += 1;
+      self.bytes_since_last_tag = self.buffer.len().saturating_sub(offset) as u64;
     }
 
     if offset > 0 {
@@ -1423,6 +2407,85 @@ fn parse_flv_timestamp(tag: &FlvTag) -> u32 {
   ts
 }
 
+fn write_flv_timestamp(bytes: &mut [u8], timestamp: u32) {
+  if bytes.len() < 8 {
+    return;
+  }
+  bytes[4] = (timestamp >> 16) as u8;
+  bytes[5] = (timestamp >> 8) as u8;
+  bytes[6] = timestamp as u8;
+  bytes[7] = (timestamp >> 24) as u8;
+}
+
+#[derive(Debug, PartialEq)]
+enum FlvTimestampOutcome {
+  Unchanged,
+  Adjusted(u32),
+  Rebased { output: u32, delta_ms: i64 },
+}
+
+/// 分段内的时间戳连续性修正。重连后新流的时间戳可能回退或大幅跳跃，
+/// 超出容差时把偏移量重新对齐到上一个写出的时间戳之后；音视频各自不允许回退。
+#[derive(Default)]
+struct FlvTimestampRebaser {
+  offset: i64,
+  last_output: Option<i64>,
+  last_audio: Option<i64>,
+  last_video: Option<i64>,
+}
+
+impl FlvTimestampRebaser {
+  fn rebase(&mut self, tag_type: u8, input: u32) -> FlvTimestampOutcome {
+    let Some(last_output) = self.last_output else {
+      if tag_type == 8 || tag_type == 9 {
+        self.record(tag_type, input as i64);
+      }
+      return FlvTimestampOutcome::Unchanged;
+    };
+    // 流中途的脚本 tag（onMetaData）时间戳通常为 0，只跟随当前进度，不参与判断
+    if tag_type != 8 && tag_type != 9 {
+      return Self::outcome(input, last_output, None);
+    }
+    let mut output = input as i64 + self.offset;
+    let delta = output - last_output;
+    let mut rebased = None;
+    if !(-FLV_TIMESTAMP_BACKWARD_TOLERANCE_MS..=FLV_TIMESTAMP_FORWARD_JUMP_MS).contains(&delta) {
+      let expected = last_output + FLV_TIMESTAMP_REBASE_GAP_MS;
+      self.offset = expected - input as i64;
+      output = expected;
+      rebased = Some(delta);
+    }
+    let track_last = if tag_type == 8 {
+      self.last_audio
+    } else {
+      self.last_video
+    };
+    if let Some(track_last) = track_last {
+      output = output.max(track_last);
+    }
+    self.record(tag_type, output);
+    Self::outcome(input, output, rebased)
+  }
+
+  fn record(&mut self, tag_type: u8, output: i64) {
+    if tag_type == 8 {
+      self.last_audio = Some(output);
+    } else {
+      self.last_video = Some(output);
+    }
+    self.last_output = Some(self.last_output.map_or(output, |last| last.max(output)));
+  }
+
+  fn outcome(input: u32, output: i64, rebased: Option<i64>) -> FlvTimestampOutcome {
+    let output = output.clamp(0, u32::MAX as i64) as u32;
+    match rebased {
+      Some(delta_ms) => FlvTimestampOutcome::Rebased { output, delta_ms },
+      None if output == input => FlvTimestampOutcome::Unchanged,
+      None => FlvTimestampOutcome::Adjusted(output),
+    }
+  }
+}
+
 fn normalize_header_tag(tag: &[u8]) -> Vec<u8> {
   let mut normalized = tag.to_vec();
   if normalized.len() >= 11 {
@@ -1466,8 +2529,11 @@ struct SegmentWriter {
   file_path: String,
   file: File,
   bytes_written: u64,
+  size_limit_bytes: u64,
+  size_split_requested: bool,
   title: String,
   metadata_path: Option<String>,
+  timestamps: FlvTimestampRebaser,
 }
 
 impl SegmentWriter {
@@ -1477,6 +2543,40 @@ impl SegmentWriter {
     Ok(())
   }
 
+  /// 写入音视频/脚本 tag，必要时改写时间戳，保证同一分段内跨重连仍然单调递增。
+  fn write_tag(&mut self, tag: &FlvTag) -> Result<(), String> {
+    let input = parse_flv_timestamp(tag);
+    let output = match self.timestamps.rebase(tag.tag_type, input) {
+      FlvTimestampOutcome::Unchanged => return self.write(&tag.bytes),
+      FlvTimestampOutcome::Adjusted(output) => output,
+      FlvTimestampOutcome::Rebased { output, delta_ms } => {
+        append_log(
+          self.log_path.as_ref(),
+          &format!(
+            "record_timestamp_rebase record_id={} tag_type={} input={} output={} delta_ms={}",
+            self.record_id, tag.tag_type, input, output, delta_ms
+          ),
+        );
+        output
+      }
+    };
+    let mut bytes = tag.bytes.clone();
+    write_flv_timestamp(&mut bytes, output);
+    self.write(&bytes)
+  }
+
+  /// 每个分段只报告一次达到大小上限，由调用方在下一个关键帧处切分。
+  fn take_size_split(&mut self) -> bool {
+    if self.size_limit_bytes == 0
+      || self.size_split_requested
+      || self.bytes_written < self.size_limit_bytes
+    {
+      return false;
+    }
+    self.size_split_requested = true;
+    true
+  }
+
   fn finish(&mut self, status: &str, error: Option<&str>) -> Result<(), String> {
     let end_time = now_rfc3339();
     update_record_task(
@@ -1536,8 +2636,36 @@ fn open_segment(
     file_path: file_path.to_string(),
     file,
     bytes_written: 0,
+    size_limit_bytes: settings.split_by_size_mb.max(0) as u64 * 1024 * 1024,
+    size_split_requested: false,
     title: title.to_string(),
     metadata_path,
+    timestamps: FlvTimestampRebaser::default(),
+  })
+}
+
+/// 备用流文件只有在主文件为空时才登记录制记录，因此不插入记录行，也不写元数据文件。
+fn open_backup_segment(
+  context: &LiveContext,
+  file_path: &str,
+  settings: &LiveSettings,
+) -> Result<SegmentWriter, String> {
+  if let Some(parent) = Path::new(file_path).parent() {
+    std::fs::create_dir_all(parent).map_err(|err| format!("创建目录失败: {}", err))?;
+  }
+  let file = File::create(file_path).map_err(|err| format!("创建文件失败: {}", err))?;
+  Ok(SegmentWriter {
+    db: Arc::clone(&context.db),
+    log_path: Arc::clone(&context.app_log_path),
+    record_id: 0,
+    file_path: file_path.to_string(),
+    file,
+    bytes_written: 0,
+    size_limit_bytes: settings.split_by_size_mb.max(0) as u64 * 1024 * 1024,
+    size_split_requested: false,
+    title: String::new(),
+    metadata_path: None,
+    timestamps: FlvTimestampRebaser::default(),
   })
 }
 
@@ -1555,13 +2683,27 @@ fn spawn_segment_remux(context: LiveContext, record_id: i64, file_path: String)
   let source = source_path.to_string_lossy().to_string();
   let target = target_path.to_string_lossy().to_string();
   let log_path = context.app_log_path.clone();
+  // 退出过程中转封装会被中断并留下残缺的 mp4，交给下次启动的重试处理
+  if crate::shutdown::is_requested() {
+    append_log(
+      log_path.as_ref(),
+      &format!("live_remux_skip record_id={} reason=shutdown", record_id),
+    );
+    return;
+  }
   let db = context.db.clone();
+  let fragmented = load_live_settings_from_db(&db)
+    .map(|settings| settings.remux_fragmented_mp4)
+    .unwrap_or(false);
   tauri::async_runtime::spawn(async move {
     append_log(
       log_path.as_ref(),
-      &format!("live_remux_start record_id={} source={} target={}", record_id, source, target),
+      &format!(
+        "live_remux_start record_id={} source={} target={} fragmented={}",
+        record_id, source, target, fragmented
+      ),
     );
-    let args = vec![
+    let mut args = vec![
       "-hide_banner".to_string(),
       "-loglevel".to_string(),
       "error".to_string(),
@@ -1571,8 +2713,13 @@ fn spawn_segment_remux(context: LiveContext, record_id: i64, file_path: String)
       "-c".to_string(),
       "copy".to_string(),
       "-shortest".to_string(),
-      target.clone(),
     ];
+    // 分片 MP4 在转封装中途断电时仍可播放已写入部分，代价是部分播放器拖动较慢
+    if fragmented {
+      args.push("-movflags".to_string());
+      args.push("+frag_keyframe+empty_moov".to_string());
+    }
+    args.push(target.clone());
     let result = tauri::async_runtime::spawn_blocking(move || run_ffmpeg(&args))
       .await
       .map_err(|_| "转封装执行失败".to_string());
@@ -1661,52 +2808,435 @@ fn update_record_task_file_path(
   file_size: u64,
 ) -> Result<(), String> {
   let now = now_rfc3339();
-  db.with_conn(|conn| {
-    conn.execute(
-      "UPDATE live_record_task SET file_path = ?1, file_size = ?2, update_time = ?3 WHERE id = ?4",
-      (file_path, file_size as i64, &now, record_id),
-    )?;
-    Ok(())
+  db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE live_record_task SET file_path = ?1, file_size = ?2, update_time = ?3 WHERE id = ?4",
+      (file_path, file_size as i64, &now, record_id),
+    )?;
+    Ok(())
+  })
+  .map_err(|err| format!("更新录播路径失败: {}", err))
+}
+
+fn update_record_remux_retry_count(db: &Db, record_id: i64, retry_count: i64) -> Result<(), String> {
+  let now = now_rfc3339();
+  db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE live_record_task SET remux_retry_count = ?1, update_time = ?2 WHERE id = ?3",
+      (retry_count, &now, record_id),
+    )?;
+    Ok(())
+  })
+  .map_err(|err| format!("更新转封装重试次数失败: {}", err))
+}
+
+fn load_anchor_room_ids(db: &Db) -> Result<Vec<String>, String> {
+  db.with_conn(|conn| {
+    let mut stmt = conn.prepare("SELECT uid FROM anchor ORDER BY id DESC")?;
+    let rows = stmt
+      .query_map([], |row| row.get(0))?
+      .collect::<Result<Vec<String>, _>>()?;
+    Ok(rows)
+  })
+  .map_err(|err| err.to_string())
+}
+
+fn load_anchor_nickname(db: &Db, room_id: &str) -> Result<Option<String>, String> {
+  db.with_conn(|conn| {
+    conn
+      .query_row(
+        "SELECT nickname FROM anchor WHERE uid = ?1",
+        [room_id],
+        |row| row.get(0),
+      )
+      .optional()
+  })
+  .map_err(|err| err.to_string())
+}
+
+fn load_room_auto_record(db: &Db, room_id: &str) -> Result<bool, String> {
+  db.with_conn(|conn| {
+    conn
+      .query_row(
+        "SELECT auto_record FROM live_room_settings WHERE room_id = ?1",
+        [room_id],
+        |row| row.get::<_, i64>(0),
+      )
+      .map(|value| value != 0)
+      .or(Ok(true))
+  })
+  .map_err(|err| err.to_string())
+}
+
+/// 读取房间的录制设置覆盖；JSON 损坏时按未设置处理。
+pub fn load_room_settings_override(
+  db: &Db,
+  room_id: &str,
+) -> Result<Option<LiveSettingsOverride>, String> {
+  let raw = db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT settings_override FROM live_room_settings WHERE room_id = ?1",
+          [room_id],
+          |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+    })
+    .map_err(|err| err.to_string())?
+    .flatten();
+  Ok(
+    raw
+      .and_then(|value| serde_json::from_str::<LiveSettingsOverride>(&value).ok())
+      .filter(|value| !value.is_empty()),
+  )
+}
+
+pub struct DailyRecordingQuota {
+  pub recorded_seconds: i64,
+  pub limit_seconds: i64,
+}
+
+impl DailyRecordingQuota {
+  pub fn exceeded(&self) -> bool {
+    self.limit_seconds > 0 && self.recorded_seconds >= self.limit_seconds
+  }
+}
+
+/// 房间未设置每日录制时长上限时返回 `None`。
+pub fn load_daily_recording_quota(
+  db: &Db,
+  room_id: &str,
+) -> Result<Option<DailyRecordingQuota>, String> {
+  let limit_hours: f64 = db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT IFNULL(max_daily_recording_hours, 0) FROM live_room_settings WHERE room_id = ?1",
+          [room_id],
+          |row| row.get(0),
+        )
+        .optional()
+    })
+    .map_err(|err| err.to_string())?
+    .unwrap_or(0.0);
+  // NaN 与非正数都视为未设置上限
+  if limit_hours.is_nan() || limit_hours <= 0.0 {
+    return Ok(None);
+  }
+  let recorded_seconds = load_room_recorded_seconds_today(db, room_id)?;
+  Ok(Some(DailyRecordingQuota {
+    recorded_seconds,
+    limit_seconds: (limit_hours * 3600.0).round() as i64,
+  }))
+}
+
+/// 自动开始录制前检查房间每日时长上限，已达上限时记录跳过日志。
+pub fn auto_record_quota_exceeded(db: &Db, log_path: &Path, room_id: &str) -> bool {
+  match load_daily_recording_quota(db, room_id) {
+    Ok(Some(quota)) if quota.exceeded() => {
+      log_daily_quota_skip(log_path, room_id, &quota);
+      true
+    }
+    _ => false,
+  }
+}
+
+fn log_daily_quota_skip(log_path: &Path, room_id: &str, quota: &DailyRecordingQuota) {
+  append_log(
+    log_path,
+    &format!(
+      "auto_record_skip reason=daily_quota room={} recorded_seconds={} limit_seconds={}",
+      room_id, quota.recorded_seconds, quota.limit_seconds
+    ),
+  );
+}
+
+fn load_room_recorded_seconds_today(db: &Db, room_id: &str) -> Result<i64, String> {
+  let now = Utc::now();
+  let day_start = Local::now()
+    .date_naive()
+    .and_hms_opt(0, 0, 0)
+    .and_then(|value| value.and_local_timezone(Local).earliest())
+    .map(|value| value.with_timezone(&Utc))
+    .unwrap_or(now);
+  let rows: Vec<(String, Option<String>, String)> = db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT start_time, end_time, status FROM live_record_task \
+         WHERE room_id = ?1 AND status != 'MERGED' AND (end_time IS NULL OR end_time >= ?2) \
+         AND COALESCE(error_message, '') != ?3",
+      )?;
+      // 备用流登记的记录与主流的空分段覆盖同一时段，不重复计入时长
+      let rows = stmt
+        .query_map((room_id, day_start.to_rfc3339(), BACKUP_STREAM_NOTE), |row| {
+          Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok(rows)
+    })
+    .map_err(|err| err.to_string())?;
+
+  let mut total = 0i64;
+  for (start_time, end_time, status) in rows {
+    let start = match DateTime::parse_from_rfc3339(&start_time) {
+      Ok(value) => value.with_timezone(&Utc),
+      Err(_) => continue,
+    };
+    let end = match end_time.as_deref() {
+      Some(value) => match DateTime::parse_from_rfc3339(value) {
+        Ok(value) => value.with_timezone(&Utc),
+        Err(_) => continue,
+      },
+      None if status == "RECORDING" => now,
+      None => continue,
+    };
+    let start = start.max(day_start);
+    if end > start {
+      total += (end - start).num_seconds();
+    }
+  }
+  Ok(total)
+}
+
+/// 合并同一房间同一录制日期（UTC，与 {{ liveDate }} 一致）的录制分段，结果写入一条 MERGED 记录。
+pub fn merge_record_session(
+  db: &Db,
+  log_path: &Path,
+  room_id_or_task_id: &str,
+) -> Result<LiveRecordMergeResult, String> {
+  let key = room_id_or_task_id.trim();
+  if key.is_empty() {
+    return Err("房间号或录制任务ID不能为空".to_string());
+  }
+  let anchor = load_merge_anchor_segment(db, key)?.ok_or_else(|| "未找到录制分段".to_string())?;
+  let session_date = record_session_date(&anchor.start_time)
+    .ok_or_else(|| format!("录制开始时间格式错误: {}", anchor.start_time))?;
+  let rows: Vec<RecordSegmentRow> = db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT id, room_id, status, file_path, start_time, end_time, title FROM live_record_task \
+         WHERE room_id = ?1 AND status != 'MERGED' ORDER BY start_time ASC, segment_index ASC, id ASC",
+      )?;
+      let rows = stmt
+        .query_map([&anchor.room_id], map_record_segment_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok(rows)
+    })
+    .map_err(|err| err.to_string())?;
+  let session: Vec<RecordSegmentRow> = rows
+    .into_iter()
+    .filter(|row| record_session_date(&row.start_time).as_deref() == Some(session_date.as_str()))
+    .collect();
+  if session.iter().any(|row| row.status == "RECORDING") {
+    return Err("存在录制中的分段，请在录制结束后再合并".to_string());
+  }
+
+  let mut segments = Vec::new();
+  for row in session {
+    let path = PathBuf::from(&row.file_path);
+    let size = std::fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+    if row.status == "FAILED" && size == 0 {
+      append_log(
+        log_path,
+        &format!(
+          "live_record_merge_skip record_id={} status={} path={}",
+          row.id, row.status, row.file_path
+        ),
+      );
+      continue;
+    }
+    if size == 0 {
+      return Err(format!("录制分段文件不存在或为空: {}", row.file_path));
+    }
+    segments.push(row);
+  }
+  if segments.len() < 2 {
+    return Err("可合并的录制分段不足两个".to_string());
+  }
+  validate_record_segments(&segments)?;
+
+  let first = &segments[0];
+  let last = &segments[segments.len() - 1];
+  let first_path = PathBuf::from(&first.file_path);
+  let ext = first_path
+    .extension()
+    .and_then(|value| value.to_str())
+    .unwrap_or("flv")
+    .to_ascii_lowercase();
+  let stem = first_path
+    .file_stem()
+    .and_then(|value| value.to_str())
+    .unwrap_or("record");
+  let output_path = first_path.with_file_name(format!("{}_merged.{}", stem, ext));
+  let inputs: Vec<PathBuf> = segments.iter().map(|row| PathBuf::from(&row.file_path)).collect();
+  append_log(
+    log_path,
+    &format!(
+      "live_record_merge_start room={} date={} inputs={} output={}",
+      anchor.room_id,
+      session_date,
+      inputs.len(),
+      output_path.to_string_lossy()
+    ),
+  );
+  for row in &segments {
+    append_log(
+      log_path,
+      &format!(
+        "live_record_merge_input room={} record_id={} path={}",
+        anchor.room_id, row.id, row.file_path
+      ),
+    );
+  }
+  match merge_files(&inputs, &output_path, None, None, |command| {
+    append_log(
+      log_path,
+      &format!("live_record_merge_command room={} args={}", anchor.room_id, command),
+    );
+  }) {
+    Ok(Some(reason)) => {
+      append_log(
+        log_path,
+        &format!("live_record_merge_reencode room={} reason={}", anchor.room_id, reason),
+      );
+    }
+    Ok(None) => {}
+    Err(err) => {
+      append_log(
+        log_path,
+        &format!("live_record_merge_fail room={} err={}", anchor.room_id, err),
+      );
+      return Err(err);
+    }
+  }
+  let file_size = std::fs::metadata(&output_path).map(|meta| meta.len()).unwrap_or(0);
+  let output_str = output_path.to_string_lossy().to_string();
+  let now = now_rfc3339();
+  let end_time = last.end_time.clone().unwrap_or_else(|| now.clone());
+  let record_id = db
+    .with_conn(|conn| {
+      conn.execute(
+        "INSERT INTO live_record_task (room_id, status, file_path, segment_index, start_time, end_time, file_size, title, create_time, update_time) \
+         VALUES (?1, 'MERGED', ?2, 0, ?3, ?4, ?5, ?6, ?7, ?8)",
+        (
+          &anchor.room_id,
+          &output_str,
+          &first.start_time,
+          &end_time,
+          file_size as i64,
+          &first.title,
+          &now,
+          &now,
+        ),
+      )?;
+      Ok(conn.last_insert_rowid())
+    })
+    .map_err(|err| format!("写入合并记录失败: {}", err))?;
+  append_log(
+    log_path,
+    &format!(
+      "live_record_merge_done room={} record_id={} output={} size={}",
+      anchor.room_id, record_id, output_str, file_size
+    ),
+  );
+  Ok(LiveRecordMergeResult {
+    record_id,
+    room_id: anchor.room_id.clone(),
+    file_path: output_str,
+    segment_count: segments.len(),
+    file_size,
   })
-  .map_err(|err| format!("更新录播路径失败: {}", err))
 }
 
-fn load_anchor_room_ids(db: &Db) -> Result<Vec<String>, String> {
-  db.with_conn(|conn| {
-    let mut stmt = conn.prepare("SELECT uid FROM anchor ORDER BY id DESC")?;
-    let rows = stmt
-      .query_map([], |row| row.get(0))?
-      .collect::<Result<Vec<String>, _>>()?;
-    Ok(rows)
+fn map_record_segment_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<RecordSegmentRow> {
+  Ok(RecordSegmentRow {
+    id: row.get(0)?,
+    room_id: row.get(1)?,
+    status: row.get(2)?,
+    file_path: row.get(3)?,
+    start_time: row.get(4)?,
+    end_time: row.get(5)?,
+    title: row.get(6)?,
   })
-  .map_err(|err| err.to_string())
 }
 
-fn load_anchor_nickname(db: &Db, room_id: &str) -> Result<Option<String>, String> {
+fn load_merge_anchor_segment(db: &Db, key: &str) -> Result<Option<RecordSegmentRow>, String> {
   db.with_conn(|conn| {
+    let by_room = conn
+      .query_row(
+        "SELECT id, room_id, status, file_path, start_time, end_time, title FROM live_record_task \
+         WHERE room_id = ?1 AND status != 'MERGED' ORDER BY start_time DESC, id DESC LIMIT 1",
+        [key],
+        map_record_segment_row,
+      )
+      .optional()?;
+    if by_room.is_some() {
+      return Ok(by_room);
+    }
+    let record_id = match key.parse::<i64>() {
+      Ok(value) => value,
+      Err(_) => return Ok(None),
+    };
     conn
       .query_row(
-        "SELECT nickname FROM anchor WHERE uid = ?1",
-        [room_id],
-        |row| row.get(0),
+        "SELECT id, room_id, status, file_path, start_time, end_time, title FROM live_record_task \
+         WHERE id = ?1 AND status != 'MERGED'",
+        [record_id],
+        map_record_segment_row,
       )
       .optional()
   })
   .map_err(|err| err.to_string())
 }
 
-fn load_room_auto_record(db: &Db, room_id: &str) -> Result<bool, String> {
-  db.with_conn(|conn| {
-    conn
-      .query_row(
-        "SELECT auto_record FROM live_room_settings WHERE room_id = ?1",
-        [room_id],
-        |row| row.get::<_, i64>(0),
-      )
-      .map(|value| value != 0)
-      .or(Ok(true))
-  })
-  .map_err(|err| err.to_string())
+fn record_session_date(start_time: &str) -> Option<String> {
+  DateTime::parse_from_rfc3339(start_time)
+    .ok()
+    .map(|value| value.with_timezone(&Utc).format("%Y%m%d").to_string())
+}
+
+fn validate_record_segments(segments: &[RecordSegmentRow]) -> Result<(), String> {
+  let mut expected_ext: Option<String> = None;
+  let mut prev_end: Option<DateTime<Utc>> = None;
+  for row in segments {
+    let ext = Path::new(&row.file_path)
+      .extension()
+      .and_then(|value| value.to_str())
+      .map(|value| value.to_ascii_lowercase())
+      .unwrap_or_default();
+    if ext != "flv" && ext != "mp4" {
+      return Err(format!("仅支持合并 FLV/MP4 分段: {}", row.file_path));
+    }
+    match expected_ext.as_deref() {
+      Some(expected) if expected != ext => {
+        return Err(format!("录制分段格式不一致: {}", row.file_path));
+      }
+      Some(_) => {}
+      None => expected_ext = Some(ext),
+    }
+    let start = DateTime::parse_from_rfc3339(&row.start_time)
+      .map(|value| value.with_timezone(&Utc))
+      .map_err(|_| format!("录制开始时间格式错误: {}", row.start_time))?;
+    if let Some(prev_end) = prev_end {
+      if start + chrono::Duration::seconds(RECORD_MERGE_OVERLAP_TOLERANCE_SECS) < prev_end {
+        return Err(format!("录制分段时间重叠，无法合并: record_id={}", row.id));
+      }
+      // 断流重连的短暂间隔可以接受，间隔过长说明中间缺少分段
+      if start > prev_end + chrono::Duration::seconds(RECORD_MERGE_GAP_TOLERANCE_SECS) {
+        return Err(format!("录制分段之间存在缺口，无法合并: record_id={}", row.id));
+      }
+    }
+    let end_time = row
+      .end_time
+      .as_deref()
+      .ok_or_else(|| format!("录制分段缺少结束时间，无法合并: record_id={}", row.id))?;
+    let end = DateTime::parse_from_rfc3339(end_time)
+      .map(|value| value.with_timezone(&Utc))
+      .map_err(|_| format!("录制结束时间格式错误: {}", end_time))?;
+    prev_end = Some(end);
+  }
+  Ok(())
 }
 
 fn update_anchor_status(db: &Db, room_id: &str, live_status: i64) -> Result<(), String> {
@@ -1863,7 +3393,7 @@ fn update_metadata_file(path: &str, end_time: &str, file_size: u64) -> Result<()
 }
 
 fn download_cover(target_file: &str, cover_url: &str) -> Result<(), String> {
-  let response = Client::new()
+  let response = crate::config::build_blocking_http_client()
     .get(cover_url)
     .send()
     .map_err(|err| format!("下载封面失败: {}", err))?;
@@ -1895,13 +3425,87 @@ fn fetch_stream_urls(
   settings: &LiveSettings,
   auth: Option<&AuthInfo>,
   with_quality: bool,
+) -> Result<Vec<String>, String> {
+  let qn = if with_quality {
+    Some(parse_quality(&settings.recording_quality))
+  } else {
+    None
+  };
+  fetch_stream_urls_with_qn(client, room_id, qn, auth)
+}
+
+/// 按录制时的参数（画质、登录态、房间覆盖设置）获取一次直播流地址，用于排查无法录制的问题。
+/// 带画质获取失败时与录制一致地回退到不带画质参数重试。
+pub fn test_stream_urls(
+  context: &LiveContext,
+  room_info: &LiveRoomInfo,
+  settings: LiveSettings,
+) -> Result<LiveStreamTestResult, String> {
+  let mut settings = settings;
+  if let Some(overrides) = load_room_settings_override(&context.db, &room_info.room_id)? {
+    overrides.apply(&mut settings);
+  }
+  let auth = context.login_store.load_auth_info(&context.db).ok().flatten();
+  let requested_qn = parse_quality(&settings.recording_quality);
+  let (urls, quality_error) = match fetch_stream_urls(
+    &context.bilibili,
+    &room_info.room_id,
+    &settings,
+    auth.as_ref(),
+    true,
+  ) {
+    Ok(urls) => (urls, None),
+    Err(err) => {
+      let urls = fetch_stream_urls(
+        &context.bilibili,
+        &room_info.room_id,
+        &settings,
+        auth.as_ref(),
+        false,
+      )
+      .map_err(|fallback_err| {
+        format!("带画质获取失败: {}；不带画质获取失败: {}", err, fallback_err)
+      })?;
+      (urls, Some(err))
+    }
+  };
+  let now = Utc::now().timestamp();
+  let urls = urls
+    .into_iter()
+    .map(|url| {
+      let expire_at = stream_url_expire_at(&url);
+      LiveStreamTestUrl {
+        host: stream_url_host(&url),
+        format: if is_hls_url(&url) { "hls" } else { "flv" }.to_string(),
+        expire_at,
+        expires_in_secs: expire_at.map(|expire| expire as i64 - now),
+        url,
+      }
+    })
+    .collect();
+  Ok(LiveStreamTestResult {
+    room_id: room_info.room_id.clone(),
+    live_status: room_info.live_status,
+    logged_in: auth.is_some(),
+    recording_quality: settings.recording_quality,
+    requested_qn,
+    quality_fallback: quality_error.is_some(),
+    quality_error,
+    urls,
+  })
+}
+
+fn fetch_stream_urls_with_qn(
+  client: &BilibiliClient,
+  room_id: &str,
+  qn: Option<i64>,
+  auth: Option<&AuthInfo>,
 ) -> Result<Vec<String>, String> {
   let mut params = vec![
     ("cid".to_string(), room_id.to_string()),
     ("platform".to_string(), "web".to_string()),
   ];
-  if with_quality {
-    let qn = parse_quality(&settings.recording_quality);
+  if let Some(qn) = qn {
     params.push(("qn".to_string(), qn.to_string()));
   }
 
@@ -1971,7 +3575,10 @@ fn record_hls_stream(
     None
   };
 
-  let referer_value = format!("Referer:https://live.bilibili.com/{}\r\n", room_info.room_id);
+  let referer_value = format!(
+    "Referer:{}\r\n",
+    crate::config::live_referer_or(&format!("https://live.bilibili.com/{}", room_info.room_id))
+  );
   let args = vec![
     "-hide_banner".to_string(),
     "-loglevel".to_string(),
@@ -1987,7 +3594,7 @@ fn record_hls_stream(
     "-reconnect_delay_max".to_string(),
     "3".to_string(),
     "-user_agent".to_string(),
-    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36".to_string(),
+    crate::config::user_agent_or("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36"),
     "-headers".to_string(),
     referer_value,
     "-i".to_string(),
@@ -2097,6 +3704,60 @@ fn record_hls_stream(
   Ok(())
 }
 
+fn stream_url_host(url: &str) -> Option<String> {
+  Url::parse(url)
+    .ok()
+    .and_then(|parsed| parsed.host_str().map(|host| host.to_string()))
+}
+
+/// 从 `start` 开始查找第一个未失败节点的地址下标。
+fn next_healthy_stream_url(
+  urls: &[String],
+  start: usize,
+  failed_hosts: &HashSet<String>,
+) -> Option<usize> {
+  if urls.is_empty() {
+    return None;
+  }
+  (0..urls.len())
+    .map(|offset| (start + offset) % urls.len())
+    .find(|index| {
+      stream_url_host(&urls[*index])
+        .map(|host| !failed_hosts.contains(&host))
+        .unwrap_or(true)
+    })
+}
+
+/// 把失败过的节点排到最后；如果全部失败则清空记录重新轮换。
+fn prioritize_healthy_stream_urls(
+  urls: &mut [String],
+  failed_hosts: &mut HashSet<String>,
+  log_path: &Path,
+  room_id: &str,
+) {
+  if failed_hosts.is_empty() {
+    return;
+  }
+  let is_failed = |url: &String| {
+    stream_url_host(url)
+      .map(|host| failed_hosts.contains(&host))
+      .unwrap_or(false)
+  };
+  if urls.iter().all(is_failed) {
+    append_log(
+      log_path,
+      &format!(
+        "stream_failed_hosts_reset room={} hosts={}",
+        room_id,
+        failed_hosts.len()
+      ),
+    );
+    failed_hosts.clear();
+    return;
+  }
+  urls.sort_by_key(is_failed);
+}
+
 fn summarize_stream_url(url: &str) -> String {
   if let Ok(parsed) = Url::parse(url) {
     let host = parsed.host_str().unwrap_or("-");
@@ -2194,6 +3855,32 @@ fn parse_quality(value: &str) -> i64 {
   10000
 }
 
+async fn fetch_room_info_with_retry(
+  client: &BilibiliClient,
+  log_path: &Path,
+  room_id: &str,
+) -> Result<LiveRoomInfo, String> {
+  let mut attempt = 1;
+  loop {
+    match fetch_room_info(client, room_id).await {
+      Ok(info) => return Ok(info),
+      Err(err) if attempt < ROOM_INFO_FETCH_ATTEMPTS => {
+        let delay_ms = (ROOM_INFO_RETRY_BASE_DELAY_MS << (attempt - 1)).min(ROOM_INFO_RETRY_MAX_DELAY_MS);
+        append_log(
+          log_path,
+          &format!(
+            "live_check_retry room={} attempt={} delay_ms={} err={}",
+            room_id, attempt, delay_ms, err
+          ),
+        );
+        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+        attempt += 1;
+      }
+      Err(err) => return Err(err),
+    }
+  }
+}
+
 pub async fn fetch_room_info(
   client: &BilibiliClient,
   room_id: &str,
@@ -2252,22 +3939,344 @@ pub async fn fetch_room_info(
 }
 
 
+#[derive(Clone, Copy, PartialEq)]
+enum DanmakuFormat {
+  Json,
+  Xml,
+  Ass,
+}
+
+impl DanmakuFormat {
+  fn from_setting(value: &str) -> Self {
+    match normalize_danmaku_format(value) {
+      "xml" => DanmakuFormat::Xml,
+      "ass" => DanmakuFormat::Ass,
+      _ => DanmakuFormat::Json,
+    }
+  }
+}
+
+const ASS_PLAY_RES_X: f64 = 1920.0;
+const ASS_PLAY_RES_Y: f64 = 1080.0;
+const ASS_FONT_SIZE: f64 = 48.0;
+const ASS_LANE_HEIGHT: f64 = 54.0;
+const ASS_LANE_COUNT: usize = 12;
+const ASS_SCROLL_SECONDS: f64 = 8.0;
+const DANMAKU_FILTER_REPORT_SECS: u64 = 60;
+
+struct DanmakuLane {
+  start: f64,
+  width: f64,
+}
+
+/// 与录制文件同名的弹幕轨（.xml/.ass），时间轴以当前分段开始为零点。
+struct DanmakuTrack {
+  format: DanmakuFormat,
+  file: File,
+  origin: Instant,
+  lanes: Vec<Option<DanmakuLane>>,
+}
+
+impl DanmakuTrack {
+  fn open(format: DanmakuFormat, record_path: &str, segment_elapsed_secs: u64) -> Result<Self, String> {
+    let ext = if format == DanmakuFormat::Xml { "xml" } else { "ass" };
+    let target_path = Path::new(record_path).with_extension(ext);
+    let mut file = OpenOptions::new()
+      .create(true)
+      .read(true)
+      .append(true)
+      .open(&target_path)
+      .map_err(|err| {
+        format!(
+          "创建弹幕轨文件失败: {} path={}",
+          err,
+          target_path.to_string_lossy()
+        )
+      })?;
+    let is_empty = file.metadata().map(|meta| meta.len() == 0).unwrap_or(true);
+    if !is_empty && format == DanmakuFormat::Xml {
+      // 断线重连后续写同一文件，先去掉上次关闭时写入的 </i>，保证 XML 结构完整
+      strip_xml_footer(&mut file).map_err(|err| format!("续写弹幕轨失败: {}", err))?;
+    }
+    if is_empty {
+      let header = if format == DanmakuFormat::Xml {
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<i>\n".to_string()
+      } else {
+        build_ass_header()
+      };
+      file
+        .write_all(header.as_bytes())
+        .map_err(|err| format!("写入弹幕轨失败: {}", err))?;
+    }
+    let origin = Instant::now()
+      .checked_sub(Duration::from_secs(segment_elapsed_secs))
+      .unwrap_or_else(Instant::now);
+    Ok(Self {
+      format,
+      file,
+      origin,
+      lanes: (0..ASS_LANE_COUNT).map(|_| None).collect(),
+    })
+  }
+
+  fn write_comment(&mut self, text: &str, color: u32, uid: i64, timestamp_ms: i64) -> Result<(), String> {
+    let offset = self.origin.elapsed().as_secs_f64();
+    let line = match self.format {
+      DanmakuFormat::Xml => format!(
+        "  <d p=\"{:.3},1,25,{},{},0,{},0\">{}</d>\n",
+        offset,
+        color,
+        timestamp_ms / 1000,
+        uid,
+        escape_xml_text(text)
+      ),
+      DanmakuFormat::Ass => {
+        let width = estimate_danmaku_width(text);
+        let lane = self.pick_lane(offset, width);
+        let y = lane as f64 * ASS_LANE_HEIGHT;
+        let color_tag = if color == 0xFFFFFF {
+          String::new()
+        } else {
+          format!("\\c&H{:02X}{:02X}{:02X}&", color & 0xFF, (color >> 8) & 0xFF, (color >> 16) & 0xFF)
+        };
+        format!(
+          "Dialogue: 0,{},{},Danmaku,,0,0,0,,{{\\move({:.0},{:.0},{:.0},{:.0}){}}}{}\n",
+          format_ass_time(offset),
+          format_ass_time(offset + ASS_SCROLL_SECONDS),
+          ASS_PLAY_RES_X,
+          y,
+          -width,
+          y,
+          color_tag,
+          escape_ass_text(text)
+        )
+      }
+      DanmakuFormat::Json => return Ok(()),
+    };
+    self
+      .file
+      .write_all(line.as_bytes())
+      .map_err(|err| format!("写入弹幕轨失败: {}", err))
+  }
+
+  // 新弹幕需等前一条尾部完全进入屏幕，且在前一条离开左边界前追不上它；
+  // 所有轨道都被占用时退回到最早空出的轨道。
+  fn pick_lane(&mut self, start: f64, width: f64) -> usize {
+    let speed = (ASS_PLAY_RES_X + width) / ASS_SCROLL_SECONDS;
+    let mut chosen = None;
+    let mut fallback = 0;
+    let mut fallback_free_at = f64::MAX;
+    for (index, lane) in self.lanes.iter().enumerate() {
+      let Some(prev) = lane else {
+        chosen = Some(index);
+        break;
+      };
+      let prev_speed = (ASS_PLAY_RES_X + prev.width) / ASS_SCROLL_SECONDS;
+      let tail_entered_at = prev.start + prev.width / prev_speed;
+      let catch_up_ok = start + ASS_PLAY_RES_X / speed >= prev.start + ASS_SCROLL_SECONDS;
+      if start >= tail_entered_at && catch_up_ok {
+        chosen = Some(index);
+        break;
+      }
+      let free_at = tail_entered_at.max(prev.start + ASS_SCROLL_SECONDS - ASS_PLAY_RES_X / speed);
+      if free_at < fallback_free_at {
+        fallback = index;
+        fallback_free_at = free_at;
+      }
+    }
+    let lane = chosen.unwrap_or(fallback);
+    self.lanes[lane] = Some(DanmakuLane { start, width });
+    lane
+  }
+
+  fn finish(&mut self) {
+    if self.format == DanmakuFormat::Xml {
+      let _ = self.file.write_all(b"</i>\n");
+    }
+  }
+}
+
+impl Drop for DanmakuTrack {
+  fn drop(&mut self) {
+    self.finish();
+  }
+}
+
+const XML_DANMAKU_FOOTER: &[u8] = b"</i>";
+
+fn strip_xml_footer(file: &mut File) -> std::io::Result<()> {
+  let len = file.metadata()?.len();
+  let tail_len = len.min(16);
+  let mut tail = vec![0u8; tail_len as usize];
+  file.seek(SeekFrom::Start(len - tail_len))?;
+  file.read_exact(&mut tail)?;
+  let trimmed = tail
+    .iter()
+    .rposition(|byte| !byte.is_ascii_whitespace())
+    .map(|index| &tail[..=index])
+    .unwrap_or(&[]);
+  if trimmed.ends_with(XML_DANMAKU_FOOTER) {
+    let footer_start = len - tail_len + (trimmed.len() - XML_DANMAKU_FOOTER.len()) as u64;
+    file.set_len(footer_start)?;
+  }
+  Ok(())
+}
+
+fn build_ass_header() -> String {
+  format!(
+    "[Script Info]\nScriptType: v4.00+\nPlayResX: {}\nPlayResY: {}\nWrapStyle: 2\nScaledBorderAndShadow: yes\n\n\
+     [V4+ Styles]\nFormat: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n\
+     Style: Danmaku,Microsoft YaHei,{},&H00FFFFFF,&H00FFFFFF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,1.5,0,7,0,0,0,1\n\n\
+     [Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n",
+    ASS_PLAY_RES_X, ASS_PLAY_RES_Y, ASS_FONT_SIZE
+  )
+}
+
+fn format_ass_time(seconds: f64) -> String {
+  let total_cs = (seconds.max(0.0) * 100.0).round() as u64;
+  let cs = total_cs % 100;
+  let total_secs = total_cs / 100;
+  format!(
+    "{}:{:02}:{:02}.{:02}",
+    total_secs / 3600,
+    (total_secs / 60) % 60,
+    total_secs % 60,
+    cs
+  )
+}
+
+fn estimate_danmaku_width(text: &str) -> f64 {
+  text
+    .chars()
+    .map(|ch| if ch.is_ascii() { ASS_FONT_SIZE * 0.55 } else { ASS_FONT_SIZE })
+    .sum()
+}
+
+fn escape_ass_text(text: &str) -> String {
+  text
+    .replace('\\', "＼")
+    .replace('{', "｛")
+    .replace('}', "｝")
+    .replace(['\r', '\n'], " ")
+}
+
+fn escape_xml_text(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace(['\r', '\n'], " ")
+}
+
+/// 弹幕屏蔽：关键词不区分大小写子串匹配，另按每秒条数限流丢弃刷屏。
+struct DanmakuFilter {
+  keywords: Vec<String>,
+  max_per_second: u32,
+  window_started_at: Instant,
+  window_count: u32,
+  dropped_keyword: u64,
+  dropped_rate: u64,
+  last_report_at: Instant,
+  log_path: PathBuf,
+  room_id: String,
+}
+
+impl DanmakuFilter {
+  fn new(settings: &LiveSettings, log_path: PathBuf, room_id: String) -> Self {
+    Self {
+      keywords: settings
+        .danmaku_filter_keywords
+        .iter()
+        .map(|keyword| keyword.trim().to_lowercase())
+        .filter(|keyword| !keyword.is_empty())
+        .collect(),
+      max_per_second: settings.danmaku_max_per_second.clamp(0, u32::MAX as i64) as u32,
+      window_started_at: Instant::now(),
+      window_count: 0,
+      dropped_keyword: 0,
+      dropped_rate: 0,
+      last_report_at: Instant::now(),
+      log_path,
+      room_id,
+    }
+  }
+
+  fn accept(&mut self, value: &Value) -> bool {
+    self.report_if_due();
+    if !self.keywords.is_empty() {
+      let text = value
+        .get("info")
+        .and_then(|info| info.get(1))
+        .and_then(|text| text.as_str())
+        .unwrap_or("")
+        .to_lowercase();
+      if self.keywords.iter().any(|keyword| text.contains(keyword.as_str())) {
+        self.dropped_keyword += 1;
+        return false;
+      }
+    }
+    if self.max_per_second > 0 {
+      if self.window_started_at.elapsed() >= Duration::from_secs(1) {
+        self.window_started_at = Instant::now();
+        self.window_count = 0;
+      }
+      if self.window_count >= self.max_per_second {
+        self.dropped_rate += 1;
+        return false;
+      }
+      self.window_count += 1;
+    }
+    true
+  }
+
+  fn report_if_due(&mut self) {
+    if self.last_report_at.elapsed() < Duration::from_secs(DANMAKU_FILTER_REPORT_SECS) {
+      return;
+    }
+    if self.dropped_keyword > 0 || self.dropped_rate > 0 {
+      append_log(
+        &self.log_path,
+        &format!(
+          "danmaku_filtered room={} keyword={} rate={} window_secs={}",
+          self.room_id, self.dropped_keyword, self.dropped_rate, DANMAKU_FILTER_REPORT_SECS
+        ),
+      );
+    }
+    self.dropped_keyword = 0;
+    self.dropped_rate = 0;
+    self.last_report_at = Instant::now();
+  }
+}
+
 struct DanmakuWriter {
   live_runtime: Arc<LiveRuntime>,
   runtime_room_id: String,
   fallback_path: String,
   current_path: Option<String>,
   file: Option<File>,
+  format: DanmakuFormat,
+  track: Option<DanmakuTrack>,
+  filter: DanmakuFilter,
 }
 
 impl DanmakuWriter {
-  fn new(live_runtime: Arc<LiveRuntime>, runtime_room_id: String, fallback_path: String) -> Self {
+  fn new(
+    live_runtime: Arc<LiveRuntime>,
+    runtime_room_id: String,
+    fallback_path: String,
+    format: DanmakuFormat,
+    filter: DanmakuFilter,
+  ) -> Self {
     Self {
       live_runtime,
       runtime_room_id,
       fallback_path,
       current_path: None,
       file: None,
+      format,
+      track: None,
+      filter,
     }
   }
 
@@ -2304,6 +4313,16 @@ impl DanmakuWriter {
         Ok(file) => {
           self.current_path = Some(target_path);
           self.file = Some(file);
+          self.track = None;
+          if self.format != DanmakuFormat::Json {
+            let segment_elapsed = self
+              .live_runtime
+              .get_record_status(&self.runtime_room_id)
+              .map(|status| status.segment_elapsed_seconds)
+              .unwrap_or(0);
+            // 弹幕轨仅为附加产物，创建失败时不影响 jsonl 记录。
+            self.track = DanmakuTrack::open(self.format, &candidate, segment_elapsed).ok();
+          }
           return Ok(());
         }
         Err(err) => {
@@ -2320,6 +4339,36 @@ impl DanmakuWriter {
     writeln!(file, "{}", line).map_err(|err| format!("写入弹幕失败: {}", err))?;
     Ok(())
   }
+
+  fn write_comment(&mut self, value: &Value) -> Result<(), String> {
+    self.ensure_file()?;
+    let Some(track) = self.track.as_mut() else {
+      return Ok(());
+    };
+    let info = value.get("info").and_then(|value| value.as_array());
+    let Some(info) = info else {
+      return Ok(());
+    };
+    let text = info.get(1).and_then(|value| value.as_str()).unwrap_or("");
+    if text.trim().is_empty() {
+      return Ok(());
+    }
+    let meta = info.first().and_then(|value| value.as_array());
+    let color = meta
+      .and_then(|meta| meta.get(3))
+      .and_then(|value| value.as_u64())
+      .unwrap_or(0xFFFFFF) as u32;
+    let timestamp_ms = meta
+      .and_then(|meta| meta.get(4))
+      .and_then(|value| value.as_i64())
+      .unwrap_or_else(|| Utc::now().timestamp_millis());
+    let uid = info
+      .get(2)
+      .and_then(|value| value.get(0))
+      .and_then(|value| value.as_i64())
+      .unwrap_or(0);
+    track.write_comment(text, color, uid, timestamp_ms)
+  }
 }
 
 async fn run_danmaku_loop(
@@ -2338,6 +4387,12 @@ async fn run_danmaku_loop(
     Arc::clone(&context.live_runtime),
     runtime_room_id.clone(),
     record_file,
+    DanmakuFormat::from_setting(&settings.danmaku_format),
+    DanmakuFilter::new(
+      &settings,
+      context.app_log_path.as_ref().clone(),
+      runtime_room_id.clone(),
+    ),
   )));
   {
     let mut writer_guard = writer.lock().map_err(|_| "弹幕文件锁定失败")?;
@@ -2580,13 +4635,19 @@ fn handle_danmaku_payload(
         }
       };
       if should_write {
+        let mut writer = output.lock().map_err(|_| "弹幕文件锁定失败")?;
+        if cmd == "DANMU_MSG" && !writer.filter.accept(&value) {
+          continue;
+        }
         let line = serde_json::json!({
           "cmd": cmd,
           "data": value,
           "timestamp": now_rfc3339(),
         });
-        let mut writer = output.lock().map_err(|_| "弹幕文件锁定失败")?;
         writer.write_line(&line.to_string())?;
+        if cmd == "DANMU_MSG" {
+          writer.write_comment(&value)?;
+        }
       }
     } else if settings.record_danmaku_raw {
       let mut writer = output.lock().map_err(|_| "弹幕文件锁定失败")?;
@@ -2690,3 +4751,82 @@ fn build_danmaku_packet(op: u32, body: Vec<u8>) -> Vec<u8> {
   buf.extend_from_slice(&body);
   buf
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn timestamp_rebaser_realigns_backward_jump_after_reconnect() {
+    let mut rebaser = FlvTimestampRebaser::default();
+    assert_eq!(rebaser.rebase(9, 5000), FlvTimestampOutcome::Unchanged);
+    assert_eq!(
+      rebaser.rebase(9, 0),
+      FlvTimestampOutcome::Rebased {
+        output: 5040,
+        delta_ms: -5000
+      }
+    );
+    assert_eq!(rebaser.rebase(9, 40), FlvTimestampOutcome::Adjusted(5080));
+  }
+
+  #[test]
+  fn timestamp_rebaser_realigns_forward_jump() {
+    let mut rebaser = FlvTimestampRebaser::default();
+    assert_eq!(rebaser.rebase(9, 1000), FlvTimestampOutcome::Unchanged);
+    assert_eq!(rebaser.rebase(9, 1040), FlvTimestampOutcome::Unchanged);
+    assert_eq!(
+      rebaser.rebase(9, 20_000),
+      FlvTimestampOutcome::Rebased {
+        output: 1080,
+        delta_ms: 18_960
+      }
+    );
+    assert_eq!(rebaser.rebase(9, 20_040), FlvTimestampOutcome::Adjusted(1120));
+  }
+
+  #[test]
+  fn timestamp_rebaser_keeps_interleaved_tracks_monotonic() {
+    let mut rebaser = FlvTimestampRebaser::default();
+    assert_eq!(rebaser.rebase(9, 100), FlvTimestampOutcome::Unchanged);
+    // 容差内的音频略早于视频属于正常交错，不做修正
+    assert_eq!(rebaser.rebase(8, 90), FlvTimestampOutcome::Unchanged);
+    assert_eq!(rebaser.rebase(9, 140), FlvTimestampOutcome::Unchanged);
+    assert_eq!(rebaser.rebase(8, 80), FlvTimestampOutcome::Adjusted(90));
+    assert_eq!(rebaser.rebase(18, 0), FlvTimestampOutcome::Adjusted(140));
+    assert_eq!(rebaser.rebase(8, 130), FlvTimestampOutcome::Unchanged);
+  }
+
+  fn segment_row(id: i64, start_time: &str, end_time: Option<&str>) -> RecordSegmentRow {
+    RecordSegmentRow {
+      id,
+      room_id: "1".to_string(),
+      status: "COMPLETED".to_string(),
+      file_path: format!("/tmp/segment_{}.flv", id),
+      start_time: start_time.to_string(),
+      end_time: end_time.map(|value| value.to_string()),
+      title: None,
+    }
+  }
+
+  #[test]
+  fn record_segments_reject_gaps_and_missing_end_time() {
+    let contiguous = [
+      segment_row(1, "2026-01-01T10:00:00Z", Some("2026-01-01T10:30:00Z")),
+      segment_row(2, "2026-01-01T10:30:10Z", Some("2026-01-01T11:00:00Z")),
+    ];
+    assert!(validate_record_segments(&contiguous).is_ok());
+
+    let gap = [
+      segment_row(1, "2026-01-01T10:00:00Z", Some("2026-01-01T10:30:00Z")),
+      segment_row(2, "2026-01-01T10:45:00Z", Some("2026-01-01T11:00:00Z")),
+    ];
+    assert!(validate_record_segments(&gap).unwrap_err().contains("缺口"));
+
+    let missing_end = [
+      segment_row(1, "2026-01-01T10:00:00Z", None),
+      segment_row(2, "2026-01-01T10:30:00Z", Some("2026-01-01T11:00:00Z")),
+    ];
+    assert!(validate_record_segments(&missing_end).unwrap_err().contains("结束时间"));
+  }
+}
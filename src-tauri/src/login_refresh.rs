@@ -15,7 +15,7 @@ use tokio::sync::Mutex;
 use crate::bilibili::client::BilibiliClient;
 use crate::db::Db;
 use crate::login_store::{extract_cookie, extract_csrf, AuthInfo, LoginStore};
-use crate::utils::append_log;
+use crate::utils::{append_log, jittered_duration};
 
 const PUBLIC_KEY_PEM: &str = "-----BEGIN PUBLIC KEY-----\n\
 MIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQDLgd2OAkcGVtoE3ThUREbio0Eg\n\
@@ -65,9 +65,9 @@ pub fn start_cookie_refresh_loop(
           &format!("cookie_refresh_loop_fail err={}", err),
         );
       }
-      tokio::time::sleep(std::time::Duration::from_secs(
+      tokio::time::sleep(jittered_duration(std::time::Duration::from_secs(
         (DEFAULT_COOKIE_REFRESH_MINUTES.max(1) as u64) * 60,
-      ))
+      )))
       .await;
     }
   });
@@ -93,7 +93,7 @@ pub async fn refresh_cookie(
     .filter(|token| !token.trim().is_empty())
     .ok_or_else(|| "登录信息缺少refresh_token".to_string())?;
 
-  let client = Client::new();
+  let client = crate::config::build_http_client();
   let info = fetch_cookie_refresh_info(&client, bilibili, &cookie, &csrf).await?;
   append_log(
     log_path,
@@ -151,7 +151,7 @@ pub async fn refresh_cookie_if_needed(
     .filter(|token| !token.trim().is_empty())
     .ok_or_else(|| "登录信息缺少refresh_token".to_string())?;
 
-  let client = Client::new();
+  let client = crate::config::build_http_client();
   let expired = load_login_expire_time(db)?
     .map(|expire_time| expire_time <= Utc::now())
     .unwrap_or(false);
@@ -203,7 +203,7 @@ pub async fn refresh_cookie_if_needed(
   Ok(true)
 }
 
-fn load_login_expire_time(db: &Db) -> Result<Option<DateTime<Utc>>, String> {
+pub(crate) fn load_login_expire_time(db: &Db) -> Result<Option<DateTime<Utc>>, String> {
   db.with_conn(|conn| {
     let mut stmt = conn.prepare(
       "SELECT expire_time FROM login_info ORDER BY login_time DESC LIMIT 1",
@@ -226,9 +226,8 @@ fn build_headers(cookie: Option<&str>) -> Result<HeaderMap, String> {
   let mut headers = HeaderMap::new();
   headers.insert(
     USER_AGENT,
-    HeaderValue::from_static(
-      "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/132.0.0.0 Safari/537.36 Edg/132.0.0.0",
-    ),
+    HeaderValue::from_str(&crate::config::user_agent_or(crate::config::DEFAULT_USER_AGENT))
+      .map_err(|_| "无效的User-Agent".to_string())?,
   );
   headers.insert(ACCEPT, HeaderValue::from_static("application/json, text/javascript, */*; q=0.01"));
   headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("zh-CN"));
@@ -415,7 +414,11 @@ async fn fetch_refresh_csrf(
   );
   headers.insert("Accept-Encoding", HeaderValue::from_static("identity"));
   headers.insert("Origin", HeaderValue::from_static("https://www.bilibili.com"));
-  headers.insert("Referer", HeaderValue::from_static("https://www.bilibili.com/"));
+  headers.insert(
+    "Referer",
+    HeaderValue::from_str(&crate::config::web_referer_or("https://www.bilibili.com/"))
+      .map_err(|_| "无效的Referer".to_string())?,
+  );
   let response = client
     .get(&url)
     .headers(headers)
@@ -1,14 +1,33 @@
+use std::collections::HashMap;
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 
 use crate::config::resolve_ffprobe_path;
-use crate::ffmpeg::{run_ffmpeg, run_ffprobe_json};
+use crate::ffmpeg::{
+  active_hwaccel, default_h264_encoder, hwaccel_h264_encoder, run_ffmpeg, run_ffmpeg_cancellable,
+  run_ffprobe_json, SOFTWARE_H264_ENCODER,
+};
 
 const START_DIFF_THRESHOLD_SECONDS: f64 = 1.0;
 const TIMESTAMP_GAP_THRESHOLD_SECONDS: f64 = 2.0;
 const NEGATIVE_JUMP_THRESHOLD_SECONDS: f64 = -0.5;
+const MEDIA_PROBE_CACHE_TTL: Duration = Duration::from_secs(60);
+const FORBIDDEN_ENCODER_OPTIONS: [&str; 6] = [
+  "-i",
+  "-f",
+  "-y",
+  "-n",
+  "-progress",
+  "-filter_complex_script",
+];
 
 #[derive(Clone)]
 pub struct ClipSource {
@@ -23,28 +42,192 @@ pub struct ClipCopyDecision {
   pub reason: Option<String>,
 }
 
-pub fn clip_sources(
+#[derive(Clone, Copy, Debug)]
+pub struct ClipNormalizeTarget {
+  pub width: i64,
+  pub height: i64,
+  pub fps: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClipNormalizeConfig {
+  pub width: Option<i64>,
+  pub height: Option<i64>,
+  pub fps: Option<f64>,
+}
+
+/// 合并与分段阶段的输出封装格式，默认 mp4。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputContainer {
+  #[default]
+  Mp4,
+  Mkv,
+  Flv,
+}
+
+impl OutputContainer {
+  pub fn parse(value: &str) -> Option<Self> {
+    match value.trim().to_lowercase().as_str() {
+      "mp4" => Some(OutputContainer::Mp4),
+      "mkv" | "matroska" => Some(OutputContainer::Mkv),
+      "flv" => Some(OutputContainer::Flv),
+      _ => None,
+    }
+  }
+
+  pub fn from_path(path: &Path) -> Option<Self> {
+    path
+      .extension()
+      .and_then(|ext| ext.to_str())
+      .and_then(OutputContainer::parse)
+  }
+
+  pub fn extension(self) -> &'static str {
+    match self {
+      OutputContainer::Mp4 => "mp4",
+      OutputContainer::Mkv => "mkv",
+      OutputContainer::Flv => "flv",
+    }
+  }
+
+  pub fn muxer(self) -> &'static str {
+    match self {
+      OutputContainer::Mp4 => "mp4",
+      OutputContainer::Mkv => "matroska",
+      OutputContainer::Flv => "flv",
+    }
+  }
+}
+
+/// 检查源文件的音视频编码能否直接封装进目标格式，不兼容时返回提示文本。
+pub fn container_stream_warning(
+  container: OutputContainer,
+  path: &Path,
+) -> Result<Option<String>, String> {
+  let info = probe_media_info(path)?;
+  let video_codec = info.video.codec_name.to_lowercase();
+  let audio_codec = info
+    .audio
+    .as_ref()
+    .map(|audio| audio.codec_name.to_lowercase());
+  let warning = match container {
+    OutputContainer::Mp4 => match audio_codec.as_deref() {
+      Some(codec) if codec.starts_with("pcm_") => {
+        Some(format!("mp4 不支持 {} 音频，建议改用 mkv", codec))
+      }
+      _ => None,
+    },
+    OutputContainer::Flv => {
+      if video_codec != "h264" {
+        Some(format!("flv 不支持 {} 视频，建议改用 mkv", video_codec))
+      } else {
+        match audio_codec.as_deref() {
+          Some(codec) if codec != "aac" && codec != "mp3" => {
+            Some(format!("flv 不支持 {} 音频，建议改用 mkv", codec))
+          }
+          _ => None,
+        }
+      }
+    }
+    OutputContainer::Mkv => None,
+  };
+  Ok(warning)
+}
+
+/// 按 `concurrency` 并行剪辑各来源，输出顺序与 `sources` 一致。
+/// 任一片段失败后会终止其余正在运行的 FFmpeg，并返回最先出现的错误。
+pub fn clip_sources<F>(
   sources: &[ClipSource],
   output_dir: &Path,
   use_copy: bool,
-) -> Result<Vec<PathBuf>, String> {
+  encoder_args: Option<&[String]>,
+  normalize: Option<&ClipNormalizeTarget>,
+  concurrency: usize,
+  on_command: F,
+) -> Result<Vec<PathBuf>, String>
+where
+  F: FnMut(&str) + Send,
+{
   fs::create_dir_all(output_dir).map_err(|err| format!("Failed to create output dir: {}", err))?;
 
-  let mut outputs = Vec::new();
-  for source in sources {
-    let output_path = output_dir.join(format!("clip_{:03}.mp4", source.order));
-    clip_single(source, &output_path, use_copy)?;
-    outputs.push(output_path);
+  let outputs: Vec<PathBuf> = sources
+    .iter()
+    .map(|source| output_dir.join(format!("clip_{:03}.mp4", source.order)))
+    .collect();
+  let worker_count = concurrency.clamp(1, sources.len().max(1));
+  let next_index = AtomicUsize::new(0);
+  let cancel = AtomicBool::new(false);
+  let first_error: Mutex<Option<String>> = Mutex::new(None);
+  let on_command = Mutex::new(on_command);
+
+  std::thread::scope(|scope| {
+    for _ in 0..worker_count {
+      scope.spawn(|| loop {
+        if cancel.load(Ordering::SeqCst) {
+          break;
+        }
+        let index = next_index.fetch_add(1, Ordering::SeqCst);
+        let Some(source) = sources.get(index) else {
+          break;
+        };
+        let mut log_command = |line: &str| {
+          if let Ok(mut callback) = on_command.lock() {
+            callback(line);
+          }
+        };
+        let result = clip_single(
+          source,
+          &outputs[index],
+          use_copy,
+          encoder_args,
+          normalize,
+          &cancel,
+          &mut log_command,
+        );
+        if let Err(err) = result {
+          if let Ok(mut slot) = first_error.lock() {
+            if slot.is_none() && !cancel.load(Ordering::SeqCst) {
+              *slot = Some(err);
+            }
+          }
+          cancel.store(true, Ordering::SeqCst);
+          break;
+        }
+      });
+    }
+  });
+
+  if let Some(err) = first_error.into_inner().ok().flatten() {
+    return Err(err);
   }
 
   Ok(outputs)
 }
 
-pub fn merge_files(files: &[PathBuf], output_path: &Path) -> Result<(), String> {
+/// 合并文件。未指定编码参数且输入流规格不一致时改用 concat 滤镜重编码，
+/// 返回值为回退原因，直接 concat copy 时为 `None`。`container` 为空时由输出扩展名推断封装格式。
+pub fn merge_files<F>(
+  files: &[PathBuf],
+  output_path: &Path,
+  container: Option<OutputContainer>,
+  encoder_args: Option<&[String]>,
+  mut on_command: F,
+) -> Result<Option<String>, String>
+where
+  F: FnMut(&str),
+{
   if let Some(parent) = output_path.parent() {
     fs::create_dir_all(parent).map_err(|err| format!("Failed to create output dir: {}", err))?;
   }
 
+  if encoder_args.is_none() && files.len() > 1 {
+    if let Ok(Some(reason)) = concat_copy_mismatch(files) {
+      let args = build_reencode_concat_args(files, output_path, container)?;
+      run_reencode_with_hwaccel(&args, output_path, None, &mut on_command)?;
+      return Ok(Some(reason));
+    }
+  }
+
   let list_path = output_path.with_extension("txt");
   let list_content = files
     .iter()
@@ -63,35 +246,248 @@ pub fn merge_files(files: &[PathBuf], output_path: &Path) -> Result<(), String>
     list_path.to_string_lossy().to_string(),
   ];
 
-  args.push("-c".to_string());
-  args.push("copy".to_string());
+  match encoder_args {
+    Some(encoder_args) => args.extend(encoder_args.iter().cloned()),
+    None => {
+      args.push("-c".to_string());
+      args.push("copy".to_string());
+    }
+  }
 
+  push_muxer_args(&mut args, container);
   args.push(output_path.to_string_lossy().to_string());
 
-  run_ffmpeg(&args)?;
+  if encoder_args.is_some() {
+    run_reencode_with_hwaccel(&args, output_path, None, &mut on_command)?;
+  } else {
+    on_command(&args.join(" "));
+    run_ffmpeg(&args)?;
+  }
   let _ = fs::remove_file(list_path);
+  Ok(None)
+}
+
+/// 重编码时在每个 `-i` 前插入 `-hwaccel`，加速解码失败（非取消）后回退软件解码重跑。
+/// 实际使用的命令都会经 `on_command` 输出，便于从日志确认是否走了硬件解码。
+fn run_reencode_with_hwaccel<F>(
+  args: &[String],
+  output_path: &Path,
+  cancel: Option<&AtomicBool>,
+  on_command: &mut F,
+) -> Result<(), String>
+where
+  F: FnMut(&str),
+{
+  run_with_hwaccel_fallback(
+    args,
+    output_path,
+    active_hwaccel(),
+    || cancel.is_some_and(|cancel| cancel.load(Ordering::SeqCst)),
+    |args| match cancel {
+      Some(cancel) => run_ffmpeg_cancellable(args, cancel),
+      None => run_ffmpeg(args),
+    },
+    on_command,
+  )
+}
+
+/// 回退前删除加速尝试可能留下的半成品输出，否则未带 `-y` 的命令会因输出已存在而失败；
+/// 同时把对应的硬件编码器换成 libx264，避免编码器本身不可用时回退仍然失败。
+fn run_with_hwaccel_fallback<C, R, F>(
+  args: &[String],
+  output_path: &Path,
+  method: Option<&str>,
+  is_cancelled: C,
+  mut run: R,
+  on_command: &mut F,
+) -> Result<(), String>
+where
+  C: Fn() -> bool,
+  R: FnMut(&[String]) -> Result<(), String>,
+  F: FnMut(&str),
+{
+  let mut software_args = args.to_vec();
+  if let Some(method) = method {
+    if let Some(encoder) = hwaccel_h264_encoder(method) {
+      software_args = replace_video_encoder(args, encoder, SOFTWARE_H264_ENCODER);
+    }
+    let accelerated = insert_hwaccel_args(args, method);
+    on_command(&accelerated.join(" "));
+    match run(&accelerated) {
+      Ok(()) => return Ok(()),
+      Err(err) => {
+        if is_cancelled() {
+          return Err(err);
+        }
+        on_command(&format!(
+          "hwaccel={} fallback=software err={}",
+          method,
+          err.lines().last().unwrap_or("")
+        ));
+        let _ = fs::remove_file(output_path);
+      }
+    }
+  }
+  on_command(&software_args.join(" "));
+  run(&software_args)
+}
+
+fn replace_video_encoder(args: &[String], from: &str, to: &str) -> Vec<String> {
+  let mut output = args.to_vec();
+  let mut after_codec_flag = false;
+  for arg in output.iter_mut() {
+    if after_codec_flag && arg == from {
+      *arg = to.to_string();
+    }
+    after_codec_flag = arg == "-c:v";
+  }
+  output
+}
+
+/// `-hwaccel` 属于输入选项，插在每个 `-i` 之前即可（包括 concat 列表输入）。
+fn insert_hwaccel_args(args: &[String], method: &str) -> Vec<String> {
+  let mut output = Vec::with_capacity(args.len() + 2);
+  for arg in args {
+    if arg == "-i" {
+      output.push("-hwaccel".to_string());
+      output.push(method.to_string());
+    }
+    output.push(arg.clone());
+  }
+  output
+}
+
+/// 以第一个输入的分辨率和帧率为准统一规格后用 concat 滤镜拼接，缺少音轨的输入补静音。
+fn build_reencode_concat_args(
+  files: &[PathBuf],
+  output_path: &Path,
+  container: Option<OutputContainer>,
+) -> Result<Vec<String>, String> {
+  let infos = files
+    .iter()
+    .map(|path| probe_media_info(path))
+    .collect::<Result<Vec<_>, _>>()?;
+  let base = &infos[0].video;
+  let target = ClipNormalizeTarget {
+    width: if base.width > 0 { base.width } else { 1920 },
+    height: if base.height > 0 { base.height } else { 1080 },
+    fps: if base.fps > 0.0 { base.fps } else { 60.0 },
+  };
+  let video_filter = build_normalize_filter(&target);
+  let mut args = Vec::new();
+  for path in files {
+    args.push("-i".to_string());
+    args.push(path.to_string_lossy().to_string());
+  }
+  let mut filters = Vec::new();
+  let mut concat_inputs = String::new();
+  for (index, info) in infos.iter().enumerate() {
+    filters.push(format!("[{}:v:0]{},format=yuv420p[v{}]", index, video_filter, index));
+    if info.audio.is_some() {
+      filters.push(format!(
+        "[{}:a:0]aresample=48000:async=1:first_pts=0,aformat=sample_rates=48000:channel_layouts=stereo[a{}]",
+        index, index
+      ));
+    } else {
+      filters.push(format!(
+        "anullsrc=r=48000:cl=stereo,atrim=duration={:.3}[a{}]",
+        info.duration_seconds.max(0.0),
+        index
+      ));
+    }
+    concat_inputs.push_str(&format!("[v{}][a{}]", index, index));
+  }
+  filters.push(format!(
+    "{}concat=n={}:v=1:a=1[vout][aout]",
+    concat_inputs,
+    files.len()
+  ));
+  args.extend([
+    "-filter_complex".to_string(),
+    filters.join(";"),
+    "-map".to_string(),
+    "[vout]".to_string(),
+    "-map".to_string(),
+    "[aout]".to_string(),
+    "-c:v".to_string(),
+    default_h264_encoder().to_string(),
+    "-b:v".to_string(),
+    "5M".to_string(),
+    "-c:a".to_string(),
+    "aac".to_string(),
+    "-ar".to_string(),
+    "48000".to_string(),
+  ]);
+  push_muxer_args(&mut args, container);
+  args.push(output_path.to_string_lossy().to_string());
+  Ok(args)
+}
+
+fn push_muxer_args(args: &mut Vec<String>, container: Option<OutputContainer>) {
+  if let Some(container) = container {
+    args.push("-f".to_string());
+    args.push(container.muxer().to_string());
+  }
+}
+
+pub fn validate_encoder_args(args: &[String]) -> Result<(), String> {
+  if args.is_empty() {
+    return Err("编码参数不能为空".to_string());
+  }
+  for arg in args {
+    let value = arg.trim();
+    if value.is_empty() {
+      return Err("编码参数包含空值".to_string());
+    }
+    if FORBIDDEN_ENCODER_OPTIONS.contains(&value) {
+      return Err(format!("编码参数不允许包含 {}", value));
+    }
+    if value.contains(['<', '>', '|']) || value.starts_with("pipe:") || value.starts_with("file:") {
+      return Err(format!("编码参数不允许包含输入输出重定向: {}", value));
+    }
+  }
   Ok(())
 }
 
-struct VideoProbeInfo {
-  codec_name: String,
-  width: i64,
-  height: i64,
-  fps: f64,
-  time_base: String,
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VideoProbeInfo {
+  pub codec_name: String,
+  pub width: i64,
+  pub height: i64,
+  pub fps: f64,
+  pub time_base: String,
+  pub bit_rate: i64,
 }
 
-struct AudioProbeInfo {
-  codec_name: String,
-  sample_rate: i64,
-  channels: i64,
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioProbeInfo {
+  pub codec_name: String,
+  pub sample_rate: i64,
+  pub channels: i64,
+  pub bit_rate: i64,
 }
 
-struct MediaProbeInfo {
-  video: VideoProbeInfo,
-  audio: Option<AudioProbeInfo>,
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaProbeInfo {
+  pub container: String,
+  pub duration_seconds: f64,
+  pub bit_rate: i64,
+  pub video: VideoProbeInfo,
+  pub audio: Option<AudioProbeInfo>,
 }
 
+struct MediaProbeCacheEntry {
+  modified: Option<SystemTime>,
+  size: u64,
+  cached_at: Instant,
+  info: MediaProbeInfo,
+}
+
+static MEDIA_PROBE_CACHE: OnceLock<Mutex<HashMap<PathBuf, MediaProbeCacheEntry>>> = OnceLock::new();
+
 fn parse_fraction(value: &str) -> Option<f64> {
   let trimmed = value.trim();
   if trimmed.is_empty() {
@@ -108,11 +504,51 @@ fn parse_fraction(value: &str) -> Option<f64> {
   trimmed.parse::<f64>().ok()
 }
 
-fn probe_media_info(path: &Path) -> Result<MediaProbeInfo, String> {
+/// 按路径 + 修改时间 + 文件大小缓存探测结果，同一文件短时间内不重复调用 ffprobe。
+pub fn probe_media_info(path: &Path) -> Result<MediaProbeInfo, String> {
+  let metadata = fs::metadata(path).map_err(|err| format!("读取文件信息失败: {}", err))?;
+  let modified = metadata.modified().ok();
+  let size = metadata.len();
+  let cache = MEDIA_PROBE_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+  if let Ok(guard) = cache.lock() {
+    if let Some(entry) = guard.get(path) {
+      if entry.modified == modified
+        && entry.size == size
+        && entry.cached_at.elapsed() < MEDIA_PROBE_CACHE_TTL
+      {
+        return Ok(entry.info.clone());
+      }
+    }
+  }
+  let info = run_media_probe(path)?;
+  if let Ok(mut guard) = cache.lock() {
+    guard.retain(|_, entry| entry.cached_at.elapsed() < MEDIA_PROBE_CACHE_TTL);
+    guard.insert(
+      path.to_path_buf(),
+      MediaProbeCacheEntry {
+        modified,
+        size,
+        cached_at: Instant::now(),
+        info: info.clone(),
+      },
+    );
+  }
+  Ok(info)
+}
+
+fn parse_bit_rate(value: Option<&serde_json::Value>) -> i64 {
+  value
+    .and_then(|value| value.as_str())
+    .and_then(|value| value.parse::<i64>().ok())
+    .unwrap_or(0)
+}
+
+fn run_media_probe(path: &Path) -> Result<MediaProbeInfo, String> {
   let args = vec![
     "-v".to_string(),
     "error".to_string(),
     "-show_streams".to_string(),
+    "-show_format".to_string(),
     "-of".to_string(),
     "json".to_string(),
     path.to_string_lossy().to_string(),
@@ -163,6 +599,7 @@ fn probe_media_info(path: &Path) -> Result<MediaProbeInfo, String> {
         height,
         fps,
         time_base,
+        bit_rate: parse_bit_rate(stream.get("bit_rate")),
       });
     }
     if codec_type == "audio" && audio.is_none() {
@@ -184,22 +621,51 @@ fn probe_media_info(path: &Path) -> Result<MediaProbeInfo, String> {
         codec_name,
         sample_rate,
         channels,
+        bit_rate: parse_bit_rate(stream.get("bit_rate")),
       });
     }
   }
 
   let video = video.ok_or_else(|| "缺少视频流".to_string())?;
-  Ok(MediaProbeInfo { video, audio })
+  let format = data.get("format");
+  let container = format
+    .and_then(|value| value.get("format_name"))
+    .and_then(|value| value.as_str())
+    .unwrap_or("")
+    .to_string();
+  let duration_seconds = format
+    .and_then(|value| value.get("duration"))
+    .and_then(|value| value.as_str())
+    .and_then(|value| value.parse::<f64>().ok())
+    .unwrap_or(0.0);
+  let bit_rate = parse_bit_rate(format.and_then(|value| value.get("bit_rate")));
+  Ok(MediaProbeInfo {
+    container,
+    duration_seconds,
+    bit_rate,
+    video,
+    audio,
+  })
 }
 
 fn can_concat_copy(files: &[PathBuf]) -> Result<bool, String> {
   if files.is_empty() {
     return Ok(false);
   }
-  let base = probe_media_info(&files[0])?;
+  Ok(concat_copy_mismatch(files)?.is_none())
+}
+
+/// 比较各输入的编码、分辨率、时间基、帧率和音频参数，返回第一处不一致的原因。
+fn concat_copy_mismatch(files: &[PathBuf]) -> Result<Option<String>, String> {
+  let Some(first) = files.first() else {
+    return Ok(None);
+  };
+  let base = probe_media_info(first)?;
   let base_audio = match base.audio {
     Some(audio) => audio,
-    None => return Ok(false),
+    None => {
+      return Ok(Some(format!("missing_audio input={}", first.to_string_lossy())));
+    }
   };
   if base.video.codec_name.is_empty()
     || base.video.width <= 0
@@ -210,38 +676,60 @@ fn can_concat_copy(files: &[PathBuf]) -> Result<bool, String> {
     || base_audio.sample_rate <= 0
     || base_audio.channels <= 0
   {
-    return Ok(false);
+    return Ok(Some(format!("incomplete_stream_info input={}", first.to_string_lossy())));
   }
 
   for path in files.iter().skip(1) {
     let current = probe_media_info(path)?;
+    let input = path.to_string_lossy();
     let current_audio = match current.audio {
       Some(audio) => audio,
-      None => return Ok(false),
+      None => return Ok(Some(format!("missing_audio input={}", input))),
     };
     if current.video.codec_name != base.video.codec_name {
-      return Ok(false);
+      return Ok(Some(format!(
+        "video_codec {}!={} input={}",
+        current.video.codec_name, base.video.codec_name, input
+      )));
     }
     if current.video.width != base.video.width || current.video.height != base.video.height {
-      return Ok(false);
+      return Ok(Some(format!(
+        "resolution {}x{}!={}x{} input={}",
+        current.video.width, current.video.height, base.video.width, base.video.height, input
+      )));
     }
     if current.video.time_base != base.video.time_base {
-      return Ok(false);
+      return Ok(Some(format!(
+        "time_base {}!={} input={}",
+        current.video.time_base, base.video.time_base, input
+      )));
     }
     if (current.video.fps - base.video.fps).abs() > 0.01 {
-      return Ok(false);
+      return Ok(Some(format!(
+        "fps {:.3}!={:.3} input={}",
+        current.video.fps, base.video.fps, input
+      )));
     }
     if current_audio.codec_name != base_audio.codec_name {
-      return Ok(false);
+      return Ok(Some(format!(
+        "audio_codec {}!={} input={}",
+        current_audio.codec_name, base_audio.codec_name, input
+      )));
     }
     if current_audio.sample_rate != base_audio.sample_rate {
-      return Ok(false);
+      return Ok(Some(format!(
+        "sample_rate {}!={} input={}",
+        current_audio.sample_rate, base_audio.sample_rate, input
+      )));
     }
     if current_audio.channels != base_audio.channels {
-      return Ok(false);
+      return Ok(Some(format!(
+        "channels {}!={} input={}",
+        current_audio.channels, base_audio.channels, input
+      )));
     }
   }
-  Ok(true)
+  Ok(None)
 }
 
 pub fn can_concat_copy_sources(sources: &[ClipSource]) -> Result<bool, String> {
@@ -355,6 +843,70 @@ fn build_read_intervals(source: &ClipSource) -> Option<String> {
   }
 }
 
+/// 剪辑起止点：去掉空白后的原文与对应秒数，None 表示不限制。
+pub type ClipBound = Option<(String, f64)>;
+
+/// 校验一段可选的起止时间；未填写或为 `00:00:00` 时视为不限制。
+pub fn parse_clip_range(
+  start_time: Option<&str>,
+  end_time: Option<&str>,
+) -> Result<(ClipBound, ClipBound), String> {
+  let start = parse_clip_time(start_time, "开始时间")?;
+  let end = parse_clip_time(end_time, "结束时间")?;
+  if let (Some((_, start)), Some((_, end))) = (&start, &end) {
+    if end <= start {
+      return Err("结束时间需晚于开始时间".to_string());
+    }
+  }
+  Ok((start, end))
+}
+
+fn parse_clip_time(value: Option<&str>, label: &str) -> Result<ClipBound, String> {
+  let Some(value) = value.map(|value| value.trim()).filter(|value| !value.is_empty()) else {
+    return Ok(None);
+  };
+  if value == "00:00:00" {
+    return Ok(None);
+  }
+  parse_time_to_seconds(value)
+    .map(|seconds| Some((value.to_string(), seconds)))
+    .ok_or_else(|| format!("{}格式不正确", label))
+}
+
+/// 把单个来源剪成一个文件保存到 `output_path`，中间文件写在 `work_dir`，结束后整个目录会被删除。
+pub fn clip_source_to_file<F>(
+  source: &ClipSource,
+  work_dir: &Path,
+  output_path: &Path,
+  use_copy: bool,
+  on_command: F,
+) -> Result<(), String>
+where
+  F: FnMut(&str) + Send,
+{
+  let result = clip_sources(
+    std::slice::from_ref(source),
+    work_dir,
+    use_copy,
+    None,
+    None,
+    1,
+    on_command,
+  )
+  .and_then(|outputs| {
+    let clip_path = outputs
+      .into_iter()
+      .next()
+      .ok_or_else(|| "剪辑输出为空".to_string())?;
+    if fs::rename(&clip_path, output_path).is_err() {
+      fs::copy(&clip_path, output_path).map_err(|err| format!("保存剪辑文件失败: {}", err))?;
+    }
+    Ok(())
+  });
+  let _ = fs::remove_dir_all(work_dir);
+  result
+}
+
 pub fn parse_time_to_seconds(value: &str) -> Option<f64> {
   let trimmed = value.trim();
   if trimmed.is_empty() || trimmed == "00:00:00" {
@@ -471,6 +1023,68 @@ fn scan_timestamp_gaps(path: &Path, interval: Option<String>) -> Result<Timestam
   })
 }
 
+pub fn extract_frame(
+  video_path: &Path,
+  timestamp_seconds: f64,
+  output_path: &Path,
+) -> Result<f64, String> {
+  let duration = probe_duration_seconds(video_path)?;
+  let mut timestamp = if timestamp_seconds.is_finite() {
+    timestamp_seconds.max(0.0)
+  } else {
+    0.0
+  };
+  if timestamp >= duration {
+    timestamp = duration / 2.0;
+  }
+  if let Some(parent) = output_path.parent() {
+    fs::create_dir_all(parent).map_err(|err| format!("Failed to create output dir: {}", err))?;
+  }
+  let args = vec![
+    "-hide_banner".to_string(),
+    "-loglevel".to_string(),
+    "error".to_string(),
+    "-y".to_string(),
+    "-ss".to_string(),
+    format!("{:.3}", timestamp),
+    "-i".to_string(),
+    video_path.to_string_lossy().to_string(),
+    "-frames:v".to_string(),
+    "1".to_string(),
+    "-q:v".to_string(),
+    "2".to_string(),
+    output_path.to_string_lossy().to_string(),
+  ];
+  run_ffmpeg(&args)?;
+  if !output_path.exists() {
+    return Err("截取封面失败".to_string());
+  }
+  Ok(timestamp)
+}
+
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+  let file = fs::File::open(path).map_err(|err| format!("打开文件失败: {}", err))?;
+  let mut reader = BufReader::new(file);
+  let mut hasher = Sha256::new();
+  let mut buffer = vec![0u8; 1024 * 1024];
+  loop {
+    let read = reader
+      .read(&mut buffer)
+      .map_err(|err| format!("读取文件失败: {}", err))?;
+    if read == 0 {
+      break;
+    }
+    hasher.update(&buffer[..read]);
+  }
+  Ok(
+    hasher
+      .finalize()
+      .iter()
+      .map(|byte| format!("{:02x}", byte))
+      .collect(),
+  )
+}
+
 pub fn probe_duration_seconds(path: &Path) -> Result<f64, String> {
   let args = vec![
     "-v".to_string(),
@@ -494,9 +1108,34 @@ pub fn probe_duration_seconds(path: &Path) -> Result<f64, String> {
   Ok(duration)
 }
 
-fn merge_last_short_segment(outputs: &mut Vec<PathBuf>, min_seconds: f64) -> Result<(), String> {
-  if outputs.len() < 2 {
-    return Ok(());
+/// 单个分段文件及其起止时间（相对合并文件）。
+#[derive(Clone, Debug)]
+pub struct SegmentBoundary {
+  pub path: PathBuf,
+  pub start_seconds: f64,
+  pub end_seconds: f64,
+}
+
+/// 分段结果：若末段过短被并入前一段，`absorbed_tail_seconds` 记录被吸收的时长。
+#[derive(Clone, Debug, Default)]
+pub struct SegmentFileResult {
+  pub segments: Vec<SegmentBoundary>,
+  pub absorbed_tail_seconds: Option<f64>,
+}
+
+impl SegmentFileResult {
+  pub fn paths(&self) -> Vec<PathBuf> {
+    self.segments.iter().map(|segment| segment.path.clone()).collect()
+  }
+}
+
+fn merge_last_short_segment(
+  outputs: &mut Vec<PathBuf>,
+  min_seconds: f64,
+  container: OutputContainer,
+) -> Result<Option<f64>, String> {
+  if outputs.len() < 2 || min_seconds <= 0.0 {
+    return Ok(None);
   }
   let last_index = outputs.len() - 1;
   let prev_index = outputs.len() - 2;
@@ -504,7 +1143,7 @@ fn merge_last_short_segment(outputs: &mut Vec<PathBuf>, min_seconds: f64) -> Res
   let prev_path = outputs[prev_index].clone();
   let last_duration = probe_duration_seconds(&last_path)?;
   if last_duration >= min_seconds {
-    return Ok(());
+    return Ok(None);
   }
 
   let output_dir = prev_path
@@ -518,7 +1157,7 @@ fn merge_last_short_segment(outputs: &mut Vec<PathBuf>, min_seconds: f64) -> Res
   );
   fs::write(&list_path, list_content)
     .map_err(|err| format!("Failed to write concat file: {}", err))?;
-  let merged_temp = output_dir.join("tail_merge.mp4");
+  let merged_temp = output_dir.join(format!("tail_merge.{}", container.extension()));
   let args = vec![
     "-f".to_string(),
     "concat".to_string(),
@@ -528,6 +1167,8 @@ fn merge_last_short_segment(outputs: &mut Vec<PathBuf>, min_seconds: f64) -> Res
     list_path.to_string_lossy().to_string(),
     "-c".to_string(),
     "copy".to_string(),
+    "-f".to_string(),
+    container.muxer().to_string(),
     merged_temp.to_string_lossy().to_string(),
   ];
   run_ffmpeg(&args)?;
@@ -537,17 +1178,20 @@ fn merge_last_short_segment(outputs: &mut Vec<PathBuf>, min_seconds: f64) -> Res
     .map_err(|err| format!("Failed to replace merged segment: {}", err))?;
   let _ = fs::remove_file(&last_path);
   outputs.pop();
-  Ok(())
+  Ok(Some(last_duration))
 }
 
+/// 按固定时长分段，末段短于 `min_last_segment_seconds` 时并入前一段（0 表示不合并）。
 pub fn segment_file(
   input_path: &Path,
   output_dir: &Path,
   segment_seconds: i64,
-) -> Result<Vec<PathBuf>, String> {
+  min_last_segment_seconds: i64,
+  container: OutputContainer,
+) -> Result<SegmentFileResult, String> {
   fs::create_dir_all(output_dir).map_err(|err| format!("Failed to create segment dir: {}", err))?;
 
-  let output_pattern = output_dir.join("part_%03d.mp4");
+  let output_pattern = output_dir.join(format!("part_%03d.{}", container.extension()));
   let args = vec![
     "-i".to_string(),
     input_path.to_string_lossy().to_string(),
@@ -557,6 +1201,8 @@ pub fn segment_file(
     "segment".to_string(),
     "-segment_time".to_string(),
     segment_seconds.to_string(),
+    "-segment_format".to_string(),
+    container.muxer().to_string(),
     "-reset_timestamps".to_string(),
     "1".to_string(),
     output_pattern.to_string_lossy().to_string(),
@@ -572,13 +1218,111 @@ pub fn segment_file(
     .collect();
 
   outputs.sort();
-  merge_last_short_segment(&mut outputs, 10.0)?;
-  Ok(outputs)
+  let absorbed_tail_seconds = merge_last_short_segment(&mut outputs, min_last_segment_seconds as f64, container)?;
+
+  let mut segments = Vec::with_capacity(outputs.len());
+  let mut cursor = 0.0;
+  for path in outputs {
+    let duration = probe_duration_seconds(&path)?;
+    segments.push(SegmentBoundary {
+      path,
+      start_seconds: cursor,
+      end_seconds: cursor + duration,
+    });
+    cursor += duration;
+  }
+  Ok(SegmentFileResult {
+    segments,
+    absorbed_tail_seconds,
+  })
+}
+
+/// 缩放到目标分辨率内并居中补黑边，保证所有片段尺寸、SAR、帧率一致，合并时可直接 concat copy。
+pub fn build_normalize_filter(target: &ClipNormalizeTarget) -> String {
+  format!(
+    "scale={w}:{h}:force_original_aspect_ratio=decrease,pad={w}:{h}:(ow-iw)/2:(oh-ih)/2,setsar=1,fps={fps}",
+    w = target.width,
+    h = target.height,
+    fps = format_normalize_fps(target.fps)
+  )
+}
+
+fn format_normalize_fps(fps: f64) -> String {
+  if (fps - fps.round()).abs() < 0.001 {
+    format!("{}", fps.round() as i64)
+  } else {
+    format!("{:.3}", fps)
+  }
+}
+
+/// 未指定的宽高/帧率取所有源视频中的最大值（宽高向下取偶数）。
+pub fn resolve_normalize_target(
+  sources: &[ClipSource],
+  config: &ClipNormalizeConfig,
+) -> Result<ClipNormalizeTarget, String> {
+  let mut videos = Vec::new();
+  if config.width.is_none() || config.height.is_none() || config.fps.is_none() {
+    for source in sources {
+      videos.push(probe_media_info(Path::new(&source.input_path))?.video);
+    }
+  }
+  merge_normalize_target(&videos, config)
 }
 
-fn clip_single(source: &ClipSource, output_path: &Path, use_copy: bool) -> Result<(), String> {
+fn merge_normalize_target(
+  videos: &[VideoProbeInfo],
+  config: &ClipNormalizeConfig,
+) -> Result<ClipNormalizeTarget, String> {
+  let mut width = config.width.unwrap_or(0);
+  let mut height = config.height.unwrap_or(0);
+  let mut fps = config.fps.unwrap_or(0.0);
+  if config.width.is_none() || config.height.is_none() || config.fps.is_none() {
+    let mut max_width = 0;
+    let mut max_height = 0;
+    let mut max_fps: f64 = 0.0;
+    for video in videos {
+      max_width = max_width.max(video.width);
+      max_height = max_height.max(video.height);
+      if video.fps.is_finite() {
+        max_fps = max_fps.max(video.fps);
+      }
+    }
+    if config.width.is_none() {
+      width = max_width;
+    }
+    if config.height.is_none() {
+      height = max_height;
+    }
+    if config.fps.is_none() {
+      fps = max_fps;
+    }
+  }
+  let width = width - width % 2;
+  let height = height - height % 2;
+  if width <= 0 || height <= 0 {
+    return Err("无法确定统一分辨率".to_string());
+  }
+  if fps <= 0.0 {
+    return Err("无法确定统一帧率".to_string());
+  }
+  Ok(ClipNormalizeTarget { width, height, fps })
+}
+
+fn clip_single<F>(
+  source: &ClipSource,
+  output_path: &Path,
+  use_copy: bool,
+  encoder_args: Option<&[String]>,
+  normalize: Option<&ClipNormalizeTarget>,
+  cancel: &AtomicBool,
+  on_command: &mut F,
+) -> Result<(), String>
+where
+  F: FnMut(&str),
+{
   let mut args = vec!["-i".to_string(), source.input_path.clone()];
 
+  // -ss 放在 -i 之后做输出端定位，重编码时切点可精确到帧
   if let Some(start) = source.start_time.as_deref() {
     if !start.is_empty() && start != "00:00:00" {
       args.push("-ss".to_string());
@@ -593,7 +1337,30 @@ fn clip_single(source: &ClipSource, output_path: &Path, use_copy: bool) -> Resul
     }
   }
 
-  if use_copy {
+  if let Some(target) = normalize {
+    args.extend([
+      "-vf".to_string(),
+      build_normalize_filter(target),
+      "-af".to_string(),
+      "aresample=48000:async=1:first_pts=0".to_string(),
+    ]);
+    if let Some(encoder_args) = encoder_args {
+      args.extend(encoder_args.iter().cloned());
+    } else {
+      args.extend([
+        "-c:v".to_string(),
+        default_h264_encoder().to_string(),
+        "-b:v".to_string(),
+        "5M".to_string(),
+        "-c:a".to_string(),
+        "aac".to_string(),
+        "-ar".to_string(),
+        "48000".to_string(),
+      ]);
+    }
+  } else if let Some(encoder_args) = encoder_args {
+    args.extend(encoder_args.iter().cloned());
+  } else if use_copy {
     args.extend(["-c".to_string(), "copy".to_string()]);
   } else {
     args.extend([
@@ -602,7 +1369,7 @@ fn clip_single(source: &ClipSource, output_path: &Path, use_copy: bool) -> Resul
       "-af".to_string(),
       "aresample=48000:async=1:first_pts=0".to_string(),
       "-c:v".to_string(),
-      "h264_videotoolbox".to_string(),
+      default_h264_encoder().to_string(),
       "-b:v".to_string(),
       "5M".to_string(),
       "-c:a".to_string(),
@@ -614,7 +1381,13 @@ fn clip_single(source: &ClipSource, output_path: &Path, use_copy: bool) -> Resul
   args.push(output_path.to_string_lossy().to_string());
 
   let args_line = args.join(" ");
-  run_ffmpeg(&args).map_err(|err| {
+  let result = if use_copy && normalize.is_none() && encoder_args.is_none() {
+    on_command(&args_line);
+    run_ffmpeg_cancellable(&args, cancel)
+  } else {
+    run_reencode_with_hwaccel(&args, output_path, Some(cancel), on_command)
+  };
+  result.map_err(|err| {
     format!(
       "clip_ffmpeg_fail input={} output={} args={} err={}",
       source.input_path,
@@ -624,3 +1397,225 @@ fn clip_single(source: &ClipSource, output_path: &Path, use_copy: bool) -> Resul
     )
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn temp_output(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("reaction-cut-test-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+  }
+
+  #[test]
+  fn hwaccel_fallback_removes_partial_output_before_retry() {
+    let output = temp_output("clip.mp4");
+    let args = vec![
+      "-i".to_string(),
+      "input.mp4".to_string(),
+      output.to_string_lossy().to_string(),
+    ];
+    let mut attempts = Vec::new();
+    let mut commands = Vec::new();
+    let result = run_with_hwaccel_fallback(
+      &args,
+      &output,
+      Some("cuda"),
+      || false,
+      |args| {
+        attempts.push(args.to_vec());
+        if args.iter().any(|arg| arg == "-hwaccel") {
+          fs::write(&output, b"partial").unwrap();
+          return Err("FFmpeg failed: hwaccel init".to_string());
+        }
+        // 与未带 -y 的 FFmpeg 一致：输出已存在则失败
+        if output.exists() {
+          return Err("FFmpeg failed: file exists".to_string());
+        }
+        fs::write(&output, b"done").unwrap();
+        Ok(())
+      },
+      &mut |command: &str| commands.push(command.to_string()),
+    );
+    assert_eq!(result, Ok(()));
+    assert_eq!(attempts.len(), 2);
+    assert_eq!(attempts[0][..2], ["-hwaccel".to_string(), "cuda".to_string()]);
+    assert_eq!(attempts[1], args);
+    assert!(commands.iter().any(|command| command.contains("fallback=software")));
+    assert_eq!(fs::read(&output).unwrap(), b"done");
+    let _ = fs::remove_dir_all(output.parent().unwrap());
+  }
+
+  #[test]
+  fn hwaccel_fallback_switches_to_software_encoder() {
+    let output = temp_output("clip.mp4");
+    let args = vec![
+      "-i".to_string(),
+      "input.mp4".to_string(),
+      "-c:v".to_string(),
+      "h264_nvenc".to_string(),
+      output.to_string_lossy().to_string(),
+    ];
+    let mut attempts = Vec::new();
+    let result = run_with_hwaccel_fallback(
+      &args,
+      &output,
+      Some("cuda"),
+      || false,
+      |args| {
+        attempts.push(args.to_vec());
+        if args.iter().any(|arg| arg == "h264_nvenc") {
+          return Err("FFmpeg failed: Unknown encoder".to_string());
+        }
+        Ok(())
+      },
+      &mut |_: &str| {},
+    );
+    assert_eq!(result, Ok(()));
+    assert_eq!(attempts.len(), 2);
+    assert!(attempts[0].iter().any(|arg| arg == "h264_nvenc"));
+    assert_eq!(attempts[1][3], "libx264");
+    assert!(!attempts[1].iter().any(|arg| arg == "-hwaccel"));
+    let _ = fs::remove_dir_all(output.parent().unwrap());
+  }
+
+  #[test]
+  fn hwaccel_fallback_skips_retry_when_cancelled() {
+    let output = temp_output("clip.mp4");
+    let args = vec!["-i".to_string(), "input.mp4".to_string()];
+    let mut attempts = 0;
+    let result = run_with_hwaccel_fallback(
+      &args,
+      &output,
+      Some("cuda"),
+      || true,
+      |_| {
+        attempts += 1;
+        Err("FFmpeg cancelled".to_string())
+      },
+      &mut |_: &str| {},
+    );
+    assert!(result.is_err());
+    assert_eq!(attempts, 1);
+    let _ = fs::remove_dir_all(output.parent().unwrap());
+  }
+  #[test]
+  fn sha256_file_matches_known_digest() {
+    let path = temp_output("digest.bin");
+    fs::write(&path, b"abc").unwrap();
+    assert_eq!(
+      sha256_file(&path).unwrap(),
+      "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    fs::write(&path, b"").unwrap();
+    assert_eq!(
+      sha256_file(&path).unwrap(),
+      "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+  }
+
+  #[test]
+  fn sha256_file_reports_missing_file() {
+    let path = temp_output("missing.bin");
+    assert!(sha256_file(&path).is_err());
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+  }
+
+  fn probe_video(width: i64, height: i64, fps: f64) -> VideoProbeInfo {
+    VideoProbeInfo {
+      codec_name: "h264".to_string(),
+      width,
+      height,
+      fps,
+      time_base: "1/1000".to_string(),
+      bit_rate: 0,
+    }
+  }
+
+  #[test]
+  fn normalize_target_takes_max_of_portrait_and_landscape() {
+    let videos = [probe_video(1080, 1920, 30.0), probe_video(1920, 1080, 60.0)];
+    let target = merge_normalize_target(&videos, &ClipNormalizeConfig::default()).unwrap();
+    assert_eq!((target.width, target.height), (1920, 1920));
+    assert_eq!(target.fps, 60.0);
+    assert_eq!(
+      build_normalize_filter(&target),
+      "scale=1920:1920:force_original_aspect_ratio=decrease,pad=1920:1920:(ow-iw)/2:(oh-ih)/2,setsar=1,fps=60"
+    );
+  }
+
+  #[test]
+  fn normalize_target_rounds_odd_sizes_down_to_even() {
+    let videos = [probe_video(1279, 719, 29.97)];
+    let target = merge_normalize_target(&videos, &ClipNormalizeConfig::default()).unwrap();
+    assert_eq!((target.width, target.height), (1278, 718));
+    assert_eq!(
+      build_normalize_filter(&target),
+      "scale=1278:718:force_original_aspect_ratio=decrease,pad=1278:718:(ow-iw)/2:(oh-ih)/2,setsar=1,fps=29.970"
+    );
+  }
+
+  #[test]
+  fn normalize_target_prefers_configured_values() {
+    let videos = [probe_video(1920, 1080, f64::NAN)];
+    let config = ClipNormalizeConfig {
+      width: Some(721),
+      height: None,
+      fps: Some(25.0),
+    };
+    let target = merge_normalize_target(&videos, &config).unwrap();
+    assert_eq!((target.width, target.height), (720, 1080));
+    assert_eq!(target.fps, 25.0);
+    let portrait = ClipNormalizeConfig {
+      width: Some(1080),
+      height: Some(1920),
+      fps: Some(30.0),
+    };
+    let target = merge_normalize_target(&[], &portrait).unwrap();
+    assert_eq!(
+      build_normalize_filter(&target),
+      "scale=1080:1920:force_original_aspect_ratio=decrease,pad=1080:1920:(ow-iw)/2:(oh-ih)/2,setsar=1,fps=30"
+    );
+  }
+
+  #[test]
+  fn normalize_target_rejects_unknown_size_or_fps() {
+    let config = ClipNormalizeConfig::default();
+    assert!(merge_normalize_target(&[], &config).is_err());
+    let videos = [probe_video(1, 1, 30.0)];
+    assert!(merge_normalize_target(&videos, &config).is_err());
+    let videos = [probe_video(1920, 1080, f64::NAN)];
+    assert!(merge_normalize_target(&videos, &config).is_err());
+  }
+
+  #[test]
+  fn clip_range_treats_blank_and_zero_as_unbounded() {
+    assert_eq!(parse_clip_range(None, Some("  ")).unwrap(), (None, None));
+    assert_eq!(parse_clip_range(Some("00:00:00"), None).unwrap(), (None, None));
+    assert_eq!(
+      parse_clip_range(Some(" 01:30 "), Some("00:02:00")).unwrap(),
+      (
+        Some(("01:30".to_string(), 90.0)),
+        Some(("00:02:00".to_string(), 120.0))
+      )
+    );
+  }
+
+  #[test]
+  fn clip_range_rejects_invalid_or_reversed_times() {
+    assert_eq!(
+      parse_clip_range(Some("abc"), None).unwrap_err(),
+      "开始时间格式不正确"
+    );
+    assert_eq!(
+      parse_clip_range(None, Some("1:2:3:4")).unwrap_err(),
+      "结束时间格式不正确"
+    );
+    assert_eq!(
+      parse_clip_range(Some("00:02:00"), Some("00:01:00")).unwrap_err(),
+      "结束时间需晚于开始时间"
+    );
+  }
+}
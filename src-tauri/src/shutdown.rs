@@ -0,0 +1,56 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use tauri::State;
+
+use crate::commands::submission::checkpoint_uploads_for_shutdown;
+use crate::live_recorder::stop_unfinished_recordings;
+use crate::utils::append_log;
+use crate::AppState;
+
+const SHUTDOWN_TIMEOUT_SECS: u64 = 15;
+const SHUTDOWN_POLL_MILLIS: u64 = 200;
+
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+pub fn is_requested() -> bool {
+  SHUTDOWN_REQUESTED.load(Ordering::SeqCst)
+}
+
+/// 标记进入退出流程，仅第一次调用返回 true。
+pub fn begin() -> bool {
+  !SHUTDOWN_REQUESTED.swap(true, Ordering::SeqCst)
+}
+
+/// 退出前收尾：通知录制结束当前分段并等待（最多 15 秒），随后保存上传断点，
+/// 把 UPLOADING/RECORDING 状态改为下次启动可恢复的状态。
+pub fn run(state: &State<'_, AppState>) {
+  let started_at = Instant::now();
+  let rooms = state.live_runtime.active_room_ids();
+  append_log(
+    &state.app_log_path,
+    &format!("shutdown_start recordings={}", rooms.len()),
+  );
+  for room_id in &rooms {
+    state.live_runtime.stop(room_id);
+  }
+
+  let deadline = started_at + Duration::from_secs(SHUTDOWN_TIMEOUT_SECS);
+  while state.live_runtime.active_recording_count() > 0 && Instant::now() < deadline {
+    std::thread::sleep(Duration::from_millis(SHUTDOWN_POLL_MILLIS));
+  }
+  let pending_recordings = state.live_runtime.active_recording_count();
+  let stopped_records = stop_unfinished_recordings(&state.db, &state.app_log_path);
+  let checkpointed_tasks = checkpoint_uploads_for_shutdown(state);
+
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "shutdown_done elapsed_ms={} pending_recordings={} stopped_records={} upload_tasks={}",
+      started_at.elapsed().as_millis(),
+      pending_recordings,
+      stopped_records,
+      checkpointed_tasks
+    ),
+  );
+}
@@ -1,8 +1,67 @@
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
 
 use chrono::Utc;
+use rand::Rng;
+use serde_json::{Map, Value};
+
+/// 后台循环休眠的随机抖动比例（±10%），避免多个循环同步触发。
+pub const LOOP_JITTER_RATIO: f64 = 0.1;
+
+/// 日志轮转后保留的历史文件数（.1 ~ .N）。
+pub const LOG_ROTATE_KEEP: usize = 5;
+pub const DEFAULT_MAX_LOG_SIZE_MB: i64 = 20;
+
+static STRUCTURED_LOGS: AtomicBool = AtomicBool::new(false);
+static MAX_LOG_SIZE_BYTES: AtomicU64 = AtomicU64::new(DEFAULT_MAX_LOG_SIZE_MB as u64 * 1024 * 1024);
+// 所有日志写入与轮转串行化，避免多任务并发追加时轮转把写入中的文件改名。
+static LOG_WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn set_structured_logs(enabled: bool) {
+  STRUCTURED_LOGS.store(enabled, Ordering::SeqCst);
+}
+
+/// `size_mb` 为 0 时不轮转。
+pub fn set_max_log_size_mb(size_mb: i64) {
+  MAX_LOG_SIZE_BYTES.store(size_mb.max(0) as u64 * 1024 * 1024, Ordering::SeqCst);
+}
+
+/// 追加一行日志，超过 `max_log_size_mb` 时先把当前文件轮转为 `.1`。
+pub fn write_log_line(path: &Path, line: &str) {
+  let _guard = LOG_WRITE_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+  let max_bytes = MAX_LOG_SIZE_BYTES.load(Ordering::SeqCst);
+  if max_bytes > 0 {
+    if let Ok(metadata) = std::fs::metadata(path) {
+      if metadata.len() >= max_bytes {
+        rotate_log_files(path);
+      }
+    }
+  }
+  if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+    let _ = writeln!(file, "{}", line);
+  }
+}
+
+fn rotated_log_path(path: &Path, index: usize) -> PathBuf {
+  let mut name = path.as_os_str().to_os_string();
+  name.push(format!(".{}", index));
+  PathBuf::from(name)
+}
+
+fn rotate_log_files(path: &Path) {
+  let _ = std::fs::remove_file(rotated_log_path(path, LOG_ROTATE_KEEP));
+  for index in (1..LOG_ROTATE_KEEP).rev() {
+    let from = rotated_log_path(path, index);
+    if from.exists() {
+      let _ = std::fs::rename(&from, rotated_log_path(path, index + 1));
+    }
+  }
+  let _ = std::fs::rename(path, rotated_log_path(path, 1));
+}
 
 pub fn now_rfc3339() -> String {
   Utc::now().to_rfc3339()
@@ -25,7 +84,130 @@ pub fn build_output_path(base_dir: &str, folder: &str, file_name: &str) -> PathB
 }
 
 pub fn append_log(path: &Path, message: &str) {
-  if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
-    let _ = writeln!(file, "ts={} {}", now_rfc3339(), message);
+  let ts = now_rfc3339();
+  write_log_line(path, &format!("ts={} {}", ts, message));
+  if STRUCTURED_LOGS.load(Ordering::SeqCst) {
+    append_structured_log(path, &ts, message);
+  }
+}
+
+/// 开启 structured_logs 时在同目录写入同名 .jsonl，每行一条 `{ts, level, event, fields}`。
+fn append_structured_log(path: &Path, ts: &str, message: &str) {
+  let (event, fields) = parse_log_message(message);
+  let mut record = Map::new();
+  record.insert("ts".to_string(), Value::String(ts.to_string()));
+  record.insert("level".to_string(), Value::String(infer_log_level(&event, &fields).to_string()));
+  record.insert("event".to_string(), Value::String(event));
+  record.insert("fields".to_string(), Value::Object(fields));
+  write_log_line(&path.with_extension("jsonl"), &Value::Object(record).to_string());
+}
+
+// "submission_upload_fail task_id=abc err=connection reset" ->
+// event=submission_upload_fail, fields={task_id: abc, err: "connection reset"}；
+// 不以 key= 开头的片段拼接到前一个值上，保留 err 等字段中的空格。
+fn parse_log_message(message: &str) -> (String, Map<String, Value>) {
+  let mut fields = Map::new();
+  let mut event = String::new();
+  let mut current_key: Option<String> = None;
+  let mut current_value = String::new();
+  for (index, token) in message.split_whitespace().enumerate() {
+    if let Some((key, value)) = token.split_once('=') {
+      if is_log_field_key(key) {
+        if let Some(key) = current_key.take() {
+          fields.insert(key, Value::String(std::mem::take(&mut current_value)));
+        }
+        current_key = Some(key.to_string());
+        current_value = value.to_string();
+        continue;
+      }
+    }
+    if index == 0 {
+      event = token.to_string();
+    } else if current_key.is_some() {
+      current_value.push(' ');
+      current_value.push_str(token);
+    } else {
+      if !event.is_empty() {
+        event.push(' ');
+      }
+      event.push_str(token);
+    }
+  }
+  if let Some(key) = current_key {
+    fields.insert(key, Value::String(current_value));
+  }
+  (event, fields)
+}
+
+fn is_log_field_key(key: &str) -> bool {
+  !key.is_empty()
+    && key
+      .chars()
+      .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '.' || ch == '-')
+}
+
+fn infer_log_level(event: &str, fields: &Map<String, Value>) -> &'static str {
+  let event = event.to_ascii_lowercase();
+  if event.contains("fail") || event.contains("error") || event.contains("panic") {
+    return "ERROR";
+  }
+  if fields.contains_key("err") || event.contains("warn") || event.contains("retry") {
+    return "WARN";
+  }
+  "INFO"
+}
+
+pub fn jittered_duration(base: Duration) -> Duration {
+  if base.is_zero() {
+    return base;
   }
+  let factor = rand::thread_rng().gen_range((1.0 - LOOP_JITTER_RATIO)..=(1.0 + LOOP_JITTER_RATIO));
+  base.mul_f64(factor)
+}
+
+/// 查询路径所在卷的可用空间（字节），不支持的平台或查询失败时返回 None。
+#[cfg(unix)]
+pub fn available_disk_space(path: &Path) -> Option<u64> {
+  use std::ffi::CString;
+  use std::os::unix::ffi::OsStrExt;
+
+  let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+  let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+  if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+    return None;
+  }
+  Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(windows)]
+pub fn available_disk_space(path: &Path) -> Option<u64> {
+  use std::os::windows::ffi::OsStrExt;
+
+  extern "system" {
+    fn GetDiskFreeSpaceExW(
+      directory_name: *const u16,
+      free_bytes_available: *mut u64,
+      total_bytes: *mut u64,
+      total_free_bytes: *mut u64,
+    ) -> i32;
+  }
+  let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+  let mut available: u64 = 0;
+  let ok = unsafe {
+    GetDiskFreeSpaceExW(
+      wide.as_ptr(),
+      &mut available,
+      std::ptr::null_mut(),
+      std::ptr::null_mut(),
+    )
+  };
+  if ok == 0 {
+    return None;
+  }
+  Some(available)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn available_disk_space(_path: &Path) -> Option<u64> {
+  None
 }
@@ -17,6 +17,9 @@ export default function AnchorSection() {
   const [syncFolders, setSyncFolders] = useState([]);
   const [syncBrowseLoading, setSyncBrowseLoading] = useState(false);
   const [syncBrowseError, setSyncBrowseError] = useState("");
+  const [overrideAnchor, setOverrideAnchor] = useState(null);
+  const [overrideForm, setOverrideForm] = useState({});
+  const [overrideMessage, setOverrideMessage] = useState("");
 
   const logClient = async (text) => {
     try {
@@ -102,6 +105,43 @@ export default function AnchorSection() {
     }
   };
 
+  const handleExport = async () => {
+    setMessage("");
+    try {
+      const data = await invokeCommand("anchor_export");
+      await navigator.clipboard.writeText(JSON.stringify(data || [], null, 2));
+      setMessage(`已复制 ${(data || []).length} 个直播间到剪贴板`);
+    } catch (error) {
+      setMessage(error.message || "导出失败");
+    }
+  };
+
+  const handleImport = async () => {
+    const json = window.prompt("粘贴导出的直播间 JSON");
+    if (!json || !json.trim()) {
+      return;
+    }
+    const replace = window.confirm("是否替换现有订阅？选择“取消”则合并导入");
+    setMessage("");
+    setLoading(true);
+    try {
+      const data = await invokeCommand("anchor_import", {
+        json,
+        mode: replace ? "replace" : "merge",
+      });
+      const invalid = data?.invalid || [];
+      setMessage(
+        `导入 ${data?.imported || 0} 个，跳过 ${data?.skipped || 0} 个` +
+          (invalid.length ? `，无效房间号：${invalid.join(", ")}` : ""),
+      );
+      await loadAnchors();
+    } catch (error) {
+      setMessage(error.message || "导入失败");
+    } finally {
+      setLoading(false);
+    }
+  };
+
   const handleUnsubscribe = async (anchor) => {
     setMessage("");
     try {
@@ -118,6 +158,18 @@ export default function AnchorSection() {
       await invokeCommand("live_record_start", { roomId: anchor.uid });
       await loadAnchors();
     } catch (error) {
+      if (
+        String(error.message || "").includes("已达到最大同时录制数") &&
+        window.confirm(`${error.message}，是否仍然开始录制？`)
+      ) {
+        try {
+          await invokeCommand("live_record_start", { roomId: anchor.uid, force: true });
+          await loadAnchors();
+        } catch (retryError) {
+          setMessage(retryError.message);
+        }
+        return;
+      }
       setMessage(error.message);
     }
   };
@@ -132,6 +184,16 @@ export default function AnchorSection() {
     }
   };
 
+  const handleMergeRecord = async (anchor) => {
+    setMessage("");
+    try {
+      const data = await invokeCommand("live_record_merge", { roomIdOrTaskId: anchor.uid });
+      setMessage(`已合并 ${data?.segmentCount || 0} 个分段：${data?.filePath || ""}`);
+    } catch (error) {
+      setMessage(error.message || "合并分段失败");
+    }
+  };
+
   const handleAutoRecordToggle = async (anchor) => {
     setMessage("");
     try {
@@ -145,6 +207,82 @@ export default function AnchorSection() {
     }
   };
 
+  const handleDailyLimitUpdate = async (anchor, value) => {
+    setMessage("");
+    const hours = Math.min(24, Math.max(0, Number(value) || 0));
+    if (hours === Number(anchor.maxDailyRecordingHours || 0)) {
+      return;
+    }
+    try {
+      await invokeCommand("live_room_daily_limit_update", {
+        roomId: anchor.uid,
+        maxDailyRecordingHours: hours,
+      });
+      await loadAnchors();
+    } catch (error) {
+      setMessage(error.message || "每日上限设置失败");
+    }
+  };
+
+  const handleTestStream = async (anchor) => {
+    setMessage(`正在测试 ${anchor.nickname || anchor.uid} 的直播流...`);
+    try {
+      const data = await invokeCommand("live_test_stream", { roomId: anchor.uid });
+      const urls = data?.urls || [];
+      const formats = Array.from(new Set(urls.map((item) => item.format.toUpperCase()))).join("/");
+      const expires = urls
+        .map((item) => item.expiresInSecs)
+        .filter((value) => typeof value === "number");
+      const expireText = expires.length
+        ? `，约 ${Math.max(0, Math.floor(Math.min(...expires) / 60))} 分钟后过期`
+        : "";
+      const fallbackText = data?.qualityFallback ? `（画质 ${data.requestedQn} 获取失败，已回退默认画质）` : "";
+      setMessage(`拉流测试成功：${urls.length} 个 ${formats} 地址${expireText}${fallbackText}`);
+    } catch (error) {
+      setMessage(`拉流测试失败：${error?.message || "未知错误"}`);
+    }
+  };
+
+  const handleOpenOverride = (anchor) => {
+    const current = anchor.settingsOverride || {};
+    setOverrideAnchor(anchor);
+    setOverrideMessage("");
+    setOverrideForm({
+      recordingQuality: current.recordingQuality || "",
+      recordPath: current.recordPath || "",
+      recordDanmaku:
+        current.recordDanmaku === undefined ? "" : current.recordDanmaku ? "on" : "off",
+      danmakuFormat: current.danmakuFormat || "",
+    });
+  };
+
+  const handleSaveOverride = async (clear = false) => {
+    if (!overrideAnchor) {
+      return;
+    }
+    setOverrideMessage("");
+    const overrides = clear
+      ? null
+      : {
+          ...(overrideAnchor.settingsOverride || {}),
+          recordingQuality: overrideForm.recordingQuality.trim() || null,
+          recordPath: overrideForm.recordPath.trim() || null,
+          recordDanmaku: overrideForm.recordDanmaku ? overrideForm.recordDanmaku === "on" : null,
+          danmakuFormat: overrideForm.danmakuFormat || null,
+        };
+    try {
+      await invokeCommand("live_room_settings_override_update", {
+        roomId: overrideAnchor.uid,
+        overrides,
+      });
+      await loadAnchors();
+      setMessage(clear ? "已恢复全局录制设置" : "录制设置已保存，下次录制生效");
+      setOverrideAnchor(null);
+    } catch (error) {
+      setOverrideMessage(error?.message || "保存失败");
+    }
+  };
+
   const handleSyncToggle = async (anchor) => {
     setMessage("");
     if (!anchor.baiduSyncEnabled && !anchor.baiduSyncPath) {
@@ -317,6 +455,20 @@ export default function AnchorSection() {
             >
               刷新
             </button>
+            <button
+              className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20"
+              onClick={handleExport}
+              disabled={loading}
+            >
+              导出
+            </button>
+            <button
+              className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20"
+              onClick={handleImport}
+              disabled={loading}
+            >
+              导入
+            </button>
           </div>
         </div>
         <div className="mt-4 text-sm text-[var(--muted)]">
@@ -430,6 +582,17 @@ export default function AnchorSection() {
                 {anchor.baiduSyncEnabled && anchor.baiduSyncPath ? (
                   <span>同步路径：{anchor.baiduSyncPath}</span>
                 ) : null}
+                <span>每日上限(小时，0为不限)：</span>
+                <input
+                  key={`${anchor.uid}-${anchor.maxDailyRecordingHours || 0}`}
+                  type="number"
+                  min={0}
+                  max={24}
+                  step={0.5}
+                  defaultValue={anchor.maxDailyRecordingHours || 0}
+                  onBlur={(event) => handleDailyLimitUpdate(anchor, event.target.value)}
+                  className="w-16 rounded-lg border border-black/10 bg-white/80 px-2 py-1 text-xs focus:border-[var(--accent)] focus:outline-none"
+                />
                 <span>上次检查：{formatDateTime(anchor.lastCheckTime)}</span>
               </div>
               <div className="mt-3 flex flex-wrap gap-2">
@@ -448,6 +611,26 @@ export default function AnchorSection() {
                     开始录制
                   </button>
                 )}
+                {anchor.liveStatus !== 1 ? (
+                  <button
+                    className="rounded-full border border-black/10 bg-white px-3 py-1.5 text-xs font-semibold text-[var(--ink)]"
+                    onClick={() => handleMergeRecord(anchor)}
+                  >
+                    合并分段
+                  </button>
+                ) : null}
+                <button
+                  className="rounded-full border border-black/10 bg-white px-3 py-1.5 text-xs font-semibold text-[var(--ink)]"
+                  onClick={() => handleTestStream(anchor)}
+                >
+                  测试拉流
+                </button>
+                <button
+                  className="rounded-full border border-black/10 bg-white px-3 py-1.5 text-xs font-semibold text-[var(--ink)]"
+                  onClick={() => handleOpenOverride(anchor)}
+                >
+                  {anchor.settingsOverride ? "录制设置（已覆盖）" : "录制设置"}
+                </button>
                 <button
                   className="rounded-full border border-black/10 bg-white px-3 py-1.5 text-xs font-semibold text-[var(--ink)]"
                   onClick={() => handleUnsubscribe(anchor)}
@@ -480,6 +663,78 @@ export default function AnchorSection() {
           <div className="mt-4 text-sm text-[var(--muted)]">暂无订阅记录。</div>
         ) : null}
       </div>
+      {overrideAnchor ? (
+        <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50">
+          <div className="w-[420px] rounded-2xl bg-[var(--block-color)] p-5 text-sm text-[var(--content-color)] shadow-xl">
+            <div className="text-base font-semibold">录制设置</div>
+            <div className="mt-2 text-xs text-[var(--desc-color)]">
+              主播：{overrideAnchor.nickname || overrideAnchor.uid}（留空沿用全局设置）
+            </div>
+            <div className="mt-3 text-xs text-[var(--desc-color)]">录制画质</div>
+            <input
+              value={overrideForm.recordingQuality}
+              onChange={(event) =>
+                setOverrideForm((prev) => ({ ...prev, recordingQuality: event.target.value }))
+              }
+              placeholder="如 avc10000,hevc10000"
+              className="mt-1 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-xs focus:border-[var(--accent)] focus:outline-none"
+            />
+            <div className="mt-3 text-xs text-[var(--desc-color)]">录制目录</div>
+            <input
+              value={overrideForm.recordPath}
+              onChange={(event) =>
+                setOverrideForm((prev) => ({ ...prev, recordPath: event.target.value }))
+              }
+              className="mt-1 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-xs focus:border-[var(--accent)] focus:outline-none"
+            />
+            <div className="mt-3 grid grid-cols-2 gap-2">
+              <div>
+                <div className="text-xs text-[var(--desc-color)]">录制弹幕</div>
+                <select
+                  value={overrideForm.recordDanmaku}
+                  onChange={(event) =>
+                    setOverrideForm((prev) => ({ ...prev, recordDanmaku: event.target.value }))
+                  }
+                  className="mt-1 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-xs focus:border-[var(--accent)] focus:outline-none"
+                >
+                  <option value="">沿用全局</option>
+                  <option value="on">开启</option>
+                  <option value="off">关闭</option>
+                </select>
+              </div>
+              <div>
+                <div className="text-xs text-[var(--desc-color)]">弹幕导出格式</div>
+                <select
+                  value={overrideForm.danmakuFormat}
+                  onChange={(event) =>
+                    setOverrideForm((prev) => ({ ...prev, danmakuFormat: event.target.value }))
+                  }
+                  className="mt-1 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-xs focus:border-[var(--accent)] focus:outline-none"
+                >
+                  <option value="">沿用全局</option>
+                  <option value="json">仅 JSON</option>
+                  <option value="xml">JSON + XML</option>
+                  <option value="ass">JSON + ASS 字幕</option>
+                </select>
+              </div>
+            </div>
+            {overrideMessage ? (
+              <div className="mt-3 text-xs text-amber-600">{overrideMessage}</div>
+            ) : null}
+            <div className="mt-4 flex justify-end gap-2">
+              <button className="h-9 rounded-lg px-4" onClick={() => setOverrideAnchor(null)}>
+                取消
+              </button>
+              <button className="h-9 rounded-lg px-4" onClick={() => handleSaveOverride(true)}>
+                清除覆盖
+              </button>
+              <button className="h-9 rounded-lg px-4" onClick={() => handleSaveOverride(false)}>
+                保存
+              </button>
+            </div>
+          </div>
+        </div>
+      ) : null}
       {syncAnchor ? (
         <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50">
           <div className="w-[420px] rounded-2xl bg-[var(--block-color)] p-5 text-sm text-[var(--content-color)] shadow-xl">
@@ -7,7 +7,7 @@ const recordTabs = [
   { key: "pending", label: "待下载", status: 0 },
   { key: "downloading", label: "下载中", status: 1 },
   { key: "completed", label: "已下载", status: 2 },
-  { key: "failed", label: "失败", status: 3 },
+  { key: "failed", label: "失败/已取消", status: 3 },
 ];
 
 const defaultDownloadConfig = {
@@ -15,6 +15,7 @@ const defaultDownloadConfig = {
   downloadPath: "",
   resolution: "",
   codec: "",
+  maxResolution: "",
   format: "dash",
   content: "audio_video",
 };
@@ -449,7 +450,7 @@ export default function DownloadSection() {
       return "";
     }
     try {
-      const data = await invokeCommand("video_proxy_image", { url });
+      const data = await invokeCommand("video_proxy_image", { url, transcode: "jpeg" });
       return data || "";
     } catch (error) {
       return "";
@@ -767,6 +768,7 @@ export default function DownloadSection() {
         codec: downloadConfig.codec || null,
         format: downloadConfig.format || null,
         content: downloadConfig.content || null,
+        maxResolution: Number(downloadConfig.maxResolution) || null,
       };
       const downloadName = isMultiVideo ? null : downloadConfig.downloadName || null;
       const requests = selectedVideoItems.map((item) => ({
@@ -927,6 +929,7 @@ export default function DownloadSection() {
         codec: downloadConfig.codec || null,
         format: downloadConfig.format || null,
         content: downloadConfig.content || null,
+        maxResolution: Number(downloadConfig.maxResolution) || null,
       };
       const downloadName = isMultiVideo ? null : downloadConfig.downloadName || null;
       const downloadRequests = selectedVideoItems.map((item) => ({
@@ -1077,6 +1080,47 @@ export default function DownloadSection() {
     }
   };
 
+  const handlePrioritizeRecord = async (record) => {
+    setMessage("");
+    const topPriority = downloadList.reduce(
+      (max, item) => Math.max(max, Number(item.priority || 0)),
+      0,
+    );
+    try {
+      await invokeCommand("download_set_priority", {
+        taskId: record.id,
+        priority: topPriority + 1,
+      });
+      await loadDownloadList();
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
+  const handlePauseRecord = async (taskId) => {
+    setMessage("");
+    try {
+      await invokeCommand("download_pause", { taskId });
+      await loadDownloadList();
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
+  const handleCancelAllDownloads = async () => {
+    if (!window.confirm("确定取消所有排队中和下载中的任务吗？")) {
+      return;
+    }
+    setMessage("");
+    try {
+      const count = await invokeCommand("download_cancel_all");
+      setMessage(`已取消 ${Number(count || 0)} 个下载任务`);
+      await loadDownloadList();
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
   const handleResumeRecord = async (taskId) => {
     setMessage("");
     try {
@@ -1474,6 +1518,23 @@ export default function DownloadSection() {
                               </option>
                             ))}
                           </select>
+                          <select
+                            value={downloadConfig.maxResolution}
+                            onChange={(event) =>
+                              setDownloadConfig((prev) => ({
+                                ...prev,
+                                maxResolution: event.target.value,
+                              }))
+                            }
+                            className="w-full"
+                          >
+                            <option value="">最高分辨率（不限）</option>
+                            {fallbackResolutions.map((item) => (
+                              <option key={item.value} value={item.value}>
+                                {item.label}
+                              </option>
+                            ))}
+                          </select>
                           <select
                             value={downloadConfig.format}
                             onChange={(event) =>
@@ -1964,6 +2025,12 @@ export default function DownloadSection() {
                 </span>
                 <button
                   className="ml-auto h-8 px-3 rounded-lg"
+                  onClick={handleCancelAllDownloads}
+                >
+                  全部取消
+                </button>
+                <button
+                  className="h-8 px-3 rounded-lg"
                   onClick={() => loadDownloadList()}
                   disabled={loadingDownloads}
                 >
@@ -1994,10 +2061,17 @@ export default function DownloadSection() {
                           </span>
                         </div>
                         <div className="flex flex-wrap gap-3 text-xs text-[var(--desc-color)]">
-                          <span>分P：{record.partTitle || "-"}</span>
+                          <span>
+                            分P：
+                            {record.partCount > 1 && record.currentPart
+                              ? `P${record.currentPart}/P${record.partCount} `
+                              : ""}
+                            {record.partTitle || "-"}
+                          </span>
                           <span>分辨率：{record.resolution || "-"}</span>
                           <span>编码：{record.codec || "-"}</span>
                           <span>格式：{record.format || "-"}</span>
+                          {record.statusMessage ? <span>{record.statusMessage}</span> : null}
                         </div>
                         <div className="flex items-center gap-3">
                           <div className="flex-1">
@@ -2014,6 +2088,22 @@ export default function DownloadSection() {
                           <span className="w-12 text-xs text-[var(--desc-color)]">
                             {progressValue}%
                           </span>
+                          {record.status === 0 ? (
+                            <button
+                              className="h-8 px-3 rounded-lg"
+                              onClick={() => handlePrioritizeRecord(record)}
+                            >
+                              优先
+                            </button>
+                          ) : null}
+                          {record.status === 0 || record.status === 1 ? (
+                            <button
+                              className="h-8 px-3 rounded-lg"
+                              onClick={() => handlePauseRecord(record.id)}
+                            >
+                              暂停
+                            </button>
+                          ) : null}
                           {record.status === 4 ? (
                             <button
                               className="h-8 px-3 rounded-lg"
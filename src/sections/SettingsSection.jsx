@@ -8,14 +8,42 @@ export default function SettingsSection() {
   const [downloadPath, setDownloadPath] = useState("");
   const [logDir, setLogDir] = useState("");
   const [uploadConcurrency, setUploadConcurrency] = useState(3);
+  const [chunkConcurrency, setChunkConcurrency] = useState(1);
+  const [autoRetrySubmission, setAutoRetrySubmission] = useState(false);
+  const [autoRetrySubmissionMaxAttempts, setAutoRetrySubmissionMaxAttempts] = useState(3);
+  const [maxConcurrentSubmissionTasks, setMaxConcurrentSubmissionTasks] = useState(1);
+  const [clipConcurrency, setClipConcurrency] = useState(2);
+  const [minLastSegmentSeconds, setMinLastSegmentSeconds] = useState(10);
+  const [segmentIndexPadding, setSegmentIndexPadding] = useState(0);
   const [submissionRemoteRefreshMinutes, setSubmissionRemoteRefreshMinutes] = useState(10);
+  const [createOptionsTtlSecs, setCreateOptionsTtlSecs] = useState(300);
   const [blockPcdn, setBlockPcdn] = useState(true);
+  const [structuredLogs, setStructuredLogs] = useState(false);
+  const [keepIntermediates, setKeepIntermediates] = useState(false);
+  const [autoCleanupAfterComplete, setAutoCleanupAfterComplete] = useState(false);
+  const [hwDecode, setHwDecode] = useState(false);
+  const [notifyRecording, setNotifyRecording] = useState(true);
+  const [notifyDownload, setNotifyDownload] = useState(true);
+  const [notifySubmission, setNotifySubmission] = useState(true);
+  const [maxLogSizeMb, setMaxLogSizeMb] = useState(20);
+  const [proxyUrl, setProxyUrl] = useState("");
+  const [customUserAgent, setCustomUserAgent] = useState("");
+  const [customLiveReferer, setCustomLiveReferer] = useState("");
+  const [customWebReferer, setCustomWebReferer] = useState("");
+  const [notifyWebhookUrl, setNotifyWebhookUrl] = useState("");
   const [aria2cConnections, setAria2cConnections] = useState(4);
   const [aria2cSplit, setAria2cSplit] = useState(4);
   const [message, setMessage] = useState("");
   const [syncConcurrency, setSyncConcurrency] = useState(3);
   const [syncConfigMessage, setSyncConfigMessage] = useState("");
+  const [syncPathTemplate, setSyncPathTemplate] = useState("");
+  const [syncFilenameTemplate, setSyncFilenameTemplate] = useState("");
   const [liveMessage, setLiveMessage] = useState("");
+  const [repairReport, setRepairReport] = useState(null);
+  const [repairMessage, setRepairMessage] = useState("");
+  const [orphanReport, setOrphanReport] = useState(null);
+  const [selectedOrphans, setSelectedOrphans] = useState([]);
+  const [orphanMessage, setOrphanMessage] = useState("");
   const [liveSettings, setLiveSettings] = useState({
     fileNameTemplate: "live/{{ roomId }}/{{ liveDate }}/录制-{{ roomId }}-{{ now }}-{{ title }}.flv",
     recordPath: "",
@@ -27,7 +55,14 @@ export default function SettingsSection() {
     cuttingNumber: 100,
     cuttingByTitle: false,
     titleSplitMinSeconds: 1800,
+    splitBySizeMb: 0,
+    minFreeSpaceMb: 1024,
+    maxConcurrentRecordings: 0,
+    recordBackupQuality: 0,
     danmakuTransport: 0,
+    danmakuFormat: "json",
+    danmakuFilterKeywords: "",
+    danmakuMaxPerSecond: 0,
     recordDanmaku: false,
     recordDanmakuRaw: false,
     recordDanmakuSuperchat: true,
@@ -38,6 +73,7 @@ export default function SettingsSection() {
     streamConnectTimeoutMs: 5000,
     checkIntervalSec: 180,
     flvFixSplitOnMissing: false,
+    remuxFragmentedMp4: false,
     flvFixDisableOnAnnexb: false,
     baiduSyncEnabled: false,
     baiduSyncPath: "/录播",
@@ -51,31 +87,62 @@ export default function SettingsSection() {
     }
   };
 
+  const applyDownloadSettings = (data) => {
+    setThreads(data.threads);
+    setQueueSize(data.queueSize);
+    setDownloadPath(data.downloadPath || "");
+    setLogDir(data.logDir || "");
+    const concurrency = Math.min(
+      5,
+      Math.max(1, Number(data.uploadConcurrency || 3)),
+    );
+    setUploadConcurrency(concurrency);
+    setChunkConcurrency(Math.min(8, Math.max(1, Number(data.chunkConcurrency || 1))));
+    setAutoRetrySubmission(Boolean(data.autoRetrySubmission));
+    setAutoRetrySubmissionMaxAttempts(
+      Math.min(10, Math.max(1, Number(data.autoRetrySubmissionMaxAttempts || 3))),
+    );
+    setMaxConcurrentSubmissionTasks(
+      Math.min(5, Math.max(1, Number(data.maxConcurrentSubmissionTasks || 1))),
+    );
+    setClipConcurrency(Math.min(8, Math.max(1, Number(data.clipConcurrency || 2))));
+    setMinLastSegmentSeconds(
+      Math.min(600, Math.max(0, Number(data.minLastSegmentSeconds ?? 10) || 0)),
+    );
+    setSegmentIndexPadding(Math.min(4, Math.max(0, Number(data.segmentIndexPadding) || 0)));
+    const refreshMinutes = Math.max(
+      1,
+      Number(data.submissionRemoteRefreshMinutes || 10),
+    );
+    setSubmissionRemoteRefreshMinutes(refreshMinutes);
+    setCreateOptionsTtlSecs(Number(data.submissionCreateOptionsTtlSecs ?? 300));
+    setBlockPcdn(Boolean(data.blockPcdn));
+    setStructuredLogs(Boolean(data.structuredLogs));
+    setKeepIntermediates(Boolean(data.keepIntermediates));
+    setAutoCleanupAfterComplete(Boolean(data.autoCleanupAfterComplete));
+    setHwDecode(Boolean(data.hwDecode));
+    setNotifyRecording(data.notifyRecording !== false);
+    setNotifyDownload(data.notifyDownload !== false);
+    setNotifySubmission(data.notifySubmission !== false);
+    setMaxLogSizeMb(Math.min(1024, Math.max(0, Number(data.maxLogSizeMb ?? 20) || 0)));
+    setProxyUrl(data.proxyUrl || "");
+    setCustomUserAgent(data.customUserAgent || "");
+    setCustomLiveReferer(data.customLiveReferer || "");
+    setCustomWebReferer(data.customWebReferer || "");
+    setNotifyWebhookUrl(data.notifyWebhookUrl || "");
+    const connections = Math.min(32, Math.max(1, Number(data.aria2cConnections || 4)));
+    const split = Math.min(32, Math.max(1, Number(data.aria2cSplit || 4)));
+    setAria2cConnections(connections);
+    setAria2cSplit(split);
+  };
+
   const loadSettings = async () => {
     setMessage("");
     try {
       await logClient("settings_load:start");
       const data = await invokeCommand("get_download_settings");
       if (data) {
-        setThreads(data.threads);
-        setQueueSize(data.queueSize);
-        setDownloadPath(data.downloadPath || "");
-        setLogDir(data.logDir || "");
-        const concurrency = Math.min(
-          5,
-          Math.max(1, Number(data.uploadConcurrency || 3)),
-        );
-        setUploadConcurrency(concurrency);
-        const refreshMinutes = Math.max(
-          1,
-          Number(data.submissionRemoteRefreshMinutes || 10),
-        );
-        setSubmissionRemoteRefreshMinutes(refreshMinutes);
-        setBlockPcdn(Boolean(data.blockPcdn));
-        const connections = Math.min(32, Math.max(1, Number(data.aria2cConnections || 4)));
-        const split = Math.min(32, Math.max(1, Number(data.aria2cSplit || 4)));
-        setAria2cConnections(connections);
-        setAria2cSplit(split);
+        applyDownloadSettings(data);
         await logClient(`settings_load:ok:${data.downloadPath || ""}`);
       }
     } catch (error) {
@@ -90,16 +157,124 @@ export default function SettingsSection() {
       const data = await invokeCommand("baidu_sync_settings");
       const concurrency = Math.max(1, Number(data?.concurrency || 3));
       setSyncConcurrency(concurrency);
+      setSyncPathTemplate(data?.pathTemplate || "");
+      setSyncFilenameTemplate(data?.filenameTemplate || "");
     } catch (error) {
       setSyncConfigMessage(error?.message || "加载同步配置失败");
     }
   };
 
+  const handleCheckBaiduPcs = async () => {
+    setSyncConfigMessage("正在检测 BaiduPCS-Go...");
+    try {
+      const report = await invokeCommand("baidu_sync_check");
+      if (!report?.resolvedPath) {
+        const tried = (report?.candidates || []).map((item) => item.path).join("、");
+        setSyncConfigMessage(`${report?.error || "未找到 BaiduPCS-Go"}（已尝试：${tried}）`);
+        return;
+      }
+      if (!report.loggedIn) {
+        setSyncConfigMessage(`可执行文件：${report.resolvedPath}，${report.error || "未登录"}`);
+        return;
+      }
+      const remainingGb =
+        typeof report.quotaRemainingBytes === "number"
+          ? `${(report.quotaRemainingBytes / 1024 ** 3).toFixed(2)} GB`
+          : "未知";
+      setSyncConfigMessage(
+        `可执行文件：${report.resolvedPath}，账号：${report.username || report.uid || "-"}，剩余空间：${remainingGb}`,
+      );
+    } catch (error) {
+      setSyncConfigMessage(error?.message || "检测失败");
+    }
+  };
+
+  const handleDbRepair = async (shouldDelete) => {
+    if (shouldDelete && !window.confirm("确认删除所有孤立记录？")) {
+      return;
+    }
+    setRepairMessage("");
+    try {
+      const report = await invokeCommand("db_repair", { delete: shouldDelete });
+      setRepairReport(report);
+      if (!report?.total) {
+        setRepairMessage("未发现孤立记录");
+      } else if (report.deleted) {
+        setRepairMessage(`已删除 ${report.total} 条孤立记录`);
+      } else {
+        setRepairMessage(`发现 ${report.total} 条孤立记录`);
+      }
+    } catch (error) {
+      setRepairMessage(error?.message || "检查数据库失败");
+    }
+  };
+
+  const formatOrphanSize = (bytes) => {
+    const value = Number(bytes) || 0;
+    if (value >= 1024 ** 3) {
+      return `${(value / 1024 ** 3).toFixed(2)} GB`;
+    }
+    return `${(value / 1024 ** 2).toFixed(1)} MB`;
+  };
+
+  const handleScanOrphans = async () => {
+    setOrphanMessage("");
+    try {
+      const report = await invokeCommand("storage_scan_orphans");
+      setOrphanReport(report);
+      setSelectedOrphans([]);
+      setOrphanMessage(
+        report?.files?.length
+          ? `发现 ${report.files.length} 个孤立文件，共 ${formatOrphanSize(report.totalSize)}`
+          : "未发现孤立文件",
+      );
+    } catch (error) {
+      setOrphanMessage(error?.message || "扫描失败");
+    }
+  };
+
+  const handleDeleteOrphans = async () => {
+    if (selectedOrphans.length === 0) {
+      return;
+    }
+    if (!window.confirm(`确认删除选中的 ${selectedOrphans.length} 个文件？`)) {
+      return;
+    }
+    setOrphanMessage("");
+    try {
+      const result = await invokeCommand("storage_delete_orphans", { paths: selectedOrphans });
+      const failed = result?.failed || [];
+      setOrphanReport((prev) =>
+        prev
+          ? {
+              ...prev,
+              files: prev.files.filter(
+                (file) =>
+                  !selectedOrphans.includes(file.path) ||
+                  failed.some((item) => item.path === file.path),
+              ),
+            }
+          : prev,
+      );
+      setSelectedOrphans([]);
+      setOrphanMessage(
+        `已删除 ${result?.deleted ?? 0} 个文件，释放 ${formatOrphanSize(result?.freedBytes)}` +
+          (failed.length ? `，${failed.length} 个失败：${failed[0].error}` : ""),
+      );
+    } catch (error) {
+      setOrphanMessage(error?.message || "删除失败");
+    }
+  };
+
   const handleSaveBaiduSyncSettings = async () => {
     setSyncConfigMessage("");
     try {
       await invokeCommand("baidu_sync_update_settings", {
-        request: { concurrency: Number(syncConcurrency || 1) },
+        request: {
+          concurrency: Number(syncConcurrency || 1),
+          pathTemplate: syncPathTemplate,
+          filenameTemplate: syncFilenameTemplate,
+        },
       });
       setSyncConfigMessage("同步配置已保存");
       await loadBaiduSyncSettings();
@@ -130,7 +305,14 @@ export default function SettingsSection() {
           cuttingNumber: Number(data.cuttingNumber || 0),
           cuttingByTitle: Boolean(data.cuttingByTitle),
           titleSplitMinSeconds: Number(data.titleSplitMinSeconds || 0),
+          splitBySizeMb: Number(data.splitBySizeMb || 0),
+          minFreeSpaceMb: Number(data.minFreeSpaceMb ?? 1024),
+          maxConcurrentRecordings: Number(data.maxConcurrentRecordings ?? 0),
+          recordBackupQuality: Number(data.recordBackupQuality || 0),
           danmakuTransport: Number(data.danmakuTransport || 0),
+          danmakuFormat: data.danmakuFormat || "json",
+          danmakuFilterKeywords: (data.danmakuFilterKeywords || []).join("\n"),
+          danmakuMaxPerSecond: Number(data.danmakuMaxPerSecond || 0),
           recordDanmaku: Boolean(data.recordDanmaku),
           recordDanmakuRaw: Boolean(data.recordDanmakuRaw),
           recordDanmakuSuperchat: Boolean(data.recordDanmakuSuperchat),
@@ -141,6 +323,7 @@ export default function SettingsSection() {
           streamConnectTimeoutMs: Number(data.streamConnectTimeoutMs || 0),
           checkIntervalSec: Number(data.checkIntervalSec || 0),
           flvFixSplitOnMissing: Boolean(data.flvFixSplitOnMissing),
+          remuxFragmentedMp4: Boolean(data.remuxFragmentedMp4),
           flvFixDisableOnAnnexb: Boolean(data.flvFixDisableOnAnnexb),
           baiduSyncEnabled: Boolean(data.baiduSyncEnabled),
           baiduSyncPath: data.baiduSyncPath || "/录播",
@@ -191,6 +374,24 @@ export default function SettingsSection() {
         5,
         Math.max(1, Number(uploadConcurrency) || 1),
       );
+      const normalizedChunkConcurrency = Math.min(
+        8,
+        Math.max(1, Number(chunkConcurrency) || 1),
+      );
+      const normalizedAutoRetryMaxAttempts = Math.min(
+        10,
+        Math.max(1, Number(autoRetrySubmissionMaxAttempts) || 1),
+      );
+      const normalizedMaxConcurrentTasks = Math.min(
+        5,
+        Math.max(1, Number(maxConcurrentSubmissionTasks) || 1),
+      );
+      const normalizedMaxLogSizeMb = Math.min(1024, Math.max(0, Number(maxLogSizeMb) || 0));
+      const normalizedClipConcurrency = Math.min(8, Math.max(1, Number(clipConcurrency) || 1));
+      const normalizedMinLastSegmentSeconds = Math.min(
+        600,
+        Math.max(0, Number(minLastSegmentSeconds) || 0),
+      );
       const normalizedRefreshMinutes = Math.max(
         1,
         Number(submissionRemoteRefreshMinutes) || 1,
@@ -201,36 +402,61 @@ export default function SettingsSection() {
       );
       const normalizedAria2cSplit = Math.min(32, Math.max(1, Number(aria2cSplit) || 1));
       await logClient(
-        `settings_save:start path=${downloadPath} logDir=${logDir} threads=${String(threads)} queue=${String(queueSize)} uploadConcurrency=${String(normalizedUploadConcurrency)} remoteRefreshMinutes=${String(normalizedRefreshMinutes)} blockPcdn=${String(blockPcdn)} aria2cConnections=${String(normalizedAria2cConnections)} aria2cSplit=${String(normalizedAria2cSplit)}`,
+        `settings_save:start path=${downloadPath} logDir=${logDir} threads=${String(threads)} queue=${String(queueSize)} uploadConcurrency=${String(normalizedUploadConcurrency)} chunkConcurrency=${String(normalizedChunkConcurrency)} remoteRefreshMinutes=${String(normalizedRefreshMinutes)} blockPcdn=${String(blockPcdn)} aria2cConnections=${String(normalizedAria2cConnections)} aria2cSplit=${String(normalizedAria2cSplit)}`,
       );
       await logClient("settings_save:invoke_start");
-      const data = await invokeCommand("update_download_settings", {
-        threads: Number(threads),
-        queueSize: Number(queueSize),
-        downloadPath: downloadPath,
-        logDir: logDir,
-        uploadConcurrency: normalizedUploadConcurrency,
-        submissionRemoteRefreshMinutes: normalizedRefreshMinutes,
-        blockPcdn: Boolean(blockPcdn),
-        aria2cConnections: normalizedAria2cConnections,
-        aria2cSplit: normalizedAria2cSplit,
-        enableAria2c: true,
+      await invokeCommand("update_download_settings", {
+        payload: {
+          threads: Number(threads),
+          queueSize: Number(queueSize),
+          downloadPath: downloadPath,
+          uploadConcurrency: normalizedUploadConcurrency,
+          chunkConcurrency: normalizedChunkConcurrency,
+          autoRetrySubmission: Boolean(autoRetrySubmission),
+          autoRetrySubmissionMaxAttempts: normalizedAutoRetryMaxAttempts,
+          maxConcurrentSubmissionTasks: normalizedMaxConcurrentTasks,
+          clipConcurrency: normalizedClipConcurrency,
+          minLastSegmentSeconds: normalizedMinLastSegmentSeconds,
+          segmentIndexPadding: Math.min(4, Math.max(0, Number(segmentIndexPadding) || 0)),
+          submissionRemoteRefreshMinutes: normalizedRefreshMinutes,
+          submissionCreateOptionsTtlSecs: Math.min(
+            86400,
+            Math.max(0, Number(createOptionsTtlSecs) || 0),
+          ),
+          blockPcdn: Boolean(blockPcdn),
+          aria2cConnections: normalizedAria2cConnections,
+          aria2cSplit: normalizedAria2cSplit,
+          keepIntermediates: Boolean(keepIntermediates),
+          autoCleanupAfterComplete: Boolean(autoCleanupAfterComplete),
+          hwDecode: Boolean(hwDecode),
+        },
+      });
+      await invokeCommand("update_network_settings", {
+        payload: {
+          proxyUrl: proxyUrl.trim(),
+          customUserAgent: customUserAgent.trim(),
+          customLiveReferer: customLiveReferer.trim(),
+          customWebReferer: customWebReferer.trim(),
+        },
+      });
+      await invokeCommand("update_notification_settings", {
+        payload: {
+          notifyWebhookUrl: notifyWebhookUrl.trim(),
+          notifyRecording: Boolean(notifyRecording),
+          notifyDownload: Boolean(notifyDownload),
+          notifySubmission: Boolean(notifySubmission),
+        },
+      });
+      const data = await invokeCommand("update_logging_settings", {
+        payload: {
+          logDir: logDir,
+          structuredLogs: Boolean(structuredLogs),
+          maxLogSizeMb: normalizedMaxLogSizeMb,
+        },
       });
       await logClient("settings_save:invoke_end");
       if (data) {
-        setThreads(data.threads);
-        setQueueSize(data.queueSize);
-        setDownloadPath(data.downloadPath || "");
-        setLogDir(data.logDir || "");
-        setUploadConcurrency(Number(data.uploadConcurrency || 3));
-        setSubmissionRemoteRefreshMinutes(
-          Math.max(1, Number(data.submissionRemoteRefreshMinutes || 10)),
-        );
-        setBlockPcdn(Boolean(data.blockPcdn));
-        setAria2cConnections(
-          Math.min(32, Math.max(1, Number(data.aria2cConnections || 4))),
-        );
-        setAria2cSplit(Math.min(32, Math.max(1, Number(data.aria2cSplit || 4))));
+        applyDownloadSettings(data);
         await logClient(`settings_save:ok:${data.downloadPath || ""}`);
       }
       setMessage("设置已保存，日志目录需重启生效");
@@ -243,34 +469,49 @@ export default function SettingsSection() {
   const handleSaveLiveSettings = async () => {
     setLiveMessage("");
     try {
-      await invokeCommand("update_live_settings", {
-        payload: {
-          fileNameTemplate: liveSettings.fileNameTemplate,
-          recordPath: liveSettings.recordPath,
-          writeMetadata: liveSettings.writeMetadata,
-          saveCover: liveSettings.saveCover,
-          recordingQuality: liveSettings.recordingQuality,
-          recordMode: Number(liveSettings.recordMode || 0),
-          cuttingMode: Number(liveSettings.cuttingMode || 0),
-          cuttingNumber: Number(liveSettings.cuttingNumber || 0),
-          cuttingByTitle: liveSettings.cuttingByTitle,
-          titleSplitMinSeconds: Number(liveSettings.titleSplitMinSeconds || 0),
-          danmakuTransport: Number(liveSettings.danmakuTransport || 0),
-          recordDanmaku: liveSettings.recordDanmaku,
-          recordDanmakuRaw: liveSettings.recordDanmakuRaw,
-          recordDanmakuSuperchat: liveSettings.recordDanmakuSuperchat,
-          recordDanmakuGift: liveSettings.recordDanmakuGift,
-          recordDanmakuGuard: liveSettings.recordDanmakuGuard,
-          streamRetryMs: Number(liveSettings.streamRetryMs || 0),
-          streamRetryNoQnSec: Number(liveSettings.streamRetryNoQnSec || 0),
-          streamConnectTimeoutMs: Number(liveSettings.streamConnectTimeoutMs || 0),
-          checkIntervalSec: Number(liveSettings.checkIntervalSec || 0),
-          flvFixSplitOnMissing: liveSettings.flvFixSplitOnMissing,
-          flvFixDisableOnAnnexb: liveSettings.flvFixDisableOnAnnexb,
-          baiduSyncEnabled: liveSettings.baiduSyncEnabled,
-          baiduSyncPath: liveSettings.baiduSyncPath,
-        },
-      });
+      const payload = {
+        fileNameTemplate: liveSettings.fileNameTemplate,
+        recordPath: liveSettings.recordPath,
+        writeMetadata: liveSettings.writeMetadata,
+        saveCover: liveSettings.saveCover,
+        recordingQuality: liveSettings.recordingQuality,
+        recordMode: Number(liveSettings.recordMode || 0),
+        cuttingMode: Number(liveSettings.cuttingMode || 0),
+        cuttingNumber: Number(liveSettings.cuttingNumber || 0),
+        cuttingByTitle: liveSettings.cuttingByTitle,
+        titleSplitMinSeconds: Number(liveSettings.titleSplitMinSeconds || 0),
+        splitBySizeMb: Math.max(0, Number(liveSettings.splitBySizeMb || 0)),
+        minFreeSpaceMb: Math.max(0, Number(liveSettings.minFreeSpaceMb || 0)),
+        maxConcurrentRecordings: Math.max(0, Number(liveSettings.maxConcurrentRecordings || 0)),
+        recordBackupQuality: Math.max(0, Number(liveSettings.recordBackupQuality || 0)),
+        danmakuTransport: Number(liveSettings.danmakuTransport || 0),
+        danmakuFormat: liveSettings.danmakuFormat || "json",
+        danmakuFilterKeywords: String(liveSettings.danmakuFilterKeywords || "")
+          .split("\n")
+          .map((item) => item.trim())
+          .filter(Boolean),
+        danmakuMaxPerSecond: Math.max(0, Number(liveSettings.danmakuMaxPerSecond || 0)),
+        recordDanmaku: liveSettings.recordDanmaku,
+        recordDanmakuRaw: liveSettings.recordDanmakuRaw,
+        recordDanmakuSuperchat: liveSettings.recordDanmakuSuperchat,
+        recordDanmakuGift: liveSettings.recordDanmakuGift,
+        recordDanmakuGuard: liveSettings.recordDanmakuGuard,
+        streamRetryMs: Number(liveSettings.streamRetryMs || 0),
+        streamRetryNoQnSec: Number(liveSettings.streamRetryNoQnSec || 0),
+        streamConnectTimeoutMs: Number(liveSettings.streamConnectTimeoutMs || 0),
+        checkIntervalSec: Number(liveSettings.checkIntervalSec || 0),
+        flvFixSplitOnMissing: liveSettings.flvFixSplitOnMissing,
+        remuxFragmentedMp4: Boolean(liveSettings.remuxFragmentedMp4),
+        flvFixDisableOnAnnexb: liveSettings.flvFixDisableOnAnnexb,
+        baiduSyncEnabled: liveSettings.baiduSyncEnabled,
+        baiduSyncPath: liveSettings.baiduSyncPath,
+      };
+      const validation = await invokeCommand("validate_settings", { live: payload });
+      if (validation && !validation.valid) {
+        setLiveMessage(validation.errors.map((item) => item.message).join("；"));
+        return;
+      }
+      await invokeCommand("update_live_settings", { payload });
       setLiveMessage("直播录制设置已保存");
     } catch (error) {
       setLiveMessage(error.message);
@@ -354,6 +595,152 @@ export default function SettingsSection() {
               className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
             />
           </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              分片并发上传数
+            </div>
+            <input
+              type="number"
+              value={chunkConcurrency}
+              onChange={(event) => setChunkConcurrency(event.target.value)}
+              min={1}
+              max={8}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              投稿失败自动重试次数
+            </div>
+            <input
+              type="number"
+              value={autoRetrySubmissionMaxAttempts}
+              onChange={(event) => setAutoRetrySubmissionMaxAttempts(event.target.value)}
+              min={1}
+              max={10}
+              disabled={!autoRetrySubmission}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              同时投稿任务数
+            </div>
+            <input
+              type="number"
+              value={maxConcurrentSubmissionTasks}
+              onChange={(event) => setMaxConcurrentSubmissionTasks(event.target.value)}
+              min={1}
+              max={5}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              并行剪辑数
+            </div>
+            <input
+              type="number"
+              value={clipConcurrency}
+              onChange={(event) => setClipConcurrency(event.target.value)}
+              min={1}
+              max={8}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              末段最短时长（秒，0 不合并）
+            </div>
+            <input
+              type="number"
+              value={minLastSegmentSeconds}
+              onChange={(event) => setMinLastSegmentSeconds(event.target.value)}
+              min={0}
+              max={600}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              分P序号补零位数（0 不补零，2 为 P01）
+            </div>
+            <input
+              type="number"
+              value={segmentIndexPadding}
+              onChange={(event) => setSegmentIndexPadding(event.target.value)}
+              min={0}
+              max={4}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              日志文件大小上限（MB，0 不轮转）
+            </div>
+            <input
+              type="number"
+              value={maxLogSizeMb}
+              onChange={(event) => setMaxLogSizeMb(event.target.value)}
+              min={0}
+              max={1024}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              代理地址（http/socks5，留空直连）
+            </div>
+            <input
+              value={proxyUrl}
+              onChange={(event) => setProxyUrl(event.target.value)}
+              placeholder="socks5://127.0.0.1:1080"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              自定义 User-Agent（留空使用默认）
+            </div>
+            <input
+              value={customUserAgent}
+              onChange={(event) => setCustomUserAgent(event.target.value)}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              直播请求 Referer（留空使用默认）
+            </div>
+            <input
+              value={customLiveReferer}
+              onChange={(event) => setCustomLiveReferer(event.target.value)}
+              placeholder="https://live.bilibili.com/"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              主站请求 Referer（留空使用默认）
+            </div>
+            <input
+              value={customWebReferer}
+              onChange={(event) => setCustomWebReferer(event.target.value)}
+              placeholder="https://www.bilibili.com/"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              完成通知 Webhook（留空不推送）
+            </div>
+            <input
+              value={notifyWebhookUrl}
+              onChange={(event) => setNotifyWebhookUrl(event.target.value)}
+              placeholder="https://example.com/hook"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
           <div>
             <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
               投稿状态刷新间隔(分钟)
@@ -368,6 +755,19 @@ export default function SettingsSection() {
               className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
             />
           </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              投稿选项缓存(秒，0 为不缓存)
+            </div>
+            <input
+              type="number"
+              value={createOptionsTtlSecs}
+              onChange={(event) => setCreateOptionsTtlSecs(event.target.value)}
+              min={0}
+              max={86400}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
           <div className="lg:col-span-2">
             <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
               下载路径
@@ -428,6 +828,70 @@ export default function SettingsSection() {
             />
             过滤 PCDN（优先镜像与 upos）
           </label>
+          <label className="flex items-center gap-2 text-sm text-[var(--muted)] lg:col-span-2">
+            <input
+              type="checkbox"
+              checked={autoRetrySubmission}
+              onChange={(event) => setAutoRetrySubmission(event.target.checked)}
+            />
+            投稿因网络等临时错误失败时自动重新排队
+          </label>
+          <label className="flex items-center gap-2 text-sm text-[var(--muted)] lg:col-span-2">
+            <input
+              type="checkbox"
+              checked={structuredLogs}
+              onChange={(event) => setStructuredLogs(event.target.checked)}
+            />
+            同时输出 JSON 结构化日志（.jsonl）
+          </label>
+          <label className="flex items-center gap-2 text-sm text-[var(--muted)] lg:col-span-2">
+            <input
+              type="checkbox"
+              checked={keepIntermediates}
+              onChange={(event) => setKeepIntermediates(event.target.checked)}
+            />
+            重新投稿/重新分段时保留中间文件（移入 debug 目录）
+          </label>
+          <label className="flex items-center gap-2 text-sm text-[var(--muted)] lg:col-span-2">
+            <input
+              type="checkbox"
+              checked={autoCleanupAfterComplete}
+              onChange={(event) => setAutoCleanupAfterComplete(event.target.checked)}
+            />
+            投稿完成后自动清理剪辑与分段文件（保留合并视频用于重新分段）
+          </label>
+          <label className="flex items-center gap-2 text-sm text-[var(--muted)] lg:col-span-2">
+            <input
+              type="checkbox"
+              checked={hwDecode}
+              onChange={(event) => setHwDecode(event.target.checked)}
+            />
+            重编码时尝试硬件解码（失败自动回退软件解码）
+          </label>
+          <label className="flex items-center gap-2 text-sm text-[var(--muted)]">
+            <input
+              type="checkbox"
+              checked={notifyRecording}
+              onChange={(event) => setNotifyRecording(event.target.checked)}
+            />
+            录制开始/结束时发送系统通知
+          </label>
+          <label className="flex items-center gap-2 text-sm text-[var(--muted)]">
+            <input
+              type="checkbox"
+              checked={notifyDownload}
+              onChange={(event) => setNotifyDownload(event.target.checked)}
+            />
+            下载完成时发送系统通知
+          </label>
+          <label className="flex items-center gap-2 text-sm text-[var(--muted)]">
+            <input
+              type="checkbox"
+              checked={notifySubmission}
+              onChange={(event) => setNotifySubmission(event.target.checked)}
+            />
+            稿件发布/退回时发送系统通知
+          </label>
         </div>
         <div className="mt-4 flex flex-wrap gap-2">
           <button
@@ -468,6 +932,31 @@ export default function SettingsSection() {
               className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
             />
           </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              子目录模板
+            </div>
+            <input
+              value={syncPathTemplate}
+              onChange={(event) => setSyncPathTemplate(event.target.value)}
+              placeholder="{{ anchor }}/{{ date }}"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              文件名模板
+            </div>
+            <input
+              value={syncFilenameTemplate}
+              onChange={(event) => setSyncFilenameTemplate(event.target.value)}
+              placeholder="{{ date }}_{{ title }}.mp4"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div className="text-xs text-[var(--muted)] lg:col-span-2">
+            可用占位符：{"{{ anchor }}"} {"{{ date }}"} {"{{ title }}"} {"{{ room_id }}"}，留空沿用默认规则
+          </div>
         </div>
         <div className="mt-4 flex flex-wrap gap-2">
           <button
@@ -482,6 +971,12 @@ export default function SettingsSection() {
           >
             刷新
           </button>
+          <button
+            className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20"
+            onClick={handleCheckBaiduPcs}
+          >
+            检测 BaiduPCS
+          </button>
         </div>
         {syncConfigMessage ? (
           <div className="mt-3 rounded-lg border border-amber-200 bg-amber-50 px-3 py-2 text-sm text-amber-700">
@@ -490,6 +985,96 @@ export default function SettingsSection() {
         ) : null}
       </div>
 
+      <div className="rounded-2xl bg-[var(--surface)]/90 p-6 shadow-sm ring-1 ring-black/5">
+        <div>
+          <p className="text-sm uppercase tracking-[0.2em] text-[var(--muted)]">数据维护</p>
+          <h2 className="text-2xl font-semibold text-[var(--ink)]">孤立记录检查</h2>
+        </div>
+        <div className="mt-2 text-xs text-[var(--muted)]">
+          检查指向已删除下载或投稿的关联记录、分段、合并视频与工作流实例。
+        </div>
+        <div className="mt-4 flex flex-wrap gap-2">
+          <button
+            className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20"
+            onClick={() => handleDbRepair(false)}
+          >
+            检查
+          </button>
+          <button
+            className="rounded-full border border-red-200 bg-white px-4 py-2 text-sm font-semibold text-red-600 transition hover:border-red-300"
+            onClick={() => handleDbRepair(true)}
+            disabled={!repairReport?.total || repairReport?.deleted}
+          >
+            删除孤立记录
+          </button>
+        </div>
+        {repairReport?.groups?.length ? (
+          <div className="mt-3 space-y-1 text-xs text-[var(--muted)]">
+            {repairReport.groups.map((group) => (
+              <div key={`${group.table}-${group.reason}`}>
+                {group.table}（{group.reason}）：{group.count} 条
+              </div>
+            ))}
+          </div>
+        ) : null}
+        {repairMessage ? (
+          <div className="mt-3 rounded-lg border border-amber-200 bg-amber-50 px-3 py-2 text-sm text-amber-700">
+            {repairMessage}
+          </div>
+        ) : null}
+      </div>
+
+      <div className="rounded-2xl bg-[var(--surface)]/90 p-6 shadow-sm ring-1 ring-black/5">
+        <div>
+          <p className="text-sm uppercase tracking-[0.2em] text-[var(--muted)]">数据维护</p>
+          <h2 className="text-2xl font-semibold text-[var(--ink)]">孤立文件清理</h2>
+        </div>
+        <div className="mt-2 text-xs text-[var(--muted)]">
+          扫描下载目录中没有任何下载、投稿或录制记录引用的媒体文件，最近 30 分钟内修改的文件不会列出。
+        </div>
+        <div className="mt-4 flex flex-wrap gap-2">
+          <button
+            className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20"
+            onClick={handleScanOrphans}
+          >
+            扫描
+          </button>
+          <button
+            className="rounded-full border border-red-200 bg-white px-4 py-2 text-sm font-semibold text-red-600 transition hover:border-red-300"
+            onClick={handleDeleteOrphans}
+            disabled={selectedOrphans.length === 0}
+          >
+            删除选中文件
+          </button>
+        </div>
+        {orphanReport?.files?.length ? (
+          <div className="mt-3 max-h-64 space-y-1 overflow-y-auto text-xs text-[var(--muted)]">
+            {orphanReport.files.map((file) => (
+              <label key={file.path} className="flex items-center gap-2">
+                <input
+                  type="checkbox"
+                  checked={selectedOrphans.includes(file.path)}
+                  onChange={(event) =>
+                    setSelectedOrphans((prev) =>
+                      event.target.checked
+                        ? [...prev, file.path]
+                        : prev.filter((path) => path !== file.path),
+                    )
+                  }
+                />
+                <span className="break-all">{file.path}</span>
+                <span className="whitespace-nowrap">{formatOrphanSize(file.size)}</span>
+              </label>
+            ))}
+          </div>
+        ) : null}
+        {orphanMessage ? (
+          <div className="mt-3 rounded-lg border border-amber-200 bg-amber-50 px-3 py-2 text-sm text-amber-700">
+            {orphanMessage}
+          </div>
+        ) : null}
+      </div>
+
       <div className="rounded-2xl bg-[var(--surface)]/90 p-6 shadow-sm ring-1 ring-black/5">
         <div>
           <p className="text-sm uppercase tracking-[0.2em] text-[var(--muted)]">直播录制</p>
@@ -531,6 +1116,27 @@ export default function SettingsSection() {
               placeholder="avc10000,hevc10000"
             />
           </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              备用流画质
+            </div>
+            <select
+              value={liveSettings.recordBackupQuality}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  recordBackupQuality: Number(event.target.value),
+                }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            >
+              <option value={0}>关闭</option>
+              <option value={80}>流畅(80)</option>
+              <option value={150}>高清(150)</option>
+              <option value={250}>超清(250)</option>
+              <option value={400}>蓝光(400)</option>
+            </select>
+          </div>
           <div className="lg:col-span-2">
             <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
               录制文件名模板
@@ -700,6 +1306,57 @@ export default function SettingsSection() {
               className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
             />
           </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              按文件大小分段（MB，0为关闭）
+            </div>
+            <input
+              type="number"
+              min={0}
+              value={liveSettings.splitBySizeMb}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  splitBySizeMb: event.target.value,
+                }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              最小剩余空间（MB，0为不检查）
+            </div>
+            <input
+              type="number"
+              min={0}
+              value={liveSettings.minFreeSpaceMb}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  minFreeSpaceMb: event.target.value,
+                }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              最大同时录制数（0为不限制）
+            </div>
+            <input
+              type="number"
+              min={0}
+              value={liveSettings.maxConcurrentRecordings}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  maxConcurrentRecordings: event.target.value,
+                }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
           <label className="flex items-center gap-2 text-sm text-[var(--muted)]">
             <input
               type="checkbox"
@@ -726,6 +1383,19 @@ export default function SettingsSection() {
             />
             FLV 修复-检测到 H264 Annex-B 时禁用修复分段
           </label>
+          <label className="flex items-center gap-2 text-sm text-[var(--muted)]">
+            <input
+              type="checkbox"
+              checked={liveSettings.remuxFragmentedMp4}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  remuxFragmentedMp4: event.target.checked,
+                }))
+              }
+            />
+            转封装为分片 MP4（异常中断时已写入部分仍可播放）
+          </label>
         </div>
       </div>
 
@@ -755,6 +1425,58 @@ export default function SettingsSection() {
               <option value={3}>WSS</option>
             </select>
           </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              弹幕导出格式
+            </div>
+            <select
+              value={liveSettings.danmakuFormat}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  danmakuFormat: event.target.value,
+                }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            >
+              <option value="json">仅 JSON</option>
+              <option value="xml">JSON + XML</option>
+              <option value="ass">JSON + ASS 字幕</option>
+            </select>
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              每秒弹幕上限（0为不限制）
+            </div>
+            <input
+              type="number"
+              min={0}
+              value={liveSettings.danmakuMaxPerSecond}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  danmakuMaxPerSecond: event.target.value,
+                }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div className="lg:col-span-2">
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              弹幕屏蔽词（每行一个，不区分大小写）
+            </div>
+            <textarea
+              rows={3}
+              value={liveSettings.danmakuFilterKeywords}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  danmakuFilterKeywords: event.target.value,
+                }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
           <label className="flex items-center gap-2 text-sm text-[var(--muted)]">
             <input
               type="checkbox"
@@ -1,6 +1,7 @@
 import { useEffect, useRef, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { open as openDialog } from "@tauri-apps/plugin-dialog";
+import { listen } from "@tauri-apps/api/event";
+import { open as openDialog, save as saveDialog } from "@tauri-apps/plugin-dialog";
 import { openUrl } from "@tauri-apps/plugin-opener";
 import { invokeCommand } from "../lib/tauri";
 import { formatDateTime } from "../lib/format";
@@ -17,6 +18,7 @@ const statusFilters = [
   { value: "UPLOADING", label: "投稿中" },
   { value: "COMPLETED", label: "已完成" },
   { value: "FAILED", label: "失败" },
+  { value: "FAILED_PERMANENT", label: "重试耗尽" },
   { value: "CANCELLED", label: "已取消" },
 ];
 
@@ -33,6 +35,22 @@ const defaultWorkflowConfig = {
     segmentDurationSeconds: 133,
     preserveOriginal: true,
   },
+  outputContainer: "mp4",
+  frameAccurate: false,
+};
+
+const toDateTimeLocalValue = (seconds) => {
+  if (!seconds) {
+    return "";
+  }
+  const date = new Date(Number(seconds) * 1000);
+  if (Number.isNaN(date.getTime())) {
+    return "";
+  }
+  const pad = (value) => String(value).padStart(2, "0");
+  return `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}T${pad(
+    date.getHours(),
+  )}:${pad(date.getMinutes())}`;
 };
 
 export default function SubmissionSection() {
@@ -46,6 +64,7 @@ export default function SubmissionSection() {
     baiduSyncEnabled: false,
     baiduSyncPath: "",
     baiduSyncFilename: "",
+    scheduledPublishAt: "",
   });
   const [tagInput, setTagInput] = useState("");
   const [tags, setTags] = useState([]);
@@ -53,16 +72,23 @@ export default function SubmissionSection() {
   const [sourceVideos, setSourceVideos] = useState([emptySource(0)]);
   const [workflowConfig, setWorkflowConfig] = useState(defaultWorkflowConfig);
   const [partitions, setPartitions] = useState([]);
+  const [partitionGroups, setPartitionGroups] = useState([]);
   const [collections, setCollections] = useState([]);
   const [tasks, setTasks] = useState([]);
   const [totalTasks, setTotalTasks] = useState(0);
   const [selectedTask, setSelectedTask] = useState(null);
   const [detailTab, setDetailTab] = useState("basic");
+  const [taskStats, setTaskStats] = useState(null);
+  const [workflowLogs, setWorkflowLogs] = useState([]);
   const [currentPage, setCurrentPage] = useState(1);
   const [pageSize, setPageSize] = useState(20);
   const [statusFilter, setStatusFilter] = useState("ALL");
+  const [searchQuery, setSearchQuery] = useState("");
+  const [sortMode, setSortMode] = useState("");
+  const [selectedTaskIds, setSelectedTaskIds] = useState([]);
   const [message, setMessage] = useState("");
   const [refreshingRemote, setRefreshingRemote] = useState(false);
+  const [queuePaused, setQueuePaused] = useState(false);
   const [submissionView, setSubmissionView] = useState("list");
   const [deleteTargetId, setDeleteTargetId] = useState("");
   const [deleteConfirmOpen, setDeleteConfirmOpen] = useState(false);
@@ -111,6 +137,8 @@ export default function SubmissionSection() {
   const lastDetailTaskIdRef = useRef(null);
   const lastEditTaskIdRef = useRef(null);
   const dragStateRef = useRef({ activeId: "", overId: "" });
+  const createRequestIdRef = useRef("");
+  const [submissionQuota, setSubmissionQuota] = useState(null);
   const isCreateView = submissionView === "create";
   const isDetailView = submissionView === "detail";
   const isEditView = submissionView === "edit";
@@ -138,6 +166,32 @@ export default function SubmissionSection() {
     return `${String(hrs).padStart(2, "0")}:${String(mins).padStart(2, "0")}:${String(secs).padStart(2, "0")}`;
   };
 
+  const formatStatBytes = (bytes) => {
+    const value = Number(bytes || 0);
+    if (value >= 1024 * 1024 * 1024) {
+      return `${(value / 1024 / 1024 / 1024).toFixed(2)} GB`;
+    }
+    return `${(value / 1024 / 1024).toFixed(1)} MB`;
+  };
+
+  const loadTaskStats = async (taskId) => {
+    try {
+      const stats = await invokeCommand("submission_stats", { taskId });
+      setTaskStats(stats || null);
+    } catch (_) {
+      setTaskStats(null);
+    }
+  };
+
+  const loadWorkflowLogs = async (taskId) => {
+    try {
+      const logs = await invokeCommand("workflow_logs", { taskId });
+      setWorkflowLogs(Array.isArray(logs) ? logs : []);
+    } catch (_) {
+      setWorkflowLogs([]);
+    }
+  };
+
   const parseHmsToSeconds = (value) => {
     if (!value) {
       return null;
@@ -184,6 +238,7 @@ export default function SubmissionSection() {
       baiduSyncEnabled: false,
       baiduSyncPath: "",
       baiduSyncFilename: "",
+      scheduledPublishAt: "",
     });
     setTagInput("");
     setTags([]);
@@ -208,8 +263,7 @@ export default function SubmissionSection() {
     setMessage("");
     setQuickFillOpen(false);
     resetFormState();
-    await loadPartitions();
-    await loadCollections();
+    await loadCreateOptions();
   };
 
   const openUpdateModal = (task) => {
@@ -336,10 +390,32 @@ export default function SubmissionSection() {
     setQuickFillOpen(false);
   };
 
+  const renderPartitionOption = (partition) => (
+    <option key={partition.tid} value={partition.tid}>
+      {partition.name}
+    </option>
+  );
+
+  const renderPartitionOptions = () => {
+    if (!partitionGroups.length) {
+      return partitions.map(renderPartitionOption);
+    }
+    return partitionGroups.map((group) =>
+      group.children?.length ? (
+        <optgroup key={group.tid} label={group.name}>
+          {group.children.map(renderPartitionOption)}
+        </optgroup>
+      ) : (
+        renderPartitionOption(group)
+      ),
+    );
+  };
+
   const loadPartitions = async () => {
     try {
       const data = await invokeCommand("bilibili_partitions");
       setPartitions(data || []);
+      setPartitionGroups([]);
       if ((data || []).length) {
         setTaskForm((prev) => {
           if (prev.partitionId) {
@@ -393,6 +469,47 @@ export default function SubmissionSection() {
     }
   };
 
+  const loadCreateOptions = async (force = false) => {
+    try {
+      const auth = await invokeCommand("auth_status");
+      if (!auth?.loggedIn) {
+        setCollections([]);
+        await loadPartitions();
+        return;
+      }
+      const userInfo = auth?.userInfo || {};
+      const level1 = userInfo?.data || userInfo;
+      const level2 = level1?.data || level1;
+      const mid = level2?.mid || level1?.mid || userInfo?.mid || 0;
+      const data = await invokeCommand("submission_create_options", {
+        mid: mid || 0,
+        force,
+      });
+      const nextGroups = data?.partitions || [];
+      const nextPartitions = nextGroups.flatMap((group) =>
+        group.children?.length ? group.children : [{ tid: group.tid, name: group.name }],
+      );
+      setPartitionGroups(nextGroups);
+      setPartitions(nextPartitions);
+      if (nextPartitions.length) {
+        setTaskForm((prev) => {
+          if (prev.partitionId) {
+            return prev;
+          }
+          return { ...prev, partitionId: String(nextPartitions[0].tid) };
+        });
+      }
+      setCollections(
+        (data?.collections || []).map((item) => ({
+          ...item,
+          seasonId: item.season_id ?? item.seasonId,
+        })),
+      );
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
   const loadTasks = async (
     filter = statusFilter,
     page = currentPage,
@@ -401,6 +518,13 @@ export default function SubmissionSection() {
   ) => {
     try {
       const payload = { page, page_size: size };
+      const query = searchQuery.trim();
+      if (query) {
+        payload.query = query;
+      }
+      if (sortMode) {
+        payload.sort = sortMode;
+      }
       if (refreshRemote) {
         payload.refresh_remote = true;
       }
@@ -506,6 +630,27 @@ export default function SubmissionSection() {
     loadCollections();
   }, []);
 
+  useEffect(() => {
+    if (!isCreateView) {
+      return;
+    }
+    let cancelled = false;
+    invokeCommand("bilibili_submission_quota")
+      .then((data) => {
+        if (!cancelled) {
+          setSubmissionQuota(data || null);
+        }
+      })
+      .catch(() => {
+        if (!cancelled) {
+          setSubmissionQuota(null);
+        }
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [isCreateView]);
+
   useEffect(() => {
     if (!isDetailView && !isEditView) {
       lastDetailTaskIdRef.current = null;
@@ -540,7 +685,13 @@ export default function SubmissionSection() {
     }
     loadTasks(statusFilter, currentPage, pageSize);
     return undefined;
-  }, [submissionView, statusFilter, currentPage, pageSize]);
+  }, [submissionView, statusFilter, currentPage, pageSize, searchQuery, sortMode]);
+
+  useEffect(() => {
+    invokeCommand("app_status")
+      .then((status) => setQueuePaused(Boolean(status?.submissionQueuePaused)))
+      .catch(() => {});
+  }, []);
 
   useEffect(() => {
     if (submissionView !== "list") {
@@ -550,7 +701,7 @@ export default function SubmissionSection() {
       loadTasks(statusFilter, currentPage, pageSize);
     }, 3000);
     return () => clearInterval(timer);
-  }, [submissionView, statusFilter, currentPage, pageSize]);
+  }, [submissionView, statusFilter, currentPage, pageSize, searchQuery, sortMode]);
 
   useEffect(() => {
     if (!quickFillOpen) {
@@ -581,9 +732,15 @@ export default function SubmissionSection() {
     };
     refreshDetail();
     const timer = setInterval(refreshDetail, 3000);
+    const unlisten = listen("workflow_status", (event) => {
+      if (event.payload?.taskId === taskId) {
+        refreshDetail();
+      }
+    });
     return () => {
       active = false;
       clearInterval(timer);
+      unlisten.then((off) => off());
     };
   }, [submissionView, selectedTask?.task?.taskId, isDetailView]);
 
@@ -622,10 +779,24 @@ export default function SubmissionSection() {
       }
     };
     refreshStatus();
-    const timer = setInterval(refreshStatus, 2000);
+    // 进度通过事件推送，轮询仅作为兜底
+    const timer = setInterval(refreshStatus, 10000);
+    const unlisten = listen("submission_upload_progress", (event) => {
+      const update = event.payload;
+      if (!active || update?.targetType !== "editSegment") {
+        return;
+      }
+      if (update.uploadStatus) {
+        // 状态变化时拉取完整记录（含 cid、文件名）
+        refreshStatus();
+        return;
+      }
+      setEditSegments((prev) => mergeEditUploadStatus(prev, [update]));
+    });
     return () => {
       active = false;
       clearInterval(timer);
+      unlisten.then((off) => off());
     };
   }, [isEditView, pendingEditUploads, selectedTask?.task?.taskId]);
 
@@ -729,6 +900,8 @@ export default function SubmissionSection() {
         segmentDurationSeconds: workflowConfig.segmentationConfig.segmentDurationSeconds,
         preserveOriginal: workflowConfig.segmentationConfig.preserveOriginal,
       },
+      outputContainer: workflowConfig.outputContainer || "mp4",
+      frameAccurate: Boolean(workflowConfig.frameAccurate),
     };
   };
 
@@ -837,9 +1010,42 @@ export default function SubmissionSection() {
       setMessage("时间范围不合法，请检查开始与结束时间");
       return;
     }
+    try {
+      const preflight = await invokeCommand("submission_preflight", {
+        sourceVideos: validSources.map((item, index) => ({
+          sourceFilePath: item.sourceFilePath,
+          sortOrder: index + 1,
+          startTime: item.startTime || null,
+          endTime: item.endTime || null,
+        })),
+      });
+      if (
+        preflight &&
+        !preflight.useCopy &&
+        !window.confirm("这些素材需要重新编码，可能较慢，是否继续创建？")
+      ) {
+        return;
+      }
+    } catch (error) {
+      setMessage(error.message);
+      return;
+    }
+    let scheduledPublishAt = null;
+    if (taskForm.scheduledPublishAt) {
+      const timestamp = new Date(taskForm.scheduledPublishAt).getTime();
+      if (!Number.isFinite(timestamp)) {
+        setMessage("定时发布时间无效");
+        return;
+      }
+      scheduledPublishAt = Math.floor(timestamp / 1000);
+    }
+    if (!createRequestIdRef.current) {
+      createRequestIdRef.current = crypto.randomUUID();
+    }
     try {
       const payload = {
         request: {
+          clientRequestId: createRequestIdRef.current,
           task: {
             title: taskForm.title,
             description: taskForm.description || null,
@@ -852,6 +1058,7 @@ export default function SubmissionSection() {
             baiduSyncEnabled: Boolean(taskForm.baiduSyncEnabled),
             baiduSyncPath: taskForm.baiduSyncPath || null,
             baiduSyncFilename: taskForm.baiduSyncFilename || null,
+            scheduledPublishAt: scheduledPublishAt,
           },
           sourceVideos: validSources.map((item, index) => ({
             sourceFilePath: item.sourceFilePath,
@@ -863,6 +1070,7 @@ export default function SubmissionSection() {
         },
       };
       await invokeCommand("submission_create", payload);
+      createRequestIdRef.current = "";
       await loadTasks();
       setSubmissionView("list");
       setMessage("");
@@ -1111,6 +1319,7 @@ export default function SubmissionSection() {
       baiduSyncEnabled: Boolean(task.baiduSyncEnabled),
       baiduSyncPath: task.baiduSyncPath || "",
       baiduSyncFilename: task.baiduSyncFilename || "",
+      scheduledPublishAt: toDateTimeLocalValue(task.scheduledPublishAt),
     });
     setTags(tagList);
     setTagInput("");
@@ -1129,6 +1338,8 @@ export default function SubmissionSection() {
             ? segmentation.preserveOriginal
             : true,
       },
+      outputContainer: config?.outputContainer || "mp4",
+      frameAccurate: Boolean(config?.frameAccurate),
     });
     const sources = (detail?.sourceVideos || []).map((item, index) => ({
       sourceFilePath: item.sourceFilePath || "",
@@ -1225,6 +1436,28 @@ export default function SubmissionSection() {
     return detail;
   };
 
+  const handleMoveSource = async (index, delta) => {
+    const sources = selectedTask?.sourceVideos || [];
+    const target = index + delta;
+    if (!selectedTask?.task?.taskId || target < 0 || target >= sources.length) {
+      return;
+    }
+    const orderedIds = sources.map((item) => item.id);
+    [orderedIds[index], orderedIds[target]] = [orderedIds[target], orderedIds[index]];
+    setMessage("");
+    try {
+      const taskId = selectedTask.task.taskId;
+      await invokeCommand("submission_reorder_sources", {
+        taskId,
+        orderedSourceIds: orderedIds,
+      });
+      const detail = await fetchTaskDetail(taskId, { log: false });
+      setSelectedTask(detail);
+    } catch (error) {
+      setMessage(error.message || "调整源视频顺序失败");
+    }
+  };
+
   const handleDetail = async (taskId) => {
     setMessage("");
     try {
@@ -1234,6 +1467,8 @@ export default function SubmissionSection() {
       const detail = await fetchTaskDetail(taskId, { log: true });
       setSelectedTask(detail);
       setDetailTab("basic");
+      loadTaskStats(taskId);
+      loadWorkflowLogs(taskId);
       try {
         const task = detail?.task || {};
         const tagCount = String(task.tags || "")
@@ -1316,6 +1551,94 @@ export default function SubmissionSection() {
     }
   };
 
+  const handleResetRetries = async (taskId) => {
+    setMessage("");
+    try {
+      await invokeCommand("submission_reset_retries", { taskId });
+      setMessage("已重置重试次数");
+      await loadTasks(statusFilter, currentPage, pageSize);
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
+  const handleRefreshRemote = async (taskId) => {
+    setMessage("");
+    try {
+      await invokeCommand("submission_refresh_remote", { taskId });
+      setMessage("审核状态已刷新");
+      await loadTasks(statusFilter, currentPage, pageSize);
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
+  const handleResubmitRejected = async (task) => {
+    const newTitle = window.prompt("新的投稿标题", task.title || "");
+    if (newTitle === null) {
+      return;
+    }
+    const newTags = window.prompt("新的投稿标签（逗号分隔）", task.tags || "");
+    if (newTags === null) {
+      return;
+    }
+    setMessage("");
+    try {
+      const result = await invokeCommand("submission_resubmit_rejected", {
+        taskId: task.taskId,
+        newTitle: newTitle.trim() || null,
+        newTags: newTags.trim() || null,
+      });
+      setMessage(result || "已重新提交");
+      await loadTasks(statusFilter, currentPage, pageSize);
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
+  const handleDuplicateTask = async (task) => {
+    const newTitle = window.prompt("新任务标题", task.title || "");
+    if (newTitle === null) {
+      return;
+    }
+    if (!newTitle.trim()) {
+      setMessage("标题不能为空");
+      return;
+    }
+    setMessage("");
+    try {
+      await invokeCommand("submission_duplicate", {
+        taskId: task.taskId,
+        newTitle: newTitle.trim(),
+      });
+      setMessage("已复制任务配置");
+      await loadTasks(statusFilter, currentPage, pageSize);
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
+  const handleNormalizeSources = async (taskId) => {
+    setMessage("");
+    try {
+      const adjustments = await invokeCommand("submission_normalize_sources", { taskId });
+      const list = Array.isArray(adjustments) ? adjustments : [];
+      const failed = list.filter((item) => item.error);
+      const adjusted = list.length - failed.length;
+      if (!list.length) {
+        setMessage("源视频时间无需校正");
+        return;
+      }
+      const parts = [`已校正 ${adjusted} 个源视频时间`];
+      if (failed.length) {
+        parts.push(`${failed.length} 个源视频无法读取时长`);
+      }
+      setMessage(parts.join("，"));
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
   const handleResegmentSubmit = async () => {
     if (!resegmentTaskId || resegmentSubmitting) {
       return;
@@ -1791,6 +2114,98 @@ export default function SubmissionSection() {
     }
   };
 
+  const toggleTaskSelection = (taskId) => {
+    setSelectedTaskIds((prev) =>
+      prev.includes(taskId) ? prev.filter((id) => id !== taskId) : [...prev, taskId],
+    );
+  };
+
+  const formatBulkResult = (label, result) => {
+    const failedItems = (result?.items || []).filter((item) => !item.success);
+    const summary = `${label}完成：成功 ${result?.succeeded ?? 0}，失败 ${result?.failed ?? 0}`;
+    if (failedItems.length === 0) {
+      return summary;
+    }
+    const details = failedItems
+      .map((item) => `${item.taskId}: ${item.error || "未知错误"}`)
+      .join("；");
+    return `${summary}。${details}`;
+  };
+
+  const handleBulkRetry = async () => {
+    if (selectedTaskIds.length === 0) {
+      return;
+    }
+    setMessage("");
+    try {
+      const result = await invokeCommand("submission_bulk_retry", {
+        taskIds: selectedTaskIds,
+      });
+      setMessage(formatBulkResult("批量重试", result));
+      setSelectedTaskIds([]);
+      await loadTasks(statusFilter, currentPage, pageSize);
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
+  const handleBulkDelete = async () => {
+    if (selectedTaskIds.length === 0) {
+      return;
+    }
+    if (!window.confirm(`确认删除选中的 ${selectedTaskIds.length} 个任务？`)) {
+      return;
+    }
+    setMessage("");
+    try {
+      const result = await invokeCommand("submission_bulk_delete", {
+        taskIds: selectedTaskIds,
+      });
+      setMessage(formatBulkResult("批量删除", result));
+      if (selectedTaskIds.includes(selectedTask?.task?.taskId)) {
+        setSelectedTask(null);
+      }
+      setSelectedTaskIds([]);
+      await loadTasks(statusFilter, currentPage, pageSize);
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
+  const handleToggleQueue = async () => {
+    setMessage("");
+    try {
+      const paused = await invokeCommand(
+        queuePaused ? "submission_queue_resume" : "submission_queue_pause",
+      );
+      setQueuePaused(Boolean(paused));
+      setMessage(paused ? "投稿队列已暂停，进行中的任务会继续完成" : "投稿队列已恢复");
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
+  const handleExportCsv = async () => {
+    const selected = await saveDialog({
+      title: "导出投稿记录",
+      defaultPath: "submissions.csv",
+      filters: [{ name: "CSV", extensions: ["csv"] }],
+    });
+    if (typeof selected !== "string") {
+      return;
+    }
+    setMessage("");
+    try {
+      const path = await invokeCommand("submission_export_csv", {
+        statusFilter,
+        outputPath: selected.toLowerCase().endsWith(".csv") ? selected : `${selected}.csv`,
+      });
+      setMessage(`已导出：${path}`);
+    } catch (error) {
+      setMessage(error.message);
+    }
+  };
+
   const handleWorkflowPause = async (taskId) => {
     setMessage("");
     try {
@@ -1850,6 +2265,8 @@ export default function SubmissionSection() {
         return "投稿中";
       case "FAILED":
         return "失败";
+      case "FAILED_PERMANENT":
+        return "重试耗尽";
       case "CANCELLED":
         return "已取消";
       default:
@@ -1880,7 +2297,7 @@ export default function SubmissionSection() {
 
   const taskStatusTone = (status) => {
     if (status === "COMPLETED") return "bg-emerald-500/10 text-emerald-600";
-    if (status === "FAILED" || status === "CANCELLED")
+    if (status === "FAILED" || status === "FAILED_PERMANENT" || status === "CANCELLED")
       return "bg-rose-500/10 text-rose-600";
     if (["UPLOADING", "WAITING_UPLOAD", "RUNNING"].includes(status)) {
       return "bg-amber-500/10 text-amber-600";
@@ -1925,23 +2342,7 @@ export default function SubmissionSection() {
     if (!task?.bvid) {
       return "进行中";
     }
-    const state = Number(task.remoteState);
-    if (!Number.isFinite(state)) {
-      return "进行中";
-    }
-    if (state === -2) {
-      return "未通过";
-    }
-    if (state === -4) {
-      return "已锁定";
-    }
-    if (state === 0) {
-      return "已通过";
-    }
-    if (state === -30) {
-      return "进行中";
-    }
-    return "进行中";
+    return task.remoteStateLabel || "进行中";
   };
 
   const isRemoteRejected = (task) => {
@@ -2092,6 +2493,18 @@ export default function SubmissionSection() {
                 返回列表
               </button>
             </div>
+            {submissionQuota &&
+            (!submissionQuota.loggedIn ||
+              submissionQuota.banned ||
+              submissionQuota.cooldownMessage ||
+              submissionQuota.remainingDaily === 0) ? (
+              <div className="mt-3 rounded-lg border border-amber-200 bg-amber-50 px-3 py-2 text-xs text-amber-700">
+                {!submissionQuota.loggedIn
+                  ? "未登录，无法投稿"
+                  : submissionQuota.cooldownMessage ||
+                    (submissionQuota.banned ? "账号当前处于投稿限制中" : "今日投稿次数已用完")}
+              </div>
+            ) : null}
             <div className="mt-4 space-y-3">
               <div className="space-y-1">
                 <div className="flex items-center justify-between text-xs text-[var(--muted)]">
@@ -2144,11 +2557,7 @@ export default function SubmissionSection() {
                 className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
               >
                 <option value="">请选择分区</option>
-                {partitions.map((partition) => (
-                  <option key={partition.tid} value={partition.tid}>
-                    {partition.name}
-                  </option>
-                ))}
+                {renderPartitionOptions()}
               </select>
             </div>
             <div className="space-y-1">
@@ -2234,6 +2643,18 @@ export default function SubmissionSection() {
                 className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
               />
             </div>
+            <div className="space-y-1">
+              <div className="text-xs text-[var(--muted)]">定时发布（可选，2 小时后至 15 天内）</div>
+              <input
+                type="datetime-local"
+                value={taskForm.scheduledPublishAt}
+                onChange={(event) =>
+                  setTaskForm((prev) => ({ ...prev, scheduledPublishAt: event.target.value }))
+                }
+                readOnly={isReadOnly}
+                className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+              />
+            </div>
           </div>
           <div className="text-xs text-[var(--muted)]">
             分段前缀会作为分段文件名的前缀（可选）
@@ -2360,6 +2781,38 @@ export default function SubmissionSection() {
               <div className="text-xs text-[var(--muted)]">
                 预计分段数：{segmentationEnabled ? estimatedSegments : "不分段"}
               </div>
+              <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+                输出格式
+              </div>
+              <select
+                value={workflowConfig.outputContainer || "mp4"}
+                onChange={(event) =>
+                  setWorkflowConfig((prev) => ({
+                    ...prev,
+                    outputContainer: event.target.value,
+                  }))
+                }
+                disabled={isReadOnly}
+                className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+              >
+                <option value="mp4">MP4</option>
+                <option value="mkv">MKV（兼容更多编码）</option>
+                <option value="flv">FLV</option>
+              </select>
+              <label className="flex items-center gap-2 text-xs text-[var(--muted)]">
+                <input
+                  type="checkbox"
+                  checked={Boolean(workflowConfig.frameAccurate)}
+                  onChange={(event) =>
+                    setWorkflowConfig((prev) => ({
+                      ...prev,
+                      frameAccurate: event.target.checked,
+                    }))
+                  }
+                  disabled={isReadOnly}
+                />
+                精确剪辑（按帧切割，需重新编码，速度较慢）
+              </label>
             </div>
           </div>
         </div>
@@ -2816,12 +3269,73 @@ export default function SubmissionSection() {
                 </option>
               ))}
             </select>
+            <select
+              value={sortMode}
+              onChange={(event) => {
+                setSortMode(event.target.value);
+                setCurrentPage(1);
+              }}
+              className="rounded-full border border-black/10 bg-white px-3 py-1 text-xs font-semibold text-[var(--ink)]"
+            >
+              <option value="">默认排序</option>
+              <option value="created">按创建时间</option>
+              <option value="updated">按更新时间</option>
+              <option value="title">按标题</option>
+            </select>
+            <button
+              className="rounded-full border border-black/10 bg-white px-3 py-1 text-xs font-semibold text-[var(--ink)]"
+              onClick={handleExportCsv}
+            >
+              导出 CSV
+            </button>
+            <button
+              className="rounded-full border border-black/10 bg-white px-3 py-1 text-xs font-semibold text-[var(--ink)]"
+              onClick={handleToggleQueue}
+            >
+              {queuePaused ? "恢复队列" : "暂停队列"}
+            </button>
+            {selectedTaskIds.length > 0 ? (
+              <>
+                <button
+                  className="rounded-full border border-black/10 bg-white px-3 py-1 text-xs font-semibold text-[var(--ink)]"
+                  onClick={handleBulkRetry}
+                >
+                  批量重试（{selectedTaskIds.length}）
+                </button>
+                <button
+                  className="rounded-full border border-red-200 bg-white px-3 py-1 text-xs font-semibold text-red-600"
+                  onClick={handleBulkDelete}
+                >
+                  批量删除（{selectedTaskIds.length}）
+                </button>
+              </>
+            ) : null}
+            <input
+              value={searchQuery}
+              onChange={(event) => {
+                setSearchQuery(event.target.value);
+                setCurrentPage(1);
+              }}
+              placeholder="搜索标题/BVID/标签"
+              className="w-44 rounded-full border border-black/10 bg-white px-3 py-1 text-xs text-[var(--ink)]"
+            />
           </div>
         </div>
         <div className="overflow-x-auto">
           <table className="w-full min-w-max table-auto text-left text-sm whitespace-nowrap">
             <thead className="bg-black/5 text-xs uppercase tracking-[0.2em] text-[var(--muted)] whitespace-nowrap">
               <tr>
+                <th className="px-3 py-3">
+                  <input
+                    type="checkbox"
+                    checked={
+                      tasks.length > 0 && tasks.every((task) => selectedTaskIds.includes(task.taskId))
+                    }
+                    onChange={(event) =>
+                      setSelectedTaskIds(event.target.checked ? tasks.map((task) => task.taskId) : [])
+                    }
+                  />
+                </th>
                 <th className="px-6 py-3">标题</th>
                 <th className="px-6 py-3">任务状态</th>
                 <th className="px-6 py-3">工作流状态</th>
@@ -2838,13 +3352,20 @@ export default function SubmissionSection() {
             <tbody className="whitespace-nowrap">
               {tasks.length === 0 ? (
                 <tr>
-                  <td className="px-6 py-4 text-[var(--muted)]" colSpan={9}>
+                  <td className="px-6 py-4 text-[var(--muted)]" colSpan={10}>
                     暂无任务。
                   </td>
                 </tr>
               ) : (
                 tasks.map((task) => (
                   <tr key={task.taskId} className="border-t border-black/5">
+                    <td className="px-3 py-3">
+                      <input
+                        type="checkbox"
+                        checked={selectedTaskIds.includes(task.taskId)}
+                        onChange={() => toggleTaskSelection(task.taskId)}
+                      />
+                    </td>
                     <td className="px-6 py-3 text-[var(--ink)] whitespace-normal">
                       <button
                         className="text-left font-semibold text-[var(--ink)] transition hover:text-[var(--accent)] hover:underline hover:underline-offset-4 break-words"
@@ -2862,6 +3383,19 @@ export default function SubmissionSection() {
                       >
                         {formatTaskStatus(task.status)}
                       </span>
+                      {task.uploadPaused ? (
+                        <div className="mt-1 text-xs text-[var(--muted)]">
+                          已取消上传，重新投稿后继续
+                        </div>
+                      ) : null}
+                      {task.lastUploadError ? (
+                        <div
+                          className="mt-1 max-w-[200px] truncate text-xs text-rose-600"
+                          title={task.lastUploadError}
+                        >
+                          失败 {task.uploadFailureCount} 次：{task.lastUploadError}
+                        </div>
+                      ) : null}
                     </td>
                     <td className="px-6 py-3 whitespace-nowrap">
                       {task.workflowStatus ? (
@@ -2985,12 +3519,48 @@ export default function SubmissionSection() {
                             视频更新
                           </button>
                         ) : null}
+                        {task.bvid ? (
+                          <button
+                            className="rounded-full border border-black/10 bg-white px-2 py-1 text-xs font-semibold text-[var(--ink)]"
+                            onClick={() => handleRefreshRemote(task.taskId)}
+                          >
+                            刷新审核
+                          </button>
+                        ) : null}
+                        {isRemoteRejected(task) ? (
+                          <button
+                            className="rounded-full border border-black/10 bg-white px-2 py-1 text-xs font-semibold text-[var(--ink)]"
+                            onClick={() => handleResubmitRejected(task)}
+                          >
+                            修改重提
+                          </button>
+                        ) : null}
                         <button
                           className="rounded-full border border-black/10 bg-white px-2 py-1 text-xs font-semibold text-[var(--ink)]"
                           onClick={() => openResegmentModal(task.taskId)}
                         >
                           重新分段
                         </button>
+                        <button
+                          className="rounded-full border border-black/10 bg-white px-2 py-1 text-xs font-semibold text-[var(--ink)]"
+                          onClick={() => handleNormalizeSources(task.taskId)}
+                        >
+                          校正时间
+                        </button>
+                        <button
+                          className="rounded-full border border-black/10 bg-white px-2 py-1 text-xs font-semibold text-[var(--ink)]"
+                          onClick={() => handleDuplicateTask(task)}
+                        >
+                          复制任务
+                        </button>
+                        {task.uploadFailureCount > 0 ? (
+                          <button
+                            className="rounded-full border border-black/10 bg-white px-2 py-1 text-xs font-semibold text-[var(--ink)]"
+                            onClick={() => handleResetRetries(task.taskId)}
+                          >
+                            重置重试
+                          </button>
+                        ) : null}
                         {task.status === "FAILED" && task.hasIntegratedDownloads ? (
                           <button
                             className="rounded-full border border-black/10 bg-white px-2 py-1 text-xs font-semibold text-[var(--ink)]"
@@ -3298,6 +3868,8 @@ export default function SubmissionSection() {
               { key: "merged", label: "合并视频" },
               { key: "segments", label: "输出分段" },
               { key: "upload", label: "上传进度" },
+              { key: "stats", label: "耗时统计" },
+              { key: "timeline", label: "工作流日志" },
             ].map((tab) => (
               <button
                 key={tab.key}
@@ -3365,11 +3937,7 @@ export default function SubmissionSection() {
                           className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none disabled:cursor-not-allowed disabled:bg-black/5"
                         >
                           <option value="">请选择分区</option>
-                          {partitions.map((partition) => (
-                            <option key={partition.tid} value={partition.tid}>
-                              {partition.name}
-                            </option>
-                          ))}
+                          {renderPartitionOptions()}
                         </select>
                       </div>
                       <div className="space-y-1">
@@ -3561,12 +4129,13 @@ export default function SubmissionSection() {
                     <th className="px-4 py-2">视频文件路径</th>
                     <th className="px-4 py-2">开始时间</th>
                     <th className="px-4 py-2">结束时间</th>
+                    <th className="px-4 py-2">顺序</th>
                   </tr>
                 </thead>
                 <tbody>
                   {selectedTask.sourceVideos.length === 0 ? (
                     <tr>
-                      <td className="px-4 py-3 text-[var(--muted)]" colSpan={4}>
+                      <td className="px-4 py-3 text-[var(--muted)]" colSpan={5}>
                         暂无源视频
                       </td>
                     </tr>
@@ -3583,6 +4152,27 @@ export default function SubmissionSection() {
                         <td className="px-4 py-2 text-[var(--muted)]">
                           {item.endTime || "-"}
                         </td>
+                        <td className="px-4 py-2">
+                          <div className="flex gap-1">
+                            <button
+                              className="rounded border border-black/10 px-2 text-xs disabled:opacity-40"
+                              disabled={index === 0 || selectedTask.task.status === "UPLOADING"}
+                              onClick={() => handleMoveSource(index, -1)}
+                            >
+                              上移
+                            </button>
+                            <button
+                              className="rounded border border-black/10 px-2 text-xs disabled:opacity-40"
+                              disabled={
+                                index === selectedTask.sourceVideos.length - 1 ||
+                                selectedTask.task.status === "UPLOADING"
+                              }
+                              onClick={() => handleMoveSource(index, 1)}
+                            >
+                              下移
+                            </button>
+                          </div>
+                        </td>
                       </tr>
                     ))
                   )}
@@ -3666,6 +4256,46 @@ export default function SubmissionSection() {
               </table>
             </div>
           ) : null}
+          {detailTab === "stats" ? (
+            <div className="mt-4 rounded-xl border border-black/5 px-4 py-3 text-sm">
+              {taskStats ? (
+                <div className="grid gap-2 sm:grid-cols-2">
+                  <div>剪辑耗时：{formatDurationHms(taskStats.clipDurationMs / 1000)}</div>
+                  <div>合并耗时：{formatDurationHms(taskStats.mergeDurationMs / 1000)}</div>
+                  <div>分段耗时：{formatDurationHms(taskStats.segmentDurationMs / 1000)}</div>
+                  <div>分段数量：{taskStats.segmentCount}</div>
+                  <div>上传数据量：{formatStatBytes(taskStats.uploadBytes)}</div>
+                  <div>上传耗时：{formatDurationHms(taskStats.uploadDurationMs / 1000)}</div>
+                  <div>平均上传速度：{formatStatBytes(taskStats.uploadSpeedBps)}/s</div>
+                  <div className="text-[var(--muted)]">更新时间：{taskStats.updatedAt}</div>
+                </div>
+              ) : (
+                <div className="text-[var(--muted)]">暂无统计数据</div>
+              )}
+            </div>
+          ) : null}
+          {detailTab === "timeline" ? (
+            <div className="mt-4 rounded-xl border border-black/5 px-4 py-3 text-sm">
+              {workflowLogs.length === 0 ? (
+                <div className="text-[var(--muted)]">暂无工作流日志</div>
+              ) : (
+                <div className="space-y-2">
+                  {workflowLogs.map((log) => (
+                    <div key={log.logId} className="flex flex-wrap gap-3">
+                      <span className="text-[var(--muted)]">{formatDateTime(log.createdAt)}</span>
+                      <span
+                        className={
+                          log.logLevel === "ERROR" ? "text-rose-600" : "text-[var(--ink)]"
+                        }
+                      >
+                        {log.message}
+                      </span>
+                    </div>
+                  ))}
+                </div>
+              )}
+            </div>
+          ) : null}
           {detailTab === "upload" ? (
             <div className="mt-4 overflow-hidden rounded-xl border border-black/5">
               {isEditView ? (
@@ -90,6 +90,17 @@ export default function SubmissionSyncSection() {
     }
   };
 
+  const handleVerify = async (taskId) => {
+    setMessage("");
+    try {
+      const report = await invokeCommand("baidu_sync_verify", { taskId });
+      setMessage(report?.matched ? "校验通过，远端文件一致" : "校验未通过，已标记为失败");
+      await loadTasks();
+    } catch (error) {
+      setMessage(error?.message || "校验失败");
+    }
+  };
+
   const handlePause = async (taskId) => {
     setMessage("");
     try {
@@ -183,6 +194,14 @@ export default function SubmissionSyncSection() {
                             重新同步
                           </button>
                         ) : null}
+                        {task.status === "SUCCESS" ? (
+                          <button
+                            className="h-8 px-3 rounded-lg"
+                            onClick={() => handleVerify(task.id)}
+                          >
+                            校验
+                          </button>
+                        ) : null}
                         {canPause ? (
                           <button
                             className="h-8 px-3 rounded-lg"
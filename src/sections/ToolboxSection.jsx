@@ -1,8 +1,13 @@
-import { useMemo, useState } from "react";
+import { useEffect, useMemo, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
 import { open, save } from "@tauri-apps/plugin-dialog";
 import { invokeCommand } from "../lib/tauri";
 
-const toolboxTabs = [{ key: "remux", label: "格式转码" }];
+const toolboxTabs = [
+  { key: "remux", label: "格式转码" },
+  { key: "clip", label: "下载剪辑" },
+  { key: "trim", label: "本地裁剪" },
+];
 
 const normalizePath = (path) => String(path || "").replace(/\\/g, "/");
 
@@ -31,9 +36,163 @@ export default function ToolboxSection() {
   const [targetPath, setTargetPath] = useState("");
   const [message, setMessage] = useState("");
   const [running, setRunning] = useState(false);
+  const [binaries, setBinaries] = useState([]);
+  const [checkingBinaries, setCheckingBinaries] = useState(false);
+  const [clipForm, setClipForm] = useState({
+    videoUrl: "",
+    page: "1",
+    startTime: "",
+    endTime: "",
+    outputPath: "",
+  });
+  const [clipMessage, setClipMessage] = useState("");
+  const [clipRunning, setClipRunning] = useState(false);
+  const [clipRecordId, setClipRecordId] = useState(null);
+  const [trimForm, setTrimForm] = useState({
+    inputPath: "",
+    startTime: "",
+    endTime: "",
+    outputPath: "",
+  });
+  const [trimMessage, setTrimMessage] = useState("");
+  const [trimRunning, setTrimRunning] = useState(false);
+
+  const updateClipForm = (key, value) => {
+    setClipForm((prev) => ({ ...prev, [key]: value }));
+  };
+
+  const handlePickClipOutput = async () => {
+    const selected = await save({
+      title: "保存剪辑文件",
+      filters: [{ name: "MP4", extensions: ["mp4"] }],
+    });
+    if (typeof selected === "string") {
+      updateClipForm("outputPath", ensureMp4Extension(selected));
+    }
+  };
+
+  const handleDownloadClip = async () => {
+    setClipMessage("");
+    if (!clipForm.videoUrl.trim()) {
+      setClipMessage("请输入 BV 号或视频链接");
+      return;
+    }
+    if (!clipForm.outputPath.trim()) {
+      setClipMessage("请选择输出路径");
+      return;
+    }
+    setClipRunning(true);
+    try {
+      const recordId = await invokeCommand("download_clip", {
+        request: {
+          videoUrl: clipForm.videoUrl.trim(),
+          page: Number(clipForm.page) || 1,
+          startTime: clipForm.startTime.trim() || null,
+          endTime: clipForm.endTime.trim() || null,
+          outputPath: clipForm.outputPath,
+        },
+      });
+      setClipRecordId(recordId);
+      setClipMessage(`已创建下载任务 #${recordId}，下载完成后自动剪辑`);
+    } catch (error) {
+      setClipMessage(error?.message || "下载剪辑失败");
+      setClipRunning(false);
+    }
+  };
+
+  useEffect(() => {
+    if (!clipRecordId) {
+      return undefined;
+    }
+    const unlisten = listen("download_clip_result", (event) => {
+      if (event.payload?.recordId !== clipRecordId) {
+        return;
+      }
+      if (event.payload?.error) {
+        setClipMessage(event.payload.error);
+      } else {
+        setClipMessage(`剪辑完成：${event.payload?.outputPath || ""}`);
+      }
+      setClipRecordId(null);
+      setClipRunning(false);
+    });
+    return () => {
+      unlisten.then((off) => off());
+    };
+  }, [clipRecordId]);
+
+  const updateTrimForm = (key, value) => {
+    setTrimForm((prev) => ({ ...prev, [key]: value }));
+  };
+
+  const handlePickTrimInput = async () => {
+    const selected = await open({
+      multiple: false,
+      directory: false,
+      title: "选择视频文件",
+      filters: [{ name: "视频", extensions: ["mp4", "flv", "mkv", "mov", "ts"] }],
+    });
+    if (typeof selected === "string") {
+      updateTrimForm("inputPath", selected);
+    }
+  };
+
+  const handlePickTrimOutput = async () => {
+    const selected = await save({
+      title: "保存裁剪文件",
+      filters: [{ name: "MP4", extensions: ["mp4"] }],
+    });
+    if (typeof selected === "string") {
+      updateTrimForm("outputPath", ensureMp4Extension(selected));
+    }
+  };
+
+  const handleTrim = async () => {
+    setTrimMessage("");
+    if (!trimForm.inputPath.trim()) {
+      setTrimMessage("请选择源文件");
+      return;
+    }
+    if (!trimForm.startTime.trim() && !trimForm.endTime.trim()) {
+      setTrimMessage("请填写开始或结束时间");
+      return;
+    }
+    setTrimRunning(true);
+    try {
+      const outputPath = await invokeCommand("toolbox_trim", {
+        payload: {
+          inputPath: trimForm.inputPath,
+          startTime: trimForm.startTime.trim() || null,
+          endTime: trimForm.endTime.trim() || null,
+          outputPath: trimForm.outputPath || null,
+        },
+      });
+      setTrimMessage(`裁剪完成：${outputPath}`);
+    } catch (error) {
+      setTrimMessage(error?.message || "裁剪失败");
+    } finally {
+      setTrimRunning(false);
+    }
+  };
 
   const defaultTarget = useMemo(() => buildDefaultTarget(sourcePath), [sourcePath]);
 
+  const handleCheckBinaries = async () => {
+    setCheckingBinaries(true);
+    try {
+      const data = await invokeCommand("toolbox_check_binaries");
+      setBinaries(Array.isArray(data) ? data : []);
+    } catch (error) {
+      setMessage(error?.message || "检测工具失败");
+    } finally {
+      setCheckingBinaries(false);
+    }
+  };
+
+  useEffect(() => {
+    handleCheckBinaries();
+  }, []);
+
   const handlePickSource = async () => {
     setMessage("");
     const selected = await open({
@@ -95,7 +254,130 @@ export default function ToolboxSection() {
   return (
     <div className="flex gap-4">
       <div className="flex-1 min-w-0 space-y-4">
-        <div className="panel p-4 space-y-3">
+        {activeTab === "clip" ? (
+          <div className="panel p-4 space-y-3">
+            <div className="space-y-1">
+              <div className="text-lg font-semibold">下载剪辑</div>
+              <div className="desc">
+                下载指定分P并裁剪时间段，输出单个文件，不创建投稿任务。
+              </div>
+            </div>
+            <div className="space-y-2">
+              <input
+                className="w-full"
+                value={clipForm.videoUrl}
+                onChange={(event) => updateClipForm("videoUrl", event.target.value)}
+                placeholder="BV 号或视频链接"
+              />
+              <div className="flex items-center gap-2">
+                <input
+                  className="w-20"
+                  value={clipForm.page}
+                  onChange={(event) => updateClipForm("page", event.target.value)}
+                  placeholder="分P"
+                />
+                <input
+                  className="flex-1 min-w-0"
+                  value={clipForm.startTime}
+                  onChange={(event) => updateClipForm("startTime", event.target.value)}
+                  placeholder="开始时间 00:00:00"
+                />
+                <input
+                  className="flex-1 min-w-0"
+                  value={clipForm.endTime}
+                  onChange={(event) => updateClipForm("endTime", event.target.value)}
+                  placeholder="结束时间 00:10:00"
+                />
+              </div>
+              <div className="flex items-center gap-2">
+                <input
+                  className="flex-1 min-w-0"
+                  value={clipForm.outputPath}
+                  readOnly
+                  placeholder="请选择输出 MP4 路径"
+                />
+                <button className="h-8 px-3 rounded-lg" onClick={handlePickClipOutput}>
+                  保存到
+                </button>
+              </div>
+              <div className="flex flex-wrap items-center gap-3">
+                <button
+                  className="h-8 px-3 rounded-lg"
+                  onClick={handleDownloadClip}
+                  disabled={clipRunning}
+                >
+                  {clipRunning ? "处理中..." : "下载并剪辑"}
+                </button>
+                {clipMessage ? (
+                  <span className="text-xs text-[var(--desc-color)]">{clipMessage}</span>
+                ) : null}
+              </div>
+            </div>
+          </div>
+        ) : null}
+
+        {activeTab === "trim" ? (
+          <div className="panel p-4 space-y-3">
+            <div className="space-y-1">
+              <div className="text-lg font-semibold">本地裁剪</div>
+              <div className="desc">
+                按时间段裁剪本地视频，时间戳正常时直接复制流，否则重新编码。
+              </div>
+            </div>
+            <div className="space-y-2">
+              <div className="flex items-center gap-2">
+                <input
+                  className="flex-1 min-w-0"
+                  value={trimForm.inputPath}
+                  readOnly
+                  placeholder="请选择源文件"
+                />
+                <button className="h-8 px-3 rounded-lg" onClick={handlePickTrimInput}>
+                  选择文件
+                </button>
+              </div>
+              <div className="flex items-center gap-2">
+                <input
+                  className="flex-1 min-w-0"
+                  value={trimForm.startTime}
+                  onChange={(event) => updateTrimForm("startTime", event.target.value)}
+                  placeholder="开始时间 00:00:00"
+                />
+                <input
+                  className="flex-1 min-w-0"
+                  value={trimForm.endTime}
+                  onChange={(event) => updateTrimForm("endTime", event.target.value)}
+                  placeholder="结束时间 00:10:00"
+                />
+              </div>
+              <div className="flex items-center gap-2">
+                <input
+                  className="flex-1 min-w-0"
+                  value={trimForm.outputPath}
+                  readOnly
+                  placeholder="输出路径（默认源文件同目录 _trim.mp4）"
+                />
+                <button className="h-8 px-3 rounded-lg" onClick={handlePickTrimOutput}>
+                  保存到
+                </button>
+              </div>
+              <div className="flex flex-wrap items-center gap-3">
+                <button
+                  className="h-8 px-3 rounded-lg"
+                  onClick={handleTrim}
+                  disabled={trimRunning}
+                >
+                  {trimRunning ? "裁剪中..." : "开始裁剪"}
+                </button>
+                {trimMessage ? (
+                  <span className="text-xs text-[var(--desc-color)]">{trimMessage}</span>
+                ) : null}
+              </div>
+            </div>
+          </div>
+        ) : null}
+
+        <div className={`panel p-4 space-y-3 ${activeTab === "remux" ? "" : "hidden"}`}>
           <div className="space-y-1">
             <div className="text-lg font-semibold">格式转码</div>
             <div className="desc">
@@ -138,7 +420,38 @@ export default function ToolboxSection() {
           </div>
         </div>
 
-        <div className="panel p-4 space-y-1 text-xs text-[var(--desc-color)]">
+        <div className="panel p-4 space-y-2">
+          <div className="flex items-center justify-between">
+            <div className="text-lg font-semibold">外部工具</div>
+            <button
+              className="h-8 px-3 rounded-lg"
+              onClick={handleCheckBinaries}
+              disabled={checkingBinaries}
+            >
+              {checkingBinaries ? "检测中..." : "重新检测"}
+            </button>
+          </div>
+          {binaries.map((item) => (
+            <div key={item.name} className="flex flex-wrap items-center gap-2 text-xs">
+              <span className="w-20 font-semibold">{item.name}</span>
+              {item.resolvedPath && !item.error ? (
+                <span className="text-[var(--desc-color)]">
+                  {item.version || "未知版本"} · {item.resolvedPath}
+                </span>
+              ) : (
+                <span className="text-red-600">
+                  {item.error || "未找到可执行文件"}（{item.configuredPath || "-"}）
+                </span>
+              )}
+            </div>
+          ))}
+        </div>
+
+        <div
+          className={`panel p-4 space-y-1 text-xs text-[var(--desc-color)] ${
+            activeTab === "remux" ? "" : "hidden"
+          }`}
+        >
           <div>1. 选择需要转封装的 FLV 文件。</div>
           <div>2. 选择 MP4 保存位置。</div>
           <div>3. 转封装会占用磁盘 IO，可能影响正在进行的录制。</div>